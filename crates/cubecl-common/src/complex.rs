@@ -0,0 +1,174 @@
+use core::{
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use bytemuck::{Pod, Zeroable};
+use num_traits::{Float, One, Zero};
+
+macro_rules! complex_type {
+    ($(#[$meta: meta])* $name: ident, $float: ty) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[repr(C)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Clone, Copy, Default, Zeroable, Pod, PartialEq, Debug)]
+        pub struct $name {
+            /// Real part
+            pub re: $float,
+            /// Imaginary part
+            pub im: $float,
+        }
+
+        impl $name {
+            /// The imaginary unit
+            pub const I: Self = Self::new(0.0, 1.0);
+
+            /// Create a complex number from its real and imaginary parts
+            pub const fn new(re: $float, im: $float) -> Self {
+                Self { re, im }
+            }
+
+            /// Create a complex number with no imaginary part
+            pub const fn from_real(re: $float) -> Self {
+                Self::new(re, 0.0)
+            }
+
+            /// The complex conjugate, `re - im * i`
+            pub fn conj(self) -> Self {
+                Self::new(self.re, -self.im)
+            }
+
+            /// The magnitude, `sqrt(re^2 + im^2)`
+            pub fn abs(self) -> $float {
+                Float::hypot(self.re, self.im)
+            }
+
+            /// `e` raised to the power of `self`
+            pub fn exp(self) -> Self {
+                let scale = Float::exp(self.re);
+                let (sin, cos) = Float::sin_cos(self.im);
+                Self::new(scale * cos, scale * sin)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self::new(self.re + rhs.re, self.im + rhs.im)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(self.re - rhs.re, self.im - rhs.im)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                Self::new(
+                    self.re * rhs.re - self.im * rhs.im,
+                    self.re * rhs.im + self.im * rhs.re,
+                )
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self {
+                let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+                Self::new(
+                    (self.re * rhs.re + self.im * rhs.im) / denom,
+                    (self.im * rhs.re - self.re * rhs.im) / denom,
+                )
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self::new(-self.re, -self.im)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Zero for $name {
+            fn zero() -> Self {
+                Self::new(0.0, 0.0)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.re == 0.0 && self.im == 0.0
+            }
+        }
+
+        impl One for $name {
+            fn one() -> Self {
+                Self::from_real(1.0)
+            }
+        }
+
+        impl From<$float> for $name {
+            fn from(re: $float) -> Self {
+                Self::from_real(re)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self.im.is_sign_negative() {
+                    true => write!(f, "{}-{}i", self.re, -self.im),
+                    false => write!(f, "{}+{}i", self.re, self.im),
+                }
+            }
+        }
+    };
+}
+
+complex_type!(
+    /// A complex number with [`prim@f32`] parts, stored as an interleaved `[re, im]` pair.
+    c32,
+    f32
+);
+complex_type!(
+    /// A complex number with [`prim@f64`] parts, stored as an interleaved `[re, im]` pair.
+    c64,
+    f64
+);
+
+impl From<c32> for c64 {
+    fn from(value: c32) -> Self {
+        c64::new(value.re as f64, value.im as f64)
+    }
+}
@@ -0,0 +1,92 @@
+use core::fmt::Debug;
+
+use bytemuck::{Pod, Zeroable};
+
+macro_rules! int4x2_type {
+    ($(#[$meta: meta])* $name: ident, $elem: ty, $min: literal, $max: literal) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[repr(transparent)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Clone, Copy, Default, Zeroable, Pod, PartialEq, Eq, Hash)]
+        pub struct $name(u8);
+
+        impl $name {
+            /// Minimum representable value of each element
+            pub const MIN: $elem = $min;
+            /// Maximum representable value of each element
+            pub const MAX: $elem = $max;
+
+            /// Create a new pair from bits, with the first element in the low nibble
+            pub const fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            /// Retrieve the stored bits
+            pub const fn to_bits(self) -> u8 {
+                self.0
+            }
+
+            /// Pack two elements. Values out of range wrap, like any other narrowing integer
+            /// conversion.
+            pub const fn new(low: $elem, high: $elem) -> Self {
+                Self((low as u8 & 0x0F) | ((high as u8) << 4))
+            }
+
+            /// The first element, stored in the low nibble. Shifting down from the top nibble sign
+            /// extends signed elements.
+            pub const fn low(self) -> $elem {
+                ((self.0 << 4) as $elem) >> 4
+            }
+
+            /// The second element, stored in the high nibble
+            pub const fn high(self) -> $elem {
+                (self.0 as $elem) >> 4
+            }
+
+            /// Pack a slice of elements, two per value. An odd trailing element is padded with
+            /// zero.
+            pub fn from_slice(values: &[$elem]) -> alloc::vec::Vec<Self> {
+                values
+                    .chunks(2)
+                    .map(|chunk| Self::new(chunk[0], chunk.get(1).copied().unwrap_or_default()))
+                    .collect()
+            }
+
+            /// Unpack a slice of packed values into two elements each
+            pub fn to_vec(values: &[Self]) -> alloc::vec::Vec<$elem> {
+                values
+                    .iter()
+                    .flat_map(|it| [it.low(), it.high()])
+                    .collect()
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&self.low())
+                    .field(&self.high())
+                    .finish()
+            }
+        }
+    };
+}
+
+int4x2_type!(
+    /// Two signed 4-bit integers packed into a byte, the first in the low nibble. Care must be
+    /// taken to ensure the shape is adjusted appropriately.
+    i4x2,
+    i8,
+    -8,
+    7
+);
+
+int4x2_type!(
+    /// Two unsigned 4-bit integers packed into a byte, the first in the low nibble. Care must be
+    /// taken to ensure the shape is adjusted appropriately.
+    u4x2,
+    u8,
+    0,
+    15
+);
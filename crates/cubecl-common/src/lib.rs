@@ -61,6 +61,16 @@ mod float;
 
 pub use float::*;
 
+/// Complex number types
+mod complex;
+
+pub use complex::*;
+
+/// Packed 4-bit integer types
+mod int4;
+
+pub use int4::*;
+
 /// An exact ratio of two integers.
 mod ratio;
 
@@ -424,7 +424,8 @@ impl QuantStore {
     pub fn size_bits(&self, value: &QuantValue) -> usize {
         match self {
             QuantStore::Native => value.size_bits(),
-            QuantStore::PackedNative(_) => value.size_bits() * value.native_packing(),
+            // Native packed types (`e2m1x2`, `i4x2`) always fill a byte
+            QuantStore::PackedNative(_) => 8,
             QuantStore::PackedU32(_) => 32,
         }
     }
@@ -444,7 +445,7 @@ impl QuantStore {
 pub enum QuantStore {
     /// Native quantization doesn't require packing and unpacking.
     Native,
-    /// Store packed quantized values in a natively supported packing format (i.e. e2m1x2).
+    /// Store packed quantized values in a natively supported packing format (i.e. e2m1x2, i4x2).
     /// Argument is the dimension the tensor is packed on, starting from the innermost dimension.
     PackedNative(usize),
     /// Store packed quantized values in a 4-byte unsigned integer.
@@ -619,6 +620,17 @@ mod tests {
         assert_eq!(BlockSize::new([32, 32]).to_dim_vec(2), vec![32, 32]);
     }
 
+    #[test]
+    fn native_packed_values_fill_a_byte() {
+        for value in [QuantValue::Q4F, QuantValue::Q4S, QuantValue::E2M1] {
+            let scheme = QuantScheme::default()
+                .with_value(value)
+                .with_store(QuantStore::PackedNative(0));
+            assert_eq!(scheme.size_bits_stored(), 8);
+            assert_eq!(scheme.num_quants(), 2);
+        }
+    }
+
     #[test]
     fn leading_unit_dimensions_canonicalize_away() {
         assert_eq!(BlockSize::new([1, 32]), BlockSize::new([32]));
@@ -2,6 +2,8 @@
 #[derive(Clone, Copy, Debug, Default)]
 pub struct WgpuCompilationOptions {
     pub supports_u64: bool,
+    /// Whether `f32` atomics are native, or need to be emulated with a compare-exchange loop
+    pub supports_float32_atomics: bool,
     /// Whether the Vulkan compiler is supported or we need to fall back to WGSL
     pub supports_vulkan_compiler: bool,
     pub supports_msl_compiler: bool,
@@ -17,6 +19,8 @@ pub struct VulkanCompilationOptions {
     pub supports_arbitrary_bitwise: bool,
     pub supports_uniform_standard_layout: bool,
     pub supports_uniform_unsized_array: bool,
+    /// Whether plane reductions can natively be restricted to clusters of units
+    pub supports_clustered_plane_ops: bool,
 
     pub max_spirv_version: (u8, u8),
     pub max_vector_size: usize,
@@ -1,7 +1,7 @@
 use alloc::vec;
 
 use cubecl_ir::{AddressType, metadata::INFO_ALIGN};
-use cubecl_runtime::server::MetadataBindingInfo;
+use cubecl_runtime::server::{MarkedScalar, MetadataBindingInfo};
 
 use crate::{MetadataBuilder, ScalarBuilder};
 
@@ -12,7 +12,11 @@ pub struct InfoBuilder {
 }
 
 impl InfoBuilder {
-    pub fn finish(&mut self, address_type: AddressType) -> MetadataBindingInfo {
+    /// Pack the info, returning it with where the scalars passed as graph parameters ended up.
+    pub fn finish(
+        &mut self,
+        address_type: AddressType,
+    ) -> (MetadataBindingInfo, Vec<MarkedScalar>) {
         let addr_packing = INFO_ALIGN / address_type.size();
 
         let scalars_size = self.scalars.len_aligned();
@@ -22,13 +26,15 @@ impl InfoBuilder {
         let dynamic_size = dynamic_len.div_ceil(addr_packing);
 
         let mut out = vec![0; scalars_size + static_size + dynamic_size];
-        self.scalars.finish(&mut out[..scalars_size]);
+        let marked = self.scalars.finish(&mut out[..scalars_size]);
         self.metadata
             .finish(address_type, out[scalars_size..].split_at_mut(static_size));
 
-        MetadataBindingInfo {
+        let info = MetadataBindingInfo {
             data: out,
+            scalars_len: scalars_size,
             dynamic_metadata_offset: scalars_size + static_size,
-        }
+        };
+        (info, marked)
     }
 }
@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 
 use cubecl_ir::{ElemType, metadata::INFO_ALIGN};
+use cubecl_runtime::{id::GraphParamId, server::MarkedScalar};
 
 use crate::ScalarArgType;
 
@@ -11,6 +12,9 @@ pub type ScalarValues = Vec<u8>;
 pub struct ScalarBuilder {
     /// Sorted list of scalars, should be faster than `BTreeMap` for this purpose. Benchmark later.
     scalars: Vec<(ElemType, ScalarValues)>,
+    /// The scalars passed as graph parameters, with their type, offset among the values of
+    /// that type and width.
+    marked: Vec<(GraphParamId, ElemType, usize, usize)>,
 }
 
 impl ScalarBuilder {
@@ -27,6 +31,14 @@ impl ScalarBuilder {
         self.get_or_insert_mut(dtype).extend(bytes.iter().copied());
     }
 
+    /// Add a new raw value passed as a graph parameter, see [`MarkedScalar`].
+    pub fn push_marked_raw(&mut self, bytes: &[u8], dtype: ElemType, param: GraphParamId) {
+        let values = self.get_or_insert_mut(dtype);
+        let offset = values.len();
+        values.extend(bytes.iter().copied());
+        self.marked.push((param, dtype, offset, bytes.len()));
+    }
+
     fn get_or_insert_mut(&mut self, ty: ElemType) -> &mut ScalarValues {
         let pos = self.scalars.iter().position(|(k, _)| *k >= ty);
 
@@ -50,15 +62,30 @@ impl ScalarBuilder {
             .sum()
     }
 
-    pub fn finish(&mut self, out: &mut [u64]) {
+    /// Pack the scalars into `out`, returning where the marked ones ended up.
+    pub fn finish(&mut self, out: &mut [u64]) -> Vec<MarkedScalar> {
         let mut out_u8 = bytemuck::cast_slice_mut::<u64, u8>(out);
+        let mut marked = Vec::with_capacity(self.marked.len());
+        let mut base = 0;
 
-        for (_, values) in self.scalars.iter_mut().filter(|(_, v)| !v.is_empty()) {
+        for (ty, values) in self.scalars.iter_mut().filter(|(_, v)| !v.is_empty()) {
             let len_padded = values.len().next_multiple_of(INFO_ALIGN);
 
             out_u8[0..values.len()].copy_from_slice(values);
             out_u8 = &mut out_u8[len_padded..];
             values.clear();
+
+            for (param, _, offset, len) in self.marked.iter().filter(|m| m.1 == *ty) {
+                marked.push(MarkedScalar {
+                    param: *param,
+                    offset: base + offset,
+                    len: *len,
+                });
+            }
+            base += len_padded;
         }
+
+        self.marked.clear();
+        marked
     }
 }
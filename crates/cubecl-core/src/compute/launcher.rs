@@ -10,6 +10,7 @@ use cubecl_ir::{AddressType, ElemType, Scope, settings::KernelSettings};
 use cubecl_runtime::server::{BufferBinding, CubeCount, KernelResource, TensorMapBinding};
 use cubecl_runtime::{
     client::ComputeClient,
+    id::GraphParamId,
     kernel::{CubeKernel, KernelTask},
     server::KernelArguments,
 };
@@ -64,6 +65,16 @@ impl<R: Runtime> KernelLauncher<R> {
         self.with_info(|info| info.scalars.push_raw(bytes, dtype));
     }
 
+    /// Register a scalar to be launched from raw data, passed as the graph parameter `param`.
+    pub fn register_marked_scalar_raw(
+        &mut self,
+        bytes: &[u8],
+        dtype: ElemType,
+        param: GraphParamId,
+    ) {
+        self.with_info(|info| info.scalars.push_marked_raw(bytes, dtype, param));
+    }
+
     /// Launch the kernel.
     #[track_caller]
     pub fn launch<K: CubeKernel>(
@@ -90,10 +101,11 @@ impl<R: Runtime> KernelLauncher<R> {
     fn into_bindings(mut self) -> KernelArguments {
         let mut bindings = KernelArguments::new();
         let address_type = self.address_type;
-        let info = self.with_info(|info| info.finish(address_type));
+        let (info, marked_scalars) = self.with_info(|info| info.finish(address_type));
 
         bindings.resources = self.resources;
         bindings.info = info;
+        bindings.marked_scalars = marked_scalars;
 
         bindings
     }
@@ -14,7 +14,9 @@ use pliron::{
         op_interfaces::OneRegionInterface,
         types::{IntegerType, Signedness},
     },
+    context::Ptr,
     irbuild::{
+        inserter::OpInsertionPoint,
         listener::DummyListener,
         rewriter::{IRRewriter, Rewriter},
     },
@@ -22,11 +24,14 @@ use pliron::{
     region::Region,
     r#type::Typed,
     utils::apint::{APInt, bw},
+    value::Value,
 };
 
 use crate::{
     IntoRuntime,
-    frontend::{ReadValue, RuntimeAssign, assign, assign_binop_expand, binary_expand},
+    frontend::{
+        FromResidualExpand, ReadValue, RuntimeAssign, assign, assign_binop_expand, binary_expand,
+    },
     prelude::{CubeEnum, ExpandTypeClone},
 };
 use crate::{ir::Scope, prelude::Assign};
@@ -65,12 +70,52 @@ pub fn for_expand<I: Iterable>(
     body: impl FnMut(&Scope, I::Item),
 ) {
     if unroll || range.const_len() == Some(1) {
-        range.expand_unroll(scope, body);
+        let mut body = body;
+        range.expand_unroll(scope, |scope, item| {
+            unrolled_iteration(scope, |scope| body(scope, item))
+        });
     } else {
         range.expand(scope, body);
     }
 }
 
+/// Expand one iteration of an unrolled loop.
+///
+/// The iteration is inlined into `scope`, so it gets its own continue flag: a `continue` skips
+/// the rest of the iteration, not the rest of the enclosing loop's. What follows the iteration is
+/// moved out of the predication on that flag, but stays predicated on a `return` or `break` it
+/// may have expanded.
+fn unrolled_iteration(scope: &Scope, body: impl FnOnce(&Scope)) {
+    let outer = *scope.expand_state();
+    let block = scope
+        .inserter()
+        .get_insertion_block(scope.ctx())
+        .expect("Should have insertion block");
+
+    let continue_flag = scope.create_continue_flag();
+    *scope.expand_state_mut() = ExpandState {
+        may_continue: false,
+        inv_continue_flag: Some(continue_flag),
+        ..outer
+    };
+    body(scope);
+    let inner = *scope.expand_state();
+    *scope.expand_state_mut() = outer;
+
+    if inner.may_continue {
+        leave_predication(scope, block);
+        if inner.may_return && !outer.may_return {
+            scope.predicate_on_return();
+        }
+        if inner.may_break && !outer.may_break {
+            scope.predicate_on_break();
+        }
+    } else {
+        scope.expand_state_mut().may_return = inner.may_return;
+        scope.expand_state_mut().may_break = inner.may_break;
+    }
+}
+
 pub fn if_expand(scope: &Scope, condition: NativeExpand<bool>, block: impl FnOnce(&Scope)) {
     let comptime_cond = condition.expand.as_const().map(|it| it.as_bool());
     match comptime_cond {
@@ -690,6 +735,16 @@ pub fn break_expand(scope: &Scope) {
     scope.expand_state_mut().may_break = true;
 }
 
+pub fn continue_expand(scope: &Scope) {
+    let inv_continue_flag = scope
+        .expand_state()
+        .inv_continue_flag
+        .expect("Should be in loop");
+    let false_ = false.__expand_runtime_method(scope).expand;
+    assign::expand_element(scope, false_, inv_continue_flag.into());
+    scope.expand_state_mut().may_continue = true;
+}
+
 pub fn return_expand(scope: &Scope) {
     let inv_return_flag = scope.expand_state().inv_return_flag;
     if let Some(inv_return_flag) = inv_return_flag {
@@ -703,6 +758,142 @@ pub fn return_expand(scope: &Scope) {
     }
 }
 
+/// The return value of an inlined `#[cube]` function that returns early.
+///
+/// Every `return` assigns the returned value to one mutable variable, declared where the function
+/// starts, and clears the function's own return flag so the rest of the function is skipped.
+pub struct FnReturnExpand<C> {
+    out: Option<C>,
+    /// Inserts right after the return flag, so `out` is declared before any use.
+    decl: Scope,
+    /// The return flag of the caller, cleared by `terminate!()`.
+    outer_return_flag: Option<Value>,
+    terminated: bool,
+}
+
+impl<C: Assign> FnReturnExpand<C> {
+    /// Expand `return value`.
+    pub fn ret<R: RuntimeAssign<Expand = C>>(&mut self, scope: &Scope, value: R) {
+        self.value(scope, value);
+        return_expand(scope);
+    }
+
+    /// Expand the return of `?`, converting the residual into the return type.
+    pub fn ret_residual<R>(&mut self, scope: &Scope, residual: R)
+    where
+        C: FromResidualExpand<R> + RuntimeAssign<Expand = C>,
+    {
+        let value = C::__expand_from_residual(scope, residual);
+        self.ret(scope, value);
+    }
+
+    /// Expand the value the function body evaluates to.
+    pub fn value<R: RuntimeAssign<Expand = C>>(&mut self, scope: &Scope, value: R) {
+        let out = self.out.get_or_insert_with(|| value.init_mut(&self.decl));
+        out.__expand_assign_method(scope, value.into_expand(scope));
+    }
+
+    /// Expand `terminate!()`, which returns from the caller too rather than just the function.
+    ///
+    /// Only one level up: a caller that is itself a function with `return`s returns, but its own
+    /// caller carries on.
+    pub fn terminate(&mut self, scope: &Scope) {
+        if let Some(flag) = self.outer_return_flag {
+            let false_ = false.__expand_runtime_method(scope).expand;
+            assign::expand_element(scope, false_, flag.into());
+            self.terminated = true;
+        }
+        return_expand(scope);
+    }
+}
+
+/// Expand the body of a `#[cube]` function that contains `return`.
+///
+/// The function is inlined into the caller's scope, so it gets its own return flag, and whatever
+/// the caller registers after it is moved out of the predication on that flag.
+pub fn fn_return_expand<C: Assign>(
+    scope: &Scope,
+    body: impl FnOnce(&Scope, &mut FnReturnExpand<C>),
+) -> C {
+    let outer = *scope.expand_state();
+    let entry_block = scope
+        .inserter()
+        .get_insertion_block(scope.ctx())
+        .expect("Should have insertion block");
+
+    let return_flag = scope.create_return_flag();
+    let mut decl = OpInserter::new_at_block_start(entry_block);
+    decl.set_insertion_point(OpInsertionPoint::AfterOperation(
+        return_flag.defining_op().expect("Flag should be declared"),
+    ));
+    let mut ret = FnReturnExpand {
+        out: None,
+        decl: scope.child(decl),
+        outer_return_flag: outer.inv_return_flag,
+        terminated: false,
+    };
+
+    *scope.expand_state_mut() = ExpandState {
+        inv_return_flag: Some(return_flag),
+        ..Default::default()
+    };
+    body(scope, &mut ret);
+    if scope.expand_state().may_return {
+        leave_predication(scope, entry_block);
+    }
+    *scope.expand_state_mut() = outer;
+
+    if ret.terminated {
+        scope.predicate_on_return();
+    }
+    ret.out.expect("Function should return a value")
+}
+
+/// Move the insertion point of `scope` after the outermost predication it is nested in, back
+/// into `block`.
+fn leave_predication(scope: &Scope, block: Ptr<BasicBlock>) {
+    let ctx = scope.ctx();
+    let mut current = scope
+        .inserter()
+        .get_insertion_block(ctx)
+        .expect("Should have insertion block");
+    let mut outermost = None;
+    while current != block {
+        let op = current
+            .deref(ctx)
+            .get_parent_op(ctx)
+            .expect("Predication should be nested in the function's block");
+        current = op
+            .deref(ctx)
+            .get_parent_block()
+            .expect("Predication should be nested in the function's block");
+        outermost = Some(op);
+    }
+    if let Some(op) = outermost {
+        scope.inserter().set_insertion_point_after_operation(op);
+    }
+}
+
+/// Reset the continue flag at the end of a loop iteration, outside of the predication on it.
+fn end_iteration(body: &Scope, body_block: Ptr<BasicBlock>) {
+    let Some(inv_continue_flag) = body.expand_state().inv_continue_flag else {
+        return;
+    };
+    if !body.expand_state().may_continue {
+        return;
+    }
+
+    let terminator = body_block.deref(body.ctx()).get_terminator(body.ctx());
+    match terminator {
+        Some(terminator) => body
+            .inserter()
+            .set_insertion_point_before_operation(terminator),
+        None => body.inserter().set_insertion_point_to_block_end(body_block),
+    }
+    let true_ = true.__expand_runtime_method(body).expand;
+    assign::expand_element(body, true_, inv_continue_flag.into());
+}
+
 pub mod unreachable_unchecked {
     use super::*;
 
@@ -741,9 +932,10 @@ impl WhileBuilder {
             cond_scope,
         } = self;
 
-        let body = while_op.after_block(scope.ctx());
-        let body = scope.loop_child(OpInserter::new_at_block_end(body));
+        let body_block = while_op.after_block(scope.ctx());
+        let body = scope.loop_child(OpInserter::new_at_block_end(body_block));
         block(&body);
+        end_iteration(&body, body_block);
         body.terminate_yield();
 
         let expand_state = *body.expand_state();
@@ -761,17 +953,21 @@ impl WhileBuilder {
         cond_scope.register(&ConditionOp::new(scope.ctx_mut(), cond.read_value(scope)));
 
         scope.register(&while_op);
+        scope.set_may_return(&[body]);
     }
 }
 
 /// register a range loop if it contains no break or return, destructure to while if it does
 pub(crate) fn register_range_loop<I: Int>(scope: &Scope, for_op: &RangeLoopOp, body: &Scope) {
+    end_iteration(body, for_op.loop_body(scope.ctx()));
+
     let ctx = scope.ctx_mut();
     let ExpandState {
         may_return,
         may_break,
         inv_return_flag,
         inv_break_flag,
+        ..
     } = *body.expand_state();
     if !may_break && !may_return {
         body.terminate_yield();
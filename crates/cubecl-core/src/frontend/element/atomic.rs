@@ -24,27 +24,77 @@ pub struct Atomic<Inner: CubePrimitive> {
 
 type AtomicExpand<Inner> = NativeExpand<Atomic<Inner>>;
 
+pub use cubecl_ir::dialect::atomic::{AtomicScope, MemoryOrdering};
+
 pub trait AtomicNumeric {
-    fn __expand_fetch_add(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue;
-    fn __expand_fetch_sub(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue;
-    fn __expand_fetch_min(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue;
-    fn __expand_fetch_max(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue;
+    fn __expand_fetch_add(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue;
+    fn __expand_fetch_sub(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue;
+    fn __expand_fetch_min(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue;
+    fn __expand_fetch_max(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue;
 }
 
 macro_rules! atomic_numeric {
     ($($ty: ty),*; $add: ty, $sub: ty, $min: ty, $max: ty) => {
         $(impl AtomicNumeric for $ty {
-            fn __expand_fetch_add(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-                atomic_binary_expand(scope, ptr, value, <$add>::new)
+            fn __expand_fetch_add(
+                scope: &Scope,
+                ptr: ExpandValue,
+                value: ExpandValue,
+                ordering: MemoryOrdering,
+                atomic_scope: AtomicScope,
+            ) -> ExpandValue {
+                atomic_binary_expand(scope, ptr, value, ordering, atomic_scope, <$add>::new)
             }
-            fn __expand_fetch_sub(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-                atomic_binary_expand(scope, ptr, value, <$sub>::new)
+            fn __expand_fetch_sub(
+                scope: &Scope,
+                ptr: ExpandValue,
+                value: ExpandValue,
+                ordering: MemoryOrdering,
+                atomic_scope: AtomicScope,
+            ) -> ExpandValue {
+                atomic_binary_expand(scope, ptr, value, ordering, atomic_scope, <$sub>::new)
             }
-            fn __expand_fetch_min(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-                atomic_binary_expand(scope, ptr, value, <$min>::new)
+            fn __expand_fetch_min(
+                scope: &Scope,
+                ptr: ExpandValue,
+                value: ExpandValue,
+                ordering: MemoryOrdering,
+                atomic_scope: AtomicScope,
+            ) -> ExpandValue {
+                atomic_binary_expand(scope, ptr, value, ordering, atomic_scope, <$min>::new)
             }
-            fn __expand_fetch_max(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-                atomic_binary_expand(scope, ptr, value, <$max>::new)
+            fn __expand_fetch_max(
+                scope: &Scope,
+                ptr: ExpandValue,
+                value: ExpandValue,
+                ordering: MemoryOrdering,
+                atomic_scope: AtomicScope,
+            ) -> ExpandValue {
+                atomic_binary_expand(scope, ptr, value, ordering, atomic_scope, <$max>::new)
             }
         })*
     };
@@ -58,74 +108,266 @@ fn atomic_binary_expand<F, O>(
     scope: &Scope,
     ptr: ExpandValue,
     value: ExpandValue,
+    ordering: MemoryOrdering,
+    atomic_scope: AtomicScope,
     func: F,
 ) -> ExpandValue
 where
-    F: Fn(&mut Context, Value, Value) -> O,
+    F: Fn(&mut Context, Value, Value, MemoryOrdering, AtomicScope) -> O,
     O: Op + OneResultInterface,
 {
-    let op = func(scope.ctx_mut(), ptr.value(scope), value.read_value(scope));
+    let op = func(
+        scope.ctx_mut(),
+        ptr.value(scope),
+        value.read_value(scope),
+        ordering,
+        atomic_scope,
+    );
+    scope.register_with_result(&op).into()
+}
+
+fn atomic_load_expand(
+    scope: &Scope,
+    ptr: ExpandValue,
+    ordering: MemoryOrdering,
+    atomic_scope: AtomicScope,
+) -> ExpandValue {
+    let op = AtomicLoadOp::new(scope.ctx_mut(), ptr.value(scope), ordering, atomic_scope);
     scope.register_with_result(&op).into()
 }
 
+fn atomic_store_expand(
+    scope: &Scope,
+    ptr: ExpandValue,
+    value: ExpandValue,
+    ordering: MemoryOrdering,
+    atomic_scope: AtomicScope,
+) {
+    let op = AtomicStoreOp::new(
+        scope.ctx_mut(),
+        ptr.value(scope),
+        value.read_value(scope),
+        ordering,
+        atomic_scope,
+    );
+    scope.register(&op);
+}
+
+fn atomic_compare_exchange_expand(
+    scope: &Scope,
+    ptr: ExpandValue,
+    cmp: ExpandValue,
+    value: ExpandValue,
+    ordering: MemoryOrdering,
+    atomic_scope: AtomicScope,
+) -> ExpandValue {
+    let op = AtomicCompareExchangeWeakOp::new(
+        scope.ctx_mut(),
+        ptr.value(scope),
+        cmp.read_value(scope),
+        value.read_value(scope),
+        ordering,
+        atomic_scope,
+    );
+    scope.register_with_result(&op).into()
+}
+
+/// The ordering and scope used by the atomic operations that don't take them explicitly.
+const DEFAULT: MemoryOrdering = MemoryOrdering::BackendDefault;
+const DEVICE: AtomicScope = AtomicScope::Device;
+
+/// Operations without an explicit ordering use [`MemoryOrdering::BackendDefault`] at
+/// [`AtomicScope::Device`], which is at least relaxed and enough for counters and reductions. The
+/// `_ordered` variants take both, for algorithms that publish data through the atomic (i.e.
+/// decoupled lookback or locks).
 #[cube]
 impl<Inner: CubePrimitive<Scalar: AtomicNumeric>> Atomic<Inner> {
     /// Load the value of the atomic.
     pub fn load(&self) -> Inner {
+        intrinsic!(|scope| atomic_load_expand(scope, self.expand, DEFAULT, DEVICE).into())
+    }
+
+    /// Load the value of the atomic with an explicit ordering and scope. The ordering can't be
+    /// [`MemoryOrdering::Release`] or [`MemoryOrdering::AcqRel`].
+    pub fn load_ordered(
+        &self,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let op = AtomicLoadOp::new(scope.ctx_mut(), ptr);
-            scope.register_with_result(&op).into()
+            assert!(
+                !matches!(ordering, MemoryOrdering::Release | MemoryOrdering::AcqRel),
+                "An atomic load can't have {ordering:?} ordering"
+            );
+            atomic_load_expand(scope, self.expand, ordering, atomic_scope).into()
         })
     }
 
     /// Store the value of the atomic.
     pub fn store(&self, value: Inner) {
+        intrinsic!(|scope| atomic_store_expand(scope, self.expand, value.expand, DEFAULT, DEVICE))
+    }
+
+    /// Store the value of the atomic with an explicit ordering and scope. The ordering can't be
+    /// [`MemoryOrdering::Acquire`] or [`MemoryOrdering::AcqRel`].
+    pub fn store_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let value = value.read_value(scope);
-            scope.register(&AtomicStoreOp::new(scope.ctx_mut(), ptr, value));
+            assert!(
+                !matches!(ordering, MemoryOrdering::Acquire | MemoryOrdering::AcqRel),
+                "An atomic store can't have {ordering:?} ordering"
+            );
+            atomic_store_expand(scope, self.expand, value.expand, ordering, atomic_scope)
         })
     }
 
     /// Atomically stores the value into the atomic and returns the old value.
     pub fn exchange(&self, value: Inner) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let value = value.read_value(scope);
-            let op = AtomicExchangeOp::new(scope.ctx_mut(), ptr, value);
-            scope.register_with_result(&op).into()
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                DEFAULT,
+                DEVICE,
+                AtomicExchangeOp::new,
+            )
+            .into()
+        })
+    }
+
+    /// [`exchange`](Self::exchange) with an explicit ordering and scope.
+    pub fn exchange_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+                AtomicExchangeOp::new,
+            )
+            .into()
         })
     }
 
     /// Atomically add a number to the atomic variable. Returns the old value.
     pub fn fetch_add(&self, value: Inner) -> Inner {
-        intrinsic!(
-            |scope| Inner::Scalar::__expand_fetch_add(scope, self.expand, value.expand).into()
-        )
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_add(scope, self.expand, value.expand, DEFAULT, DEVICE)
+                .into()
+        })
+    }
+
+    /// [`fetch_add`](Self::fetch_add) with an explicit ordering and scope.
+    pub fn fetch_add_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_add(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+            )
+            .into()
+        })
     }
 
     /// Atomically subtracts a number from the atomic variable. Returns the old value.
     pub fn fetch_sub(&self, value: Inner) -> Inner {
-        intrinsic!(
-            |scope| Inner::Scalar::__expand_fetch_sub(scope, self.expand, value.expand).into()
-        )
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_sub(scope, self.expand, value.expand, DEFAULT, DEVICE)
+                .into()
+        })
+    }
+
+    /// [`fetch_sub`](Self::fetch_sub) with an explicit ordering and scope.
+    pub fn fetch_sub_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_sub(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+            )
+            .into()
+        })
     }
 
     /// Atomically sets the value of the atomic variable to `max(current_value, value)`. Returns
     /// the old value.
     pub fn fetch_max(&self, value: Inner) -> Inner {
-        intrinsic!(
-            |scope| Inner::Scalar::__expand_fetch_max(scope, self.expand, value.expand).into()
-        )
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_max(scope, self.expand, value.expand, DEFAULT, DEVICE)
+                .into()
+        })
+    }
+
+    /// [`fetch_max`](Self::fetch_max) with an explicit ordering and scope.
+    pub fn fetch_max_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_max(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+            )
+            .into()
+        })
     }
 
     /// Atomically sets the value of the atomic variable to `min(current_value, value)`. Returns the
     /// old value.
     pub fn fetch_min(&self, value: Inner) -> Inner {
-        intrinsic!(
-            |scope| Inner::Scalar::__expand_fetch_min(scope, self.expand, value.expand).into()
-        )
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_min(scope, self.expand, value.expand, DEFAULT, DEVICE)
+                .into()
+        })
+    }
+
+    /// [`fetch_min`](Self::fetch_min) with an explicit ordering and scope.
+    pub fn fetch_min_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            Inner::Scalar::__expand_fetch_min(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+            )
+            .into()
+        })
     }
 }
 
@@ -138,41 +380,143 @@ impl<Inner: CubePrimitive<Scalar: Int>> Atomic<Inner> {
     /// Compare the returned value to `cmp` to determine whether the store was successful.
     pub fn compare_exchange_weak(&self, cmp: Inner, value: Inner) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let cmp = cmp.read_value(scope);
-            let value = value.read_value(scope);
-            let op = AtomicCompareExchangeWeakOp::new(scope.ctx_mut(), ptr, cmp, value);
-            scope.register_with_result(&op).into()
+            atomic_compare_exchange_expand(
+                scope,
+                self.expand,
+                cmp.expand,
+                value.expand,
+                DEFAULT,
+                DEVICE,
+            )
+            .into()
+        })
+    }
+
+    /// [`compare_exchange_weak`](Self::compare_exchange_weak) with an explicit ordering and scope.
+    /// `ordering` applies when the store happens, a failed exchange uses
+    /// [`MemoryOrdering::failure`] of it.
+    pub fn compare_exchange_weak_ordered(
+        &self,
+        cmp: Inner,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            atomic_compare_exchange_expand(
+                scope,
+                self.expand,
+                cmp.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+            )
+            .into()
         })
     }
 
     /// Executes an atomic bitwise and operation on the atomic variable. Returns the old value.
     pub fn fetch_and(&self, value: Inner) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let value = value.read_value(scope);
-            let op = AtomicAndOp::new(scope.ctx_mut(), ptr, value);
-            scope.register_with_result(&op).into()
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                DEFAULT,
+                DEVICE,
+                AtomicAndOp::new,
+            )
+            .into()
+        })
+    }
+
+    /// [`fetch_and`](Self::fetch_and) with an explicit ordering and scope.
+    pub fn fetch_and_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+                AtomicAndOp::new,
+            )
+            .into()
         })
     }
 
     /// Executes an atomic bitwise or operation on the atomic variable. Returns the old value.
     pub fn fetch_or(&self, value: Inner) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let value = value.read_value(scope);
-            let op = AtomicOrOp::new(scope.ctx_mut(), ptr, value);
-            scope.register_with_result(&op).into()
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                DEFAULT,
+                DEVICE,
+                AtomicOrOp::new,
+            )
+            .into()
+        })
+    }
+
+    /// [`fetch_or`](Self::fetch_or) with an explicit ordering and scope.
+    pub fn fetch_or_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+                AtomicOrOp::new,
+            )
+            .into()
         })
     }
 
     /// Executes an atomic bitwise xor operation on the atomic variable. Returns the old value.
     pub fn fetch_xor(&self, value: Inner) -> Inner {
         intrinsic!(|scope| {
-            let ptr = self.value(scope);
-            let value = value.read_value(scope);
-            let op = AtomicXorOp::new(scope.ctx_mut(), ptr, value);
-            scope.register_with_result(&op).into()
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                DEFAULT,
+                DEVICE,
+                AtomicXorOp::new,
+            )
+            .into()
+        })
+    }
+
+    /// [`fetch_xor`](Self::fetch_xor) with an explicit ordering and scope.
+    pub fn fetch_xor_ordered(
+        &self,
+        value: Inner,
+        #[comptime] ordering: MemoryOrdering,
+        #[comptime] atomic_scope: AtomicScope,
+    ) -> Inner {
+        intrinsic!(|scope| {
+            atomic_binary_expand(
+                scope,
+                self.expand,
+                value.expand,
+                ordering,
+                atomic_scope,
+                AtomicXorOp::new,
+            )
+            .into()
         })
     }
 }
@@ -202,3 +546,60 @@ impl<Inner: CubePrimitive> CubePrimitive for Atomic<Inner> {
 }
 
 impl<Inner: CubePrimitive> NativeAssign for Atomic<Inner> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::*;
+    use crate::ir::{UIntKind, settings::Dim3};
+
+    #[cube]
+    fn unordered(atomics: &[Atomic<u32>]) {
+        atomics[0].store(1);
+        let value = atomics[0].load();
+        atomics[1].fetch_add(value);
+    }
+
+    #[cube]
+    fn ordered(atomics: &[Atomic<u32>]) {
+        atomics[0].store_ordered(1, MemoryOrdering::Release, AtomicScope::Device);
+        let value = atomics[0].load_ordered(MemoryOrdering::Relaxed, AtomicScope::Device);
+        atomics[1].fetch_add_ordered(value, MemoryOrdering::SeqCst, AtomicScope::Device);
+    }
+
+    /// The module printed after expanding `kernel` over a buffer of atomics.
+    fn expand_atomics(kernel: impl FnOnce(&Scope, &'static NativeExpand<[Atomic<u32>]>)) -> String {
+        let mut builder = KernelBuilder::new(KernelSettings::new(
+            Dim3::new_single(),
+            ExecutionMode::Checked,
+            AddressType::U32,
+        ));
+        builder
+            .scope
+            .register_type::<usize>(ElemType::UInt(UIntKind::U32));
+
+        let arg = BufferCompilationArg { inplace: None };
+        let atomics = <&'static [Atomic<u32>] as LaunchArg>::expand(&arg, &mut builder);
+        kernel(&builder.scope, atomics);
+        builder.scope.to_string()
+    }
+
+    #[test]
+    fn unordered_atomics_keep_the_backend_default() {
+        let module = expand_atomics(|scope, atomics| unordered::expand(scope, atomics));
+
+        assert_eq!(module.matches("BackendDefault").count(), 3);
+        assert!(!module.contains("Relaxed"));
+    }
+
+    #[test]
+    fn ordered_atomics_keep_their_ordering() {
+        let module = expand_atomics(|scope, atomics| ordered::expand(scope, atomics));
+
+        assert!(module.contains("Release"));
+        assert!(module.contains("Relaxed"));
+        assert!(module.contains("SeqCst"));
+        assert!(!module.contains("BackendDefault"));
+    }
+}
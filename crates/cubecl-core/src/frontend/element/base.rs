@@ -6,7 +6,7 @@ use crate::{
 };
 use alloc::{boxed::Box, vec::Vec};
 use core::{fmt::Debug, marker::PhantomData};
-use cubecl_common::{e2m1, e2m1x2, e2m3, e3m2, e4m3, e5m2, flex32, tf32, ue8m0};
+use cubecl_common::{e2m1, e2m1x2, e2m3, e3m2, e4m3, e5m2, flex32, i4x2, tf32, u4x2, ue8m0};
 use cubecl_ir::{
     VectorSize, ident,
     interfaces::TypedExt,
@@ -233,8 +233,9 @@ pub trait CubeEnum: Sized {
 
     fn discriminant(&self) -> NativeExpand<i32>;
 
-    /// Return the runtime value of this enum, if only one variant has a value.
-    /// Should return () for all other cases.
+    /// Return the runtime value of this enum. This is the value of the variants if they all share
+    /// one value type, a tuple with one entry per value type if they don't, and `()` if no
+    /// variant has a value.
     fn runtime_value(self) -> Self::RuntimeValue;
 
     fn discriminant_of_value(&self, variant_name: &'static str) -> i32 {
@@ -707,6 +708,8 @@ from_const!(tf32);
 from_const!(f32);
 from_const!(e2m1);
 from_const!(e2m1x2);
+from_const!(i4x2);
+from_const!(u4x2);
 from_const!(e2m3);
 from_const!(e3m2);
 from_const!(e4m3);
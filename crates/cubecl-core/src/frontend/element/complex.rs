@@ -0,0 +1,207 @@
+use cubecl_common::{c32, c64};
+use cubecl_ir::{
+    ComplexKind, ConstantValue, ElemType, ExpandValue, Scope,
+    dialect::complex::{
+        ComplexAbsOp, ComplexConjOp, ComplexExpOp, ComplexImagOp, ComplexNewOp, ComplexRealOp,
+    },
+    types::scalar::{ComplexType, Float32Type, Float64Type},
+};
+use pliron::r#type::TypeHandle;
+
+use crate::{frontend::operation::base::unary_expand, prelude::*};
+
+/// Complex numbers, stored in buffers as interleaved `[re, im]` pairs.
+///
+/// Complex values are lowered to float math on their parts before codegen, so they're supported
+/// wherever [`Complex::Real`] is.
+pub trait Complex:
+    Scalar
+    + ScalarAdd
+    + ScalarSub
+    + ScalarMul
+    + ScalarDiv
+    + ScalarNeg
+    + IntoMut
+    + IntoExpand<Expand = NativeExpand<Self>>
+{
+    /// The type of the real and imaginary parts
+    type Real: Float;
+
+    fn new(re: Self::Real, im: Self::Real) -> Self;
+    fn from_real(re: Self::Real) -> Self;
+    fn real(self) -> Self::Real;
+    fn imag(self) -> Self::Real;
+    /// The complex conjugate, `re - im * i`
+    fn conj(self) -> Self;
+    /// The magnitude, `sqrt(re^2 + im^2)`
+    fn abs(self) -> Self::Real;
+    /// `e` raised to the power of `self`
+    fn exp(self) -> Self;
+
+    fn __expand_new(
+        scope: &Scope,
+        re: NativeExpand<Self::Real>,
+        im: NativeExpand<Self::Real>,
+    ) -> NativeExpand<Self> {
+        let re = re.read_value(scope);
+        let im = im.read_value(scope);
+        let op = ComplexNewOp::new(scope.ctx_mut(), re, im);
+        scope.register_with_result(&op).into()
+    }
+
+    fn __expand_from_real(scope: &Scope, re: NativeExpand<Self::Real>) -> NativeExpand<Self> {
+        let im = Self::Real::__expand_new(scope, 0.0);
+        Self::__expand_new(scope, re, im)
+    }
+
+    fn __expand_real(scope: &Scope, x: NativeExpand<Self>) -> NativeExpand<Self::Real> {
+        x.__expand_real_method(scope)
+    }
+
+    fn __expand_imag(scope: &Scope, x: NativeExpand<Self>) -> NativeExpand<Self::Real> {
+        x.__expand_imag_method(scope)
+    }
+
+    fn __expand_conj(scope: &Scope, x: NativeExpand<Self>) -> NativeExpand<Self> {
+        x.__expand_conj_method(scope)
+    }
+
+    fn __expand_abs(scope: &Scope, x: NativeExpand<Self>) -> NativeExpand<Self::Real> {
+        x.__expand_abs_method(scope)
+    }
+
+    fn __expand_exp(scope: &Scope, x: NativeExpand<Self>) -> NativeExpand<Self> {
+        x.__expand_exp_method(scope)
+    }
+}
+
+pub trait ComplexExpand {
+    type Real;
+
+    fn __expand_real_method(self, scope: &Scope) -> Self::Real;
+    fn __expand_imag_method(self, scope: &Scope) -> Self::Real;
+    fn __expand_conj_method(self, scope: &Scope) -> Self;
+    fn __expand_abs_method(self, scope: &Scope) -> Self::Real;
+    fn __expand_exp_method(self, scope: &Scope) -> Self;
+}
+
+impl<C: Complex> ComplexExpand for NativeExpand<C> {
+    type Real = NativeExpand<C::Real>;
+
+    fn __expand_real_method(self, scope: &Scope) -> Self::Real {
+        unary_expand(scope, self.expand, ComplexRealOp::new).into()
+    }
+
+    fn __expand_imag_method(self, scope: &Scope) -> Self::Real {
+        unary_expand(scope, self.expand, ComplexImagOp::new).into()
+    }
+
+    fn __expand_conj_method(self, scope: &Scope) -> Self {
+        unary_expand(scope, self.expand, ComplexConjOp::new).into()
+    }
+
+    fn __expand_abs_method(self, scope: &Scope) -> Self::Real {
+        unary_expand(scope, self.expand, ComplexAbsOp::new).into()
+    }
+
+    fn __expand_exp_method(self, scope: &Scope) -> Self {
+        unary_expand(scope, self.expand, ComplexExpOp::new).into()
+    }
+}
+
+/// Splits a complex value into its real and imaginary parts.
+pub(crate) fn expand_parts(scope: &Scope, value: ExpandValue) -> (ExpandValue, ExpandValue) {
+    let re = unary_expand(scope, value, ComplexRealOp::new);
+    let im = unary_expand(scope, value, ComplexImagOp::new);
+    (re, im)
+}
+
+macro_rules! impl_complex {
+    ($primitive: ident, $real: ident, $real_ty: ty, $kind: ident) => {
+        impl CubeType for $primitive {
+            type ExpandType = NativeExpand<$primitive>;
+        }
+
+        impl CubeDebug for $primitive {}
+        impl Scalar for $primitive {
+            fn elem_type_native() -> ElemType {
+                ComplexKind::$kind.into()
+            }
+        }
+        impl CubePrimitive for $primitive {
+            type Scalar = Self;
+            type Size = Const<1>;
+            type WithScalar<S: Scalar> = S;
+
+            fn __expand_as_type(scope: &Scope) -> TypeHandle {
+                let ctx = scope.ctx();
+                ComplexType::get(ctx, <$real_ty>::get(ctx).into()).into()
+            }
+
+            fn from_const_value(value: ConstantValue) -> Self {
+                let ConstantValue::Float(value) = value else {
+                    unreachable!()
+                };
+                $primitive::from_real(value as $real)
+            }
+        }
+
+        // There's no complex constant, so build it from its parts
+        impl IntoRuntime for $primitive {
+            fn __expand_runtime_method(self, scope: &Scope) -> NativeExpand<Self> {
+                <$primitive as Complex>::__expand_new(scope, self.re.into(), self.im.into())
+            }
+        }
+
+        impl IntoExpand for $primitive {
+            type Expand = NativeExpand<$primitive>;
+
+            fn into_expand(self, scope: &Scope) -> Self::Expand {
+                self.__expand_runtime_method(scope)
+            }
+        }
+
+        impl NativeAssign for $primitive {}
+
+        impl IntoMut for $primitive {
+            fn into_mut(self, _scope: &Scope) -> Self {
+                self
+            }
+        }
+
+        impl Complex for $primitive {
+            type Real = $real;
+
+            fn new(re: $real, im: $real) -> Self {
+                $primitive::new(re, im)
+            }
+
+            fn from_real(re: $real) -> Self {
+                $primitive::from_real(re)
+            }
+
+            fn real(self) -> $real {
+                self.re
+            }
+
+            fn imag(self) -> $real {
+                self.im
+            }
+
+            fn conj(self) -> Self {
+                $primitive::conj(self)
+            }
+
+            fn abs(self) -> $real {
+                $primitive::abs(self)
+            }
+
+            fn exp(self) -> Self {
+                $primitive::exp(self)
+            }
+        }
+    };
+}
+
+impl_complex!(c32, f32, Float32Type, C32);
+impl_complex!(c64, f64, Float64Type, C64);
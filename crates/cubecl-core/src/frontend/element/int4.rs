@@ -0,0 +1,60 @@
+use cubecl_common::{i4x2, u4x2};
+use cubecl_ir::{
+    ConstantValue, ElemType, IntKind, Scope, UIntKind,
+    types::scalar::{Int4x2Type, UInt4x2Type},
+};
+use pliron::r#type::TypeHandle;
+
+use crate::prelude::*;
+
+macro_rules! impl_int4x2 {
+    ($primitive: ident, $elem: ty, $kind: expr, $ty: ident, $const: ident) => {
+        impl CubeType for $primitive {
+            type ExpandType = NativeExpand<$primitive>;
+        }
+
+        impl CubeDebug for $primitive {}
+        // Like `e2m1x2`, this is a `u8` holding two values, so it's treated as a scalar that can be
+        // stored in a `Vector`. Cast a `Vector<_, N>` of it to a `Vector<_, 2N>` of a wider type to
+        // unpack it.
+        impl Scalar for $primitive {
+            fn elem_type_native() -> ElemType {
+                $kind.into()
+            }
+        }
+        impl CubePrimitive for $primitive {
+            type Scalar = Self;
+            type Size = Const<1>;
+            type WithScalar<S: Scalar> = S;
+
+            fn __expand_as_type(scope: &Scope) -> TypeHandle {
+                $ty::get(scope.ctx()).into()
+            }
+
+            fn from_const_value(value: ConstantValue) -> Self {
+                let ConstantValue::$const(value) = value else {
+                    unreachable!()
+                };
+                // Fill both values, same as `e2m1x2`
+                $primitive::new(value as $elem, value as $elem)
+            }
+        }
+
+        impl IntoRuntime for $primitive {
+            fn __expand_runtime_method(self, _scope: &Scope) -> NativeExpand<Self> {
+                self.into()
+            }
+        }
+        impl IntoExpand for $primitive {
+            type Expand = NativeExpand<$primitive>;
+            fn into_expand(self, _scope: &Scope) -> Self::Expand {
+                self.into()
+            }
+        }
+
+        impl NativeAssign for $primitive {}
+    };
+}
+
+impl_int4x2!(i4x2, i8, IntKind::I4x2, Int4x2Type, Int);
+impl_int4x2!(u4x2, u8, UIntKind::U4x2, UInt4x2Type, UInt);
@@ -2,9 +2,11 @@ mod atomic;
 mod base;
 mod bool;
 mod cast;
+mod complex;
 mod cube_elem;
 mod float;
 mod int;
+mod int4;
 mod numeric;
 mod typemap;
 mod uint;
@@ -13,6 +15,7 @@ pub use atomic::*;
 pub use base::*;
 pub use bool::*;
 pub use cast::*;
+pub use complex::*;
 pub use cube_elem::*;
 pub use float::*;
 pub use int::*;
@@ -54,6 +54,33 @@ macro_rules! binary_dispatch {
     };
 }
 
+macro_rules! plane_dispatch {
+    ($method: ident, $scope: expr, $input: expr, $cluster_size: expr) => {{
+        let cluster_size = $cluster_size;
+        unary_expand::<Self>(
+            $scope,
+            $input,
+            |scope, value| i32::$method(scope, value, cluster_size),
+            |scope, value| u32::$method(scope, value, cluster_size),
+            |scope, value| f32::$method(scope, value, cluster_size),
+        )
+    }};
+}
+
+macro_rules! atomic_dispatch {
+    ($method: ident, $scope: expr, $ptr: expr, $value: expr, $ordering: expr, $atomic_scope: expr) => {{
+        let (ordering, atomic_scope) = ($ordering, $atomic_scope);
+        binary_expand::<Self>(
+            $scope,
+            $ptr,
+            $value,
+            |scope, ptr, value| i32::$method(scope, ptr, value, ordering, atomic_scope),
+            |scope, ptr, value| u32::$method(scope, ptr, value, ordering, atomic_scope),
+            |scope, ptr, value| f32::$method(scope, ptr, value, ordering, atomic_scope),
+        )
+    }};
+}
+
 macro_rules! binary_dispatch_int {
     ($method: ident, $scope: expr, $lhs: expr, $rhs: expr) => {
         binary_expand::<Self>($scope, $lhs, $rhs, i32::$method, u32::$method, i32::$method)
@@ -375,23 +402,79 @@ impl<Marker: 'static> Numeric for DynamicScalar<Marker> {
 }
 
 impl<Marker: 'static> AtomicNumeric for DynamicScalar<Marker> {
-    fn __expand_fetch_add(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-        binary_dispatch!(__expand_fetch_add, scope, ptr, value)
-    }
-    fn __expand_fetch_sub(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-        binary_dispatch!(__expand_fetch_sub, scope, ptr, value)
-    }
-    fn __expand_fetch_min(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-        binary_dispatch!(__expand_fetch_min, scope, ptr, value)
-    }
-    fn __expand_fetch_max(scope: &Scope, ptr: ExpandValue, value: ExpandValue) -> ExpandValue {
-        binary_dispatch!(__expand_fetch_max, scope, ptr, value)
+    fn __expand_fetch_add(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue {
+        atomic_dispatch!(
+            __expand_fetch_add,
+            scope,
+            ptr,
+            value,
+            ordering,
+            atomic_scope
+        )
+    }
+    fn __expand_fetch_sub(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue {
+        atomic_dispatch!(
+            __expand_fetch_sub,
+            scope,
+            ptr,
+            value,
+            ordering,
+            atomic_scope
+        )
+    }
+    fn __expand_fetch_min(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue {
+        atomic_dispatch!(
+            __expand_fetch_min,
+            scope,
+            ptr,
+            value,
+            ordering,
+            atomic_scope
+        )
+    }
+    fn __expand_fetch_max(
+        scope: &Scope,
+        ptr: ExpandValue,
+        value: ExpandValue,
+        ordering: MemoryOrdering,
+        atomic_scope: AtomicScope,
+    ) -> ExpandValue {
+        atomic_dispatch!(
+            __expand_fetch_max,
+            scope,
+            ptr,
+            value,
+            ordering,
+            atomic_scope
+        )
     }
 }
 
 impl<Marker: 'static> PlaneNumeric for DynamicScalar<Marker> {
-    fn __expand_native_sum(scope: &Scope, value: ExpandValue) -> ExpandValue {
-        unary_dispatch!(__expand_native_sum, scope, value)
+    fn __expand_native_sum(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue {
+        plane_dispatch!(__expand_native_sum, scope, value, cluster_size)
     }
     fn __expand_native_inclusive_sum(scope: &Scope, value: ExpandValue) -> ExpandValue {
         unary_dispatch!(__expand_native_inclusive_sum, scope, value)
@@ -399,8 +482,12 @@ impl<Marker: 'static> PlaneNumeric for DynamicScalar<Marker> {
     fn __expand_native_exclusive_sum(scope: &Scope, value: ExpandValue) -> ExpandValue {
         unary_dispatch!(__expand_native_exclusive_sum, scope, value)
     }
-    fn __expand_native_prod(scope: &Scope, value: ExpandValue) -> ExpandValue {
-        unary_dispatch!(__expand_native_prod, scope, value)
+    fn __expand_native_prod(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue {
+        plane_dispatch!(__expand_native_prod, scope, value, cluster_size)
     }
     fn __expand_native_inclusive_prod(scope: &Scope, value: ExpandValue) -> ExpandValue {
         unary_dispatch!(__expand_native_inclusive_prod, scope, value)
@@ -408,11 +495,19 @@ impl<Marker: 'static> PlaneNumeric for DynamicScalar<Marker> {
     fn __expand_native_exclusive_prod(scope: &Scope, value: ExpandValue) -> ExpandValue {
         unary_dispatch!(__expand_native_exclusive_prod, scope, value)
     }
-    fn __expand_native_plane_min(scope: &Scope, value: ExpandValue) -> ExpandValue {
-        unary_dispatch!(__expand_native_plane_min, scope, value)
+    fn __expand_native_plane_min(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue {
+        plane_dispatch!(__expand_native_plane_min, scope, value, cluster_size)
     }
-    fn __expand_native_plane_max(scope: &Scope, value: ExpandValue) -> ExpandValue {
-        unary_dispatch!(__expand_native_plane_max, scope, value)
+    fn __expand_native_plane_max(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue {
+        plane_dispatch!(__expand_native_plane_max, scope, value, cluster_size)
     }
 }
 
@@ -22,6 +22,7 @@ mod plane;
 pub mod polyfills;
 mod ranges;
 mod runtime_option;
+mod runtime_result;
 mod scalar;
 mod tensor_layout;
 mod topology;
@@ -43,6 +44,7 @@ pub use options::*;
 pub use plane::*;
 pub use ranges::*;
 pub use runtime_option::*;
+pub use runtime_result::*;
 pub use scalar::*;
 pub use synchronization::*;
 pub use tensor_layout::*;
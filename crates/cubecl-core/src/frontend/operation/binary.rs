@@ -7,11 +7,13 @@ use crate::{
 use crate::{frontend::CubeType, tf32};
 use crate::{frontend::operation::base::binary_expand, unexpanded};
 use core::ops::*;
+use cubecl_common::{c32, c64};
 use cubecl_ir::dialect::{
     bitwise::*,
+    complex::{ComplexDivOp, ComplexMulOp},
     general::{BoolAndOp, BoolOrOp},
     math::*,
-    vector::{FDotOp, SDotOp, UDotOp},
+    vector::{FDotOp, SDot4x8PackedOp, SDotOp, UDotOp},
 };
 use half::{bf16, f16};
 
@@ -350,16 +352,20 @@ define_core_binop!(Rem, rem);
 
 impl_core_binop!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize; Add, add, IAddOp);
 impl_core_binop!(f16, bf16, f32, flex32, tf32, f64; Add, add, FAddOp);
+impl_core_binop!(c32, c64; Add, add, FAddOp);
 
 impl_core_binop!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize; Sub, sub, ISubOp);
 impl_core_binop!(f16, bf16, f32, flex32, tf32, f64; Sub, sub, FSubOp);
+impl_core_binop!(c32, c64; Sub, sub, FSubOp);
 
 impl_core_binop!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize; Mul, mul, IMulOp);
 impl_core_binop!(f16, bf16, f32, flex32, tf32, f64; Mul, mul, FMulOp);
+impl_core_binop!(c32, c64; Mul, mul, ComplexMulOp);
 
 impl_core_binop!(i8, i16, i32, i64, isize; Div, div, SDivOp);
 impl_core_binop!(u8, u16, u32, u64, usize; Div, div, UDivOp);
 impl_core_binop!(f16, bf16, f32, flex32, tf32, f64; Div, div, FDivOp);
+impl_core_binop!(c32, c64; Div, div, ComplexDivOp);
 
 impl_core_binop!(i8, i16, i32, i64, isize; Rem, rem, SRemOp);
 impl_core_binop!(u8, u16, u32, u64, usize; Rem, rem, URemOp);
@@ -488,6 +494,26 @@ impl_binary_func_scalar_out!(i8, i16, i32, i64, isize; Dot, dot, SDotOp, IMulOp)
 impl_binary_func_scalar_out!(u8, u16, u32, u64, usize; Dot, dot, UDotOp, IMulOp);
 impl_binary_func_scalar_out!(f16, bf16, flex32, tf32, f32, f64; Dot, dot, FDotOp, FMulOp);
 
+/// Dot product of the four `i8` lanes packed in `lhs` and `rhs`, accumulated in `i32` so the
+/// products can't wrap. Lowers to `dp4a` style instructions where the target has them.
+#[allow(unused_variables)]
+pub fn dot4_i8_packed(lhs: u32, rhs: u32) -> i32 {
+    unexpanded!()
+}
+
+/// Expand method of [`dot4_i8_packed()`].
+pub mod dot4_i8_packed {
+    use super::*;
+
+    pub fn expand(
+        scope: &Scope,
+        lhs: NativeExpand<u32>,
+        rhs: NativeExpand<u32>,
+    ) -> NativeExpand<i32> {
+        binary_expand(scope, lhs.into(), rhs.into(), SDot4x8PackedOp::new).into()
+    }
+}
+
 impl_binary_func_mixed_types!(
     Powi, powi, i32, PowiOp, f16, bf16, flex32, tf32, f32, f64, i8, i16, i32, i64, u8, u16, u32,
     u64, usize, isize
@@ -2,10 +2,17 @@ use core::cmp::Ordering;
 use cubecl_common::*;
 use half::{bf16, f16};
 
-use cubecl_ir::{ExpandValue, dialect::cmp::*};
+use cubecl_ir::{
+    ExpandValue,
+    dialect::{
+        cmp::*,
+        general::{BoolAndOp, BoolOrOp},
+    },
+};
 
 use crate as cubecl;
 use crate::frontend::NativeExpand;
+use crate::frontend::element::expand_parts;
 use crate::ir::Scope;
 use crate::prelude::*;
 
@@ -96,10 +103,44 @@ macro_rules! impl_partial_eq {
 }
 
 impl_partial_eq!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize; IEqualOp, INotEqualOp);
+// Packed ints are equal when their bytes are
+impl_partial_eq!(i4x2, u4x2; IEqualOp, INotEqualOp);
 impl_partial_eq!(f16, bf16, f32, flex32, tf32, f64; FEqualOp, FNotEqualOp);
 impl_partial_eq!(e2m1, e2m1x2, e3m2, e2m3, e4m3, e5m2, ue8m0; FEqualOp, FNotEqualOp);
 impl_partial_eq!(bool; BoolEqualOp, BoolNotEqualOp);
 
+// Complex numbers are equal when both parts are equal
+macro_rules! impl_partial_eq_complex {
+    ($($ty: ty),*) => {
+        $(impl PartialEqNativeExpand for $ty {
+            fn __expand_native_eq(
+                scope: &Scope,
+                lhs: ExpandValue,
+                rhs: ExpandValue,
+            ) -> ExpandValue {
+                let (lhs_re, lhs_im) = expand_parts(scope, lhs);
+                let (rhs_re, rhs_im) = expand_parts(scope, rhs);
+                let re = binary_expand(scope, lhs_re, rhs_re, FEqualOp::new);
+                let im = binary_expand(scope, lhs_im, rhs_im, FEqualOp::new);
+                binary_expand(scope, re, im, BoolAndOp::new)
+            }
+            fn __expand_native_ne(
+                scope: &Scope,
+                lhs: ExpandValue,
+                rhs: ExpandValue,
+            ) -> ExpandValue {
+                let (lhs_re, lhs_im) = expand_parts(scope, lhs);
+                let (rhs_re, rhs_im) = expand_parts(scope, rhs);
+                let re = binary_expand(scope, lhs_re, rhs_re, FNotEqualOp::new);
+                let im = binary_expand(scope, lhs_im, rhs_im, FNotEqualOp::new);
+                binary_expand(scope, re, im, BoolOrOp::new)
+            }
+        })*
+    };
+}
+
+impl_partial_eq_complex!(c32, c64);
+
 #[derive_expand(CubeType, CubeTypeMut, IntoRuntime)]
 #[cube(runtime_variants, no_constructors)]
 pub enum Ordering {
@@ -1,5 +1,5 @@
 use core::ops::{Div, Neg, Not};
-use cubecl_common::{e2m1, e2m1x2, e4m3, e5m2, ue8m0};
+use cubecl_common::{c32, c64, e2m1, e2m1x2, e4m3, e5m2, ue8m0};
 use cubecl_ir::dialect::{bitwise::*, general::BoolNotOp, math::*, vector::*};
 use half::{bf16, f16};
 
@@ -313,6 +313,7 @@ impl_not!(
 define_core_unop!(Neg, neg);
 impl_core_unop!(i8, i16, i32, i64, isize; Neg, neg, SNegOp);
 impl_core_unop!(f16, bf16, f32, flex32, tf32, f64; Neg, neg, FNegOp);
+impl_core_unop!(c32, c64; Neg, neg, FNegOp);
 
 define_unary_func!(Abs, abs, SAbsOp, i8, i16, i32, i64, isize);
 impl_unary_func_nop!(u8, u16, u32, u64, usize; Abs, abs);
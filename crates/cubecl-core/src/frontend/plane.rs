@@ -4,28 +4,50 @@ use half::{bf16, f16};
 use super::{CubePrimitive, Vector};
 use crate::prelude::*;
 use crate::{
+    self as cubecl,
     ir::{Scope, attributes::IndexAttr, dialect::plane::*},
     unexpanded,
 };
 
 pub trait PlaneNumeric {
-    fn __expand_native_sum(scope: &Scope, value: ExpandValue) -> ExpandValue;
+    fn __expand_native_sum(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue;
     fn __expand_native_inclusive_sum(scope: &Scope, value: ExpandValue) -> ExpandValue;
     fn __expand_native_exclusive_sum(scope: &Scope, value: ExpandValue) -> ExpandValue;
 
-    fn __expand_native_prod(scope: &Scope, value: ExpandValue) -> ExpandValue;
+    fn __expand_native_prod(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue;
     fn __expand_native_inclusive_prod(scope: &Scope, value: ExpandValue) -> ExpandValue;
     fn __expand_native_exclusive_prod(scope: &Scope, value: ExpandValue) -> ExpandValue;
 
-    fn __expand_native_plane_min(scope: &Scope, value: ExpandValue) -> ExpandValue;
-    fn __expand_native_plane_max(scope: &Scope, value: ExpandValue) -> ExpandValue;
+    fn __expand_native_plane_min(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue;
+    fn __expand_native_plane_max(
+        scope: &Scope,
+        value: ExpandValue,
+        cluster_size: Option<u32>,
+    ) -> ExpandValue;
 }
 
 macro_rules! plane_numeric {
     ($($ty: ty),*; $sum: ty, $inc_sum: ty, $exc_sum: ty, $prod: ty, $inc_prod: ty, $exc_prod: ty, $min: ty, $max: ty) => {
         $(impl PlaneNumeric for $ty {
-            fn __expand_native_sum(scope: &Scope, value: ExpandValue) -> ExpandValue {
-                unary_expand(scope, value, <$sum>::new)
+            fn __expand_native_sum(
+                scope: &Scope,
+                value: ExpandValue,
+                cluster_size: Option<u32>,
+            ) -> ExpandValue {
+                let cluster_size = cluster_size.map(|size| IndexAttr::new(size as usize));
+                unary_expand(scope, value, |ctx, value| <$sum>::new(ctx, value, cluster_size))
             }
             fn __expand_native_inclusive_sum(scope: &Scope, value: ExpandValue) -> ExpandValue {
                 unary_expand(scope, value, <$inc_sum>::new)
@@ -34,8 +56,13 @@ macro_rules! plane_numeric {
                 unary_expand(scope, value, <$exc_sum>::new)
             }
 
-            fn __expand_native_prod(scope: &Scope, value: ExpandValue) -> ExpandValue {
-                unary_expand(scope, value, <$prod>::new)
+            fn __expand_native_prod(
+                scope: &Scope,
+                value: ExpandValue,
+                cluster_size: Option<u32>,
+            ) -> ExpandValue {
+                let cluster_size = cluster_size.map(|size| IndexAttr::new(size as usize));
+                unary_expand(scope, value, |ctx, value| <$prod>::new(ctx, value, cluster_size))
             }
             fn __expand_native_inclusive_prod(scope: &Scope, value: ExpandValue) -> ExpandValue {
                 unary_expand(scope, value, <$inc_prod>::new)
@@ -44,11 +71,21 @@ macro_rules! plane_numeric {
                 unary_expand(scope, value, <$exc_prod>::new)
             }
 
-            fn __expand_native_plane_min(scope: &Scope, value: ExpandValue) -> ExpandValue {
-                unary_expand(scope, value, <$min>::new)
+            fn __expand_native_plane_min(
+                scope: &Scope,
+                value: ExpandValue,
+                cluster_size: Option<u32>,
+            ) -> ExpandValue {
+                let cluster_size = cluster_size.map(|size| IndexAttr::new(size as usize));
+                unary_expand(scope, value, |ctx, value| <$min>::new(ctx, value, cluster_size))
             }
-            fn __expand_native_plane_max(scope: &Scope, value: ExpandValue) -> ExpandValue {
-                unary_expand(scope, value, <$max>::new)
+            fn __expand_native_plane_max(
+                scope: &Scope,
+                value: ExpandValue,
+                cluster_size: Option<u32>,
+            ) -> ExpandValue {
+                let cluster_size = cluster_size.map(|size| IndexAttr::new(size as usize));
+                unary_expand(scope, value, |ctx, value| <$max>::new(ctx, value, cluster_size))
             }
         })*
     };
@@ -228,7 +265,7 @@ pub mod plane_sum {
         scope: &Scope,
         elem: NativeExpand<E>,
     ) -> NativeExpand<E> {
-        E::Scalar::__expand_native_sum(scope, elem.into()).into()
+        E::Scalar::__expand_native_sum(scope, elem.into(), None).into()
     }
 }
 
@@ -295,7 +332,7 @@ pub mod plane_prod {
         scope: &Scope,
         elem: NativeExpand<E>,
     ) -> NativeExpand<E> {
-        E::Scalar::__expand_native_prod(scope, elem.into()).into()
+        E::Scalar::__expand_native_prod(scope, elem.into(), None).into()
     }
 }
 
@@ -362,7 +399,7 @@ pub mod plane_max {
         scope: &Scope,
         elem: NativeExpand<E>,
     ) -> NativeExpand<E> {
-        E::Scalar::__expand_native_plane_max(scope, elem.into()).into()
+        E::Scalar::__expand_native_plane_max(scope, elem.into(), None).into()
     }
 }
 
@@ -380,10 +417,133 @@ pub mod plane_min {
         scope: &Scope,
         elem: NativeExpand<E>,
     ) -> NativeExpand<E> {
-        E::Scalar::__expand_native_plane_min(scope, elem.into()).into()
+        E::Scalar::__expand_native_plane_min(scope, elem.into(), None).into()
+    }
+}
+
+/// Perform a reduce sum operation over clusters of `cluster_size` consecutive units in a plane.
+/// Each unit receives the sum of its own cluster. Requires a constant cluster size, which must be
+/// a power of two no larger than the plane.
+///
+/// # Example
+/// `plane_sum_clustered([1, 2, 3, 4, 5, 6, 7, 8], 4) == [10, 10, 10, 10, 26, 26, 26, 26]`
+#[allow(unused_variables)]
+pub fn plane_sum_clustered<E: CubePrimitive<Scalar: PlaneNumeric>>(
+    value: E,
+    cluster_size: u32,
+) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [`plane_sum_clustered()`].
+pub mod plane_sum_clustered {
+    use super::*;
+
+    /// Expand method of [`plane_sum_clustered()`].
+    pub fn expand<E: CubePrimitive<Scalar: PlaneNumeric>>(
+        scope: &Scope,
+        elem: NativeExpand<E>,
+        cluster_size: u32,
+    ) -> NativeExpand<E> {
+        assert_cluster_size(cluster_size);
+        E::Scalar::__expand_native_sum(scope, elem.into(), Some(cluster_size)).into()
+    }
+}
+
+/// Perform a reduce product operation over clusters of `cluster_size` consecutive units in a plane.
+/// Each unit receives the product of its own cluster. Requires a constant cluster size, which must be
+/// a power of two no larger than the plane.
+///
+/// # Example
+/// `plane_prod_clustered([1, 2, 3, 4, 5, 6, 7, 8], 2) == [2, 2, 12, 12, 30, 30, 56, 56]`
+#[allow(unused_variables)]
+pub fn plane_prod_clustered<E: CubePrimitive<Scalar: PlaneNumeric>>(
+    value: E,
+    cluster_size: u32,
+) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [`plane_prod_clustered()`].
+pub mod plane_prod_clustered {
+    use super::*;
+
+    /// Expand method of [`plane_prod_clustered()`].
+    pub fn expand<E: CubePrimitive<Scalar: PlaneNumeric>>(
+        scope: &Scope,
+        elem: NativeExpand<E>,
+        cluster_size: u32,
+    ) -> NativeExpand<E> {
+        assert_cluster_size(cluster_size);
+        E::Scalar::__expand_native_prod(scope, elem.into(), Some(cluster_size)).into()
+    }
+}
+
+/// Perform a reduce max operation over clusters of `cluster_size` consecutive units in a plane.
+/// Each unit receives the max of its own cluster. Requires a constant cluster size, which must be
+/// a power of two no larger than the plane.
+///
+/// # Example
+/// `plane_max_clustered([3, 1, 4, 1, 5, 9, 2, 6], 4) == [4, 4, 4, 4, 9, 9, 9, 9]`
+#[allow(unused_variables)]
+pub fn plane_max_clustered<E: CubePrimitive<Scalar: PlaneNumeric>>(
+    value: E,
+    cluster_size: u32,
+) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [`plane_max_clustered()`].
+pub mod plane_max_clustered {
+    use super::*;
+
+    /// Expand method of [`plane_max_clustered()`].
+    pub fn expand<E: CubePrimitive<Scalar: PlaneNumeric>>(
+        scope: &Scope,
+        elem: NativeExpand<E>,
+        cluster_size: u32,
+    ) -> NativeExpand<E> {
+        assert_cluster_size(cluster_size);
+        E::Scalar::__expand_native_plane_max(scope, elem.into(), Some(cluster_size)).into()
+    }
+}
+
+/// Perform a reduce min operation over clusters of `cluster_size` consecutive units in a plane.
+/// Each unit receives the min of its own cluster. Requires a constant cluster size, which must be
+/// a power of two no larger than the plane.
+///
+/// # Example
+/// `plane_min_clustered([3, 1, 4, 1, 5, 9, 2, 6], 4) == [1, 1, 1, 1, 2, 2, 2, 2]`
+#[allow(unused_variables)]
+pub fn plane_min_clustered<E: CubePrimitive<Scalar: PlaneNumeric>>(
+    value: E,
+    cluster_size: u32,
+) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [`plane_min_clustered()`].
+pub mod plane_min_clustered {
+    use super::*;
+
+    /// Expand method of [`plane_min_clustered()`].
+    pub fn expand<E: CubePrimitive<Scalar: PlaneNumeric>>(
+        scope: &Scope,
+        elem: NativeExpand<E>,
+        cluster_size: u32,
+    ) -> NativeExpand<E> {
+        assert_cluster_size(cluster_size);
+        E::Scalar::__expand_native_plane_min(scope, elem.into(), Some(cluster_size)).into()
     }
 }
 
+fn assert_cluster_size(cluster_size: u32) {
+    assert!(
+        cluster_size.is_power_of_two(),
+        "Cluster size must be a power of two, got {cluster_size}"
+    );
+}
+
 /// Perform a reduce all operation across all units in a plane.
 pub fn plane_all(_elem: bool) -> bool {
     unexpanded!()
@@ -438,3 +598,206 @@ pub mod plane_ballot {
         scope.register_with_result(&op).into()
     }
 }
+
+/// A value that can be exchanged between the units of a plane, as used by [`plane_reduce_with()`]
+/// and [`plane_scan_with()`]. Implemented for all primitives and for tuples of them, so a combiner
+/// can carry extra state such as an index or a running count.
+pub trait PlaneShuffle: CubeType<ExpandType: RuntimeAssign> + Sized {
+    /// Same as [`plane_shuffle_xor()`], for any shuffleable value.
+    #[allow(unused_variables)]
+    fn shuffle_xor(value: Self, mask: u32) -> Self {
+        unexpanded!()
+    }
+
+    /// Same as [`plane_shuffle_up()`], for any shuffleable value.
+    #[allow(unused_variables)]
+    fn shuffle_up(value: Self, delta: u32) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_shuffle_xor(
+        scope: &Scope,
+        value: Self::ExpandType,
+        mask: NativeExpand<u32>,
+    ) -> Self::ExpandType;
+
+    fn __expand_shuffle_up(
+        scope: &Scope,
+        value: Self::ExpandType,
+        delta: NativeExpand<u32>,
+    ) -> Self::ExpandType;
+}
+
+impl<E: CubePrimitive> PlaneShuffle for E {
+    fn __expand_shuffle_xor(
+        scope: &Scope,
+        value: NativeExpand<E>,
+        mask: NativeExpand<u32>,
+    ) -> NativeExpand<E> {
+        plane_shuffle_xor::expand(scope, value, mask)
+    }
+
+    fn __expand_shuffle_up(
+        scope: &Scope,
+        value: NativeExpand<E>,
+        delta: NativeExpand<u32>,
+    ) -> NativeExpand<E> {
+        plane_shuffle_up::expand(scope, value, delta)
+    }
+}
+
+macro_rules! tuple_plane_shuffle {
+    ($(($n: tt, $P: ident)),*) => {
+        impl<$($P: PlaneShuffle),*> PlaneShuffle for ($($P,)*) {
+            fn __expand_shuffle_xor(
+                scope: &Scope,
+                value: Self::ExpandType,
+                mask: NativeExpand<u32>,
+            ) -> Self::ExpandType {
+                ($($P::__expand_shuffle_xor(scope, value.$n, mask.clone()),)*)
+            }
+
+            fn __expand_shuffle_up(
+                scope: &Scope,
+                value: Self::ExpandType,
+                delta: NativeExpand<u32>,
+            ) -> Self::ExpandType {
+                ($($P::__expand_shuffle_up(scope, value.$n, delta.clone()),)*)
+            }
+        }
+    };
+}
+
+tuple_plane_shuffle!((0, A), (1, B));
+tuple_plane_shuffle!((0, A), (1, B), (2, C));
+tuple_plane_shuffle!((0, A), (1, B), (2, C), (3, D));
+
+/// Combines two partial results of [`plane_reduce_with()`] or [`plane_scan_with()`]. For scans,
+/// `lhs` always holds the values of the lower units.
+#[cube]
+pub trait PlaneCombiner<E: CubeType> {
+    fn combine(lhs: E, rhs: E) -> E;
+}
+
+/// Combines with `+`, same as [`plane_sum()`]
+pub struct PlaneSum;
+/// Combines with `*`, same as [`plane_prod()`]
+pub struct PlaneProd;
+/// Combines with [`min()`], same as [`plane_min()`]
+pub struct PlaneMin;
+/// Combines with [`max()`], same as [`plane_max()`]
+pub struct PlaneMax;
+
+#[cube]
+impl<E: CubeAdd> PlaneCombiner<E> for PlaneSum {
+    fn combine(lhs: E, rhs: E) -> E {
+        lhs + rhs
+    }
+}
+
+#[cube]
+impl<E: CubeMul> PlaneCombiner<E> for PlaneProd {
+    fn combine(lhs: E, rhs: E) -> E {
+        lhs * rhs
+    }
+}
+
+#[cube]
+impl<E: CubePartialOrd> PlaneCombiner<E> for PlaneMin {
+    fn combine(lhs: E, rhs: E) -> E {
+        min(lhs, rhs)
+    }
+}
+
+#[cube]
+impl<E: CubePartialOrd> PlaneCombiner<E> for PlaneMax {
+    fn combine(lhs: E, rhs: E) -> E {
+        max(lhs, rhs)
+    }
+}
+
+/// The number of units a reduction or scan spans. Shuffling from a unit outside the cube returns
+/// an unspecified value, so cubes smaller than the plane are clamped to the cube dim. The shuffles
+/// only pair up units within a power of two, so the width is rounded down to one. That's only
+/// exact if cubes smaller than the plane have a power of two size, the units past the width get
+/// unspecified results otherwise.
+///
+/// A comptime cluster size must be a nonzero power of two, like for the clustered reductions. A
+/// runtime size of zero is clamped to a single unit.
+#[cube]
+fn plane_cluster_width(cluster_size: u32) -> u32 {
+    check_cluster_size(cluster_size);
+    let width = max(min(cluster_size, min(PLANE_DIM, CUBE_DIM)), 1);
+    1u32 << (31 - width.leading_zeros())
+}
+
+/// Checks the cluster size with [`assert_cluster_size`] if it's known at compile time.
+#[cube]
+fn check_cluster_size(cluster_size: u32) {
+    intrinsic!(|_| {
+        if let Some(size) = cluster_size.constant() {
+            assert_cluster_size(size.as_u32());
+        }
+    })
+}
+
+/// Reduces `value` over clusters of `cluster_size` consecutive units with a custom combiner, using
+/// a butterfly of shuffles. Each unit receives the result of its own cluster. `cluster_size` must
+/// be a power of two, pass `PLANE_DIM` to reduce over the whole plane. A cube smaller than the
+/// plane must also have a power of two size.
+///
+/// The combiner must be associative and commutative, since units combine their partial results in
+/// different orders.
+///
+/// # Example
+/// An argmax that keeps the index of the largest value, over rows of 8 units:
+/// ```ignore
+/// struct ArgMax;
+///
+/// #[cube]
+/// impl PlaneCombiner<(f32, u32)> for ArgMax {
+///     fn combine(lhs: (f32, u32), rhs: (f32, u32)) -> (f32, u32) {
+///         let take_rhs = rhs.0 > lhs.0 || (rhs.0 == lhs.0 && rhs.1 < lhs.1);
+///         (select(take_rhs, rhs.0, lhs.0), select(take_rhs, rhs.1, lhs.1))
+///     }
+/// }
+///
+/// let (max, index) = plane_reduce_with::<(f32, u32), ArgMax>((value, UNIT_POS_PLANE), 8);
+/// ```
+#[cube]
+pub fn plane_reduce_with<E: PlaneShuffle, C: PlaneCombiner<E>>(value: E, cluster_size: u32) -> E {
+    let width = plane_cluster_width(cluster_size);
+    let mut acc = value;
+    let mut offset = 1;
+    while offset < width {
+        acc = C::combine(acc, E::shuffle_xor(acc, offset));
+        offset *= 2;
+    }
+    acc
+}
+
+/// Performs an inclusive scan of `value` over clusters of `cluster_size` consecutive units with a
+/// custom combiner, using shuffles. Each unit receives the combination of all values in its
+/// cluster up to and including its own. `cluster_size` must be a power of two, pass `PLANE_DIM`
+/// to scan the whole plane. A cube smaller than the plane must also have a power of two size.
+///
+/// The combiner must be associative, but not necessarily commutative.
+///
+/// # Example
+/// With [`PlaneSum`] and a cluster size of 4:
+/// `[1, 2, 3, 4, 5, 6, 7, 8] -> [1, 3, 6, 10, 5, 11, 18, 26]`
+#[cube]
+pub fn plane_scan_with<E: PlaneShuffle, C: PlaneCombiner<E>>(value: E, cluster_size: u32) -> E {
+    let width = plane_cluster_width(cluster_size);
+    let lane = UNIT_POS_PLANE & (width - 1);
+    let mut acc = value;
+    let mut offset = 1;
+    while offset < width {
+        let lower = E::shuffle_up(acc, offset);
+        if lane >= offset {
+            acc = C::combine(lower, acc);
+        }
+        offset *= 2;
+    }
+    acc
+}
@@ -156,6 +156,30 @@ pub trait CubeOptionDefault<T: CubeType + Default + IntoRuntime>: CubeOption<T>
 impl<T: CubeType> CubeOption<T> for Option<T> {}
 impl<T: CubeType + Default + IntoRuntime> CubeOptionDefault<T> for Option<T> {}
 
+/// The residual of `?` on an [`Option`].
+pub struct OptionResidual;
+
+impl<T: CubeType> TryExpand for OptionExpand<T> {
+    type Output = T::ExpandType;
+    type Residual = OptionResidual;
+
+    fn __expand_branch_method(
+        self,
+        scope: &Scope,
+    ) -> (NativeExpand<bool>, T::ExpandType, OptionResidual) {
+        let is_none = self
+            .discriminant
+            .__expand_eq_method(scope, &discriminant("None").into());
+        (is_none, self.value, OptionResidual)
+    }
+}
+
+impl<T: CubeType + Default + IntoRuntime> FromResidualExpand<OptionResidual> for OptionExpand<T> {
+    fn __expand_from_residual(scope: &Scope, _residual: OptionResidual) -> Self {
+        Option::__expand_new_None(scope)
+    }
+}
+
 mod impls {
     use core::ops::{Deref, DerefMut};
 
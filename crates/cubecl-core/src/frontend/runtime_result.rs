@@ -0,0 +1,243 @@
+use cubecl_macros::CubeTypeMut;
+
+use crate as cubecl;
+use crate::prelude::*;
+
+/// A runtime [`Result`], with the variant selected at runtime.
+///
+/// Named differently from [`Result`] so importing the prelude doesn't shadow it for host code.
+/// Both values are kept in the expansion, only the one selected by the discriminant is meaningful.
+#[derive(CubeType, CubeTypeMut, IntoRuntime)]
+#[cube(runtime_variants, no_constructors)]
+pub enum RuntimeResult<T: CubeType, E: CubeType> {
+    /// Contains the success value.
+    Ok(T),
+    /// Contains the error value.
+    Err(E),
+}
+
+fn discriminant(variant_name: &'static str) -> i32 {
+    RuntimeResultExpand::<u32, u32>::discriminant_of(variant_name)
+}
+
+/// Constructors for [`RuntimeResult`]
+#[allow(non_snake_case)]
+pub trait CubeResult<T: CubeType + Default + IntoRuntime, E: CubeType + Default + IntoRuntime> {
+    /// Create a new [`RuntimeResult::Ok`] in a kernel
+    fn new_Ok(_0: T) -> RuntimeResult<T, E> {
+        RuntimeResult::Ok(_0)
+    }
+    /// Create a new [`RuntimeResult::Err`] in a kernel
+    fn new_Err(_0: E) -> RuntimeResult<T, E> {
+        RuntimeResult::Err(_0)
+    }
+
+    #[doc(hidden)]
+    fn __expand_Ok(scope: &Scope, value: T::ExpandType) -> RuntimeResultExpand<T, E> {
+        Self::__expand_new_Ok(scope, value)
+    }
+    #[doc(hidden)]
+    fn __expand_Err(scope: &Scope, value: E::ExpandType) -> RuntimeResultExpand<T, E> {
+        Self::__expand_new_Err(scope, value)
+    }
+    #[doc(hidden)]
+    fn __expand_new_Ok(scope: &Scope, value: T::ExpandType) -> RuntimeResultExpand<T, E> {
+        RuntimeResultExpand {
+            discriminant: discriminant("Ok").into(),
+            value: (value, E::default().__expand_runtime_method(scope)),
+        }
+    }
+    #[doc(hidden)]
+    fn __expand_new_Err(scope: &Scope, value: E::ExpandType) -> RuntimeResultExpand<T, E> {
+        RuntimeResultExpand {
+            discriminant: discriminant("Err").into(),
+            value: (T::default().__expand_runtime_method(scope), value),
+        }
+    }
+}
+
+impl<T: CubeType + Default + IntoRuntime, E: CubeType + Default + IntoRuntime> CubeResult<T, E>
+    for RuntimeResult<T, E>
+{
+}
+
+#[doc(hidden)]
+impl<T: CubeType, E: CubeType> RuntimeResultExpand<T, E> {
+    pub fn __expand_is_ok_method(&self, scope: &Scope) -> NativeExpand<bool> {
+        self.discriminant
+            .__expand_eq_method(scope, &discriminant("Ok").into())
+    }
+
+    pub fn __expand_is_err_method(&self, scope: &Scope) -> NativeExpand<bool> {
+        self.discriminant
+            .__expand_eq_method(scope, &discriminant("Err").into())
+    }
+
+    pub fn __expand_ok_method(self, scope: &Scope) -> OptionExpand<T>
+    where
+        OptionExpand<T>: RuntimeAssign + IntoExpand<Expand = OptionExpand<T>>,
+    {
+        let is_ok = self.__expand_is_ok_method(scope);
+        let (ok, _) = self.value;
+        let value = ok.clone_unchecked();
+        if_else_expr_expand(scope, is_ok, |scope| {
+            Option::__expand_new_Some(scope, value)
+        })
+        .or_else(scope, |scope| Option::__expand_none_with_default(scope, ok))
+    }
+
+    pub fn __expand_err_method(self, scope: &Scope) -> OptionExpand<E>
+    where
+        OptionExpand<E>: RuntimeAssign + IntoExpand<Expand = OptionExpand<E>>,
+    {
+        let is_err = self.__expand_is_err_method(scope);
+        let (_, err) = self.value;
+        let value = err.clone_unchecked();
+        if_else_expr_expand(scope, is_err, |scope| {
+            Option::__expand_new_Some(scope, value)
+        })
+        .or_else(scope, |scope| {
+            Option::__expand_none_with_default(scope, err)
+        })
+    }
+
+    pub fn __expand_expect_method(self, scope: &Scope, msg: &str) -> T::ExpandType {
+        // Replace with `trap` eventually to ensure execution doesn't continue to the next kernel
+        let is_err = self.__expand_is_err_method(scope);
+        if_expand(scope, is_err, |scope| {
+            printf_expand(scope, msg, alloc::vec![]);
+            terminate!();
+        });
+        self.value.0
+    }
+
+    pub fn __expand_unwrap_method(self, scope: &Scope) -> T::ExpandType {
+        self.__expand_expect_method(scope, "called `Result::unwrap()` on an `Err` value")
+    }
+
+    pub fn __expand_unwrap_or_method(self, scope: &Scope, default: T::ExpandType) -> T::ExpandType
+    where
+        T::ExpandType: RuntimeAssign,
+    {
+        let is_ok = self.__expand_is_ok_method(scope);
+        let (ok, _) = self.value;
+        if_else_expr_expand(scope, is_ok, |_| ok).or_else(scope, |_| default)
+    }
+
+    pub fn __expand_unwrap_or_else_method<F>(self, scope: &Scope, f: F) -> T::ExpandType
+    where
+        F: FnOnce(&Scope, E::ExpandType) -> T::ExpandType,
+        T::ExpandType: RuntimeAssign,
+    {
+        let is_ok = self.__expand_is_ok_method(scope);
+        let (ok, err) = self.value;
+        if_else_expr_expand(scope, is_ok, |_| ok).or_else(scope, |scope| f(scope, err))
+    }
+
+    pub fn __expand_map_method<U, F>(self, scope: &Scope, f: F) -> RuntimeResultExpand<U, E>
+    where
+        F: FnOnce(&Scope, T::ExpandType) -> U::ExpandType,
+        U: CubeType + Default + IntoRuntime,
+        RuntimeResultExpand<U, E>: RuntimeAssign<Expand = RuntimeResultExpand<U, E>>,
+    {
+        let is_ok = self.__expand_is_ok_method(scope);
+        let (ok, err) = self.value;
+        let err_ok = err.clone_unchecked();
+        if_else_expr_expand(scope, is_ok, |scope| RuntimeResultExpand {
+            discriminant: discriminant("Ok").into(),
+            value: (f(scope, ok), err_ok),
+        })
+        .or_else(scope, |scope| RuntimeResultExpand {
+            discriminant: discriminant("Err").into(),
+            value: (U::default().__expand_runtime_method(scope), err),
+        })
+    }
+
+    pub fn __expand_map_err_method<F, O>(self, scope: &Scope, op: O) -> RuntimeResultExpand<T, F>
+    where
+        O: FnOnce(&Scope, E::ExpandType) -> F::ExpandType,
+        F: CubeType + Default + IntoRuntime,
+        RuntimeResultExpand<T, F>: RuntimeAssign<Expand = RuntimeResultExpand<T, F>>,
+    {
+        let is_err = self.__expand_is_err_method(scope);
+        let (ok, err) = self.value;
+        let ok_err = ok.clone_unchecked();
+        if_else_expr_expand(scope, is_err, |scope| RuntimeResultExpand {
+            discriminant: discriminant("Err").into(),
+            value: (ok_err, op(scope, err)),
+        })
+        .or_else(scope, |scope| RuntimeResultExpand {
+            discriminant: discriminant("Ok").into(),
+            value: (ok, F::default().__expand_runtime_method(scope)),
+        })
+    }
+
+    pub fn __expand_and_then_method<U, F>(self, scope: &Scope, f: F) -> RuntimeResultExpand<U, E>
+    where
+        F: FnOnce(&Scope, T::ExpandType) -> RuntimeResultExpand<U, E>,
+        U: CubeType + Default + IntoRuntime,
+        RuntimeResultExpand<U, E>: RuntimeAssign<Expand = RuntimeResultExpand<U, E>>,
+    {
+        let is_ok = self.__expand_is_ok_method(scope);
+        let (ok, err) = self.value;
+        if_else_expr_expand(scope, is_ok, |scope| f(scope, ok)).or_else(scope, |scope| {
+            RuntimeResultExpand {
+                discriminant: discriminant("Err").into(),
+                value: (U::default().__expand_runtime_method(scope), err),
+            }
+        })
+    }
+}
+
+/// Expansion of the `?` operator on a runtime value, mirroring `core::ops::Try`.
+pub trait TryExpand {
+    /// The value `?` evaluates to when it doesn't return.
+    type Output;
+    /// What `?` returns from the function, converted through [`FromResidualExpand`].
+    type Residual;
+
+    /// Split into whether `?` returns, the output and the residual. Only one of the last two is
+    /// meaningful at runtime, depending on the condition.
+    fn __expand_branch_method(
+        self,
+        scope: &Scope,
+    ) -> (NativeExpand<bool>, Self::Output, Self::Residual);
+}
+
+/// Construct a runtime return value from the residual of `?`, mirroring
+/// `core::ops::FromResidual`.
+pub trait FromResidualExpand<R> {
+    /// Build the value returned by `?` when the branch condition holds. Only called inside the
+    /// predicated early return, so the returned value is only observed when `?` returns.
+    fn __expand_from_residual(scope: &Scope, residual: R) -> Self;
+}
+
+/// The residual of `?` on a [`RuntimeResult`], holding the error.
+pub struct ResultResidual<E: CubeType> {
+    err: E::ExpandType,
+}
+
+impl<T: CubeType, E: CubeType> TryExpand for RuntimeResultExpand<T, E> {
+    type Output = T::ExpandType;
+    type Residual = ResultResidual<E>;
+
+    fn __expand_branch_method(
+        self,
+        scope: &Scope,
+    ) -> (NativeExpand<bool>, T::ExpandType, ResultResidual<E>) {
+        let is_err = self.__expand_is_err_method(scope);
+        let (ok, err) = self.value;
+        (is_err, ok, ResultResidual { err })
+    }
+}
+
+impl<T: CubeType + Default + IntoRuntime, E: CubeType> FromResidualExpand<ResultResidual<E>>
+    for RuntimeResultExpand<T, E>
+{
+    fn __expand_from_residual(scope: &Scope, residual: ResultResidual<E>) -> Self {
+        RuntimeResultExpand {
+            discriminant: discriminant("Err").into(),
+            value: (T::default().__expand_runtime_method(scope), residual.err),
+        }
+    }
+}
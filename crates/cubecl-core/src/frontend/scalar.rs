@@ -4,6 +4,7 @@ use cubecl_ir::{
     dialect::general::ReadScalarOp,
     pliron::{builtin::op_interfaces::OneResultInterface, value::Value},
 };
+use cubecl_runtime::id::GraphParamId;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -18,6 +19,7 @@ use crate::{
 pub struct InputScalar {
     data: [u8; 8],
     dtype: ElemType,
+    param: Option<GraphParamId>,
 }
 
 #[derive(Clone)]
@@ -73,6 +75,7 @@ impl InputScalar {
         let mut out = InputScalar {
             data: Default::default(),
             dtype,
+            param: None,
         };
         fn write<E: ScalarArgType>(val: impl num_traits::ToPrimitive, out: &mut [u8]) {
             let val = [E::from(val).unwrap()];
@@ -98,17 +101,21 @@ impl InputScalar {
                 FloatKind::UE8M0 => write::<ue8m0>(val, &mut out.data),
             },
             ElemType::Int(int_kind) => match int_kind {
+                // Packed pairs are passed as their raw byte, so negative values keep their bits
+                IntKind::I4x2 => write::<u8>(val.to_i64().unwrap() as u8, &mut out.data),
                 IntKind::I8 => write::<i8>(val, &mut out.data),
                 IntKind::I16 => write::<i16>(val, &mut out.data),
                 IntKind::I32 => write::<i32>(val, &mut out.data),
                 IntKind::I64 => write::<i64>(val, &mut out.data),
             },
             ElemType::UInt(uint_kind) => match uint_kind {
+                UIntKind::U4x2 => write::<u8>(val, &mut out.data),
                 UIntKind::U8 => write::<u8>(val, &mut out.data),
                 UIntKind::U16 => write::<u16>(val, &mut out.data),
                 UIntKind::U32 => write::<u32>(val, &mut out.data),
                 UIntKind::U64 => write::<u64>(val, &mut out.data),
             },
+            ElemType::Complex(_) => panic!("Complex scalars aren't supported"),
             ElemType::Bool => panic!("Bool isn't a scalar"),
         };
         out
@@ -133,6 +140,16 @@ impl InputScalar {
         // Address type is irrelevant since we don't allow it as a dtype
         &self.data[..self.dtype.size()]
     }
+
+    /// Pass the scalar as the graph parameter `param`, marked with
+    /// [`graph_scalar_param`](cubecl_runtime::client::ComputeClient::graph_scalar_param).
+    ///
+    /// The recorded launch then knows exactly where the scalar lives, instead of looking for
+    /// its value among the other scalars.
+    pub fn graph_param(mut self, param: GraphParamId) -> Self {
+        self.param = Some(param);
+        self
+    }
 }
 
 impl LaunchArg for InputScalar {
@@ -145,7 +162,10 @@ impl LaunchArg for InputScalar {
     ) -> Self::CompilationArg {
         let dtype = arg.dtype;
 
-        launcher.register_scalar_raw(arg.as_bytes(), dtype);
+        match arg.param {
+            Some(param) => launcher.register_marked_scalar_raw(arg.as_bytes(), dtype, param),
+            None => launcher.register_scalar_raw(arg.as_bytes(), dtype),
+        }
         InputScalarCompilationArg::new(arg.dtype)
     }
 
@@ -1,7 +1,7 @@
-use cubecl_common::{e2m1, e2m1x2, e4m3, e5m2, flex32, tf32, ue8m0};
+use cubecl_common::{c32, c64, e2m1, e2m1x2, e4m3, e5m2, flex32, i4x2, tf32, u4x2, ue8m0};
 
 use crate::{
-    ir::{ElemType, FloatKind, IntKind, UIntKind},
+    ir::{ComplexKind, ElemType, FloatKind, IntKind, UIntKind},
     prelude::{Numeric, Scalar},
 };
 
@@ -437,3 +437,79 @@ impl CubeElement for e2m1x2 {
         e2m1x2::from_bits(min << 4 | min)
     }
 }
+
+macro_rules! impl_cube_element_int4x2 {
+    ($primitive: ident, $kind: expr) => {
+        impl CubeElement for $primitive {
+            fn type_name() -> &'static str {
+                stringify!($primitive)
+            }
+
+            fn as_bytes(slice: &[Self]) -> &[u8] {
+                bytemuck::cast_slice(slice)
+            }
+
+            fn from_bytes(bytes: &[u8]) -> &[Self] {
+                bytemuck::cast_slice(bytes)
+            }
+
+            fn cube_type() -> ElemType {
+                $kind.into()
+            }
+
+            fn maximum_value() -> Self {
+                $primitive::new($primitive::MAX, $primitive::MAX)
+            }
+
+            fn minimum_value() -> Self {
+                $primitive::new($primitive::MIN, $primitive::MIN)
+            }
+        }
+    };
+}
+
+impl_cube_element_int4x2!(i4x2, IntKind::I4x2);
+impl_cube_element_int4x2!(u4x2, UIntKind::U4x2);
+
+// Complex numbers aren't ordered, so the extremes are taken per part
+impl CubeElement for c32 {
+    fn type_name() -> &'static str {
+        "c32"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_type() -> ElemType {
+        ElemType::Complex(ComplexKind::C32)
+    }
+    fn maximum_value() -> Self {
+        c32::new(f32::MAX, f32::MAX)
+    }
+    fn minimum_value() -> Self {
+        c32::new(f32::MIN, f32::MIN)
+    }
+}
+
+impl CubeElement for c64 {
+    fn type_name() -> &'static str {
+        "c64"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_type() -> ElemType {
+        ElemType::Complex(ComplexKind::C64)
+    }
+    fn maximum_value() -> Self {
+        c64::new(f64::MAX, f64::MAX)
+    }
+    fn minimum_value() -> Self {
+        c64::new(f64::MIN, f64::MIN)
+    }
+}
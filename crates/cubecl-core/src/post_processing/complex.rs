@@ -0,0 +1,253 @@
+use cubecl_environment::collections::HashMap;
+use cubecl_ir::{
+    dialect::{
+        base::OperationPtrExt,
+        complex::{
+            ComplexAbsOp, ComplexConjOp, ComplexDivOp, ComplexExpOp, ComplexImagOp, ComplexMulOp,
+            ComplexNewOp, ComplexRealOp,
+        },
+        general::CastOp,
+    },
+    interfaces::TypedExt,
+    prelude::*,
+    types::{VectorType, scalar::ComplexType},
+};
+use pliron::irbuild::match_rewrite::{RewriterOrder, apply_match_rewrite};
+
+use crate::{self as cubecl, post_processing::util::retype_all, prelude::*};
+
+define_scalar!(Part);
+define_scalar!(Other);
+define_size!(N);
+define_size!(M);
+
+/// Lowers complex numbers to interleaved `[re, im]` pairs of their part type, so
+/// `Vector<c32, N>` becomes `Vector<f32, 2 * N>`. Componentwise ops (add, sub, neg, casts between
+/// complex types) are left as is, everything else is replaced with float math on the parts.
+///
+/// Must run before unrolling, since lowering doubles the vector size.
+#[derive(Debug, Default)]
+pub struct LowerComplexNumbersPass;
+
+#[derive(Clone, Copy, Debug)]
+enum CastKind {
+    FromReal,
+    ToReal,
+}
+
+#[pass_name]
+impl Pass for LowerComplexNumbersPass {
+    fn run(
+        &mut self,
+        op: Ptr<Operation>,
+        ctx: &mut Context,
+        _analyses: &mut AnalysisManager,
+    ) -> Result<PassResult> {
+        let mut res = PassResult::default();
+
+        // Casts between real and complex can't be told apart from elementwise casts after
+        // retyping, so find them first
+        let mut casts = HashMap::new();
+        visit_all_ops_of_type::<CastOp, _>(ctx, &mut casts, op, |ctx, casts, cast| {
+            let from_complex = is_complex(ctx, cast.input(ctx));
+            let to_complex = is_complex(ctx, cast.get_result(ctx));
+            match (from_complex, to_complex) {
+                (false, true) => casts.insert(cast.get_operation(), CastKind::FromReal),
+                (true, false) => casts.insert(cast.get_operation(), CastKind::ToReal),
+                _ => None,
+            };
+        });
+
+        res.ir_changed |= retype_all(ctx, op, lower_ty);
+        let mut rewrite = LowerComplexOps { casts };
+        res.ir_changed |= apply_match_rewrite(ctx, &mut rewrite, RewriterOrder::default(), op)?;
+
+        Ok(res)
+    }
+}
+
+/// Replaces complex numbers with pairs of their parts. Returns `None` if `ty` isn't a complex
+/// scalar or vector.
+fn lower_ty(ctx: &Context, ty: TypeHandle) -> Option<TypeHandle> {
+    let ty_obj = ty.deref(ctx);
+    if let Some(complex) = ty_obj.downcast_ref::<ComplexType>() {
+        Some(VectorType::get(ctx, complex.inner, 2).into())
+    } else if let Some(vector) = ty_obj.downcast_ref::<VectorType>() {
+        let complex = vector.inner.deref(ctx);
+        let complex = complex.downcast_ref::<ComplexType>()?;
+        Some(VectorType::get(ctx, complex.inner, vector.vectorization * 2).into())
+    } else {
+        None
+    }
+}
+
+fn is_complex(ctx: &Context, value: Value) -> bool {
+    value.scalar_ty(ctx).deref(ctx).is::<ComplexType>()
+}
+
+/// Replaces complex ops with float math on the already lowered parts
+struct LowerComplexOps {
+    casts: HashMap<Ptr<Operation>, CastKind>,
+}
+
+impl MatchRewrite for LowerComplexOps {
+    fn r#match(&mut self, ctx: &Context, op: Ptr<Operation>) -> bool {
+        op.is_op::<ComplexNewOp>(ctx)
+            || op.is_op::<ComplexRealOp>(ctx)
+            || op.is_op::<ComplexImagOp>(ctx)
+            || op.is_op::<ComplexAbsOp>(ctx)
+            || op.is_op::<ComplexMulOp>(ctx)
+            || op.is_op::<ComplexDivOp>(ctx)
+            || op.is_op::<ComplexConjOp>(ctx)
+            || op.is_op::<ComplexExpOp>(ctx)
+            || self.casts.contains_key(&op)
+    }
+
+    fn rewrite(
+        &mut self,
+        ctx: &mut Context,
+        rewriter: &mut MatchRewriter,
+        op: Ptr<Operation>,
+    ) -> Result<()> {
+        let scope = Scope::from_context_and_inserter(ctx, rewriter);
+        let operand = op.operand(ctx, 0);
+        let result = op.deref(ctx).get_result(0);
+
+        let value = if op.is_op::<ComplexNewOp>(ctx) {
+            register_complex(&scope, result);
+            let im = op.operand(ctx, 1);
+            complex_new::expand::<Part, N, M>(&scope, operand.into(), im.into()).value(&scope)
+        } else if op.is_op::<ComplexMulOp>(ctx) {
+            register_complex(&scope, operand);
+            let rhs = op.operand(ctx, 1);
+            complex_mul::expand::<Part, N, M>(&scope, operand.into(), rhs.into()).value(&scope)
+        } else if op.is_op::<ComplexDivOp>(ctx) {
+            register_complex(&scope, operand);
+            let rhs = op.operand(ctx, 1);
+            complex_div::expand::<Part, N, M>(&scope, operand.into(), rhs.into()).value(&scope)
+        } else if let Some(kind) = self.casts.get(&op) {
+            match kind {
+                CastKind::FromReal => {
+                    register_complex(&scope, result);
+                    scope.register_value_type::<Other, N>(operand);
+                    complex_from_real::expand::<Other, Part, N, M>(&scope, operand.into())
+                        .value(&scope)
+                }
+                CastKind::ToReal => {
+                    register_complex(&scope, operand);
+                    scope.register_value_type::<Other, N>(result);
+                    complex_to_real::expand::<Other, Part, N, M>(&scope, operand.into())
+                        .value(&scope)
+                }
+            }
+        } else {
+            register_complex(&scope, operand);
+            let x = operand.into();
+            if op.is_op::<ComplexRealOp>(ctx) {
+                complex_real::expand::<Part, N, M>(&scope, x).value(&scope)
+            } else if op.is_op::<ComplexImagOp>(ctx) {
+                complex_imag::expand::<Part, N, M>(&scope, x).value(&scope)
+            } else if op.is_op::<ComplexAbsOp>(ctx) {
+                complex_abs::expand::<Part, N, M>(&scope, x).value(&scope)
+            } else if op.is_op::<ComplexConjOp>(ctx) {
+                complex_conj::expand::<Part, N, M>(&scope, x).value(&scope)
+            } else {
+                complex_exp::expand::<Part, N, M>(&scope, x).value(&scope)
+            }
+        };
+
+        rewriter.replace_operation_with_values(ctx, op, vec![value]);
+        Ok(())
+    }
+}
+
+/// Registers the part type and sizes for a lowered complex value
+fn register_complex(scope: &Scope, complex: Value) {
+    scope.register_value_type::<Part, M>(complex);
+    let vector_size = complex.vector_size(scope.ctx());
+    scope.register_size::<N>(vector_size / 2);
+}
+
+#[cube]
+fn complex_new<F: Float, N: Size, M: Size>(re: Vector<F, N>, im: Vector<F, N>) -> Vector<F, M> {
+    let mut out = Vector::<F, M>::empty();
+    #[unroll]
+    for i in 0..N::value() {
+        out.insert(comptime![2 * i], re.extract(i));
+        out.insert(comptime![2 * i + 1], im.extract(i));
+    }
+    out
+}
+
+#[cube]
+fn complex_real<F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<F, N> {
+    let mut out = Vector::<F, N>::empty();
+    #[unroll]
+    for i in 0..N::value() {
+        out.insert(i, x.extract(comptime![2 * i]));
+    }
+    out
+}
+
+#[cube]
+fn complex_imag<F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<F, N> {
+    let mut out = Vector::<F, N>::empty();
+    #[unroll]
+    for i in 0..N::value() {
+        out.insert(i, x.extract(comptime![2 * i + 1]));
+    }
+    out
+}
+
+#[cube]
+fn complex_abs<F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<F, N> {
+    let re = complex_real::<F, N, M>(x);
+    let im = complex_imag::<F, N, M>(x);
+    hypot(re, im)
+}
+
+#[cube]
+fn complex_conj<F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<F, M> {
+    let re = complex_real::<F, N, M>(x);
+    let im = complex_imag::<F, N, M>(x);
+    complex_new::<F, N, M>(re, -im)
+}
+
+#[cube]
+fn complex_exp<F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<F, M> {
+    let scale = complex_real::<F, N, M>(x).exp();
+    let im = complex_imag::<F, N, M>(x);
+    complex_new::<F, N, M>(scale * im.cos(), scale * im.sin())
+}
+
+#[cube]
+fn complex_mul<F: Float, N: Size, M: Size>(lhs: Vector<F, M>, rhs: Vector<F, M>) -> Vector<F, M> {
+    let (a, b) = (complex_real::<F, N, M>(lhs), complex_imag::<F, N, M>(lhs));
+    let (c, d) = (complex_real::<F, N, M>(rhs), complex_imag::<F, N, M>(rhs));
+    complex_new::<F, N, M>(a * c - b * d, a * d + b * c)
+}
+
+#[cube]
+fn complex_div<F: Float, N: Size, M: Size>(lhs: Vector<F, M>, rhs: Vector<F, M>) -> Vector<F, M> {
+    let (a, b) = (complex_real::<F, N, M>(lhs), complex_imag::<F, N, M>(lhs));
+    let (c, d) = (complex_real::<F, N, M>(rhs), complex_imag::<F, N, M>(rhs));
+    // Smith's algorithm, scales by the larger part of the divisor so the denominator can't
+    // overflow or underflow like `c * c + d * d` would
+    let c_larger = c.abs().greater_equal(&d.abs());
+    let ratio = select_many(c_larger, d / c, c / d);
+    let denom = select_many(c_larger, c + d * ratio, c * ratio + d);
+    let re = select_many(c_larger, a + b * ratio, a * ratio + b);
+    let im = select_many(c_larger, b - a * ratio, b * ratio - a);
+    complex_new::<F, N, M>(re / denom, im / denom)
+}
+
+#[cube]
+fn complex_from_real<T: Scalar, F: Float, N: Size, M: Size>(x: Vector<T, N>) -> Vector<F, M> {
+    let re = Vector::<F, N>::cast_from(x);
+    complex_new::<F, N, M>(re, Vector::new(F::new(0.0)))
+}
+
+#[cube]
+fn complex_to_real<T: Scalar, F: Float, N: Size, M: Size>(x: Vector<F, M>) -> Vector<T, N> {
+    Vector::cast_from(complex_real::<F, N, M>(x))
+}
@@ -0,0 +1,172 @@
+use cubecl_environment::collections::HashMap;
+use cubecl_ir::{
+    dialect::{base::OperationPtrExt, general::CastOp},
+    interfaces::TypedExt,
+    prelude::*,
+    types::{
+        VectorType,
+        scalar::{Int4x2Type, UInt4x2Type},
+    },
+};
+use pliron::{
+    builtin::types::{IntegerType, Signedness},
+    irbuild::match_rewrite::{RewriterOrder, apply_match_rewrite},
+};
+
+use crate::{self as cubecl, post_processing::util::retype_all, prelude::*};
+
+define_scalar!(T);
+define_size!(N);
+define_size!(M);
+
+/// Lowers packed `i4x2` and `u4x2` to `u8`, so `Vector<i4x2, N>` becomes `Vector<u8, N>`. Loads,
+/// stores and casts between packed types are left as is, casts to and from other types are
+/// replaced with shifts and masks on the nibbles.
+///
+/// Must run before unrolling, since casts change the vector size.
+#[derive(Debug, Default)]
+pub struct LowerPackedIntPass;
+
+#[derive(Clone, Copy, Debug)]
+enum CastKind {
+    Unpack { signed: bool },
+    Pack,
+}
+
+#[pass_name]
+impl Pass for LowerPackedIntPass {
+    fn run(
+        &mut self,
+        op: Ptr<Operation>,
+        ctx: &mut Context,
+        _analyses: &mut AnalysisManager,
+    ) -> Result<PassResult> {
+        let mut res = PassResult::default();
+
+        // Signedness is lost after retyping, so find the casts first
+        let mut casts = HashMap::new();
+        visit_all_ops_of_type::<CastOp, _>(ctx, &mut casts, op, |ctx, casts, cast| {
+            let from = packed_signedness(ctx, cast.input(ctx));
+            let to = packed_signedness(ctx, cast.get_result(ctx));
+            match (from, to) {
+                (Some(signed), None) => {
+                    casts.insert(cast.get_operation(), CastKind::Unpack { signed })
+                }
+                (None, Some(_)) => casts.insert(cast.get_operation(), CastKind::Pack),
+                _ => None,
+            };
+        });
+
+        res.ir_changed |= retype_all(ctx, op, lower_ty);
+        let mut rewrite = LowerPackedIntCasts { casts };
+        res.ir_changed |= apply_match_rewrite(ctx, &mut rewrite, RewriterOrder::default(), op)?;
+
+        Ok(res)
+    }
+}
+
+/// Replaces packed ints with `u8`. Returns `None` if `ty` isn't a packed int scalar or vector.
+fn lower_ty(ctx: &Context, ty: TypeHandle) -> Option<TypeHandle> {
+    let is_packed = |ty: TypeHandle| {
+        let ty = ty.deref(ctx);
+        ty.is::<Int4x2Type>() || ty.is::<UInt4x2Type>()
+    };
+    let u8_ty = || IntegerType::get(ctx, 8, Signedness::Unsigned).to_handle();
+
+    if is_packed(ty) {
+        Some(u8_ty())
+    } else if let Some(vector) = ty.deref(ctx).downcast_ref::<VectorType>() {
+        is_packed(vector.inner).then(|| VectorType::get(ctx, u8_ty(), vector.vectorization).into())
+    } else {
+        None
+    }
+}
+
+/// Whether the value is signed, or `None` if it isn't a packed int
+fn packed_signedness(ctx: &Context, value: Value) -> Option<bool> {
+    let scalar = value.scalar_ty(ctx);
+    let scalar = scalar.deref(ctx);
+    if scalar.is::<Int4x2Type>() {
+        Some(true)
+    } else if scalar.is::<UInt4x2Type>() {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Replaces casts from and to packed ints with (un)packing of the already lowered bytes
+struct LowerPackedIntCasts {
+    casts: HashMap<Ptr<Operation>, CastKind>,
+}
+
+impl MatchRewrite for LowerPackedIntCasts {
+    fn r#match(&mut self, _ctx: &Context, op: Ptr<Operation>) -> bool {
+        self.casts.contains_key(&op)
+    }
+
+    fn rewrite(
+        &mut self,
+        ctx: &mut Context,
+        rewriter: &mut MatchRewriter,
+        op: Ptr<Operation>,
+    ) -> Result<()> {
+        let scope = Scope::from_context_and_inserter(ctx, rewriter);
+        let input = op.operand(ctx, 0);
+        let result = op.deref(ctx).get_result(0);
+
+        let value = match self.casts[&op] {
+            CastKind::Unpack { signed } => {
+                scope.register_value_type::<T, M>(result);
+                scope.register_size::<N>(input.vector_size(scope.ctx()));
+                unpack_int4::expand::<T, N, M>(&scope, input.into(), signed).value(&scope)
+            }
+            CastKind::Pack => {
+                scope.register_value_type::<T, M>(input);
+                scope.register_size::<N>(result.vector_size(scope.ctx()));
+                pack_int4::expand::<T, N, M>(&scope, input.into()).value(&scope)
+            }
+        };
+
+        rewriter.replace_operation_with_values(ctx, op, vec![value]);
+        Ok(())
+    }
+}
+
+/// Splits each byte into its low and high nibble, sign extending them if `signed`
+#[cube]
+fn unpack_int4<T: Scalar, N: Size, M: Size>(
+    x: Vector<u8, N>,
+    #[comptime] signed: bool,
+) -> Vector<T, M> {
+    let x = Vector::<u32, N>::cast_from(x);
+    let mut out = Vector::<i32, M>::empty();
+    #[unroll]
+    for i in 0..N::value() {
+        let bits = x.extract(i);
+        let low = bits & 0xF;
+        let high = bits >> 4;
+        if signed {
+            out.insert(comptime![2 * i], i32::cast_from(low ^ 8) - 8);
+            out.insert(comptime![2 * i + 1], i32::cast_from(high ^ 8) - 8);
+        } else {
+            out.insert(comptime![2 * i], i32::cast_from(low));
+            out.insert(comptime![2 * i + 1], i32::cast_from(high));
+        }
+    }
+    Vector::cast_from(out)
+}
+
+/// Packs pairs of values into the low and high nibble of each byte. Out of range values wrap.
+#[cube]
+fn pack_int4<T: Scalar, N: Size, M: Size>(x: Vector<T, M>) -> Vector<u8, N> {
+    let x = Vector::<i32, M>::cast_from(x);
+    let mut out = Vector::<u8, N>::empty();
+    #[unroll]
+    for i in 0..N::value() {
+        let low = u32::cast_from(x.extract(comptime![2 * i])) & 0xF;
+        let high = u32::cast_from(x.extract(comptime![2 * i + 1])) & 0xF;
+        out.insert(i, u8::cast_from(low | (high << 4)));
+    }
+    out
+}
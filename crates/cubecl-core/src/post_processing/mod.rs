@@ -1,5 +1,8 @@
 pub mod bitwise;
 pub mod checked_io;
+pub mod complex;
+pub mod int4;
+pub mod plane;
 pub mod saturating;
 pub mod unroll;
 pub mod util;
@@ -0,0 +1,81 @@
+use crate as cubecl;
+use alloc::vec;
+use cubecl_ir::{
+    NamedRewrite, Scope,
+    dialect::{base::OperationPtrExt, plane::*},
+    prelude::*,
+};
+
+use crate::prelude::*;
+
+define_scalar!(T);
+define_size!(N);
+
+pub type LowerClusteredPlaneOpsPass = MatchRewritePass<LowerClusteredPlaneOps>;
+
+#[op_interface]
+trait ClusteredPlaneOp {
+    verify_op_succ!();
+    fn is_clustered(&self, ctx: &Context) -> bool;
+    fn lower_clustered(&self, scope: &Scope) -> Value;
+}
+
+macro_rules! clustered_op {
+    ($ty: ty, $combiner: ty) => {
+        #[op_interface_impl]
+        impl ClusteredPlaneOp for $ty {
+            fn is_clustered(&self, ctx: &Context) -> bool {
+                self.cluster_size(ctx).is_some()
+            }
+
+            fn lower_clustered(&self, scope: &Scope) -> Value {
+                let input = self.input(scope.ctx());
+                let cluster_size = self.cluster_size(scope.ctx()).unwrap().0 as u32;
+                scope.register_value_type::<T, N>(input);
+                let value = plane_reduce_with::expand::<Vector<T, N>, $combiner>(
+                    scope,
+                    input.into(),
+                    cluster_size.into(),
+                );
+                value.read_value(scope)
+            }
+        }
+    };
+}
+
+clustered_op!(ISumOp, PlaneSum);
+clustered_op!(FSumOp, PlaneSum);
+clustered_op!(IProdOp, PlaneProd);
+clustered_op!(FProdOp, PlaneProd);
+clustered_op!(SMinOp, PlaneMin);
+clustered_op!(UMinOp, PlaneMin);
+clustered_op!(FMinOp, PlaneMin);
+clustered_op!(SMaxOp, PlaneMax);
+clustered_op!(UMaxOp, PlaneMax);
+clustered_op!(FMaxOp, PlaneMax);
+
+/// Replaces clustered plane reductions with a butterfly of shuffles, for targets that can't
+/// restrict a reduction to a cluster natively. Reductions over the whole plane are left as is.
+#[derive(new, Debug, Default, NamedRewrite)]
+pub struct LowerClusteredPlaneOps;
+
+impl MatchRewrite for LowerClusteredPlaneOps {
+    fn r#match(&mut self, ctx: &Context, op: Ptr<Operation>) -> bool {
+        op_cast::<dyn ClusteredPlaneOp>(&*op.dyn_op(ctx)).is_some_and(|it| it.is_clustered(ctx))
+    }
+
+    fn rewrite(
+        &mut self,
+        ctx: &mut Context,
+        rewriter: &mut DialectConversionRewriter,
+        op: Ptr<Operation>,
+    ) -> Result<()> {
+        let dyn_op = op.dyn_op(ctx);
+        let scope = Scope::from_context_and_inserter(ctx, rewriter);
+        let clustered = op_cast::<dyn ClusteredPlaneOp>(&*dyn_op).unwrap();
+        let value = clustered.lower_clustered(&scope);
+
+        rewriter.replace_operation_with_values(ctx, op, vec![value]);
+        Ok(())
+    }
+}
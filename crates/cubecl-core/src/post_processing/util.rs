@@ -1,5 +1,15 @@
-use alloc::rc::Rc;
+use alloc::{rc::Rc, vec::Vec};
 use core::sync::atomic::{AtomicUsize, Ordering};
+use cubecl_ir::{
+    attributes::ZeroAttr,
+    dialect::memory::DeclareVariableOp,
+    prelude::*,
+    types::{
+        ArrayType, AtomicType, PointerType, RuntimeArrayType,
+        aggregate::{CheckedPtrType, SliceType},
+    },
+};
+use pliron::builtin::{ops::FuncOp, types::FunctionType};
 
 /// An atomic counter with a simplified interface.
 #[derive(Clone, Debug, Default)]
@@ -29,3 +39,106 @@ impl AtomicCounter {
         self.inner.swap(0, Ordering::SeqCst)
     }
 }
+
+/// Retypes all values, variables and the function signature of `op`. `lower` replaces scalar and
+/// vector types, and returns `None` for types that should be kept as is.
+pub fn retype_all(
+    ctx: &mut Context,
+    op: Ptr<Operation>,
+    lower: impl Fn(&Context, TypeHandle) -> Option<TypeHandle> + Copy,
+) -> IRStatus {
+    let mut status = IRStatus::Unchanged;
+
+    let mut values = Vec::new();
+    visit_all_values(ctx, &mut values, op, |ctx, values, value| {
+        if let Some(ty) = retype(ctx, value.get_type(ctx), lower) {
+            values.push((value, ty));
+        }
+    });
+    for (value, ty) in values {
+        status |= IRStatus::Changed;
+        value.set_type(ctx, ty);
+    }
+
+    let mut changed = false;
+    visit_all_ops_of_type_mut::<DeclareVariableOp, _>(
+        ctx,
+        &mut changed,
+        op,
+        |ctx, changed, decl| {
+            let value_ty = decl.value_ty(ctx).get_type(ctx);
+            let Some(new_ty) = retype(ctx, value_ty, lower) else {
+                return;
+            };
+            *changed = true;
+            let zero_init = decl
+                .initializer(ctx)
+                .is_some_and(|init| init.is::<ZeroAttr>());
+            decl.set_value_ty(ctx, new_ty);
+            if zero_init {
+                decl.set_initializer(ctx, ZeroAttr::new(new_ty).into());
+            }
+        },
+    );
+    if changed {
+        status |= IRStatus::Changed;
+    }
+
+    let func = op.as_op::<FuncOp>(ctx).expect("Should be func");
+    let func_ty = func.get_attr_func_type(ctx).unwrap().get_type(ctx);
+    let func_ty = func_ty.deref(ctx);
+    let func_ty = func_ty.downcast_ref::<FunctionType>().unwrap();
+    let lower_all = |ctx: &Context, types: Vec<TypeHandle>| {
+        let lowered = types
+            .iter()
+            .map(|ty| retype(ctx, *ty, lower).unwrap_or(*ty))
+            .collect::<Vec<_>>();
+        (lowered != types).then_some(lowered)
+    };
+    let inputs = lower_all(ctx, func_ty.arg_types());
+    let outputs = lower_all(ctx, func_ty.res_types());
+    if inputs.is_some() || outputs.is_some() {
+        status |= IRStatus::Changed;
+        let inputs = inputs.unwrap_or_else(|| func_ty.arg_types());
+        let outputs = outputs.unwrap_or_else(|| func_ty.res_types());
+        let new_func_ty = FunctionType::get(ctx, inputs, outputs).to_handle();
+        func.set_attr_func_type(ctx, new_func_ty.into());
+    }
+
+    status
+}
+
+/// Applies `lower` to the scalar or vector type nested in `ty`, rebuilding any pointers, arrays
+/// and other containers around it. Returns `None` if nothing was replaced.
+pub fn retype(
+    ctx: &Context,
+    ty: TypeHandle,
+    lower: impl Fn(&Context, TypeHandle) -> Option<TypeHandle> + Copy,
+) -> Option<TypeHandle> {
+    if let Some(lowered) = lower(ctx, ty) {
+        return Some(lowered);
+    }
+
+    let ty_obj = ty.deref(ctx);
+    if let Some(ptr) = ty_obj.downcast_ref::<PointerType>() {
+        let inner = retype(ctx, ptr.inner, lower)?;
+        Some(PointerType::get(ctx, inner, ptr.address_space).into())
+    } else if let Some(array) = ty_obj.downcast_ref::<ArrayType>() {
+        let inner = retype(ctx, array.inner, lower)?;
+        Some(ArrayType::get(ctx, inner, array.length).into())
+    } else if let Some(array) = ty_obj.downcast_ref::<RuntimeArrayType>() {
+        let inner = retype(ctx, array.inner, lower)?;
+        Some(RuntimeArrayType::get(ctx, inner).into())
+    } else if let Some(atomic) = ty_obj.downcast_ref::<AtomicType>() {
+        let inner = retype(ctx, atomic.inner, lower)?;
+        Some(AtomicType::get(ctx, inner).into())
+    } else if let Some(slice) = ty_obj.downcast_ref::<SliceType>() {
+        let base = retype(ctx, slice.base_ty, lower)?;
+        Some(SliceType::get(ctx, base).into())
+    } else if let Some(ptr) = ty_obj.downcast_ref::<CheckedPtrType>() {
+        let base = retype(ctx, ptr.base_ty, lower)?;
+        Some(CheckedPtrType::get(ctx, base).into())
+    } else {
+        None
+    }
+}
@@ -8,7 +8,7 @@ pub use crate::{
     pod::CubeElement,
     terminate,
 };
-pub use cubecl_common::{flex32, format::type_name_short_sanitized, tf32};
+pub use cubecl_common::{c32, c64, flex32, format::type_name_short_sanitized, i4x2, tf32, u4x2};
 pub use cubecl_ir::{
     AddressType, ElemType, FastMath, Scope, Type, VectorSize,
     settings::{ExecutionMode, KernelSettings},
@@ -1,4 +1,4 @@
-use std::{println, vec::Vec};
+use std::{println, vec, vec::Vec};
 
 use crate::{self as cubecl};
 
@@ -290,6 +290,145 @@ pub fn test_kernel_atomic_max_contention<R: Runtime, I: Numeric + CubeElement>(
     assert_eq!(actual, &[I::from_int((n - 1) as i64)]);
 }
 
+#[cube(launch)]
+pub fn kernel_atomic_add_contention<I: Numeric>(atomics: &[Atomic<I>]) {
+    atomics[0].fetch_add(I::from_int(1));
+}
+
+/// Many threads race `fetch_add` into one cell, so every contribution has to land even when the
+/// backend retries with a compare-exchange loop.
+pub fn test_kernel_atomic_add_contention<R: Runtime, I: Numeric + CubeElement>(
+    client: ComputeClient<R>,
+) {
+    if !require_feature::<R, I>(&client, AtomicUsage::Add, 1, "Add contention") {
+        return;
+    }
+
+    let cube_dim = std::cmp::min(32u32, client.properties().hardware.max_cube_dim.0);
+    let cube_count = 4u32;
+    let n = cube_dim * cube_count;
+
+    let handle = client.create_from_slice(I::as_bytes(&[I::from_int(0)]));
+
+    kernel_atomic_add_contention::launch::<I, R>(
+        &client,
+        CubeCount::new_1d(cube_count),
+        CubeDim::new_1d(cube_dim),
+        unsafe { BufferArg::from_raw_parts(handle.clone(), 1) },
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = I::from_bytes(&actual);
+
+    assert_eq!(actual, &[I::from_int(n as i64)]);
+}
+
+#[cube(launch)]
+pub fn kernel_atomic_last_cube<I: Int>(
+    partials: &mut [I],
+    counter: &[Atomic<I>],
+    output: &mut [I],
+) {
+    if UNIT_POS == 0 {
+        partials[CUBE_POS] = I::cast_from(CUBE_POS + 1);
+        // Release the partial, and acquire the ones of every cube that arrived before.
+        let arrived = counter[0].fetch_add_ordered(
+            I::from_int(1),
+            MemoryOrdering::AcqRel,
+            AtomicScope::Device,
+        );
+        if arrived == I::cast_from(CUBE_COUNT - 1) {
+            let mut sum = I::from_int(0);
+            for i in 0..CUBE_COUNT {
+                sum += partials[i];
+            }
+            output[0] = sum;
+        }
+    }
+}
+
+/// Every cube publishes a partial with an acquire-release increment, and the last cube to arrive
+/// must see all of them.
+pub fn test_kernel_atomic_last_cube<R: Runtime, I: Int + CubeElement>(client: ComputeClient<R>) {
+    if !require_feature::<R, I>(&client, AtomicUsage::Add, 1, "Last cube") {
+        return;
+    }
+
+    let cube_count = 8u32;
+
+    let partials = client.empty(cube_count as usize * size_of::<I>());
+    let counter = client.create_from_slice(I::as_bytes(&[I::from_int(0)]));
+    let output = client.create_from_slice(I::as_bytes(&[I::from_int(0)]));
+
+    kernel_atomic_last_cube::launch::<I, R>(
+        &client,
+        CubeCount::new_1d(cube_count),
+        CubeDim::new_1d(32),
+        unsafe { BufferArg::from_raw_parts(partials, cube_count as usize) },
+        unsafe { BufferArg::from_raw_parts(counter, 1) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 1) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = I::from_bytes(&actual);
+
+    let expected = (cube_count * (cube_count + 1) / 2) as i64;
+    assert_eq!(actual, &[I::from_int(expected)]);
+}
+
+#[cube(launch)]
+pub fn kernel_atomic_message_passing<I: Int>(data: &mut [I], flags: &[Atomic<I>], stale: &mut [I]) {
+    if UNIT_POS == 0 {
+        data[CUBE_POS] = I::cast_from(CUBE_POS + 1);
+        // Publish the data written above.
+        flags[CUBE_POS].store_ordered(I::from_int(1), MemoryOrdering::Release, AtomicScope::Device);
+
+        // A flag seen set must come with the data it published.
+        let mut count = I::from_int(0);
+        for i in 0..CUBE_COUNT {
+            let published = flags[i].load_ordered(MemoryOrdering::Acquire, AtomicScope::Device);
+            if published == I::from_int(1) {
+                if data[i] != I::cast_from(i + 1) {
+                    count += I::from_int(1);
+                }
+            }
+        }
+        stale[CUBE_POS] = count;
+    }
+}
+
+/// Every cube publishes data with a release store, then checks the data of every flag it sees set
+/// with an acquire load. Cubes never wait on each other, so whichever flags are seen, none of them
+/// may come with stale data.
+pub fn test_kernel_atomic_message_passing<R: Runtime, I: Int + CubeElement>(
+    client: ComputeClient<R>,
+) {
+    if !require_feature::<R, I>(&client, AtomicUsage::LoadStore, 1, "Message passing") {
+        return;
+    }
+
+    let cube_count = 8u32;
+    let zeros = vec![I::from_int(0); cube_count as usize];
+
+    let data = client.create_from_slice(I::as_bytes(&zeros));
+    let flags = client.create_from_slice(I::as_bytes(&zeros));
+    let stale = client.create_from_slice(I::as_bytes(&zeros));
+
+    kernel_atomic_message_passing::launch::<I, R>(
+        &client,
+        CubeCount::new_1d(cube_count),
+        CubeDim::new_1d(32),
+        unsafe { BufferArg::from_raw_parts(data, cube_count as usize) },
+        unsafe { BufferArg::from_raw_parts(flags, cube_count as usize) },
+        unsafe { BufferArg::from_raw_parts(stale.clone(), cube_count as usize) },
+    );
+
+    let actual = client.read_one_unchecked(stale);
+    let actual = I::from_bytes(&actual);
+
+    assert_eq!(actual, zeros.as_slice());
+}
+
 #[macro_export]
 macro_rules! testgen_atomic_int {
     () => {
@@ -464,6 +603,24 @@ macro_rules! testgen_atomic_uint {
                 UintType,
             >(client);
         }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_atomic_last_cube_uint() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_last_cube::<
+                TestRuntime,
+                UintType,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_atomic_message_passing_uint() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_message_passing::<
+                TestRuntime,
+                UintType,
+            >(client);
+        }
     };
 }
 
@@ -524,5 +681,23 @@ macro_rules! testgen_atomic_float {
             test_atomic_max_float,
             cubecl_core::runtime_tests::atomic::NumericAtomicOp::Max
         );
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_atomic_add_contention_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_add_contention::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_atomic_max_contention_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_max_contention::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
     };
 }
@@ -480,6 +480,54 @@ test_mulhi_impl!(
     ]
 );
 
+#[cube(launch_unchecked)]
+fn test_dot4_i8_packed_kernel(lhs: &[u32], rhs: &[u32], output: &mut [i32]) {
+    if ABSOLUTE_POS < rhs.len() {
+        output[ABSOLUTE_POS] = dot4_i8_packed(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+fn pack_i8x4(lanes: [i8; 4]) -> u32 {
+    u32::from_le_bytes(lanes.map(|lane| lane as u8))
+}
+
+pub fn test_dot4_i8_packed<R: Runtime>(client: ComputeClient<R>) {
+    let lhs = [
+        pack_i8x4([1, 2, 3, 4]),
+        pack_i8x4([-1, -2, -3, -4]),
+        pack_i8x4([127, 127, 127, 127]),
+        pack_i8x4([-128, -128, -128, -128]),
+    ];
+    let rhs = [
+        pack_i8x4([5, 6, 7, 8]),
+        pack_i8x4([5, -6, 7, -8]),
+        pack_i8x4([127, 127, 127, 127]),
+        pack_i8x4([-128, 127, -128, 127]),
+    ];
+    // The last two overflow `i8` by far: the products are accumulated in `i32`.
+    let expected: &[i32] = &[70, 18, 4 * 127 * 127, 2 * 128 * 128 - 2 * 128 * 127];
+
+    let output_handle = client.empty(expected.len() * core::mem::size_of::<i32>());
+    let lhs_handle = client.create_from_slice(u32::as_bytes(&lhs));
+    let rhs_handle = client.create_from_slice(u32::as_bytes(&rhs));
+
+    unsafe {
+        test_dot4_i8_packed_kernel::launch_unchecked(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(lhs.len() as u32),
+            BufferArg::from_raw_parts(lhs_handle, lhs.len()),
+            BufferArg::from_raw_parts(rhs_handle, rhs.len()),
+            BufferArg::from_raw_parts(output_handle.clone(), expected.len()),
+        )
+    };
+
+    let actual = client.read_one_unchecked(output_handle);
+    let actual = i32::from_bytes(&actual);
+
+    assert_eq!(actual, expected);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_binary {
@@ -535,6 +583,7 @@ macro_rules! testgen_binary_untyped {
             }
 
             add_test!(test_mulhi);
+            add_test!(test_dot4_i8_packed);
         }
     };
 }
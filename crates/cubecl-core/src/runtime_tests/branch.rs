@@ -244,6 +244,135 @@ pub fn kernel_for_loop_with_return<F: Float>(output: &mut [F]) {
     output[0] = F::new(5f32);
 }
 
+#[cube(launch)]
+pub fn kernel_for_loop_with_continue<F: Float>(output: &mut [F]) {
+    let max_iterations = comptime!(20_i32);
+    for i in 0..max_iterations {
+        if i % 2 == 0 {
+            continue;
+        }
+        output[i as usize] = F::new(1f32);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_unrolled_loop_with_continue<F: Float>(output: &mut [F], rows: u32) {
+    for row in 0..rows {
+        #[unroll]
+        for col in 0..4u32 {
+            if (row + col) % 2 == 0 {
+                continue;
+            }
+            output[(row * 4 + col) as usize] = F::new(1f32);
+        }
+        // Only the unrolled iterations are continued, not the enclosing one.
+        output[(row * 4) as usize] += F::new(2f32);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_loop_with_continue_and_break<F: Float>(output: &mut [F]) {
+    let mut i = 0u32;
+    loop {
+        i += 1;
+        if i % 3 == 0 {
+            continue;
+        }
+        if i > 10 {
+            break;
+        }
+        output[i as usize] = F::new(1f32);
+    }
+}
+
+#[cube]
+fn first_above<F: Float>(input: &[F], threshold: F) -> u32 {
+    for i in 0..input.len() {
+        if input[i] > threshold {
+            return i as u32;
+        }
+    }
+    input.len() as u32
+}
+
+#[cube]
+fn write_if_positive<F: Float>(output: &mut [F], index: usize, value: F) {
+    if value <= F::new(0f32) {
+        return;
+    }
+    output[index] = value;
+}
+
+#[cube(launch)]
+pub fn kernel_fn_early_return<F: Float>(input: &[F], output: &mut [F]) {
+    output[0] = F::cast_from(first_above(input, F::new(2f32)));
+    output[1] = F::cast_from(first_above(input, F::new(10f32)));
+    write_if_positive(output, 2, input[0]);
+    write_if_positive(output, 3, F::new(0f32) - input[0]);
+    // Runs whether or not the functions above returned early.
+    output[4] = F::new(7f32);
+}
+
+#[cube]
+fn checked_get<F: Float>(input: &[F], index: usize) -> Option<F> {
+    if index < input.len() {
+        Option::new_Some(input[index])
+    } else {
+        Option::new_None()
+    }
+}
+
+#[cube]
+fn sum_pair<F: Float>(input: &[F], index: usize) -> Option<F> {
+    let a = checked_get(input, index)?;
+    let b = checked_get(input, index + 1)?;
+    Option::new_Some(a + b)
+}
+
+#[cube]
+fn checked_div<F: Float>(a: F, b: F) -> RuntimeResult<F, u32> {
+    if b == F::new(0f32) {
+        RuntimeResult::new_Err(1u32)
+    } else {
+        RuntimeResult::new_Ok(a / b)
+    }
+}
+
+#[cube]
+fn ratio_plus_one<F: Float>(input: &[F], index: usize) -> RuntimeResult<F, u32> {
+    let ratio = checked_div(input[index], input[index + 1])?;
+    RuntimeResult::new_Ok(ratio + F::new(1f32))
+}
+
+#[cube]
+fn double_ratio<F: Float>(input: &[F], index: usize) -> RuntimeResult<F, u32> {
+    let value = ratio_plus_one(input, index)?;
+    RuntimeResult::new_Ok(value * F::new(2f32))
+}
+
+#[cube(launch)]
+pub fn kernel_try_operator<F: Float>(input: &[F], output: &mut [F]) {
+    output[0] = sum_pair(input, 0).unwrap_or(F::new(-1f32));
+    output[1] = sum_pair(input, 3).unwrap_or(F::new(-1f32));
+    output[2] = ratio_plus_one(input, 0).unwrap_or(F::new(-1f32));
+    output[3] = F::cast_from(ratio_plus_one(input, 1).err().unwrap_or(0u32));
+}
+
+#[cube]
+fn ok_or_negated_err<F: Float>(result: RuntimeResult<F, u32>) -> F {
+    match result {
+        RuntimeResult::Ok(value) => value,
+        RuntimeResult::Err(err) => F::cast_from(err) * F::new(-1f32),
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_try_operator_nested<F: Float>(input: &[F], output: &mut [F]) {
+    output[0] = ok_or_negated_err(double_ratio(input, 0));
+    output[1] = ok_or_negated_err(double_ratio(input, 1));
+    output[2] = ok_or_negated_err(double_ratio(input, 2));
+}
+
 pub fn test_switch_const<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
     let handle = client.create_from_slice(as_bytes![F: 0.0, 1.0]);
 
@@ -394,6 +523,141 @@ pub fn test_for_loop_with_return<R: Runtime, F: Float + CubeElement>(client: Com
     assert_eq!(actual, expected.as_slice());
 }
 
+pub fn test_for_loop_with_continue<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
+    let zeros = vec![F::new(0.0); 20];
+    let handle = client.create_from_slice(F::as_bytes(&zeros));
+
+    kernel_for_loop_with_continue::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(handle.clone(), 20) },
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = F::from_bytes(&actual);
+
+    let expected: Vec<F> = (0..20)
+        .map(|i| if i % 2 == 1 { F::new(1.0) } else { F::new(0.0) })
+        .collect();
+    assert_eq!(actual, expected.as_slice());
+}
+
+pub fn test_unrolled_loop_with_continue<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R>,
+) {
+    let zeros = vec![F::new(0.0); 8];
+    let handle = client.create_from_slice(F::as_bytes(&zeros));
+
+    kernel_unrolled_loop_with_continue::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(handle.clone(), 8) },
+        2,
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = F::from_bytes(&actual);
+
+    let expected: Vec<F> = [2.0, 1.0, 0.0, 1.0, 3.0, 0.0, 1.0, 0.0]
+        .into_iter()
+        .map(F::new)
+        .collect();
+    assert_eq!(actual, expected.as_slice());
+}
+
+pub fn test_loop_with_continue_and_break<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R>,
+) {
+    let zeros = vec![F::new(0.0); 16];
+    let handle = client.create_from_slice(F::as_bytes(&zeros));
+
+    kernel_loop_with_continue_and_break::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(handle.clone(), 16) },
+    );
+
+    let actual = client.read_one_unchecked(handle);
+    let actual = F::from_bytes(&actual);
+
+    let expected: Vec<F> = (0..16)
+        .map(|i| match i > 0 && i <= 10 && i % 3 != 0 {
+            true => F::new(1.0),
+            false => F::new(0.0),
+        })
+        .collect();
+    assert_eq!(actual, expected.as_slice());
+}
+
+pub fn test_fn_early_return<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
+    let input = client.create_from_slice(as_bytes![F: 1.0, 2.0, 3.0, 4.0]);
+    let output = client.create_from_slice(as_bytes![F: 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+    kernel_fn_early_return::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(input, 4) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 5) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(
+        actual,
+        &[
+            F::new(2.0),
+            F::new(4.0),
+            F::new(1.0),
+            F::new(0.0),
+            F::new(7.0)
+        ]
+    );
+}
+
+pub fn test_try_operator<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
+    let input = client.create_from_slice(as_bytes![F: 1.0, 2.0, 0.0, 4.0]);
+    let output = client.create_from_slice(as_bytes![F: 0.0, 0.0, 0.0, 0.0]);
+
+    kernel_try_operator::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(input, 4) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 4) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(
+        actual,
+        &[F::new(3.0), F::new(-1.0), F::new(1.5), F::new(1.0)]
+    );
+}
+
+pub fn test_try_operator_nested<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>) {
+    let input = client.create_from_slice(as_bytes![F: 1.0, 2.0, 0.0, 4.0]);
+    let output = client.create_from_slice(as_bytes![F: 0.0, 0.0, 0.0]);
+
+    kernel_try_operator_nested::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(input, 4) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 3) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, &[F::new(3.0), F::new(-1.0), F::new(2.0)]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_branch {
@@ -483,6 +747,57 @@ macro_rules! testgen_branch {
                 client,
             );
         }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_for_loop_with_continue() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_for_loop_with_continue::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_unrolled_loop_with_continue() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_unrolled_loop_with_continue::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_loop_with_continue_and_break() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_loop_with_continue_and_break::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_fn_early_return() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_fn_early_return::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_try_operator() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_try_operator::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_try_operator_nested() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_try_operator_nested::<TestRuntime, FloatType>(
+                client,
+            );
+        }
     };
 }
 
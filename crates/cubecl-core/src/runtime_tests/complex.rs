@@ -0,0 +1,200 @@
+use alloc::{vec, vec::Vec};
+use std::println;
+
+use crate as cubecl;
+use cubecl::prelude::*;
+use cubecl_ir::features::TypeUsage;
+use num_traits::ToPrimitive;
+
+#[cube(launch)]
+pub fn kernel_complex_arithmetic<C: Complex>(
+    input: &Tensor<C>,
+    output: &mut [C],
+    parts: &mut [C::Real],
+) {
+    if UNIT_POS == 0 {
+        let a = input[0];
+        let b = input[1];
+        output[0] = a + b;
+        output[1] = a - b;
+        output[2] = a * b;
+        output[3] = a / b;
+        output[4] = -a.conj();
+        output[5] = a.exp();
+        output[6] = C::cast_from(b.real());
+        output[7] = C::new(a.real(), b.imag());
+        output[8] = input[2] / input[3];
+        parts[0] = a.abs();
+        parts[1] = C::Real::cast_from(b);
+        parts[2] = a.imag();
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_complex_vectorized<C: Complex, N: Size>(
+    lhs: &[Vector<C, N>],
+    rhs: &[Vector<C, N>],
+    output: &mut [Vector<C, N>],
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        let (a, b) = (lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+        let num_vectors = lhs.len();
+        output[ABSOLUTE_POS] = a + b;
+        output[ABSOLUTE_POS + num_vectors] = a * b;
+        output[ABSOLUTE_POS + 2 * num_vectors] = a / b;
+    }
+}
+
+fn assert_close<C: Complex + CubeElement>(actual: &[C], expected: &[C]) {
+    for (actual, expected) in actual.iter().zip(expected) {
+        let diff = Complex::abs(*actual - *expected).to_f64().unwrap();
+        assert!(diff < 1e-4, "Expected {expected:?}, got {actual:?}");
+    }
+}
+
+pub fn test_complex_arithmetic<R: Runtime, C: Complex + CubeElement>(client: ComputeClient<R>)
+where
+    C::Real: CubeElement,
+{
+    if !C::supported_uses(&client).contains(TypeUsage::Arithmetic) {
+        println!("Unsupported, skipping");
+        return;
+    }
+
+    let new = |re: f32, im: f32| C::new(C::Real::new(re), C::Real::new(im));
+    let a = new(1.5, -2.0);
+    let b = new(-0.5, 0.75);
+    // `c * c + d * d` overflows `f32` for this divisor, the quotient is `1 - i`
+    let (big_num, big_den) = (new(2e30, 0.0), new(1e30, 1e30));
+    let input = client.create_from_slice(C::as_bytes(&[a, b, big_num, big_den]));
+    let output = client.empty(9 * core::mem::size_of::<C>());
+    let parts = client.empty(3 * core::mem::size_of::<C::Real>());
+
+    kernel_complex_arithmetic::launch::<C, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { TensorArg::from_raw_parts(input, vec![1].into(), vec![4].into()) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 9) },
+        unsafe { BufferArg::from_raw_parts(parts.clone(), 3) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = C::from_bytes(&actual);
+    let expected: Vec<C> = vec![
+        a + b,
+        a - b,
+        a * b,
+        a / b,
+        -a.conj(),
+        a.exp(),
+        C::from_real(b.real()),
+        C::new(a.real(), b.imag()),
+        new(1.0, -1.0),
+    ];
+    assert_close(actual, &expected);
+
+    let actual = client.read_one_unchecked(parts);
+    let actual = C::Real::from_bytes(&actual);
+    let expected = [a.abs(), b.real(), a.imag()];
+    for (actual, expected) in actual.iter().zip(expected) {
+        let diff = (actual.to_f64().unwrap() - expected.to_f64().unwrap()).abs();
+        assert!(diff < 1e-4, "Expected {expected:?}, got {actual:?}");
+    }
+}
+
+/// Vectors of complex numbers are lowered to vectors of twice as many parts, so this checks
+/// every lane ends up paired with its own imaginary part.
+pub fn test_complex_vectorized<R: Runtime, C: Complex + CubeElement>(client: ComputeClient<R>) {
+    if !C::supported_uses(&client).contains(TypeUsage::Arithmetic) {
+        println!("Unsupported, skipping");
+        return;
+    }
+
+    let new = |re: f32, im: f32| C::new(C::Real::new(re), C::Real::new(im));
+    let lhs = [
+        new(1.5, -2.0),
+        new(0.25, 3.0),
+        new(-1.0, 0.5),
+        new(4.0, 2.0),
+    ];
+    let rhs = [
+        new(-0.5, 0.75),
+        new(2.0, -1.0),
+        new(0.0, 1.5),
+        new(-3.0, -0.25),
+    ];
+    let pairs = || lhs.iter().zip(&rhs).map(|(a, b)| (*a, *b));
+    let expected: Vec<C> = pairs()
+        .map(|(a, b)| a + b)
+        .chain(pairs().map(|(a, b)| a * b))
+        .chain(pairs().map(|(a, b)| a / b))
+        .collect();
+
+    for vector_size in [2, 4] {
+        let num_vectors = lhs.len() / vector_size;
+        let lhs = client.create_from_slice(C::as_bytes(&lhs));
+        let rhs = client.create_from_slice(C::as_bytes(&rhs));
+        let output = client.empty(expected.len() * core::mem::size_of::<C>());
+
+        kernel_complex_vectorized::launch::<C, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(num_vectors as u32),
+            vector_size,
+            unsafe { BufferArg::from_raw_parts(lhs, num_vectors) },
+            unsafe { BufferArg::from_raw_parts(rhs, num_vectors) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), 3 * num_vectors) },
+        );
+
+        let actual = client.read_one_unchecked(output);
+        let actual = C::from_bytes(&actual);
+        assert_close(actual, &expected);
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_complex {
+    () => {
+        mod complex {
+            use super::*;
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_complex_arithmetic() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::complex::test_complex_arithmetic::<
+                    TestRuntime,
+                    $crate::prelude::c32,
+                >(client);
+            }
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_complex_arithmetic_c64() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::complex::test_complex_arithmetic::<
+                    TestRuntime,
+                    $crate::prelude::c64,
+                >(client);
+            }
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_complex_vectorized() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::complex::test_complex_vectorized::<
+                    TestRuntime,
+                    $crate::prelude::c32,
+                >(client);
+            }
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_complex_vectorized_c64() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::complex::test_complex_vectorized::<
+                    TestRuntime,
+                    $crate::prelude::c64,
+                >(client);
+            }
+        }
+    };
+}
@@ -0,0 +1,93 @@
+use alloc::vec::Vec;
+use std::println;
+
+use crate as cubecl;
+use cubecl::prelude::*;
+use cubecl_ir::features::TypeUsage;
+
+#[cube(launch_unchecked)]
+pub fn kernel_int4<N: Size, N2: Size>(
+    signed: &[Vector<i4x2, N>],
+    unsigned: &[Vector<u4x2, N>],
+    ints: &mut [Vector<i32, N2>],
+    uints: &mut [Vector<u32, N2>],
+    floats: &mut [Vector<f32, N2>],
+    repacked: &mut [Vector<i4x2, N>],
+) {
+    if ABSOLUTE_POS == 0 {
+        let value = signed[0];
+        ints[0] = Vector::cast_from(value);
+        uints[0] = Vector::cast_from(unsigned[0]);
+        floats[0] = Vector::cast_from(value);
+        repacked[0] = Vector::cast_from(floats[0]);
+    }
+}
+
+pub fn test_int4<R: Runtime>(client: ComputeClient<R>, vector_size: VectorSize) {
+    if !i4x2::supported_uses(&client).contains(TypeUsage::Buffer) {
+        println!("Unsupported, skipping");
+        return;
+    }
+
+    let num_values = 2 * vector_size;
+    let ints = (-8..8).step_by(16 / num_values).collect::<Vec<i8>>();
+    let uints = (0..16).rev().step_by(16 / num_values).collect::<Vec<u8>>();
+    let signed = i4x2::from_slice(&ints);
+    let unsigned = u4x2::from_slice(&uints);
+
+    let signed_handle = client.create_from_slice(i4x2::as_bytes(&signed));
+    let unsigned_handle = client.create_from_slice(u4x2::as_bytes(&unsigned));
+    let ints_handle = client.empty(num_values * size_of::<i32>());
+    let uints_handle = client.empty(num_values * size_of::<u32>());
+    let floats_handle = client.empty(num_values * size_of::<f32>());
+    let repacked_handle = client.empty(vector_size * size_of::<i4x2>());
+
+    unsafe {
+        kernel_int4::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            vector_size,
+            num_values,
+            BufferArg::from_raw_parts(signed_handle, vector_size),
+            BufferArg::from_raw_parts(unsigned_handle, vector_size),
+            BufferArg::from_raw_parts(ints_handle.clone(), num_values),
+            BufferArg::from_raw_parts(uints_handle.clone(), num_values),
+            BufferArg::from_raw_parts(floats_handle.clone(), num_values),
+            BufferArg::from_raw_parts(repacked_handle.clone(), vector_size),
+        )
+    };
+
+    let actual = client.read_one_unchecked(ints_handle);
+    let expected = ints.iter().map(|it| *it as i32).collect::<Vec<_>>();
+    assert_eq!(i32::from_bytes(&actual), &expected);
+
+    let actual = client.read_one_unchecked(uints_handle);
+    let expected = uints.iter().map(|it| *it as u32).collect::<Vec<_>>();
+    assert_eq!(u32::from_bytes(&actual), &expected);
+
+    let actual = client.read_one_unchecked(floats_handle);
+    let expected = ints.iter().map(|it| *it as f32).collect::<Vec<_>>();
+    assert_eq!(f32::from_bytes(&actual), &expected);
+
+    let actual = client.read_one_unchecked(repacked_handle);
+    assert_eq!(i4x2::from_bytes(&actual), &signed);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_int4 {
+    () => {
+        mod int4 {
+            use super::*;
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_int4() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::int4::test_int4::<TestRuntime>(client.clone(), 1);
+                cubecl_core::runtime_tests::int4::test_int4::<TestRuntime>(client.clone(), 2);
+                cubecl_core::runtime_tests::int4::test_int4::<TestRuntime>(client, 4);
+            }
+        }
+    };
+}
@@ -0,0 +1,122 @@
+use alloc::{vec, vec::Vec};
+
+use crate as cubecl;
+use cubecl::prelude::*;
+
+// Operands straddle the 32-bit boundary so a carry, borrow or shift that only looks at the
+// low word gives a visibly wrong answer on backends that emulate 64-bit integers.
+#[cube(launch)]
+pub fn kernel_u64_arithmetic(input: &[u64], output: &mut [u64]) {
+    if UNIT_POS == 0 {
+        let a = input[0];
+        let b = input[1];
+        output[0] = a + b;
+        output[1] = a - b;
+        output[2] = a * b;
+        output[3] = a / b;
+        output[4] = a % b;
+        output[5] = a << 33u64;
+        output[6] = a >> 33u64;
+        output[7] = u64::cast_from(a > b) + u64::cast_from(a != b) * 2;
+        output[8] = u64::cast_from(u32::cast_from(a));
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_i64_arithmetic(input: &[i64], output: &mut [i64]) {
+    if UNIT_POS == 0 {
+        let a = input[0];
+        let b = input[1];
+        output[0] = a + b;
+        output[1] = a * b;
+        output[2] = a / b;
+        output[3] = a % b;
+        output[4] = a >> 3i64;
+        output[5] = -a;
+        output[6] = i64::cast_from(a < b);
+        output[7] = i64::cast_from(i32::cast_from(b));
+    }
+}
+
+pub fn test_u64_arithmetic<R: Runtime>(client: ComputeClient<R>) {
+    let a = 0x1_8000_0001u64;
+    let b = 0xFFFF_FFFFu64;
+    let input = client.create_from_slice(u64::as_bytes(&[a, b]));
+    let output = client.empty(9 * core::mem::size_of::<u64>());
+
+    kernel_u64_arithmetic::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(input, 2) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 9) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = u64::from_bytes(&actual);
+    let expected: Vec<u64> = vec![
+        a.wrapping_add(b),
+        a.wrapping_sub(b),
+        a.wrapping_mul(b),
+        a / b,
+        a % b,
+        a << 33,
+        a >> 33,
+        3,
+        a as u32 as u64,
+    ];
+
+    assert_eq!(actual, expected.as_slice());
+}
+
+pub fn test_i64_arithmetic<R: Runtime>(client: ComputeClient<R>) {
+    let a = -0x1_2345_6789i64;
+    let b = 0x7_0000i64;
+    let input = client.create_from_slice(i64::as_bytes(&[a, b]));
+    let output = client.empty(8 * core::mem::size_of::<i64>());
+
+    kernel_i64_arithmetic::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { BufferArg::from_raw_parts(input, 2) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), 8) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = i64::from_bytes(&actual);
+    let expected: Vec<i64> = vec![
+        a.wrapping_add(b),
+        a.wrapping_mul(b),
+        a / b,
+        a % b,
+        a >> 3,
+        -a,
+        1,
+        b as i32 as i64,
+    ];
+
+    assert_eq!(actual, expected.as_slice());
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_int64 {
+    () => {
+        mod int64 {
+            use super::*;
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_u64_arithmetic() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::int64::test_u64_arithmetic::<TestRuntime>(client);
+            }
+
+            #[$crate::runtime_tests::test_log::test]
+            fn test_i64_arithmetic() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::int64::test_i64_arithmetic::<TestRuntime>(client);
+            }
+        }
+    };
+}
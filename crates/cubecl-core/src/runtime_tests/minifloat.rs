@@ -5,6 +5,7 @@ use crate::{self as cubecl, as_type};
 use cubecl::prelude::*;
 use cubecl_common::{e2m1x2, e2m3, e3m2, e4m3, e5m2, ue8m0};
 use cubecl_ir::features::TypeUsage;
+use half::bf16;
 
 #[cube(launch_unchecked)]
 pub fn kernel_fp8<F: Float, N: Size>(input: &mut [Vector<F, N>], out: &mut [Vector<u8, N>]) {
@@ -41,6 +42,28 @@ pub fn kernel_fp4<F: Float, N: Size, N2: Size>(
     }
 }
 
+#[cube(launch)]
+pub fn kernel_storage_round_trip<S: Float, N: Size>(
+    input: &[Vector<f32, N>],
+    storage: &mut [Vector<S, N>],
+    output: &mut [Vector<f32, N>],
+) {
+    if ABSOLUTE_POS < input.len() {
+        storage[ABSOLUTE_POS] = Vector::cast_from(input[ABSOLUTE_POS]);
+        output[ABSOLUTE_POS] = Vector::cast_from(storage[ABSOLUTE_POS]);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_storage_read<S: Float, N: Size>(
+    storage: &[Vector<S, N>],
+    output: &mut [Vector<f32, N>],
+) {
+    if ABSOLUTE_POS < storage.len() {
+        output[ABSOLUTE_POS] = Vector::cast_from(storage[ABSOLUTE_POS]);
+    }
+}
+
 #[cube(launch_unchecked)]
 pub fn kernel_scale<N: Size>(input: &mut [Vector<f32, N>], out: &mut [Vector<ue8m0, N>]) {
     if ABSOLUTE_POS == 0 {
@@ -56,7 +79,10 @@ pub fn test_fp8<R: Runtime, F: Float + CubeElement>(
     client: ComputeClient<R>,
     vector_size: VectorSize,
 ) {
-    if !e4m3::supported_uses(&client).contains(TypeUsage::Conversion) {
+    // The bits are inspected through `u8`, which WGSL can't store
+    if !e4m3::supported_uses(&client).contains(TypeUsage::Conversion)
+        || !u8::supported_uses(&client).contains(TypeUsage::Buffer)
+    {
         println!("Unsupported, skipping");
         return;
     }
@@ -223,6 +249,65 @@ pub fn test_scale<R: Runtime>(client: ComputeClient<R>, vector_size: VectorSize)
     //assert_eq!(&actual_2[..num_out], &data[..num_out]);
 }
 
+/// Every unit writes its own element, so on backends that pack several elements into one word
+/// this also checks that neighbouring writes don't clobber each other. The storage is then read
+/// again by a kernel that never writes it.
+fn storage_round_trip<R: Runtime, S: Float + CubeElement>(
+    client: &ComputeClient<R>,
+    vector_size: VectorSize,
+) {
+    if !S::supported_uses(client).contains(TypeUsage::Buffer) {
+        println!("Unsupported, skipping");
+        return;
+    }
+
+    // Exactly representable in bf16, e4m3 and e5m2
+    let data = [-2.0f32, 1.75, 0.5, 1.25, -0.375, 3.0, 0.0, -1.5];
+    let num_vectors = data.len() / vector_size;
+    let input = client.create_from_slice(f32::as_bytes(&data));
+    let storage = client.empty(data.len() * size_of::<S>());
+    let output = client.empty(data.len() * size_of::<f32>());
+
+    kernel_storage_round_trip::launch::<S, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(num_vectors as u32),
+        vector_size,
+        unsafe { BufferArg::from_raw_parts(input, num_vectors) },
+        unsafe { BufferArg::from_raw_parts(storage.clone(), num_vectors) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), num_vectors) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &data);
+
+    let output = client.empty(data.len() * size_of::<f32>());
+
+    kernel_storage_read::launch::<S, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(num_vectors as u32),
+        vector_size,
+        unsafe { BufferArg::from_raw_parts(storage, num_vectors) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), num_vectors) },
+    );
+
+    let actual = client.read_one_unchecked(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &data);
+}
+
+pub fn test_storage_round_trip<R: Runtime>(client: ComputeClient<R>) {
+    for vector_size in [1, 2, 4] {
+        storage_round_trip::<R, bf16>(&client, vector_size);
+        storage_round_trip::<R, e4m3>(&client, vector_size);
+        storage_round_trip::<R, e5m2>(&client, vector_size);
+    }
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_minifloat {
@@ -276,6 +361,12 @@ macro_rules! testgen_minifloat {
             );
         }
 
+        #[$crate::runtime_tests::test_log::test]
+        fn test_storage_round_trip() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::minifloat::test_storage_round_trip::<TestRuntime>(client);
+        }
+
         #[$crate::runtime_tests::test_log::test]
         fn test_scale() {
             let client = TestRuntime::client(&Default::default());
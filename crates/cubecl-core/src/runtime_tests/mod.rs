@@ -11,12 +11,15 @@ pub mod cluster;
 pub mod cmma;
 pub mod cmma2;
 pub mod comparison;
+pub mod complex;
 pub mod const_match;
 pub mod debug;
 pub mod different_rank;
 pub mod enums;
 pub mod file;
 pub mod index;
+pub mod int4;
+pub mod int64;
 pub mod launch;
 pub mod metadata;
 pub mod minifloat;
@@ -156,6 +159,9 @@ macro_rules! testgen_untyped {
 
         cubecl_core::testgen_enums!();
         cubecl_core::testgen_comparison!();
+        cubecl_core::testgen_int64!();
+        cubecl_core::testgen_complex!();
+        cubecl_core::testgen_int4!();
 
         cubecl_core::testgen_to_client!();
         cubecl_core::testgen_all_reduce!();
@@ -151,6 +151,52 @@ pub fn kernel_shuffle_down<F: Float, N: Size>(output: &mut Tensor<Vector<F, N>>)
     output[UNIT_POS as usize] = val2;
 }
 
+#[cube(launch)]
+pub fn kernel_sum_clustered<F: Float, N: Size>(output: &mut Tensor<Vector<F, N>>) {
+    let val = output[UNIT_POS as usize];
+    let val2 = plane_sum_clustered(val, 8u32);
+
+    output[UNIT_POS as usize] = val2;
+}
+
+#[cube(launch)]
+pub fn kernel_max_clustered<F: Float, N: Size>(output: &mut Tensor<Vector<F, N>>) {
+    let val = output[UNIT_POS as usize];
+    let val2 = plane_max_clustered(val, 4u32);
+
+    output[UNIT_POS as usize] = val2;
+}
+
+/// Keeps the largest value and its lane, preferring the lower lane on ties
+pub struct ArgMax;
+
+#[cube]
+impl<F: Float> PlaneCombiner<(F, u32)> for ArgMax {
+    fn combine(lhs: (F, u32), rhs: (F, u32)) -> (F, u32) {
+        let take_rhs = rhs.0 > lhs.0 || (rhs.0 == lhs.0 && rhs.1 < lhs.1);
+        (
+            select(take_rhs, rhs.0, lhs.0),
+            select(take_rhs, rhs.1, lhs.1),
+        )
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_argmax_with<F: Float>(output: &mut Tensor<F>) {
+    let val = output[UNIT_POS as usize];
+    let max = plane_reduce_with::<(F, u32), ArgMax>((val, UNIT_POS_PLANE), 8);
+
+    output[UNIT_POS as usize] = F::cast_from(max.1);
+}
+
+#[cube(launch)]
+pub fn kernel_scan_with<F: Float, N: Size>(output: &mut Tensor<Vector<F, N>>) {
+    let val = output[UNIT_POS as usize];
+    let val2 = plane_scan_with::<Vector<F, N>, PlaneSum>(val, 4);
+
+    output[UNIT_POS as usize] = val2;
+}
+
 pub fn test_plane_sum<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -846,6 +892,181 @@ pub fn test_plane_shuffle_down<
     );
 }
 
+pub fn test_plane_sum_clustered<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime>,
+    vectorization: VectorSize,
+) {
+    let plane_size = 32;
+    let cluster_size = 8;
+    let input: Vec<f32> = (0..plane_size * vectorization as u32)
+        .map(|x| x as f32)
+        .collect();
+    let mut expected = input.clone();
+
+    for lane in 0..plane_size as usize {
+        let cluster_start = lane / cluster_size * cluster_size;
+        for v in 0..vectorization {
+            expected[lane * vectorization + v] = (cluster_start..cluster_start + cluster_size)
+                .map(|k| input[k * vectorization + v])
+                .sum();
+        }
+    }
+
+    let input: Vec<F> = input.into_iter().map(|x| F::new(x)).collect();
+    let expected: Vec<F> = expected.into_iter().map(|x| F::new(x)).collect();
+
+    test_plane_operation::<TestRuntime, F, _>(
+        &input,
+        &expected,
+        client.clone(),
+        |cube_count, handle| {
+            kernel_sum_clustered::launch::<F, TestRuntime>(
+                &client,
+                cube_count,
+                CubeDim::new_1d(plane_size),
+                vectorization,
+                handle,
+            )
+        },
+    );
+}
+
+pub fn test_plane_max_clustered<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime>,
+    vectorization: VectorSize,
+) {
+    let plane_size = 32;
+    let cluster_size = 4;
+    // Alternate between rising and falling so the max isn't always at the same position
+    let input: Vec<f32> = (0..plane_size * vectorization as u32)
+        .map(|x| {
+            if x % 3 == 0 {
+                100.0 - x as f32
+            } else {
+                x as f32
+            }
+        })
+        .collect();
+    let mut expected = input.clone();
+
+    for lane in 0..plane_size as usize {
+        let cluster_start = lane / cluster_size * cluster_size;
+        for v in 0..vectorization {
+            expected[lane * vectorization + v] = (cluster_start..cluster_start + cluster_size)
+                .map(|k| input[k * vectorization + v])
+                .fold(f32::NEG_INFINITY, f32::max);
+        }
+    }
+
+    let input: Vec<F> = input.into_iter().map(|x| F::new(x)).collect();
+    let expected: Vec<F> = expected.into_iter().map(|x| F::new(x)).collect();
+
+    test_plane_operation::<TestRuntime, F, _>(
+        &input,
+        &expected,
+        client.clone(),
+        |cube_count, handle| {
+            kernel_max_clustered::launch::<F, TestRuntime>(
+                &client,
+                cube_count,
+                CubeDim::new_1d(plane_size),
+                vectorization,
+                handle,
+            )
+        },
+    );
+}
+
+pub fn test_plane_argmax_with<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime>,
+) {
+    let plane_size = 32;
+    let cluster_size = 8;
+    let mut input: Vec<f32> = (0..plane_size).map(|x| (x % 5) as f32).collect();
+    input[17] = 10.0;
+    // Tie with a later lane, the lower one should win
+    input[26] = 4.0;
+    input[27] = 4.0;
+
+    let expected: Vec<f32> = (0..plane_size as usize)
+        .map(|lane| {
+            let cluster_start = lane / cluster_size * cluster_size;
+            let cluster = &input[cluster_start..cluster_start + cluster_size];
+            let max = cluster.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let index = cluster.iter().position(|it| *it == max).unwrap();
+            (cluster_start + index) as f32
+        })
+        .collect();
+
+    let input: Vec<F> = input.into_iter().map(|x| F::new(x)).collect();
+    let expected: Vec<F> = expected.into_iter().map(|x| F::new(x)).collect();
+
+    test_plane_operation::<TestRuntime, F, _>(
+        &input,
+        &expected,
+        client.clone(),
+        |cube_count, handle| {
+            kernel_argmax_with::launch::<F, TestRuntime>(
+                &client,
+                cube_count,
+                CubeDim::new_1d(plane_size),
+                handle,
+            )
+        },
+    );
+}
+
+pub fn test_plane_scan_with<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime>,
+    vectorization: VectorSize,
+) {
+    let plane_size = 32;
+    let cluster_size = 4;
+    let input: Vec<f32> = (0..plane_size * vectorization as u32)
+        .map(|x| x as f32)
+        .collect();
+    let mut expected = input.clone();
+
+    for lane in 0..plane_size as usize {
+        let cluster_start = lane / cluster_size * cluster_size;
+        for v in 0..vectorization {
+            expected[lane * vectorization + v] = (cluster_start..=lane)
+                .map(|k| input[k * vectorization + v])
+                .sum();
+        }
+    }
+
+    let input: Vec<F> = input.into_iter().map(|x| F::new(x)).collect();
+    let expected: Vec<F> = expected.into_iter().map(|x| F::new(x)).collect();
+
+    test_plane_operation::<TestRuntime, F, _>(
+        &input,
+        &expected,
+        client.clone(),
+        |cube_count, handle| {
+            kernel_scan_with::launch::<F, TestRuntime>(
+                &client,
+                cube_count,
+                CubeDim::new_1d(plane_size),
+                vectorization,
+                handle,
+            )
+        },
+    );
+}
+
 fn test_plane_operation<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -1182,5 +1403,61 @@ macro_rules! testgen_plane {
         fn test_plane_shuffle_down_vec4() {
             impl_test_plane_shuffle_down(4);
         }
+
+        fn impl_test_plane_sum_clustered(vectorization: VectorSize) {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_sum_clustered::<TestRuntime, FloatType>(
+                client.clone(),
+                vectorization,
+            );
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_sum_clustered_vec1() {
+            impl_test_plane_sum_clustered(1);
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_sum_clustered_vec4() {
+            impl_test_plane_sum_clustered(4);
+        }
+
+        fn impl_test_plane_max_clustered(vectorization: VectorSize) {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_max_clustered::<TestRuntime, FloatType>(
+                client.clone(),
+                vectorization,
+            );
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_max_clustered_vec1() {
+            impl_test_plane_max_clustered(1);
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_max_clustered_vec4() {
+            impl_test_plane_max_clustered(4);
+        }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_argmax_with() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_argmax_with::<TestRuntime, FloatType>(
+                client.clone(),
+            );
+        }
+
+        fn impl_test_plane_scan_with(vectorization: VectorSize) {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_scan_with::<TestRuntime, FloatType>(
+                client.clone(),
+                vectorization,
+            );
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_scan_with_vec1() {
+            impl_test_plane_scan_with(1);
+        }
+        #[$crate::runtime_tests::test_log::test]
+        fn test_plane_scan_with_vec4() {
+            impl_test_plane_scan_with(4);
+        }
     };
 }
@@ -54,6 +54,68 @@ pub fn test_stream<R: Runtime, F: Float + CubeElement>(client: ComputeClient<R>)
     assert_eq!(actual[0], F::new(1318936000.0));
 }
 
+#[cube(launch)]
+pub fn write_position(output: &mut [u32]) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = ABSOLUTE_POS as u32;
+    }
+}
+
+/// The consumer only reads what the producer wrote because it waits on the producer's event.
+pub fn test_stream_event<R: Runtime>(client: ComputeClient<R>) {
+    let producer = unsafe {
+        let mut c = client.clone();
+        c.set_stream(StreamId { value: 10002 });
+        c
+    };
+    let consumer = unsafe {
+        let mut c = client.clone();
+        c.set_stream(StreamId { value: 10003 });
+        c
+    };
+
+    let len = 4096;
+    // Created on the consumer, so only the event tells its stream that the producer writes it.
+    let output = consumer.empty(len * core::mem::size_of::<u32>());
+
+    // Keep the producer busy, so a read that doesn't wait for its work sees the output unwritten.
+    let input: Vec<u32> = (0..len as u32).collect();
+    let mut input = producer.create_from_slice(u32::as_bytes(&input));
+    for _ in 0..20 {
+        let scratch = producer.empty(len * core::mem::size_of::<f32>());
+        unsafe {
+            big_task::launch::<f32, R>(
+                &producer,
+                CubeCount::Static(len as u32 / 32, 1, 1),
+                CubeDim::new_1d(32),
+                BufferArg::from_raw_parts(input, len),
+                BufferArg::from_raw_parts(scratch.clone(), len),
+                4096,
+            )
+        };
+        input = scratch;
+    }
+    unsafe {
+        write_position::launch::<R>(
+            &producer,
+            CubeCount::Static(len as u32 / 32, 1, 1),
+            CubeDim::new_1d(32),
+            BufferArg::from_raw_parts(output.clone(), len),
+        )
+    };
+
+    let event = producer.record_event().unwrap();
+    consumer.wait_event(&event).unwrap();
+    let actual = consumer.read_one_unchecked(output);
+
+    cubecl_environment::future::block_on(event.synchronize()).unwrap();
+    assert!(event.query().unwrap());
+    assert_eq!(
+        u32::from_bytes(&actual),
+        (0..len as u32).collect::<Vec<_>>()
+    );
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_stream {
@@ -66,5 +128,11 @@ macro_rules! testgen_stream {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::stream::test_stream::<TestRuntime, FloatType>(client);
         }
+
+        #[$crate::runtime_tests::test_log::test]
+        fn test_stream_event() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_event::<TestRuntime>(client);
+        }
     };
 }
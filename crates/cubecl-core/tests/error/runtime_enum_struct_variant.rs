@@ -0,0 +1,11 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[derive(IntoRuntime)]
+#[cube(runtime_variants)]
+enum Shape {
+    Empty,
+    Rect { width: u32, height: u32 },
+}
+
+fn main() {}
@@ -75,6 +75,25 @@ fn atom_ty_cmp(ctx: &Context, val: impl Typed) -> &'static str {
     }
 }
 
+fn atom_sem(ordering: MemoryOrdering) -> &'static str {
+    match ordering {
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed => ".relaxed",
+        MemoryOrdering::Acquire => ".acquire",
+        MemoryOrdering::Release => ".release",
+        // `seq_cst` has no `atom` semantic, it's a `fence.sc` followed by an `acq_rel` atom
+        MemoryOrdering::AcqRel | MemoryOrdering::SeqCst => ".acq_rel",
+    }
+}
+
+fn atom_scope(scope: AtomicScope) -> &'static str {
+    match scope {
+        // PTX has no warp scope, the CTA is the narrowest one
+        AtomicScope::Plane | AtomicScope::Cube => ".cta",
+        AtomicScope::Device => ".gpu",
+        AtomicScope::System => ".sys",
+    }
+}
+
 // Reinterpet f16 etc
 fn as_registers(scope: &Scope, val: Value) -> Value {
     let vec = val.vector_size(scope.ctx());
@@ -110,7 +129,18 @@ macro_rules! atomic_binop {
                 let ty = $atom_ty(ctx, value);
                 let value = as_registers(scope, value);
 
-                let ptx = format!("atom.relaxed.{}{ftz}{vec}.{ty} $0, [$1], $2;", $op);
+                let ordering = self.ordering(ctx).0;
+                let atomic_scope = atom_scope(self.scope(ctx).0);
+                let sem = atom_sem(ordering);
+                let fence = match ordering {
+                    MemoryOrdering::SeqCst => format!("fence.sc{atomic_scope}; "),
+                    _ => String::new(),
+                };
+
+                let ptx = format!(
+                    "{fence}atom{sem}{atomic_scope}.{}{ftz}{vec}.{ty} $0, [$1], $2;",
+                    $op
+                );
                 let op = InlinePtxOp::new_volatile(
                     ctx,
                     Some(value.get_type(ctx)),
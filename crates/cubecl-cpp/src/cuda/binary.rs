@@ -1,10 +1,16 @@
 use cubecl_core::ir::{
-    dialect::math::{SaturatingSAddOp, SaturatingSSubOp},
+    dialect::{
+        math::{SaturatingSAddOp, SaturatingSSubOp},
+        vector::SDot4x8PackedOp,
+    },
     interfaces::TypedExt,
     prelude::*,
 };
 
-use crate::cuda::ptx_with_out;
+use crate::{
+    cuda::{cuda_op_with_out, ptx_with_out},
+    shared::CppValue,
+};
 
 ptx_with_out!(
     SaturatingSAddOp,
@@ -18,3 +24,9 @@ ptx_with_out!(
     |op, ctx| op.result_type(ctx).is_int_of_width(ctx, 32)
         && op.result_type(ctx).is_signed_int(ctx)
 );
+
+cuda_op_with_out!(SDot4x8PackedOp, |op, ctx| {
+    let lhs = op.lhs(ctx).name(ctx);
+    let rhs = op.rhs(ctx).name(ctx);
+    format!("__dp4a(int({lhs}), int({rhs}), 0)")
+});
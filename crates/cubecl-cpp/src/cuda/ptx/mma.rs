@@ -315,17 +315,20 @@ pub fn ptx_mma_ty<T: Scalar>() -> comptime_type!(&'static str) {
                 FloatKind::F64 => "f64",
             },
             ElemType::Int(kind) => match kind {
+                IntKind::I4x2 => "s4",
                 IntKind::I8 => "s8",
                 IntKind::I16 => "s16",
                 IntKind::I32 => "s32",
                 IntKind::I64 => "s64",
             },
             ElemType::UInt(kind) => match kind {
+                UIntKind::U4x2 => "u4",
                 UIntKind::U8 => "u8",
                 UIntKind::U16 => "u16",
                 UIntKind::U32 => "u32",
                 UIntKind::U64 => "u64",
             },
+            ElemType::Complex(_) => panic!("Complex isn't supported by PTX MMA"),
             ElemType::Bool => "b1",
         }
     })
@@ -364,17 +367,20 @@ pub fn mma_ty(ctx: &Context, elem: &dyn Type) -> &'static str {
             FloatKind::F64 => "f64",
         },
         ElemType::Int(kind) => match kind {
+            IntKind::I4x2 => "s4",
             IntKind::I8 => "s8",
             IntKind::I16 => "s16",
             IntKind::I32 => "s32",
             IntKind::I64 => "s64",
         },
         ElemType::UInt(kind) => match kind {
+            UIntKind::U4x2 => "u4",
             UIntKind::U8 => "u8",
             UIntKind::U16 => "u16",
             UIntKind::U32 => "u32",
             UIntKind::U64 => "u64",
         },
+        ElemType::Complex(_) => panic!("Complex isn't supported by PTX MMA"),
         ElemType::Bool => "b1",
     }
 }
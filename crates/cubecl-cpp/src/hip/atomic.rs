@@ -2,14 +2,37 @@ use cubecl_core::ir::{dialect::atomic::*, prelude::*};
 
 use crate::hip::hip_op_with_out;
 
+fn hip_order(ordering: MemoryOrdering) -> &'static str {
+    match ordering {
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed => "__ATOMIC_RELAXED",
+        MemoryOrdering::Acquire => "__ATOMIC_ACQUIRE",
+        MemoryOrdering::Release => "__ATOMIC_RELEASE",
+        MemoryOrdering::AcqRel => "__ATOMIC_ACQ_REL",
+        MemoryOrdering::SeqCst => "__ATOMIC_SEQ_CST",
+    }
+}
+
+fn hip_scope(scope: AtomicScope) -> &'static str {
+    match scope {
+        AtomicScope::Plane => "__HIP_MEMORY_SCOPE_WAVEFRONT",
+        AtomicScope::Cube => "__HIP_MEMORY_SCOPE_WORKGROUP",
+        AtomicScope::Device => "__HIP_MEMORY_SCOPE_AGENT",
+        AtomicScope::System => "__HIP_MEMORY_SCOPE_SYSTEM",
+    }
+}
+
 hip_op_with_out!(AtomicIAddOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicAdd({ptr}, {value})")
+    let order = hip_order(op.ordering(ctx).0);
+    let scope = hip_scope(op.scope(ctx).0);
+    format!("__hip_atomic_fetch_add({ptr}, {value}, {order}, {scope})")
 });
 
 hip_op_with_out!(AtomicFAddOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicAdd({ptr}, {value})")
+    let order = hip_order(op.ordering(ctx).0);
+    let scope = hip_scope(op.scope(ctx).0);
+    format!("__hip_atomic_fetch_add({ptr}, {value}, {order}, {scope})")
 });
@@ -2,103 +2,129 @@ use cubecl_core::ir::{dialect::atomic::*, prelude::*};
 
 use crate::{
     metal::{metal_op, metal_op_with_out},
-    shared::CppValue,
+    shared::{CppValue, scoped_block},
 };
 
+/// Metal atomics only support `memory_order_relaxed`, so stronger orderings are built from
+/// scoped thread fences (MSL 3.2) around the relaxed atomic. That's also the default ordering.
+fn fence(ordering: MemoryOrdering, scope: AtomicScope) -> String {
+    let order = match ordering {
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed => "memory_order_relaxed",
+        MemoryOrdering::Acquire => "memory_order_acquire",
+        MemoryOrdering::Release => "memory_order_release",
+        MemoryOrdering::AcqRel => "memory_order_acq_rel",
+        MemoryOrdering::SeqCst => "memory_order_seq_cst",
+    };
+    let scope = match scope {
+        AtomicScope::Plane => "thread_scope_simdgroup",
+        AtomicScope::Cube => "thread_scope_threadgroup",
+        // No scope wider than the device
+        AtomicScope::Device | AtomicScope::System => "thread_scope_device",
+    };
+    format!(
+        "atomic_thread_fence(mem_flags::mem_device | mem_flags::mem_threadgroup, {order}, {scope});\n"
+    )
+}
+
+fn fenced(ordering: MemoryOrdering, scope: AtomicScope, expr: String) -> String {
+    if matches!(
+        ordering,
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed
+    ) {
+        return expr;
+    }
+    let fence = fence(ordering, scope);
+    let release = if ordering.is_release() {
+        fence.as_str()
+    } else {
+        ""
+    };
+    let acquire = if ordering.is_acquire() {
+        fence.as_str()
+    } else {
+        ""
+    };
+    scoped_block! {
+        release
+        format!("const auto tmp = {expr};")
+        acquire
+        "return tmp;"
+    }
+}
+
+macro_rules! metal_atomic_rmw {
+    ($ty: ty, $func: literal) => {
+        metal_op_with_out!($ty, |op, ctx| {
+            let ptr = op.ptr(ctx).name(ctx);
+            let value = op.value(ctx).name(ctx);
+            fenced(
+                op.ordering(ctx).0,
+                op.scope(ctx).0,
+                format!("{}({ptr}, {value}, memory_order_relaxed)", $func),
+            )
+        });
+    };
+}
+
 metal_op_with_out!(AtomicLoadOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
-    format!("atomic_load_explicit({ptr}, memory_order_relaxed)")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomic_load_explicit({ptr}, memory_order_relaxed)"),
+    )
 });
 
 metal_op!(AtomicStoreOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomic_store_explicit({ptr}, {value}, memory_order_relaxed)")
+    let ordering = op.ordering(ctx).0;
+    let scope = op.scope(ctx).0;
+    let fence = fence(ordering, scope);
+    let release = if ordering.is_release() {
+        fence.as_str()
+    } else {
+        ""
+    };
+    let acquire = if ordering.is_acquire() {
+        fence.as_str()
+    } else {
+        ""
+    };
+    format!("{release}atomic_store_explicit({ptr}, {value}, memory_order_relaxed);\n{acquire}")
 });
 
-metal_op_with_out!(AtomicExchangeOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_exchange_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicExchangeOp, "atomic_exchange_explicit");
 
 metal_op_with_out!(AtomicCompareExchangeWeakOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let cmp = op.cmp(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!(
-        "atomic_compare_exchange_weak_explicit({ptr}, &{cmp}, {value}, memory_order_relaxed, memory_order_relaxed)"
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!(
+            "atomic_compare_exchange_weak_explicit({ptr}, &{cmp}, {value}, memory_order_relaxed, memory_order_relaxed)"
+        ),
     )
 });
 
-metal_op_with_out!(AtomicIAddOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_add_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicFAddOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_add_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicIAddOp, "atomic_fetch_add_explicit");
+metal_atomic_rmw!(AtomicFAddOp, "atomic_fetch_add_explicit");
 
-metal_op_with_out!(AtomicISubOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_sub_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicFSubOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_sub_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicISubOp, "atomic_fetch_sub_explicit");
+metal_atomic_rmw!(AtomicFSubOp, "atomic_fetch_sub_explicit");
 
-metal_op_with_out!(AtomicSMinOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_min_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicUMinOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_min_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicFMinOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_min_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicSMinOp, "atomic_fetch_min_explicit");
+metal_atomic_rmw!(AtomicUMinOp, "atomic_fetch_min_explicit");
+metal_atomic_rmw!(AtomicFMinOp, "atomic_fetch_min_explicit");
 
-metal_op_with_out!(AtomicSMaxOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_max_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicUMaxOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_max_explicit({ptr}, {value}, memory_order_relaxed)")
-});
-metal_op_with_out!(AtomicFMaxOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_max_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicSMaxOp, "atomic_fetch_max_explicit");
+metal_atomic_rmw!(AtomicUMaxOp, "atomic_fetch_max_explicit");
+metal_atomic_rmw!(AtomicFMaxOp, "atomic_fetch_max_explicit");
 
-metal_op_with_out!(AtomicAndOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_and_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicAndOp, "atomic_fetch_and_explicit");
 
-metal_op_with_out!(AtomicOrOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_or_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicOrOp, "atomic_fetch_or_explicit");
 
-metal_op_with_out!(AtomicXorOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomic_fetch_xor_explicit({ptr}, {value}, memory_order_relaxed)")
-});
+metal_atomic_rmw!(AtomicXorOp, "atomic_fetch_xor_explicit");
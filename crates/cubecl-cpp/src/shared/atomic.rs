@@ -6,9 +6,7 @@ use cubecl_core::{
 use num_traits::One;
 
 use crate::{
-    shared::{
-        binary::lower_binop, lowering::LowerOp, scoped_block, shared_op_with_out, ty::TypeExtCPP,
-    },
+    shared::{CppValue, lowering::LowerOp, scoped_block, shared_op_with_out, ty::TypeExtCPP},
     target::{CtxTarget, Target},
 };
 
@@ -16,21 +14,30 @@ use crate::{
 fn atomic_i_sub<T: Numeric + CubeNot, N: Size>(
     ptr: Atomic<Vector<T, N>>,
     value: Vector<T, N>,
+    #[comptime] ordering: MemoryOrdering,
+    #[comptime] atomic_scope: AtomicScope,
 ) -> Vector<T, N> {
-    ptr.fetch_add(!value + Vector::one())
+    ptr.fetch_add_ordered(!value + Vector::one(), ordering, atomic_scope)
 }
 
 #[cube]
 fn atomic_f_sub<T: Numeric + CubeNeg, N: Size>(
     ptr: Atomic<Vector<T, N>>,
     value: Vector<T, N>,
+    #[comptime] ordering: MemoryOrdering,
+    #[comptime] atomic_scope: AtomicScope,
 ) -> Vector<T, N> {
-    ptr.fetch_add(-value)
+    ptr.fetch_add_ordered(-value, ordering, atomic_scope)
 }
 
 #[cube]
-fn atomic_store<T: Numeric + CubeNeg, N: Size>(ptr: Atomic<Vector<T, N>>, value: Vector<T, N>) {
-    ptr.exchange(value);
+fn atomic_store<T: Numeric + CubeNeg, N: Size>(
+    ptr: Atomic<Vector<T, N>>,
+    value: Vector<T, N>,
+    #[comptime] ordering: MemoryOrdering,
+    #[comptime] atomic_scope: AtomicScope,
+) {
+    ptr.exchange_ordered(value, ordering, atomic_scope);
 }
 
 #[op_interface_impl]
@@ -40,20 +47,71 @@ impl LowerOp for AtomicStoreOp {
         define_size!(S);
         let ptr = self.ptr(scope.ctx());
         let value = self.value(scope.ctx());
+        let ordering = self.ordering(scope.ctx()).0;
+        let atomic_scope = self.scope(scope.ctx()).0;
         scope.register_value_type::<T, S>(value);
-        atomic_store::expand::<T, S>(scope, ptr.into(), value.into());
+        atomic_store::expand::<T, S>(scope, ptr.into(), value.into(), ordering, atomic_scope);
         vec![]
     }
 }
 
-lower_binop!(AtomicISubOp, atomic_i_sub, |_, ctx| {
-    ctx.target() != Target::Metal
-});
-lower_binop!(AtomicFSubOp, atomic_f_sub, |_, ctx| {
-    ctx.target() != Target::Metal
-});
+macro_rules! lower_atomic_sub {
+    ($ty: ty, $name: ident) => {
+        #[op_interface_impl]
+        impl LowerOp for $ty {
+            fn should_lower(&self, ctx: &Context) -> bool {
+                ctx.target() != Target::Metal
+            }
+
+            fn lower(&self, scope: &Scope) -> Vec<Value> {
+                define_scalar!(T);
+                define_size!(S);
+                let ptr = self.ptr(scope.ctx());
+                let value = self.value(scope.ctx());
+                let ordering = self.ordering(scope.ctx()).0;
+                let atomic_scope = self.scope(scope.ctx()).0;
+                scope.register_value_type::<T, S>(value);
+                let out =
+                    $name::expand::<T, S>(scope, ptr.into(), value.into(), ordering, atomic_scope);
+                vec![out.read_value(scope)]
+            }
+        }
+    };
+}
+
+lower_atomic_sub!(AtomicISubOp, atomic_i_sub);
+lower_atomic_sub!(AtomicFSubOp, atomic_f_sub);
+
+/// Gives the relaxed atomic `expr` the ordering it should have, with a fence before it if it
+/// releases and one after it if it acquires. HIP and CUDA both have the `__threadfence` family.
+/// Atomics have always been relaxed here, so that's also the default ordering.
+fn fenced(ordering: MemoryOrdering, atomic_scope: AtomicScope, expr: String) -> String {
+    if matches!(
+        ordering,
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed
+    ) {
+        return expr;
+    }
+    let fence = match atomic_scope {
+        AtomicScope::Plane | AtomicScope::Cube => "__threadfence_block();",
+        AtomicScope::Device => "__threadfence();",
+        AtomicScope::System => "__threadfence_system();",
+    };
+    let release = if ordering.is_release() { fence } else { "" };
+    let acquire = if ordering.is_acquire() { fence } else { "" };
+    scoped_block! {
+        release
+        format!("const auto tmp = {expr};")
+        acquire
+        "return tmp;"
+    }
+}
 
 shared_op_with_out!(AtomicLoadOp, |op, ctx| {
+    fenced(op.ordering(ctx).0, op.scope(ctx).0, atomic_load(op, ctx))
+});
+
+fn atomic_load(op: &AtomicLoadOp, ctx: &Context) -> String {
     let ptr = op.ptr(ctx).name(ctx);
     let out_ty = op.get_result(ctx).get_type(ctx);
     let uint_ty = match out_ty.size(ctx) {
@@ -75,9 +133,17 @@ shared_op_with_out!(AtomicLoadOp, |op, ctx| {
         format!("const {uint_ty} tmp_2 = *tmp;")
         format!("return reinterpret_cast<const {}&>(tmp_2);", out_ty.to_cpp(ctx))
     }
-});
+}
 
 shared_op_with_out!(AtomicExchangeOp, |op, ctx| {
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        atomic_exchange(op, ctx),
+    )
+});
+
+fn atomic_exchange(op: &AtomicExchangeOp, ctx: &Context) -> String {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
     let out_ty = op.get_result(ctx).get_type(ctx);
@@ -97,9 +163,17 @@ shared_op_with_out!(AtomicExchangeOp, |op, ctx| {
         format!("const {uint_ty} tmp = atomicExch({ptr}, {value});")
         format!("return reinterpret_cast<const {}&>(tmp);", out_ty.to_cpp(ctx))
     }
-});
+}
 
 shared_op_with_out!(AtomicCompareExchangeWeakOp, |op, ctx| {
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        atomic_compare_exchange(op, ctx),
+    )
+});
+
+fn atomic_compare_exchange(op: &AtomicCompareExchangeWeakOp, ctx: &Context) -> String {
     let ptr = op.ptr(ctx).name(ctx);
     let cmp = op.cmp(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
@@ -121,54 +195,90 @@ shared_op_with_out!(AtomicCompareExchangeWeakOp, |op, ctx| {
         format!("const {uint_ty} tmp = atomicCAS({ptr}, {cmp}, {value});")
         format!("return reinterpret_cast<const {}&>(tmp);", out_ty.to_cpp(ctx))
     }
-});
+}
 
 shared_op_with_out!(AtomicSMinOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMin({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMin({ptr}, {value})"),
+    )
 });
 shared_op_with_out!(AtomicUMinOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMin({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMin({ptr}, {value})"),
+    )
 });
 shared_op_with_out!(AtomicFMinOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMin({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMin({ptr}, {value})"),
+    )
 });
 
 shared_op_with_out!(AtomicSMaxOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMax({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMax({ptr}, {value})"),
+    )
 });
 shared_op_with_out!(AtomicUMaxOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMax({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMax({ptr}, {value})"),
+    )
 });
 shared_op_with_out!(AtomicFMaxOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicMax({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicMax({ptr}, {value})"),
+    )
 });
 
 shared_op_with_out!(AtomicAndOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicAnd({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicAnd({ptr}, {value})"),
+    )
 });
 
 shared_op_with_out!(AtomicOrOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicOr({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicOr({ptr}, {value})"),
+    )
 });
 
 shared_op_with_out!(AtomicXorOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
-    format!("atomicXor({ptr}, {value})")
+    fenced(
+        op.ordering(ctx).0,
+        op.scope(ctx).0,
+        format!("atomicXor({ptr}, {value})"),
+    )
 });
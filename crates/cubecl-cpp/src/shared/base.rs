@@ -30,17 +30,23 @@ use cubecl_core::{
     post_processing::{
         bitwise::PromoteBitwisePass,
         checked_io::{CheckedIo, CheckedIoPass},
+        complex::LowerComplexNumbersPass,
+        int4::LowerPackedIntPass,
         saturating::LowerSaturatingArithmeticPass,
     },
     prelude::KernelDefinition,
 };
 use cubecl_environment::backtrace::BackTrace;
+use cubecl_opt::analyses::cost::StaticCost;
 use cubecl_opt::passes::{
     alloc_shared_memory::AllocateSharedMemoryBlockPass,
     annotate_buffer_visibility::AnnotateGlobalVisibilityPass, simple_cse::SimpleCSEPass,
     sroa::SROAPass,
 };
-use cubecl_runtime::compiler::{CompilationError, Compiler};
+use cubecl_runtime::{
+    compiler::{CompilationError, Compiler},
+    throughput::KernelCost,
+};
 use pliron::{
     builtin::ops::{FuncOp, ModuleOp},
     context::Context,
@@ -144,6 +150,10 @@ where
     fn extension(&self) -> &'static str {
         "cpp"
     }
+
+    fn kernel_cost(&self, kernel: &Self::Representation) -> Option<KernelCost> {
+        Some(kernel.cost.clone())
+    }
 }
 
 impl<T: CppTarget> CppCompiler<T>
@@ -198,6 +208,8 @@ where
         let mut passes = OpPass::<ModuleOp, Passes>::default();
         let mut func_passes = OpPass::<FuncOp, Passes>::default();
 
+        func_passes.add_pass(LowerComplexNumbersPass);
+        func_passes.add_pass(LowerPackedIntPass);
         func_passes.add_pass(LowerInfoPass);
         func_passes.add_pass(SROAPass);
         func_passes.add_pass(CheckedIoPass::new(CheckedIo::new(
@@ -258,6 +270,10 @@ where
 
         let shared_memory_size = shared_memory_size(&ctx, module_op);
         let buffers = buffers(&ctx, entry_func);
+        let cost = analyses
+            .get_analysis::<StaticCost>(entry_func.get_operation(), &ctx)?
+            .0
+            .clone();
 
         // Emit here rather than lazily from `Display`, so an op that survives lowering with no
         // `OpToCPP` impl fails the compilation instead of panicking on the compiler thread.
@@ -288,6 +304,7 @@ where
             shared_memory_size,
             buffers,
             source,
+            cost,
         };
 
         #[cfg(feature = "pliron-dump")]
@@ -1,7 +1,7 @@
 use crate::shared::ty::TypeExtCPP;
 
 use cubecl_core::ir::metadata::Info;
-use cubecl_runtime::kernel::Visibility;
+use cubecl_runtime::{kernel::Visibility, throughput::KernelCost};
 use pliron::context::Context;
 
 use core::fmt::{Display, Write};
@@ -12,6 +12,8 @@ pub struct ComputeKernel {
     /// The emitted source, rendered once during `compile_ir` where emission errors can still
     /// fail the compilation.
     pub source: String,
+    /// The static cost of one unit, counted on the lowered IR.
+    pub cost: KernelCost,
 }
 
 impl Display for ComputeKernel {
@@ -57,12 +57,17 @@ fn plane_dim_checked() -> u32 {
     min(PLANE_DIM, CUBE_DIM)
 }
 
+/// Reduces over clusters of `cluster_size` units. The xor butterfly never crosses a cluster
+/// boundary, so the whole plane is simply a cluster that's at least as large as the plane.
 #[cube]
-pub fn plane_reduce<T: Scalar, N: Size, Op: PlaneOp<T, N>>(val: Vector<T, N>) -> Vector<T, N> {
-    let plane_dim = plane_dim_checked();
+pub fn plane_reduce<T: Scalar, N: Size, Op: PlaneOp<T, N>>(
+    val: Vector<T, N>,
+    cluster_size: u32,
+) -> Vector<T, N> {
+    let width = min(plane_dim_checked(), cluster_size);
     let mut acc = val;
     let mut offset = 1;
-    while offset < plane_dim {
+    while offset < width {
         acc = Op::apply(acc, plane_shuffle_xor(acc, offset));
         offset *= 2;
     }
@@ -115,16 +120,39 @@ macro_rules! lower_unop {
     };
 }
 
-lower_unop!(plane::ISumOp, plane_reduce, OpAdd);
-lower_unop!(plane::FSumOp, plane_reduce, OpAdd);
-lower_unop!(plane::IProdOp, plane_reduce, OpMul);
-lower_unop!(plane::FProdOp, plane_reduce, OpMul);
-lower_unop!(plane::SMinOp, plane_reduce, OpMin);
-lower_unop!(plane::UMinOp, plane_reduce, OpMin);
-lower_unop!(plane::FMinOp, plane_reduce, OpMin);
-lower_unop!(plane::SMaxOp, plane_reduce, OpMax);
-lower_unop!(plane::UMaxOp, plane_reduce, OpMax);
-lower_unop!(plane::FMaxOp, plane_reduce, OpMax);
+// Metal only has native reductions over the whole plane, so clusters always use shuffles
+macro_rules! lower_reduce {
+    ($ty: ty, $op: ty) => {
+        #[op_interface_impl]
+        impl LowerOp for $ty {
+            fn should_lower(&self, ctx: &Context) -> bool {
+                ctx.target() != Target::Metal || self.cluster_size(ctx).is_some()
+            }
+            fn lower(&self, scope: &Scope) -> Vec<Value> {
+                let input = self.input(scope.ctx());
+                let cluster_size = self
+                    .cluster_size(scope.ctx())
+                    .map_or(u32::MAX, |size| size.0 as u32);
+                scope.register_value_type::<T, S>(input);
+                vec![
+                    plane_reduce::expand::<T, S, $op>(scope, input.into(), cluster_size.into())
+                        .read_value(scope),
+                ]
+            }
+        }
+    };
+}
+
+lower_reduce!(plane::ISumOp, OpAdd);
+lower_reduce!(plane::FSumOp, OpAdd);
+lower_reduce!(plane::IProdOp, OpMul);
+lower_reduce!(plane::FProdOp, OpMul);
+lower_reduce!(plane::SMinOp, OpMin);
+lower_reduce!(plane::UMinOp, OpMin);
+lower_reduce!(plane::FMinOp, OpMin);
+lower_reduce!(plane::SMaxOp, OpMax);
+lower_reduce!(plane::UMaxOp, OpMax);
+lower_reduce!(plane::FMaxOp, OpMax);
 
 lower_unop!(plane::InclusiveISumOp, plane_reduce_inclusive, OpAdd);
 lower_unop!(plane::InclusiveFSumOp, plane_reduce_inclusive, OpAdd);
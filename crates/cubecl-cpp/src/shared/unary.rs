@@ -3,7 +3,7 @@ use cubecl_core::{
     frontend::polyfills::{erf, log1p, recip, to_degrees, to_radians},
     ir::{
         dialect::{
-            atomic::AtomicLoadOp,
+            atomic::{AtomicLoadOp, AtomicScope, MemoryOrdering},
             bitwise::{
                 BitwiseNotOp, CountOnesOp, FindFirstSetOp, LeadingZerosBitsOp, ReverseBitsOp,
                 TrailingZerosBitsOp,
@@ -352,6 +352,12 @@ impl LowerOp for AtomicUniformLoadOp {
             SyncScopeAttr::new(SyncScope::Cube),
         ));
         let ptr = self.ptr(scope.ctx());
-        vec![scope.register_with_result(&AtomicLoadOp::new(scope.ctx_mut(), ptr))]
+        let op = AtomicLoadOp::new(
+            scope.ctx_mut(),
+            ptr,
+            MemoryOrdering::Relaxed,
+            AtomicScope::Device,
+        );
+        vec![scope.register_with_result(&op)]
     }
 }
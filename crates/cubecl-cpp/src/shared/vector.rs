@@ -120,3 +120,14 @@ lower_unop!(FSumOp, sum);
 lower_binop!(SDotOp, dot);
 lower_binop!(UDotOp, dot);
 lower_binop!(FDotOp, dot, |_, ctx| ctx.target() != Target::Metal);
+
+#[cube]
+fn packed_dot4<T: Numeric, N: Size>(lhs: Vector<T, N>, rhs: Vector<T, N>) -> i32 {
+    let lhs = Vector::<i32, Const<4>>::cast_from(Vector::<i8, Const<4>>::reinterpret(lhs));
+    let rhs = Vector::<i32, Const<4>>::cast_from(Vector::<i8, Const<4>>::reinterpret(rhs));
+    (lhs * rhs).vector_sum()
+}
+
+lower_binop!(SDot4x8PackedOp, packed_dot4, |_, ctx| {
+    ctx.target() != Target::Cuda
+});
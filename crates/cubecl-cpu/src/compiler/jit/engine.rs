@@ -3,6 +3,7 @@ use std::fmt::Display;
 use std::rc::Rc;
 use std::sync::{Arc, Once};
 
+use cubecl_runtime::throughput::KernelCost;
 use pliron::builtin::ops::ModuleOp;
 use pliron::context::Context;
 use pliron_llvm::llvm_sys::core::{LLVMContext, LLVMMemoryBuffer, LLVMModule};
@@ -34,6 +35,7 @@ pub struct KernelRequirements {
 struct JitKernel {
     func: KernelFn,
     requirements: KernelRequirements,
+    cost: KernelCost,
     _lljit: LLVMLLJIT,
     _llvm_ctx: Rc<LLVMContext>,
 }
@@ -55,6 +57,7 @@ impl PlironEngine {
         module: ModuleOp,
         kernel_name: &str,
         requirements: KernelRequirements,
+        cost: KernelCost,
     ) -> pliron::result::Result<Self> {
         INIT_NATIVE.call_once(|| {
             initialize_native().expect("failed to initialize native target");
@@ -85,6 +88,7 @@ impl PlironEngine {
         Ok(PlironEngine(Arc::new(JitKernel {
             func,
             requirements,
+            cost,
             _lljit: lljit,
             _llvm_ctx: llvm_ctx,
         })))
@@ -95,6 +99,11 @@ impl PlironEngine {
         &self.0.requirements
     }
 
+    /// The static cost of one unit, counted before the kernel was lowered to LLVM.
+    pub(crate) fn cost(&self) -> &KernelCost {
+        &self.0.cost
+    }
+
     pub(crate) fn run_kernel(&self, data: &mut PlironData) {
         let b = data.builtins;
         let buffer_ptrs = data.shared.buffer_ptrs.as_ptr() as *mut *mut c_void;
@@ -14,12 +14,18 @@ use pliron_llvm::builtin_to_llvm::builtin_to_llvm_pass;
 #[cfg(feature = "pliron-dump")]
 use std::{path::PathBuf, str::FromStr};
 
-use cubecl_opt::passes::{simple_cse::SimpleCSEPass, sroa::SROAPass};
-use cubecl_runtime::compiler::CompilationError;
+use cubecl_opt::{
+    analyses::cost::StaticCost,
+    passes::{simple_cse::SimpleCSEPass, sroa::SROAPass},
+};
+use cubecl_runtime::{compiler::CompilationError, throughput::KernelCost};
 
 use cubecl_core::{
     Compiler, ir::dialect::scf::BranchToSCFPass, ir::rewrite::SimplifyOpsPass,
-    post_processing::bitwise::PromoteBitwisePass, prelude::*,
+    post_processing::{
+        bitwise::PromoteBitwisePass, complex::LowerComplexNumbersPass, int4::LowerPackedIntPass,
+    },
+    prelude::*,
 };
 use pliron::{
     builtin::ops::{FuncOp, ModuleOp},
@@ -74,18 +80,23 @@ impl Compiler for PlironCompiler {
             });
         }
 
-        Ok(self.clone().compile_ir(kernel))
+        self.clone().compile_ir(kernel)
     }
 
     fn extension(&self) -> &'static str {
         "plir"
     }
+
+    fn kernel_cost(&self, kernel: &Self::Representation) -> Option<KernelCost> {
+        Some(kernel.cost().clone())
+    }
 }
 
 impl PlironCompiler {
-    fn compile_ir(self, kernel: KernelDefinition) -> PlironEngine {
+    fn compile_ir(self, kernel: KernelDefinition) -> Result<PlironEngine, CompilationError> {
         let module = kernel.body.state().module;
         let module_op = module.get_operation();
+        let entry_func = kernel.body.state().entry_func;
         let mut ctx = kernel.body.into_context().expect("Should be owned scope");
 
         let needs_parallelism = kernel.settings.cube_dim.num_elems() > 1
@@ -108,6 +119,8 @@ impl PlironCompiler {
 
         let mut passes = OpPass::<ModuleOp, Passes>::default();
         let mut func_passes = OpPass::<FuncOp, Passes>::default();
+        func_passes.add_pass(LowerComplexNumbersPass);
+        func_passes.add_pass(LowerPackedIntPass);
         func_passes.add_pass(InsertConstantEmulationPass);
         func_passes.add_pass(SROAPass);
         func_passes.add_pass(SCCPPass);
@@ -118,6 +131,19 @@ impl PlironCompiler {
         func_passes.add_pass(DCEPass);
         func_passes.add_pass(SROAPass);
         func_passes.add_pass(BranchToSCFPass::default());
+
+        passes.add_pass(NestedOpsPass::new(func_passes));
+        passes.run(module_op, &mut ctx, &mut analyses).unwrap();
+
+        // Counted before the conversion to LLVM ops, which the analysis doesn't know.
+        let cost = analyses
+            .get_analysis::<StaticCost>(entry_func.get_operation(), &ctx)
+            .expect("Cost analysis shouldn't fail")
+            .0
+            .clone();
+
+        let mut passes = OpPass::<ModuleOp, Passes>::default();
+        let mut func_passes = OpPass::<FuncOp, Passes>::default();
         func_passes.add_pass(SCFToLlvmCf::default());
         func_passes.add_pass(LowerEntryAbiPass::new(
             kernel.info.clone(),
@@ -131,7 +157,8 @@ impl PlironCompiler {
         passes.add_pass(NestedOpsPass::new(func_passes));
         passes.add_pass(builtin_to_llvm_pass());
 
-        passes.run(module_op, &mut ctx, &mut analyses).unwrap();
+        // Ops the host can't lower, like inline assembly for another architecture, fail here
+        passes.run(module_op, &mut ctx, &mut analyses)?;
 
         if let Err(e) = verify_operation(module_op, &ctx) {
             panic!("{}", e.disp(&ctx));
@@ -142,8 +169,15 @@ impl PlironCompiler {
             shared_memories: shared_memories.take(),
         };
 
-        PlironEngine::compile(&ctx, module, &kernel.settings.kernel_name, requirements)
-            .expect("Failed to convert to LLVM IR")
+        let engine = PlironEngine::compile(
+            &ctx,
+            module,
+            &kernel.settings.kernel_name,
+            requirements,
+            cost,
+        )
+        .expect("Failed to convert to LLVM IR");
+        Ok(engine)
     }
 }
 
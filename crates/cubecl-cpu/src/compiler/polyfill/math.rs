@@ -5,7 +5,9 @@ use cubecl_core::ir::dialect::math::{
     ArcCoshOp, ArcSinhOp, ArcTanhOp, DegreesOp, ErfOp, Expm1Op, FModFloorOp, HypotOp, Log1pOp,
     PowiOp, RadiansOp, RecipOp, RhypotOp, RsqrtOp, SModFloorOp, SMulHiOp, SNegOp, UMulHiOp,
 };
-use cubecl_core::ir::dialect::vector::{FDotOp, MagnitudeOp, NormalizeOp, SDotOp, UDotOp};
+use cubecl_core::ir::dialect::vector::{
+    FDotOp, MagnitudeOp, NormalizeOp, SDot4x8PackedOp, SDotOp, UDotOp,
+};
 use cubecl_core::ir::interfaces::TypedExt;
 use cubecl_core::ir::prelude::*;
 use cubecl_core::prelude::polyfills::{
@@ -60,6 +62,15 @@ lower_binary_math_arith!(FDotOp => dot);
 lower_binary_math_arith!(UDotOp => dot);
 lower_binary_math_arith!(SDotOp => dot);
 
+#[cube]
+fn packed_dot4<T: Numeric, N: Size>(rhs: Vector<T, N>, lhs: Vector<T, N>) -> i32 {
+    let rhs = Vector::<i32, Const<4>>::cast_from(Vector::<i8, Const<4>>::reinterpret(rhs));
+    let lhs = Vector::<i32, Const<4>>::cast_from(Vector::<i8, Const<4>>::reinterpret(lhs));
+    (rhs * lhs).vector_sum()
+}
+
+lower_binary_math_arith!(SDot4x8PackedOp => packed_dot4);
+
 #[cube]
 pub fn powi<T: Float, N: Size>(base: Vector<T, N>, exp: Vector<i32, N>) -> Vector<T, N> {
     let one_u = Vector::<i32, N>::new(1);
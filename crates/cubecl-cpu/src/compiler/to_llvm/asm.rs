@@ -0,0 +1,128 @@
+use super::prelude::*;
+use cubecl_core::ir::dialect::InlineAsmOp;
+use pliron::{input_err, location::Location, printable::Printable};
+use pliron_llvm::types::{StructType, VoidType};
+
+/// The register classes for integer and float/vector operands, as LLVM constraint codes. The
+/// kernel is JIT'd for the host, so the target is simply the one we are built for.
+const fn register_classes() -> Option<(&'static str, &'static str)> {
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        Some(("r", "x"))
+    } else if cfg!(target_arch = "aarch64") {
+        Some(("r", "w"))
+    } else {
+        None
+    }
+}
+
+/// Registers every asm block may clobber, declared implicitly like clang and rustc do. On x86
+/// that's the flags, which LLVM otherwise assumes the asm preserves.
+const fn implicit_clobbers() -> &'static [&'static str] {
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        &["~{dirflag}", "~{fpsr}", "~{flags}"]
+    } else {
+        &[]
+    }
+}
+
+/// The constraint code for an already converted LLVM type
+fn constraint(
+    ctx: &Context,
+    loc: Location,
+    (int_class, float_class): (&'static str, &'static str),
+    ty: TypeHandle,
+) -> Result<&'static str> {
+    let ty_obj = ty.deref(ctx);
+    if ty_obj.is::<IntegerType>() || ty_obj.is::<LlvmPointerType>() {
+        Ok(int_class)
+    } else if ty_obj.is::<FP16Type>()
+        || ty_obj.is::<FP32Type>()
+        || ty_obj.is::<FP64Type>()
+        || ty_obj.is::<LlvmVectorType>()
+    {
+        Ok(float_class)
+    } else {
+        input_err!(
+            loc,
+            "The register class could not be deduced for type {}. Supported types are integers, \
+            floats, vectors and pointers.",
+            ty.disp(ctx)
+        )
+    }
+}
+
+/// Lowers to LLVM inline asm. Operands are already numbered `$0`, `$1`, etc with results first,
+/// which is the same numbering LLVM uses, so the template is passed through as is. Multiple
+/// results are returned as a struct and extracted again.
+///
+/// The template uses LLVM's default syntax for the target, so AT&T on x86.
+///
+/// Outputs are early-clobber, since `out` operands may be written before every input is read and
+/// so can't share a register with one. `gpu_asm!` rejects `lateout`, so there are no late outputs.
+#[op_interface_impl]
+impl ToLLVMDialect for InlineAsmOp {
+    fn rewrite(
+        &self,
+        ctx: &mut Context,
+        rewriter: &mut DialectConversionRewriter,
+        _operands_info: &OperandsInfo,
+    ) -> Result<()> {
+        let asm = self.asm(ctx).as_str().to_owned();
+        let loc = self.loc(ctx);
+        let Some(classes) = register_classes() else {
+            return input_err!(
+                loc,
+                "Inline assembly is only supported on x86, x86_64 and aarch64 hosts, not {}",
+                std::env::consts::ARCH
+            );
+        };
+        let inputs = self.inputs(ctx);
+        let result_tys = self
+            .results(ctx)
+            .iter()
+            .map(|res| cube_type_to_llvm(ctx, res.get_type(ctx)))
+            .collect::<Vec<_>>();
+
+        let mut constraints = Vec::new();
+        for ty in &result_tys {
+            constraints.push(format!("=&{}", constraint(ctx, loc.clone(), classes, *ty)?));
+        }
+        for input in &inputs {
+            let ty = cube_type_to_llvm(ctx, input.get_type(ctx));
+            constraints.push(constraint(ctx, loc.clone(), classes, ty)?.to_owned());
+        }
+        if !self.nomem(ctx) {
+            constraints.push("~{memory}".to_owned());
+        }
+        constraints.extend(implicit_clobbers().iter().map(|it| it.to_string()));
+        let constraints = constraints.join(",");
+
+        let res_ty = match result_tys.as_slice() {
+            [] => VoidType::get(ctx).into(),
+            [ty] => *ty,
+            tys => StructType::get_unnamed(ctx, tys.to_vec()).into(),
+        };
+        let op = llvm::InlineAsmOp::new(ctx, res_ty, inputs, &asm, &constraints, !self.pure(ctx));
+        rewriter.insert_op(ctx, &op);
+
+        let results = match result_tys.len() {
+            0 => vec![],
+            1 => vec![op.get_result(ctx)],
+            n => {
+                let mut results = Vec::with_capacity(n);
+                for i in 0..n as u32 {
+                    let extract = llvm::ExtractValueOp::new(ctx, op.get_result(ctx), vec![i])?;
+                    rewriter.insert_op(ctx, &extract);
+                    results.push(extract.get_result(ctx));
+                }
+                results
+            }
+        };
+        if results.is_empty() {
+            rewriter.erase_operation(ctx, self.get_operation());
+        } else {
+            rewriter.replace_operation_with_values(ctx, self.get_operation(), results);
+        }
+        Ok(())
+    }
+}
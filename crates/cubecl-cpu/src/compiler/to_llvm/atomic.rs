@@ -1,12 +1,21 @@
-use crate::compiler::polyfill::ordered_atomic::{
-    OrderedAtomicFetchAddOp, OrderedAtomicLoadOp, OrderedAtomicStoreOp,
-};
 use crate::compiler::to_llvm::ty::scalar_alignment;
 
 use super::prelude::*;
 use cubecl_core::ir::dialect::atomic::*;
 use pliron_llvm::attributes::{AtomicOrderingAttr, AtomicRmwKindAttr};
 
+/// LLVM has no scoped atomics on the CPU target, every unit shares the same coherent memory, so
+/// only the ordering is kept.
+fn llvm_ordering(ordering: MemoryOrdering) -> AtomicOrderingAttr {
+    match ordering {
+        MemoryOrdering::BackendDefault | MemoryOrdering::Relaxed => AtomicOrderingAttr::Monotonic,
+        MemoryOrdering::Acquire => AtomicOrderingAttr::Acquire,
+        MemoryOrdering::Release => AtomicOrderingAttr::Release,
+        MemoryOrdering::AcqRel => AtomicOrderingAttr::AcqRel,
+        MemoryOrdering::SeqCst => AtomicOrderingAttr::SeqCst,
+    }
+}
+
 macro_rules! lower_atomic_rmw {
     ($cube_op:ty => $pred:expr) => {
         #[op_interface_impl]
@@ -20,7 +29,7 @@ macro_rules! lower_atomic_rmw {
                 let ptr = self.ptr(ctx);
                 let val = self.value(ctx);
                 let kind = $pred;
-                let ordering = AtomicOrderingAttr::Monotonic;
+                let ordering = llvm_ordering(self.ordering(ctx).0);
 
                 let op = llvm::AtomicRmwOp::new(ctx, ptr, val, kind, ordering, None);
                 rewriter.insert_op(ctx, &op);
@@ -59,7 +68,7 @@ impl ToLLVMDialect for AtomicLoadOp {
         operands_info: &OperandsInfo,
     ) -> Result<()> {
         let ptr = self.ptr(ctx);
-        let ordering = AtomicOrderingAttr::Monotonic;
+        let ordering = llvm_ordering(self.ordering(ctx).0);
         let result = self.get_result(ctx);
         let res_cube_ty = operands_info
             .lookup_most_recent_type(result)
@@ -89,56 +98,7 @@ impl ToLLVMDialect for AtomicStoreOp {
             .lookup_most_recent_type(value)
             .unwrap_or_else(|| value.get_type(ctx));
         let align = scalar_alignment(ctx, value_cube_ty);
-        let ordering = AtomicOrderingAttr::Monotonic;
-
-        let store = llvm::AtomicStoreOp::new(ctx, value, ptr, ordering, None);
-        store.set_alignment(ctx, align);
-        rewriter.insert_op(ctx, &store);
-        rewriter.replace_operation(ctx, self.get_operation(), store.get_operation());
-        Ok(())
-    }
-}
-
-#[op_interface_impl]
-impl ToLLVMDialect for OrderedAtomicLoadOp {
-    fn rewrite(
-        &self,
-        ctx: &mut Context,
-        rewriter: &mut DialectConversionRewriter,
-        operands_info: &OperandsInfo,
-    ) -> Result<()> {
-        let ptr = self.ptr(ctx);
-        let ordering = self.ordering(ctx).clone();
-        let result = self.get_result(ctx);
-        let res_cube_ty = operands_info
-            .lookup_most_recent_type(result)
-            .unwrap_or_else(|| result.get_type(ctx));
-        let align = scalar_alignment(ctx, res_cube_ty);
-        let res_ty = cube_type_to_llvm(ctx, res_cube_ty);
-
-        let op = llvm::AtomicLoadOp::new(ctx, ptr, res_ty, ordering, None);
-        op.set_alignment(ctx, align);
-        rewriter.insert_op(ctx, &op);
-        rewriter.replace_operation_with_values(ctx, self.get_operation(), vec![op.get_result(ctx)]);
-        Ok(())
-    }
-}
-
-#[op_interface_impl]
-impl ToLLVMDialect for OrderedAtomicStoreOp {
-    fn rewrite(
-        &self,
-        ctx: &mut Context,
-        rewriter: &mut DialectConversionRewriter,
-        operands_info: &OperandsInfo,
-    ) -> Result<()> {
-        let ptr = self.ptr(ctx);
-        let value = self.value(ctx);
-        let ordering = self.ordering(ctx).clone();
-        let value_cube_ty = operands_info
-            .lookup_most_recent_type(value)
-            .unwrap_or_else(|| value.get_type(ctx));
-        let align = scalar_alignment(ctx, value_cube_ty);
+        let ordering = llvm_ordering(self.ordering(ctx).0);
 
         let store = llvm::AtomicStoreOp::new(ctx, value, ptr, ordering, None);
         store.set_alignment(ctx, align);
@@ -148,25 +108,6 @@ impl ToLLVMDialect for OrderedAtomicStoreOp {
     }
 }
 
-#[op_interface_impl]
-impl ToLLVMDialect for OrderedAtomicFetchAddOp {
-    fn rewrite(
-        &self,
-        ctx: &mut Context,
-        rewriter: &mut DialectConversionRewriter,
-        _operands_info: &OperandsInfo,
-    ) -> Result<()> {
-        let ptr = self.ptr(ctx);
-        let value = self.value(ctx);
-        let ordering = self.ordering(ctx).clone();
-
-        let op = llvm::AtomicRmwOp::new(ctx, ptr, value, AtomicRmwKindAttr::Add, ordering, None);
-        rewriter.insert_op(ctx, &op);
-        rewriter.replace_operation_with_values(ctx, self.get_operation(), vec![op.get_result(ctx)]);
-        Ok(())
-    }
-}
-
 #[op_interface_impl]
 impl ToLLVMDialect for AtomicCompareExchangeWeakOp {
     fn rewrite(
@@ -178,8 +119,9 @@ impl ToLLVMDialect for AtomicCompareExchangeWeakOp {
         let ptr = self.ptr(ctx);
         let cmp = self.cmp(ctx);
         let new_val = self.value(ctx);
-        let before = AtomicOrderingAttr::Monotonic;
-        let after = AtomicOrderingAttr::Monotonic;
+        let ordering = self.ordering(ctx).0;
+        let before = llvm_ordering(ordering);
+        let after = llvm_ordering(ordering.failure());
 
         let op = llvm::AtomicCmpxchgOp::new(ctx, ptr, cmp, new_val, before, after, None);
         rewriter.insert_op(ctx, &op);
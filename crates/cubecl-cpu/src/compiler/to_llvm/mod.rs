@@ -1,3 +1,4 @@
+pub mod asm;
 pub mod atomic;
 pub mod cmp;
 pub mod constant;
@@ -0,0 +1,64 @@
+//! Software graph capture for the CPU runtime.
+//!
+//! Like wgpu's, a captured graph is a **software graph**: everything a launch
+//! resolves per dispatch — the compilation cache lookup, binding resolution,
+//! metadata packing — is done once while recording, and
+//! [`CpuStream::replay_graph`](super::stream::CpuStream::replay_graph) hands
+//! the prebuilt launches straight to the threadpool.
+//!
+//! Replay stays O(n) in recorded launches, since each one is still split into
+//! per-unit tasks; only the per-launch work on the server thread goes away.
+//! That is what makes a graph worth it for small, launch-bound kernels, and
+//! what lets graph-based code paths run on CPU-only CI.
+
+use crate::compiler::jit::engine::PlironEngine;
+use cubecl_core::{
+    CubeDim,
+    server::{GraphParamSite, GraphParamTable},
+};
+use cubecl_runtime::{
+    memory_management::ManagedMemoryHandle,
+    storage::{BytesResource, ManagedResource},
+};
+
+/// A captured graph: the recorded launch sequence, fully resolved (see the
+/// [module docs](self)).
+///
+/// Owned by the [`CpuServer`](super::server::CpuServer) registry and
+/// referenced by [`GraphId`](cubecl_runtime::id::GraphId); the client
+/// references the graph by id and, on the last drop, asks the server to
+/// release it.
+pub struct CpuGraph {
+    /// The recorded launches, replayed in order.
+    pub(crate) launches: Vec<RecordedLaunch>,
+    /// Every pool slice the capture window allocated, pinned so the pool
+    /// cannot reuse memory a replay still runs against. Dropped with the graph.
+    pub(crate) _retained: Vec<ManagedMemoryHandle>,
+    /// The graph's parameters, updated in place: a binding swaps the
+    /// recorded resource, a scalar rewrites the recorded info.
+    pub(crate) params: GraphParamTable,
+}
+
+impl core::fmt::Debug for CpuGraph {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CpuGraph")
+            .field("launches", &self.launches.len())
+            .finish()
+    }
+}
+
+/// One recorded launch, resolved down to what the threadpool needs.
+pub(crate) struct RecordedLaunch {
+    pub(crate) pliron_engine: PlironEngine,
+    /// The buffers the kernel binds, resolved once at record time. Holding
+    /// them pins the memory for the graph's lifetime; a buffer the *caller*
+    /// rewrites between replays is picked up, since only its location is baked
+    /// in.
+    pub(crate) resources: Vec<ManagedResource<BytesResource>>,
+    /// Scalar and metadata values, as packed at record time.
+    pub(crate) info: Vec<u64>,
+    /// Where the graph's parameters live in this launch.
+    pub(crate) params: Vec<GraphParamSite>,
+    pub(crate) cube_dim: CubeDim,
+    pub(crate) cube_count: [u32; 3],
+}
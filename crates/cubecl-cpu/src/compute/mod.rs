@@ -4,5 +4,6 @@ pub mod threadpool;
 
 pub(crate) mod alloc_controller;
 pub(crate) mod cpu_kernel;
+pub(crate) mod graph;
 pub(crate) mod schedule;
 pub(crate) mod stream;
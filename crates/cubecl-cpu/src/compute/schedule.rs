@@ -1,13 +1,15 @@
 use crate::{compiler::jit::engine::PlironEngine, compute::stream::CpuStream};
 use cubecl_common::bytes::Bytes;
 use cubecl_core::{
-    CubeDim, MemoryConfiguration, ir::MemoryDeviceProperties, server::MetadataBindingInfo,
+    CubeDim, MemoryConfiguration,
+    ir::MemoryDeviceProperties,
+    server::{GraphParamSite, MetadataBindingInfo},
 };
 use cubecl_environment::stream::StreamId;
 use cubecl_runtime::{
     logging::ServerLogger,
     storage::{BytesResource, ManagedResource},
-    stream::{StreamFactory, scheduler::SchedulerStreamBackend},
+    stream::{StreamCaptureState, StreamFactory, scheduler::SchedulerStreamBackend},
 };
 use std::sync::Arc;
 
@@ -59,6 +61,9 @@ pub struct BindingsResource {
     pub resources: Vec<ManagedResource<BytesResource>>,
     /// Metadata for uniform bindings.
     pub info: MetadataBindingInfo,
+    /// Where the graph parameters marked on a recording stream live in this
+    /// launch; empty outside of a capture.
+    pub params: Vec<GraphParamSite>,
 }
 
 /// Represents a cpu backend for scheduling tasks on streams.
@@ -126,4 +131,11 @@ impl SchedulerStreamBackend for ScheduledCpuBackend {
     fn factory(&mut self) -> &mut Self::Factory {
         &mut self.factory
     }
+
+    fn requires_isolation(stream: &Self::Stream) -> bool {
+        // For the whole prepare → record window: warmup must prime this
+        // stream's own pools, and the recording must contain exactly this
+        // stream's launches.
+        stream.capturing != StreamCaptureState::NoCapture
+    }
 }
@@ -3,7 +3,9 @@ use crate::{
     compiler::PlironOptions,
     compute::{
         cpu_kernel::CpuKernel,
+        graph::CpuGraph,
         schedule::{BindingsResource, ScheduleTask, ScheduledCpuBackend},
+        stream::CpuEvent,
     },
 };
 use cubecl_common::{bytes::Bytes, profile::ProfileDuration};
@@ -11,9 +13,9 @@ use cubecl_core::{
     CompilationError, CubeCount, MemoryConfiguration, MemoryUsage,
     ir::MemoryDeviceProperties,
     server::{
-        BufferBinding, ComputeServer, CopyDescriptor, IoError, KernelArguments, KernelResource,
-        LaunchError, ProfileError, ProfilingToken, ServerCommunication, ServerError,
-        ServerUtilities,
+        BufferBinding, ComputeServer, CopyDescriptor, GraphParamSite, GraphParamTarget,
+        GraphParamValue, IoError, KernelArguments, KernelResource, LaunchError, ProfileError,
+        ProfilingToken, ServerCommunication, ServerError, ServerUtilities, StreamErrorMode,
     },
     zspace::{Shape, Strides, strides},
 };
@@ -23,9 +25,9 @@ use cubecl_environment::stream::StreamId;
 use cubecl_runtime::{
     allocator::ContiguousMemoryLayoutPolicy,
     compiler::CubeTask,
-    config::{CubeClRuntimeConfig, RuntimeConfig},
+    config::{CubeClRuntimeConfig, RuntimeConfig, streaming::StreamPriority},
     dry_run::LaunchMode,
-    id::KernelId,
+    id::{EventId, GraphId, GraphParamId, KernelId},
     logging::ServerLogger,
     memory_management::{ManagedMemoryHandle, MemoryAllocationMode},
     storage::{BytesStorage, ComputeStorage, ManagedResource},
@@ -40,6 +42,12 @@ pub struct CpuServer {
     compilation_cache: HashMap<KernelId, CpuKernel>,
     // A buffer that can be used to store stream id without extra allocations.
     streams_pool: Vec<StreamId>,
+    /// Captured graphs owned by this server, keyed by the [`GraphId`] handed to
+    /// the client. `end_capture` inserts, `replay` looks up, `graph_destroy`
+    /// removes.
+    graphs: HashMap<GraphId, CpuGraph>,
+    /// Events recorded on request, keyed by the [`EventId`] handed to the client.
+    events: HashMap<EventId, CpuEvent>,
 }
 
 impl CpuServer {
@@ -63,6 +71,7 @@ impl CpuServer {
                 max_streams,
                 max_tasks: 8,
                 strategy: SchedulerStrategy::Interleave,
+                priority: config.streaming.priority,
             },
         );
 
@@ -71,10 +80,16 @@ impl CpuServer {
             utilities,
             compilation_cache: HashMap::new(),
             streams_pool: Vec::new(),
+            graphs: HashMap::new(),
+            events: HashMap::new(),
         }
     }
 
-    fn prepare_bindings(&mut self, bindings: KernelArguments) -> BindingsResource {
+    fn prepare_bindings(
+        &mut self,
+        bindings: KernelArguments,
+        params: Vec<GraphParamSite>,
+    ) -> BindingsResource {
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
         let resources = bindings
@@ -97,6 +112,7 @@ impl CpuServer {
         BindingsResource {
             resources,
             info: bindings.info,
+            params,
         }
     }
 
@@ -221,6 +237,15 @@ impl ComputeServer for CpuServer {
         descriptors: Vec<CopyDescriptor>,
         stream_id: StreamId,
     ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        // A read is a host sync: it cannot be recorded, and the recorded work
+        // has not executed, so there is nothing meaningful to read anyway.
+        if let Err(err) = self
+            .scheduler
+            .stream(&stream_id)
+            .reject_while_recording("read")
+        {
+            return Box::pin(async move { Err(err) });
+        }
         let mut streams = vec![stream_id];
         let mut results = Vec::with_capacity(descriptors.len());
         let mut resources = Vec::with_capacity(descriptors.len());
@@ -250,6 +275,16 @@ impl ComputeServer for CpuServer {
     }
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor, Bytes)>, stream_id: StreamId) {
+        // Host writes cannot be recorded into a software graph. Reject them
+        // lazily so `end_capture` fails the capture instead of handing back a
+        // graph missing an operation.
+        {
+            let stream = self.scheduler.stream(&stream_id);
+            if let Err(err) = stream.reject_while_recording("write") {
+                stream.error(err);
+                return;
+            }
+        }
         for (desc, data) in descriptors {
             let stream = self.scheduler.stream(&desc.handle.stream);
 
@@ -281,6 +316,10 @@ impl ComputeServer for CpuServer {
         }
     }
 
+    fn set_stream_priority(&mut self, stream_id: StreamId, priority: StreamPriority) {
+        self.scheduler.set_priority(stream_id, priority);
+    }
+
     fn memory_usage(&mut self, stream_id: StreamId) -> Result<MemoryUsage, ServerError> {
         let stream = self.scheduler.stream(&stream_id);
         Ok(stream.memory_management.memory_usage())
@@ -325,6 +364,34 @@ impl ComputeServer for CpuServer {
             return;
         }
 
+        // The count of a dynamic launch is read when it is enqueued, so a
+        // recording would bake in the count of the capture run (v1; wgpu
+        // re-reads it on every replay).
+        if matches!(count, CubeCount::Dynamic(_)) {
+            let stream = self.scheduler.stream(&stream_id);
+            if let Err(err) = stream.reject_while_recording("a dynamic cube count launch") {
+                stream.error(err);
+                return;
+            }
+        }
+
+        // Where the graph parameters marked so far live in this launch, matched
+        // before resolving the arguments loses which buffer is which.
+        let params = {
+            let stream = self.scheduler.stream(&stream_id);
+            if stream.capturing.is_recording() {
+                match stream.param_marks.sites(&bindings) {
+                    Ok(params) => params,
+                    Err(err) => {
+                        stream.error(err);
+                        return;
+                    }
+                }
+            } else {
+                Vec::new()
+            }
+        };
+
         self.streams_pool.clear();
         bindings
             .resources
@@ -336,7 +403,7 @@ impl ComputeServer for CpuServer {
                 Some(b)
             })
             .for_each(|b| self.streams_pool.push(b.stream));
-        let bindings = self.prepare_bindings(bindings);
+        let bindings = self.prepare_bindings(bindings, params);
         let task = match self.prepare_task(kernel, count, bindings, stream_id) {
             Ok(task) => task,
             Err(err) => {
@@ -360,6 +427,13 @@ impl ComputeServer for CpuServer {
     }
 
     fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
+        if let Err(err) = self
+            .scheduler
+            .stream(&stream_id)
+            .reject_while_recording("sync")
+        {
+            return Box::pin(async move { Err(err) });
+        }
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
         let result = stream.sync();
@@ -367,7 +441,65 @@ impl ComputeServer for CpuServer {
         Box::pin(async move { result })
     }
 
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        self.scheduler
+            .stream(&stream_id)
+            .reject_while_recording("event_record")?;
+        self.scheduler.execute_streams(vec![stream_id]);
+        let event = self.scheduler.stream(&stream_id).record_event();
+        let id = EventId::new();
+        self.events.insert(id, event);
+        Ok(id)
+    }
+
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        let event = self
+            .events
+            .get(&event)
+            .ok_or_else(|| ServerError::unknown_event(event))?
+            .clone();
+        self.scheduler
+            .stream(&stream_id)
+            .reject_while_recording("event_wait")?;
+        // Work registered before the wait doesn't depend on the event.
+        self.scheduler.execute_streams(vec![stream_id]);
+        self.scheduler.stream(&stream_id).wait_event(event);
+        Ok(())
+    }
+
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        self.events
+            .get(&event)
+            .map(CpuEvent::is_complete)
+            .ok_or_else(|| ServerError::unknown_event(event))
+    }
+
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        match self.events.get(&event) {
+            Some(recorded) => {
+                let recorded = recorded.clone();
+                Box::pin(async move {
+                    recorded.wait();
+                    Ok(())
+                })
+            }
+            None => {
+                let err = ServerError::unknown_event(event);
+                Box::pin(async move { Err(err) })
+            }
+        }
+    }
+
+    fn event_destroy(&mut self, event: EventId) {
+        self.events.remove(&event);
+    }
+
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
+        // Recorded launches do not execute, so a profile of the window would
+        // measure nothing.
+        self.scheduler
+            .stream(&stream_id)
+            .reject_while_recording("start_profile")?;
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
         stream.start_profile()
@@ -405,6 +537,222 @@ impl ComputeServer for CpuServer {
         let stream = self.scheduler.stream(&stream_id);
         stream.allocation_mode(mode);
     }
+
+    fn graph_prepare(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
+        // Drain queued tasks first so pre-capture work is not attributed to
+        // the capture window.
+        self.scheduler.execute_streams(vec![stream_id]);
+        let stream = self.scheduler.stream(&stream_id);
+
+        stream.capturing.prepare()?;
+
+        // Route every allocation from here until `end_capture` into the
+        // persistent pools and track the touched slices: warmup populates the
+        // pools with the capture run's full working set, the recorded run
+        // reuses those slices, and everything it touches is pinned to the
+        // graph at `end_capture`. The non-`NoCapture` state also isolates this
+        // stream in the scheduler (see `requires_isolation`).
+        stream.memory_management.capture_begin();
+        Ok(())
+    }
+
+    fn begin_capture(&mut self, stream_id: StreamId) -> Result<(), ServerError> {
+        // Run the warmup work queued in the scheduler before the recording
+        // window opens.
+        self.scheduler.execute_streams(vec![stream_id]);
+        let stream = self.scheduler.stream(&stream_id);
+
+        stream.capturing.begin()?;
+
+        // Surface the warmup's queued errors now, so a warmup failure is
+        // reported here instead of failing `end_capture` later.
+        if let Err(err) = stream.flush(StreamErrorMode {
+            ignore: false,
+            flush: true,
+        }) {
+            // The capture never opened: disarm retention and return to
+            // `NoCapture`, so the stream stays usable and re-capturable.
+            stream.memory_management.capture_end();
+            stream.capturing.abort();
+            return Err(err);
+        }
+
+        // Warmup is over: release the slices it retained so the recorded run
+        // reuses them instead of growing the pool further.
+        stream.memory_management.capture_priming_end();
+        Ok(())
+    }
+
+    fn end_capture(&mut self, stream_id: StreamId) -> Result<GraphId, ServerError> {
+        // Record the launches still queued in the scheduler.
+        self.scheduler.execute_streams(vec![stream_id]);
+        let stream = self.scheduler.stream(&stream_id);
+
+        // The capture is over even on the failure path below, so an error here
+        // doesn't leave the stream stuck in capture/persistent state.
+        stream.capturing.end()?;
+        let launches = stream.take_recording();
+        let retained = stream.memory_management.capture_end();
+
+        // An error queued during the window (a rejected write, a failed
+        // compilation) means the recording is missing an operation: reject the
+        // capture rather than hand back a graph that silently skips work.
+        let errors = stream.flush_errors_queue();
+        if !errors.is_empty() {
+            stream.param_marks.clear();
+            return Err(ServerError::ServerUnhealthy {
+                errors,
+                backtrace: BackTrace::capture(),
+            });
+        }
+        let params = stream.param_marks.finish()?;
+
+        let id = GraphId::new();
+        self.graphs.insert(
+            id,
+            CpuGraph {
+                launches,
+                _retained: retained,
+                params,
+            },
+        );
+        Ok(id)
+    }
+
+    fn replay(&mut self, graph: GraphId, stream_id: StreamId) {
+        // Order the replay after previously queued work on this stream.
+        self.scheduler.execute_streams(vec![stream_id]);
+
+        // Fire-and-forget like `launch`: on failure, push the error onto the
+        // stream's queue so it surfaces on the next flush/sync.
+        let Some(cpu_graph) = self.graphs.get(&graph) else {
+            let stream = self.scheduler.stream(&stream_id);
+            stream.error(ServerError::graph_state(
+                "replay was given an unknown or already-destroyed graph",
+            ));
+            return;
+        };
+        let stream = self.scheduler.stream(&stream_id);
+        if let Err(err) = stream.reject_while_recording("replay") {
+            stream.error(err);
+            return;
+        }
+        stream.replay_graph(cpu_graph);
+    }
+
+    fn graph_destroy(&mut self, graph: GraphId, stream_id: StreamId) {
+        // No-op for an unknown id (e.g. a double release).
+        let Some(cpu_graph) = self.graphs.remove(&graph) else {
+            return;
+        };
+        // Replayed launches don't pin their buffers, the graph does: wait for
+        // the in-flight ones before releasing it.
+        self.scheduler.execute_streams(vec![stream_id]);
+        let stream = self.scheduler.stream(&stream_id);
+        let _ = stream
+            .flush(StreamErrorMode {
+                ignore: true,
+                flush: false,
+            })
+            .ok();
+        drop(cpu_graph);
+    }
+
+    fn graph_param(
+        &mut self,
+        value: GraphParamValue,
+        stream_id: StreamId,
+    ) -> Result<GraphParamId, ServerError> {
+        let stream = self.scheduler.stream(&stream_id);
+        if !stream.capturing.is_recording() {
+            return Err(ServerError::graph_state(
+                "graph_param: no graph capture is recording on this stream",
+            ));
+        }
+        stream.param_marks.mark(value)
+    }
+
+    fn graph_update(
+        &mut self,
+        graph: GraphId,
+        updates: Vec<(GraphParamId, GraphParamValue)>,
+        stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        let Some(cpu_graph) = self.graphs.get(&graph) else {
+            return Err(ServerError::graph_state(
+                "graph_update was given an unknown or already-destroyed graph",
+            ));
+        };
+
+        // Order the update after the work queued on the graph's stream and on
+        // the streams the new bindings were written from.
+        let mut streams = vec![stream_id];
+        for (_, value) in updates.iter() {
+            if let GraphParamValue::Binding(binding) = value
+                && !streams.contains(&binding.stream)
+            {
+                streams.push(binding.stream);
+            }
+        }
+        self.scheduler.execute_streams(streams);
+
+        // Resolve every new binding before touching the graph, so a failure
+        // leaves it as it was.
+        let mut bound = Vec::new();
+        for (index, launch) in cpu_graph.launches.iter().enumerate() {
+            for site in launch.params.iter() {
+                let GraphParamTarget::Binding(resource) = site.target else {
+                    continue;
+                };
+                let Some((_, GraphParamValue::Binding(binding))) =
+                    updates.iter().find(|(param, _)| *param == site.param)
+                else {
+                    continue;
+                };
+                let stream = self.scheduler.stream(&binding.stream);
+                let memory = binding.memory.clone();
+                let managed = ManagedResource::new(memory, stream.get_resource(binding.clone())?);
+                bound.push((index, resource, managed));
+            }
+        }
+
+        let cpu_graph = self.graphs.get_mut(&graph).expect("looked up above");
+        // Held past the drain below, which the replays reading them end with.
+        let replaced = cpu_graph.params.update(&updates)?;
+
+        // Replays run against the recorded resources without pinning them:
+        // drain the in-flight ones before swapping a resource out from under
+        // them. Scalars need no drain, every replay copies the info.
+        if !bound.is_empty() {
+            let _ = self
+                .scheduler
+                .stream(&stream_id)
+                .flush(StreamErrorMode {
+                    ignore: true,
+                    flush: false,
+                })
+                .ok();
+        }
+        drop(replaced);
+
+        for (index, resource, managed) in bound {
+            cpu_graph.launches[index].resources[resource] = managed;
+        }
+        for launch in cpu_graph.launches.iter_mut() {
+            for site in launch.params.iter() {
+                let GraphParamTarget::Scalar { offset, len } = site.target else {
+                    continue;
+                };
+                if let Some((_, GraphParamValue::Scalar(bytes))) =
+                    updates.iter().find(|(param, _)| *param == site.param)
+                {
+                    bytemuck::cast_slice_mut::<u64, u8>(&mut launch.info)[offset..offset + len]
+                        .copy_from_slice(bytes);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ServerCommunication for CpuServer {
@@ -1,14 +1,18 @@
+use crate::compiler::jit::engine::PlironEngine;
 use crate::compute::{
-    alloc_controller::CpuAllocController, schedule::ScheduleTask, threadpool::Threadpool,
+    alloc_controller::CpuAllocController,
+    graph::{CpuGraph, RecordedLaunch},
+    schedule::{BindingsResource, ScheduleTask},
+    threadpool::{Threadpool, buffer_pointers},
 };
 use crossbeam_utils::CachePadded;
 use cubecl_common::{bytes::Bytes, profile::ProfileDuration};
 use cubecl_core::{
-    MemoryConfiguration,
+    CubeDim, MemoryConfiguration,
     ir::MemoryDeviceProperties,
     server::{
-        BufferBinding, CopyDescriptor, IoError, ProfileError, ProfilingToken, ServerError,
-        StreamErrorMode,
+        BufferBinding, CopyDescriptor, GraphParamMarks, IoError, ProfileError, ProfilingToken,
+        ServerError, StreamErrorMode,
     },
 };
 use cubecl_environment::backtrace::BackTrace;
@@ -18,9 +22,49 @@ use cubecl_runtime::{
         ManagedMemoryHandle, MemoryAllocationMode, MemoryManagement, MemoryManagementOptions,
     },
     storage::{BytesResource, BytesStorage},
+    stream::StreamCaptureState,
     timestamp_profiler::TimestampProfiler,
 };
-use std::sync::{Arc, atomic::AtomicU64};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+/// A user event: the point a stream's unit counter reaches once every unit launched before
+/// the event has run.
+#[derive(Debug, Clone)]
+pub struct CpuEvent {
+    counter: Arc<CachePadded<AtomicU64>>,
+    step: u64,
+    /// The events the stream was waiting on when this one was recorded, with no launch since
+    /// to carry the wait.
+    after: Arc<[CpuEvent]>,
+}
+
+impl CpuEvent {
+    /// Whether the event has completed, without blocking.
+    pub fn is_complete(&self) -> bool {
+        self.counter.load(Ordering::Acquire) >= self.step
+            && self.after.iter().all(CpuEvent::is_complete)
+    }
+
+    /// Blocks the calling thread until the event completes.
+    pub fn wait(&self) {
+        // Spin briefly, then yield between polls: the client is not pinned,
+        // and a pure spin parked on a worker's logical CPU keeps that worker
+        // off it until the next timer tick (~3 ms unit-start stalls).
+        const SPINS_BEFORE_YIELD: u32 = 1_000;
+        let mut spins = 0u32;
+        while !self.is_complete() {
+            spins += 1;
+            if spins < SPINS_BEFORE_YIELD {
+                std::hint::spin_loop();
+            } else {
+                std::thread::yield_now();
+            }
+        }
+    }
+}
 
 pub struct CpuStream {
     pub(crate) memory_management: MemoryManagement<BytesStorage>,
@@ -36,6 +80,18 @@ pub struct CpuStream {
     threadpool: &'static spin::Mutex<Threadpool>,
     next_counter_step: u64,
     atomic_counter: Arc<CachePadded<AtomicU64>>,
+    /// Events of other streams the next launch waits for, see [`wait_event`](Self::wait_event).
+    waits: Vec<CpuEvent>,
+    /// Graph-capture lifecycle of this stream, driven by the server's
+    /// `graph_prepare` → `begin_capture` → `end_capture` transitions; while
+    /// recording, enqueued launches append to `recording` instead of running.
+    pub(crate) capturing: StreamCaptureState,
+    /// The launches recorded since `begin_capture`, drained into a
+    /// [`CpuGraph`] at `end_capture`.
+    recording: Vec<RecordedLaunch>,
+    /// The graph parameters marked since `begin_capture`, matched against
+    /// each launch the server records.
+    pub(crate) param_marks: GraphParamMarks,
 }
 
 impl core::fmt::Debug for CpuStream {
@@ -80,7 +136,30 @@ impl CpuStream {
             threadpool,
             next_counter_step,
             atomic_counter,
+            waits: Vec::new(),
+            capturing: StreamCaptureState::NoCapture,
+            recording: Vec::new(),
+            param_marks: GraphParamMarks::default(),
+        }
+    }
+
+    /// Refuse `operation` while a capture is recording on this stream.
+    ///
+    /// A software graph records launches and nothing else, so everything a
+    /// launch is not — a read, a sync, a profile, a host write, a replay — has
+    /// no recorded form and must not silently do nothing.
+    ///
+    /// # Errors
+    ///
+    /// Fails while [`StreamCaptureState::Capture`] is set, naming `operation`.
+    pub(crate) fn reject_while_recording(&self, operation: &str) -> Result<(), ServerError> {
+        if !self.capturing.is_recording() {
+            return Ok(());
         }
+        Err(ServerError::graph_state(alloc::format!(
+            "{operation}: a cpu capture window records launches only, so this operation \
+             cannot be part of a graph"
+        )))
     }
 
     pub fn enqueue_task(&mut self, task: ScheduleTask) {
@@ -93,6 +172,11 @@ impl CpuStream {
         //   enqueue — sound only while one such launch has the pool to itself.
         match task {
             ScheduleTask::Write { data, mut buffer } => {
+                // Defensive: the server already rejects writes while recording.
+                if let Err(err) = self.reject_while_recording("write") {
+                    self.errors.push(err);
+                    return;
+                }
                 self.flush_uncheck();
                 buffer.resource_mut().write().copy_from_slice(&data);
             }
@@ -103,43 +187,119 @@ impl CpuStream {
                 cube_count,
                 ..
             } => {
-                if !pliron_engine.requirements().shared_memories.blocks.is_empty() {
-                    self.flush_uncheck();
+                let BindingsResource {
+                    resources,
+                    info,
+                    params,
+                } = bindings;
+
+                if self.capturing.is_recording() {
+                    self.recording.push(RecordedLaunch {
+                        pliron_engine,
+                        resources,
+                        info: info.data,
+                        params,
+                        cube_dim,
+                        cube_count,
+                    });
+                    return;
                 }
-                // No unit cap: the threadpool grows to fit any cube_dim, one
-                // worker per unit for barrier kernels.
-                let units = cube_dim.num_elems();
-                self.threadpool.lock().execute_data(
+
+                let buffer_ptrs = buffer_pointers(&resources);
+                // Pin the resources for the launch's lifetime (see
+                // `SharedData::keepalive`).
+                let keepalive = resources
+                    .into_iter()
+                    .map(|resource| Box::new(resource) as Box<dyn std::any::Any + Send>)
+                    .collect();
+                self.execute(
                     pliron_engine,
-                    bindings,
+                    buffer_ptrs,
+                    info.data,
+                    keepalive,
                     cube_dim,
                     cube_count,
-                    &mut self.shared_memory_management,
-                    self.next_counter_step,
-                    &self.atomic_counter,
                 );
-                self.next_counter_step += units as u64;
             }
         }
     }
 
+    fn execute(
+        &mut self,
+        pliron_engine: PlironEngine,
+        buffer_ptrs: Vec<*mut std::ffi::c_void>,
+        info: Vec<u64>,
+        keepalive: Vec<Box<dyn std::any::Any + Send>>,
+        cube_dim: CubeDim,
+        cube_count: [u32; 3],
+    ) {
+        if !pliron_engine.requirements().shared_memories.blocks.is_empty() {
+            self.flush_uncheck();
+        }
+        // No unit cap: the threadpool grows to fit any cube_dim, one
+        // worker per unit for barrier kernels.
+        let units = cube_dim.num_elems();
+        // Only the next launch has to wait: every later one waits for its units anyway.
+        let dependencies = core::mem::take(&mut self.waits).into();
+        self.threadpool.lock().execute_data(
+            pliron_engine,
+            buffer_ptrs,
+            info,
+            keepalive,
+            cube_dim,
+            cube_count,
+            &mut self.shared_memory_management,
+            self.next_counter_step,
+            &self.atomic_counter,
+            dependencies,
+        );
+        self.next_counter_step += units as u64;
+    }
+
+    /// Move the in-progress recording out of the stream (leaving it empty),
+    /// for `end_capture` to seal into a [`CpuGraph`].
+    pub(crate) fn take_recording(&mut self) -> Vec<RecordedLaunch> {
+        core::mem::take(&mut self.recording)
+    }
+
+    /// Run a captured graph's launches in recorded order, skipping everything
+    /// the server resolved at record time. Fire-and-forget like a launch.
+    pub(crate) fn replay_graph(&mut self, graph: &CpuGraph) {
+        for launch in graph.launches.iter() {
+            // No keepalive: the graph pins the resources, and `graph_destroy`
+            // drains the stream before dropping it.
+            self.execute(
+                launch.pliron_engine.clone(),
+                buffer_pointers(&launch.resources),
+                launch.info.clone(),
+                Vec::new(),
+                launch.cube_dim,
+                launch.cube_count,
+            );
+        }
+    }
+
     fn flush_uncheck(&mut self) {
-        // Spin briefly, then yield between polls: the client is not pinned,
-        // and a pure spin parked on a worker's logical CPU keeps that worker
-        // off it until the next timer tick (~3 ms unit-start stalls).
-        const SPINS_BEFORE_YIELD: u32 = 1_000;
-        let mut spins = 0u32;
-        while self
-            .atomic_counter
-            .load(std::sync::atomic::Ordering::Acquire)
-            != self.next_counter_step
-        {
-            spins += 1;
-            if spins < SPINS_BEFORE_YIELD {
-                std::hint::spin_loop();
-            } else {
-                std::thread::yield_now();
-            }
+        self.record_event().wait();
+        self.waits.clear();
+    }
+
+    /// An event completing once every unit launched so far on this stream has run, and every
+    /// event the stream waits on has completed.
+    pub(crate) fn record_event(&self) -> CpuEvent {
+        CpuEvent {
+            counter: self.atomic_counter.clone(),
+            step: self.next_counter_step,
+            after: self.waits.as_slice().into(),
+        }
+    }
+
+    /// Make the units launched on this stream from now on wait for `event`, without blocking
+    /// the caller: the workers hold them back until it completes. Host accesses to the
+    /// stream's memory flush it, which waits for the event too.
+    pub(crate) fn wait_event(&mut self, event: CpuEvent) {
+        if !event.is_complete() {
+            self.waits.push(event);
         }
     }
 
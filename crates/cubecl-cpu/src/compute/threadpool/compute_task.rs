@@ -4,7 +4,7 @@ use crossbeam_utils::CachePadded;
 
 use crate::{
     compiler::jit::{data::PlironData, engine::PlironEngine},
-    compute::threadpool::ThreadTask,
+    compute::{stream::CpuEvent, threadpool::ThreadTask},
 };
 
 pub struct ComputeTask {
@@ -12,6 +12,8 @@ pub struct ComputeTask {
     pub pliron_data: PlironData,
     pub next_counter_step: u64,
     pub atomic_counter: Arc<CachePadded<AtomicU64>>,
+    /// Events of other streams the launch waits for.
+    pub dependencies: Arc<[CpuEvent]>,
 }
 
 impl ThreadTask for ComputeTask {
@@ -19,6 +21,7 @@ impl ThreadTask for ComputeTask {
         self.atomic_counter
             .load(std::sync::atomic::Ordering::Acquire)
             >= self.next_counter_step
+            && self.dependencies.iter().all(CpuEvent::is_complete)
     }
 }
 
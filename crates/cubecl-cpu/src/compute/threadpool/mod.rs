@@ -1,13 +1,16 @@
 use crossbeam_utils::CachePadded;
 use cubecl_core::CubeDim;
-use cubecl_runtime::{memory_management::MemoryManagement, storage::BytesStorage};
+use cubecl_runtime::{
+    memory_management::MemoryManagement,
+    storage::{BytesResource, BytesStorage, ManagedResource},
+};
 use std::sync::{Arc, OnceLock, atomic::AtomicU64};
 
 use crate::{
     compiler::jit::{data::PlironData, engine::PlironEngine},
     compiler::shared_memory::SharedMemories,
     compute::{
-        schedule::BindingsResource,
+        stream::CpuEvent,
         threadpool::{
             compute_task::ComputeTask,
             scheduler::{Scheduler, SchedulerVariant},
@@ -44,34 +47,27 @@ impl Threadpool {
         INSTANCE.get_or_init(|| spin::Mutex::new(Self::init()))
     }
 
+    /// Split a launch into per-unit tasks. `buffer_ptrs` are the kernel's buffers, in binding
+    /// order; `keepalive` is held until the launch completes (see `SharedData::keepalive`).
+    /// No unit starts before the events of `dependencies` completed.
     #[allow(clippy::too_many_arguments)]
     pub fn execute_data(
         &mut self,
         pliron_engine: PlironEngine,
-        bindings: BindingsResource,
+        mut buffer_ptrs: Vec<*mut std::ffi::c_void>,
+        info: Vec<u64>,
+        keepalive: Vec<Box<dyn std::any::Any + Send>>,
         cube_dim: CubeDim,
         cube_count: [u32; 3],
         memory: &mut MemoryManagement<BytesStorage>,
         next_counter_step: u64,
         atomic_counter: &Arc<CachePadded<AtomicU64>>,
+        dependencies: Arc<[CpuEvent]>,
     ) {
         let requirements = pliron_engine.requirements().clone();
 
-        let BindingsResource { resources, info } = bindings;
-        let mut buffer_ptrs: Vec<*mut std::ffi::c_void> = resources
-            .iter()
-            .map(|resource| {
-                resource.resource().get_write_ptr_and_length().0 as *mut std::ffi::c_void
-            })
-            .collect();
         reserve_shared_memories(memory, &requirements.shared_memories, &mut buffer_ptrs);
-        // Pin the resources for the launch's lifetime (see
-        // `SharedData::keepalive`).
-        let keepalive: Vec<Box<dyn std::any::Any + Send>> = resources
-            .into_iter()
-            .map(|resource| Box::new(resource) as Box<dyn std::any::Any + Send>)
-            .collect();
-        let base_data = PlironData::new(buffer_ptrs, info.data, cube_count, keepalive);
+        let base_data = PlironData::new(buffer_ptrs, info, cube_count, keepalive);
 
         // A cube barrier only completes if every unit of the cube is running, so such a kernel
         // needs as many workers as the cube has units.
@@ -93,6 +89,7 @@ impl Threadpool {
                         pliron_data,
                         next_counter_step,
                         atomic_counter,
+                        dependencies: dependencies.clone(),
                     };
                     self.scheduler.send(i, compute_task);
                     i += 1;
@@ -102,6 +99,16 @@ impl Threadpool {
     }
 }
 
+/// The pointers the kernel reads its buffers from, in binding order.
+pub(crate) fn buffer_pointers(
+    resources: &[ManagedResource<BytesResource>],
+) -> Vec<*mut std::ffi::c_void> {
+    resources
+        .iter()
+        .map(|resource| resource.resource().get_write_ptr_and_length().0 as *mut std::ffi::c_void)
+        .collect()
+}
+
 /// Reserves the shared memory of a launch out of the stream's dedicated pool, and writes each
 /// block into the slot of `table` the kernel reads it from. Those slots follow the buffers, so
 /// the table is padded first when the kernel takes fewer buffers than the launch provides.
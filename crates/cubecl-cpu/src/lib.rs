@@ -14,13 +14,14 @@ mod tests {
     use cubecl_core::prelude::*;
     use cubecl_environment::config::RuntimeConfig;
     use cubecl_environment::stream::StreamId;
-    use cubecl_runtime::config::CubeClRuntimeConfig;
+    use cubecl_runtime::config::{CubeClRuntimeConfig, streaming::StreamPriority};
 
     cubecl_core::testgen_all!(f32: [f16, f32, f64], i32: [i8, i16, i32, i64], u32: [u8, u16, u32, u64]);
     cubecl_std::testgen!();
     cubecl_std::testgen_tensor_identity!([f16, f32, u32]);
     cubecl_std::testgen_tensor_into_contiguous!();
     cubecl_std::testgen_quantized_view!(f32);
+    cubecl_std::testgen_safetensors!();
 
     #[cube(launch)]
     fn barrier_smoke(out: &mut [f32]) {
@@ -111,6 +112,65 @@ mod tests {
         }
     }
 
+    #[cfg(target_arch = "x86_64")]
+    #[cube]
+    fn asm_add(a: u64, b: u64) -> u64 {
+        let sum: u64;
+        gpu_asm!(
+            "lea ({a}, {b}), {sum}",
+            a = in(_) a, b = in(_) b, sum = out(_) sum,
+            options(pure, nomem),
+        );
+        sum
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[cube]
+    fn asm_add(a: u64, b: u64) -> u64 {
+        let sum: u64;
+        gpu_asm!(
+            "add {sum}, {a}, {b}",
+            a = in(_) a, b = in(_) b, sum = out(_) sum,
+            options(pure, nomem),
+        );
+        sum
+    }
+
+    // Writes the output before reading the inputs, so it breaks if the output shares a register
+    // with either of them. Also clobbers the flags on x86.
+    #[cfg(target_arch = "x86_64")]
+    #[cube]
+    fn asm_add_zeroed(a: u64, b: u64) -> u64 {
+        let sum: u64;
+        gpu_asm!(
+            "xor {sum}, {sum}\nadd {a}, {sum}\nadd {b}, {sum}",
+            a = in(_) a, b = in(_) b, sum = out(_) sum,
+            options(pure, nomem),
+        );
+        sum
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[cube]
+    fn asm_add_zeroed(a: u64, b: u64) -> u64 {
+        let sum: u64;
+        gpu_asm!(
+            "mov {sum}, #0\nadd {sum}, {sum}, {a}\nadd {sum}, {sum}, {b}",
+            a = in(_) a, b = in(_) b, sum = out(_) sum,
+            options(pure, nomem),
+        );
+        sum
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    #[cube(launch)]
+    fn inline_asm(input: &[u64], out: &mut [u64]) {
+        if UNIT_POS == 0 {
+            out[0] = asm_add(input[0], input[1]);
+            out[1] = asm_add_zeroed(input[0], input[1]);
+        }
+    }
+
     #[cube(launch_unchecked)]
     fn delayed_copy(input: &[u32], output: &mut [u32], num_loop: usize) {
         if UNIT_POS == 0 {
@@ -285,6 +345,76 @@ mod tests {
         let actual = u32::from_bytes(&bytes);
         assert_eq!(actual, &[7]);
     }
+
+    #[test]
+    fn deferred_low_priority_work_is_visible_to_high_priority_stream() {
+        let client = TestRuntime::client(&Default::default());
+        let max_streams = CubeClRuntimeConfig::get().streaming.max_streams as u64;
+
+        let client_low = unsafe {
+            let mut client = client.clone();
+            client.set_stream(StreamId {
+                value: max_streams - 1,
+            });
+            client
+        };
+        let client_high = unsafe {
+            let mut client = client.clone();
+            client.set_stream(StreamId {
+                value: max_streams - 2,
+            });
+            client
+        };
+        client_low.set_stream_priority(StreamPriority::Low);
+        client_high.set_stream_priority(StreamPriority::High);
+
+        // Pending high-priority work, so the low-priority stream is deferred.
+        let pending = client_high.create_from_slice(u32::as_bytes(&[1, 2]));
+
+        let input = client_low.create_from_slice(u32::as_bytes(&[7, 7]));
+        let outputs = (0..16)
+            .map(|_| {
+                let output = client_low.empty(core::mem::size_of::<u32>());
+                unsafe {
+                    delayed_copy::launch_unchecked::<TestRuntime>(
+                        &client_low,
+                        CubeCount::new_single(),
+                        CubeDim::new_1d(1),
+                        BufferArg::from_raw_parts(input.clone(), 2),
+                        BufferArg::from_raw_parts(output.clone(), 1),
+                        10,
+                    )
+                }
+                output
+            })
+            .collect::<Vec<_>>();
+
+        for output in outputs {
+            let bytes = client_high.read_one_unchecked(output);
+            assert_eq!(u32::from_bytes(&bytes), &[7]);
+        }
+        let bytes = client_high.read_one_unchecked(pending);
+        assert_eq!(u32::from_bytes(&bytes), &[1, 2]);
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    #[test]
+    fn test_inline_asm_cpu() {
+        let client = TestRuntime::client(&Default::default());
+        let input = client.create_from_slice(u64::as_bytes(&[40, 2]));
+        let out = client.empty(2 * core::mem::size_of::<u64>());
+
+        inline_asm::launch::<TestRuntime>(
+            &client,
+            CubeCount::new_single(),
+            CubeDim::new_1d(1),
+            unsafe { BufferArg::from_raw_parts(input, 2) },
+            unsafe { BufferArg::from_raw_parts(out.clone(), 2) },
+        );
+
+        let bytes = client.read_one_unchecked(out);
+        assert_eq!(u64::from_bytes(&bytes), &[42, 42]);
+    }
 }
 
 pub mod compiler;
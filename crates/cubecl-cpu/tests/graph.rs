@@ -0,0 +1,376 @@
+//! Validates CPU graph capture/replay.
+//!
+//! The CPU graph is a software graph (recorded, fully-resolved launches handed
+//! to the threadpool on replay), with the same lifecycle contract as the other
+//! backends: `graph_prepare`, run the workload once, `start_capture`, run it
+//! again (recorded, not executed), `stop_capture`, then `replay`.
+
+use cubecl_common::bytes::Bytes;
+use cubecl_core as cubecl;
+use cubecl_core::client::GraphUpdate;
+use cubecl_core::ir::{ElemType, FloatKind};
+use cubecl_core::prelude::*;
+use cubecl_cpu::CpuRuntime;
+use std::sync::Mutex;
+
+/// Test threads share the cached client, and the scheduler's stream pool can
+/// map two test threads' stream ids onto the same stream — where two
+/// concurrent captures would reject each other. One capture at a time, as in
+/// real use.
+static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+#[cube(launch)]
+fn add_one(input: &[f32], output: &mut [f32]) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + 1.0;
+    }
+}
+
+#[cube(launch)]
+fn add_scalar(input: &[f32], output: &mut [f32], value: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + value;
+    }
+}
+
+#[cube(launch)]
+fn mul_add(input: &[f32], output: &mut [f32], factor: f32, offset: InputScalar) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor + offset.get::<f32>();
+    }
+}
+
+#[cube(launch)]
+fn mul_two(input: &[f32], output: &mut [f32]) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+/// Capture two chained launches into a graph, replay it, and check the output.
+#[test]
+fn cpu_graph_capture_replay() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let tmp = client.empty(n * core::mem::size_of::<f32>());
+    let output = client.empty(n * core::mem::size_of::<f32>());
+
+    let launch = |client: &ComputeClient<CpuRuntime>| {
+        add_one::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(tmp.clone(), n) },
+        );
+        mul_two::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(tmp.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    // Clear the output, so only a replay can produce the expected values.
+    client.write(&output, Bytes::from_bytes_vec(vec![0; n * 4]));
+
+    client.start_capture().expect("start_capture");
+    launch(&client);
+    let graph = client.stop_capture().expect("stop_capture");
+
+    let out = client.read_one(output.clone()).unwrap();
+    assert_eq!(
+        f32::from_bytes(&out),
+        &[0.0; 4],
+        "recorded launches must not execute"
+    );
+
+    unsafe { graph.replay() };
+    let out = client.read_one(output.clone()).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[4.0, 6.0, 8.0, 10.0]);
+
+    // Replaying again re-runs it deterministically.
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[4.0, 6.0, 8.0, 10.0]);
+}
+
+/// A replay runs against the buffers resolved at record time, so writing new
+/// bytes into the captured input and replaying must produce output for the
+/// new input.
+#[test]
+fn cpu_graph_input_rewrite() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    let launch = |client: &ComputeClient<CpuRuntime>| {
+        add_one::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    launch(&client);
+    let graph = client.stop_capture().expect("stop_capture");
+
+    client.write(
+        &input,
+        Bytes::from_bytes_vec(f32::as_bytes(&[10.0, 20.0, 30.0, 40.0]).to_vec()),
+    );
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[11.0, 21.0, 31.0, 41.0]);
+}
+
+/// Out-of-order lifecycle calls are rejected and leave the stream usable.
+#[test]
+fn cpu_graph_lifecycle_state_errors() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    assert!(
+        client.start_capture().is_err(),
+        "start_capture without graph_prepare must be rejected"
+    );
+    assert!(
+        client.stop_capture().is_err(),
+        "stop_capture without a recording capture must be rejected"
+    );
+
+    client.graph_prepare().expect("graph_prepare");
+    assert!(
+        client.graph_prepare().is_err(),
+        "a second graph_prepare on a prepared stream must be rejected"
+    );
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    let launch = |client: &ComputeClient<CpuRuntime>| {
+        add_one::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        );
+    };
+    launch(&client);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    launch(&client);
+    let graph = client.stop_capture().expect("stop_capture");
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[2.0, 3.0, 4.0, 5.0]);
+}
+
+/// A read inside a recording window is rejected directly, and a write lazily,
+/// failing `stop_capture` rather than handing back a graph missing it. Either
+/// way the stream stays usable.
+#[test]
+fn cpu_graph_host_io_rejected_while_recording() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    let launch = |client: &ComputeClient<CpuRuntime>| {
+        add_one::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    launch(&client);
+    assert!(
+        client.read_one(output.clone()).is_err(),
+        "a read inside a capture window must be rejected"
+    );
+    client.write(
+        &input,
+        Bytes::from_bytes_vec(f32::as_bytes(&[9.0, 9.0, 9.0, 9.0]).to_vec()),
+    );
+    assert!(
+        client.stop_capture().is_err(),
+        "a capture window containing a write must be rejected"
+    );
+
+    launch(&client);
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[2.0, 3.0, 4.0, 5.0]);
+}
+
+/// Parameters marked during the capture are updated on the graph between
+/// replays, without re-capturing it.
+#[test]
+fn cpu_graph_param_update() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let other = client.create_from_slice(f32::as_bytes(&[10.0, 20.0, 30.0, 40.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    // Distinct from every other argument of the launch, so it matches once.
+    let placeholder = 1234.5f32;
+    let launch = |client: &ComputeClient<CpuRuntime>, value: f32| {
+        add_scalar::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+            value,
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client, placeholder);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    let value = client
+        .graph_scalar_param(placeholder)
+        .expect("scalar param");
+    let source = client.graph_binding_param(&input).expect("binding param");
+    launch(&client, placeholder);
+    let graph = client.stop_capture().expect("stop_capture");
+
+    graph
+        .update(GraphUpdate::new().scalar(value, 1.0f32))
+        .expect("scalar update");
+    unsafe { graph.replay() };
+    let out = client.read_one(output.clone()).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[2.0, 3.0, 4.0, 5.0]);
+
+    graph
+        .update(
+            GraphUpdate::new()
+                .scalar(value, 2.0f32)
+                .binding(source, &other),
+        )
+        .expect("binding update");
+    unsafe { graph.replay() };
+    let out = client.read_one(output.clone()).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[12.0, 22.0, 32.0, 42.0]);
+
+    // A value of another width is rejected and changes nothing.
+    assert!(
+        graph
+            .update(GraphUpdate::new().scalar(value, 3.0f64))
+            .is_err()
+    );
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[12.0, 22.0, 32.0, 42.0]);
+}
+
+/// A scalar passed as a marked parameter is found where it was packed, even
+/// when another scalar of the launch has the same value.
+#[test]
+fn cpu_graph_marked_scalar_param() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    let launch = |client: &ComputeClient<CpuRuntime>, offset: InputScalar| {
+        mul_add::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+            2.0,
+            offset,
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(
+        &client,
+        InputScalar::new(2.0f32, ElemType::Float(FloatKind::F32)),
+    );
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    let offset = client.graph_scalar_param(2.0f32).expect("scalar param");
+    launch(
+        &client,
+        InputScalar::new(2.0f32, ElemType::Float(FloatKind::F32)).graph_param(offset),
+    );
+    let graph = client.stop_capture().expect("stop_capture");
+
+    graph
+        .update(GraphUpdate::new().scalar(offset, 5.0f32))
+        .expect("scalar update");
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[7.0, 9.0, 11.0, 13.0]);
+}
+
+/// A parameter no recorded launch uses fails the capture instead of making
+/// later updates silently do nothing.
+#[test]
+fn cpu_graph_unused_param_rejected() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = CpuRuntime::client(&Default::default());
+
+    assert!(
+        client.graph_scalar_param(7u32).is_err(),
+        "a parameter can only be marked while recording"
+    );
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    let launch = |client: &ComputeClient<CpuRuntime>| {
+        add_one::launch::<CpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    client.graph_scalar_param(98765u32).expect("scalar param");
+    launch(&client);
+    assert!(client.stop_capture().is_err());
+}
@@ -0,0 +1,48 @@
+//! Validates that a replay log recorded on the CPU runtime replays from the log alone: every
+//! kernel is compiled back from the IR it was logged with.
+
+use cubecl_core as cubecl;
+use cubecl_core::client::ReplayLog;
+use cubecl_core::prelude::*;
+use cubecl_cpu::CpuRuntime;
+
+#[cube(launch)]
+fn scale_add(input: &[f32], output: &mut [f32], factor: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor + 1.0;
+    }
+}
+
+/// Record a launch and the read of its output, then replay the log on the same runtime.
+#[test]
+fn cpu_replay_log_round_trip() {
+    let client = CpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    // Created before the recording, so the log has to snapshot it at the launch.
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+
+    let path = std::env::temp_dir().join(format!("cubecl-cpu-replay-{}.log", std::process::id()));
+    client.start_recording(&path).unwrap();
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    scale_add::launch::<CpuRuntime>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(&client, n),
+        unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+        3.0,
+    );
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[4.0, 7.0, 10.0, 13.0]);
+    client.stop_recording().unwrap();
+
+    let log = ReplayLog::from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    let log = log.unwrap();
+    assert_eq!(log.kernels().count(), 1);
+
+    let report = log.replay(&client).unwrap();
+    assert_eq!((report.launches, report.reads), (1, 1));
+    assert!(report.is_match(), "{report:?}");
+}
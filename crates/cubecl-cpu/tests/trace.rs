@@ -0,0 +1,68 @@
+//! Validates that a trace file configured before the device is created records the launches,
+//! reads and syncs of the CPU runtime. Its own test binary, since the config is process-wide.
+
+use std::time::{Duration, Instant};
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_cpu::CpuRuntime;
+use cubecl_runtime::config::{CubeClRuntimeConfig, RuntimeConfig};
+
+#[cube(launch)]
+fn double(input: &[f32], output: &mut [f32]) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+/// The categories of the events written so far, skipping the track names.
+fn categories(path: &std::path::Path) -> Vec<String> {
+    let text = std::fs::read_to_string(path).unwrap_or_default();
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            serde_json::from_str::<serde_json::Value>(line.trim_end_matches(',')).ok()
+        })
+        .filter(|event| event["ph"] != "M")
+        .filter_map(|event| event["cat"].as_str().map(String::from))
+        .collect()
+}
+
+#[test]
+fn cpu_trace_records_launch_read_and_sync() {
+    let dir = std::env::temp_dir().join(format!("cubecl-cpu-trace-{}", std::process::id()));
+    let path = dir.join("trace.json");
+    let mut config = CubeClRuntimeConfig::default();
+    config.profiling.trace_file = Some(path.clone());
+    CubeClRuntimeConfig::set(config);
+
+    let client = CpuRuntime::client(&Default::default());
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    double::launch::<CpuRuntime>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(&client, n),
+        unsafe { BufferArg::from_raw_parts(input, n) },
+        unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+    );
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[2.0, 4.0, 6.0, 8.0]);
+    cubecl_environment::future::block_on(client.sync()).unwrap();
+
+    // Events are written once their durations resolve, off the thread that issued them.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let expected = ["launch", "read", "sync"];
+    let mut seen = categories(&path);
+    while !expected.iter().all(|cat| seen.iter().any(|it| it == cat)) {
+        assert!(
+            Instant::now() < deadline,
+            "Missing trace events, got {seen:?}"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+        seen = categories(&path);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -76,6 +76,7 @@ pub(crate) fn get_nccl_dtype_count(
             (size / 8) as usize,
         ),
         ElemType::Int(int_kind) => match int_kind {
+            cubecl_core::ir::IntKind::I4x2 => panic!("NCCL doesn't support Int4 format."),
             cubecl_core::ir::IntKind::I8 => {
                 (cudarc::nccl::sys::ncclDataType_t::ncclInt8, size as usize)
             }
@@ -90,6 +91,7 @@ pub(crate) fn get_nccl_dtype_count(
             ),
         },
         ElemType::UInt(uint_kind) => match uint_kind {
+            cubecl_core::ir::UIntKind::U4x2 => panic!("NCCL doesn't support UInt4 format."),
             cubecl_core::ir::UIntKind::U8 => {
                 (cudarc::nccl::sys::ncclDataType_t::ncclUint8, size as usize)
             }
@@ -103,6 +105,7 @@ pub(crate) fn get_nccl_dtype_count(
                 (size / 8) as usize,
             ),
         },
+        ElemType::Complex(_) => panic!("NCCL doesn't support Complex format."),
         ElemType::Bool => panic!("NCCL doesn't support Bool format."),
     }
 }
@@ -1,5 +1,7 @@
+use crate::compute::sync::Fence;
+use cubecl_core::server::{GraphParamSite, GraphParamTable, GraphParamValue};
 use cubecl_runtime::memory_management::ManagedMemoryHandle;
-use cudarc::driver::sys::CUgraphExec;
+use cudarc::driver::sys::{CUgraph, CUgraphExec, CUgraphNode, CUstream};
 
 /// An instantiated CUDA executable graph (`CUgraphExec`), destroyed on drop.
 ///
@@ -12,20 +14,86 @@ use cudarc::driver::sys::CUgraphExec;
 #[derive(Debug)]
 pub struct CudaGraph {
     pub(crate) exec: CUgraphExec,
+    /// The captured graph `exec` was instantiated from, kept alive while
+    /// `nodes` reference its kernel nodes; null for a graph without
+    /// parameters, whose captured graph is freed at instantiation.
+    pub(crate) graph: CUgraph,
+    /// The graph's parameters, patched into `nodes` on update.
+    pub(crate) params: GraphParamTable,
+    /// The kernel nodes holding a graph parameter.
+    pub(crate) nodes: Vec<KernelNodeParams>,
     /// Every buffer the captured graph touches, pinned for the graph's
     /// lifetime. A replay re-runs the recorded kernels against these exact
     /// device pointers; retaining the handles keeps the memory pool from
     /// reusing those slices (a reuse would let a later allocation share memory
     /// the replay overwrites). Dropped with the graph, releasing the memory.
     pub(crate) _retained: Vec<ManagedMemoryHandle>,
+    /// Bindings replaced by a graph update, each batch held until the fence
+    /// recorded after the update (and so after every replay enqueued before
+    /// it) completes.
+    pub(crate) retired: Vec<(Fence, Vec<GraphParamValue>)>,
+}
+
+impl CudaGraph {
+    /// Retires the values a graph update replaced on `stream`.
+    ///
+    /// Replays enqueued before the update still read the replaced bindings, so
+    /// they are kept behind a fence recorded now; batches whose fence already
+    /// completed are released first.
+    pub(crate) fn retire(&mut self, replaced: Vec<GraphParamValue>, stream: CUstream) {
+        let (done, pending) = core::mem::take(&mut self.retired)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(fence, _)| fence.is_complete());
+        self.retired = pending;
+        for (fence, _) in done {
+            // Completed: only destroys the event.
+            let _ = fence.wait_sync();
+        }
+
+        if replaced
+            .iter()
+            .any(|value| matches!(value, GraphParamValue::Binding(_)))
+        {
+            self.retired.push((Fence::new(stream), replaced));
+        }
+    }
 }
 
 impl Drop for CudaGraph {
     fn drop(&mut self) {
+        // `graph_destroy` synced the stream, so these return at once; waiting
+        // destroys the events.
+        for (fence, _) in self.retired.drain(..) {
+            let _ = fence.wait_sync();
+        }
         // SAFETY: `exec` was produced by `cuGraphInstantiateWithFlags` and is
         // destroyed exactly once here.
         unsafe {
             cudarc::driver::sys::cuGraphExecDestroy(self.exec);
         }
+        if !self.graph.is_null() {
+            // SAFETY: `graph` was returned by `cuStreamEndCapture`, kept rather
+            // than freed at instantiation, and is destroyed exactly once here.
+            unsafe {
+                cudarc::driver::sys::cuGraphDestroy(self.graph);
+            }
+        }
     }
 }
+
+/// A captured kernel node holding at least one graph parameter.
+///
+/// A graph update reads the node's kernel arguments back from the captured
+/// graph, replaces the ones a parameter lives in, and sets the result on both
+/// the executable (`cuGraphExecKernelNodeSetParams`) and the captured node, so
+/// the next update starts from the current values.
+#[derive(Debug)]
+pub(crate) struct KernelNodeParams {
+    pub(crate) node: CUgraphNode,
+    pub(crate) sites: Vec<GraphParamSite>,
+    /// The number of kernel arguments of the launch.
+    pub(crate) arg_count: usize,
+    /// The index of the grid-constant info argument and the words it holds,
+    /// when a scalar parameter lives in it.
+    pub(crate) info: Option<(usize, Vec<u64>)>,
+}
@@ -5,7 +5,7 @@ use crate::{
         command::Command,
         communication::{get_nccl_comm_id, get_nccl_dtype_count, to_nccl_op},
         context::CudaContext,
-        graph::CudaGraph,
+        graph::{CudaGraph, KernelNodeParams},
         stream::CudaStreamBackend,
         sync::Fence,
     },
@@ -17,9 +17,10 @@ use cubecl_core::{
     ir::{ElemType, FloatKind, IntKind, MemoryDeviceProperties, UIntKind},
     prelude::*,
     server::{
-        BufferBinding, CommunicationId, CopyDescriptor, Handle, KernelArguments, KernelResource,
-        LaunchError, ProfileError, ProfilingToken, ReduceOperation, ServerCommunication,
-        ServerError, ServerUtilities, StreamErrorMode, TensorMapBinding, TensorMapMeta,
+        BufferBinding, CommunicationId, CopyDescriptor, GraphParamTarget, GraphParamValue, Handle,
+        KernelArguments, KernelResource, LaunchError, ProfileError, ProfilingToken,
+        ReduceOperation, ServerCommunication, ServerError, ServerUtilities, StreamErrorMode,
+        TensorMapBinding, TensorMapMeta,
     },
     zspace::SmallVec,
 };
@@ -31,7 +32,7 @@ use cubecl_runtime::{
     compiler::CubeTask,
     config::{CubeClRuntimeConfig, RuntimeConfig},
     dry_run::LaunchMode,
-    id::GraphId,
+    id::{EventId, GraphId, GraphParamId},
     logging::ServerLogger,
     memory_management::{
         InstallMemoryPoolsError, ManagedMemoryHandle, MemoryAllocationMode, MemoryReport,
@@ -123,6 +124,112 @@ unsafe fn count_memory_nodes(graph: cudarc::driver::sys::CUgraph) -> usize {
         .count()
 }
 
+/// The kernel node the last launch on `stream` recorded into its capture.
+///
+/// # Safety
+///
+/// `stream` must be a valid CUDA stream that is recording a capture.
+unsafe fn captured_kernel_node(
+    stream: cudarc::driver::sys::CUstream,
+) -> Result<cudarc::driver::sys::CUgraphNode, ServerError> {
+    let mut status = cudarc::driver::sys::CUstreamCaptureStatus::CU_STREAM_CAPTURE_STATUS_NONE;
+    let mut dependencies: *const cudarc::driver::sys::CUgraphNode = std::ptr::null();
+    let mut num_dependencies = 0usize;
+    // SAFETY: `stream` is valid per this function's contract; the id and graph
+    // outputs are optional and left null.
+    cuda_check("cuStreamGetCaptureInfo", unsafe {
+        cudarc::driver::sys::cuStreamGetCaptureInfo_v2(
+            stream,
+            &mut status,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut dependencies,
+            &mut num_dependencies,
+        )
+    })?;
+    // A kernel launch on a capturing stream leaves its own node as the sole
+    // dependency of whatever is recorded next.
+    if num_dependencies != 1 || dependencies.is_null() {
+        return Err(ServerError::graph_state(format!(
+            "expected the launch to leave one kernel node as the capture's dependency, \
+             found {num_dependencies}"
+        )));
+    }
+    // SAFETY: the driver returned one valid node in `dependencies`.
+    Ok(unsafe { *dependencies })
+}
+
+/// Patch a validated graph update into one captured kernel node: rebind the
+/// arguments whose parameter got a new device pointer in `pointers`, rewrite
+/// the scalars updated in `updates`, and set the result on `exec` and on the
+/// captured node.
+///
+/// # Safety
+///
+/// `exec` must be the live executable instantiated from the graph holding
+/// `node.node`, and that graph must still be alive.
+unsafe fn update_kernel_node(
+    exec: cudarc::driver::sys::CUgraphExec,
+    node: &mut KernelNodeParams,
+    updates: &[(GraphParamId, GraphParamValue)],
+    pointers: &[(GraphParamId, u64)],
+) -> Result<(), ServerError> {
+    let mut bound = SmallVec::<[(usize, u64); 4]>::new();
+    let mut rewritten = false;
+    for site in node.sites.iter() {
+        match site.target {
+            GraphParamTarget::Binding(index) => {
+                if let Some((_, ptr)) = pointers.iter().find(|(param, _)| *param == site.param) {
+                    bound.push((index, *ptr));
+                }
+            }
+            GraphParamTarget::Scalar { offset, len } => {
+                if let (Some((_, info)), Some((_, GraphParamValue::Scalar(bytes)))) = (
+                    node.info.as_mut(),
+                    updates.iter().find(|(param, _)| *param == site.param),
+                ) {
+                    bytemuck::cast_slice_mut::<u64, u8>(info)[offset..offset + len]
+                        .copy_from_slice(bytes);
+                    rewritten = true;
+                }
+            }
+        }
+    }
+    if bound.is_empty() && !rewritten {
+        return Ok(());
+    }
+
+    // SAFETY: `node.node` is a kernel node of a live graph per this function's
+    // contract. The argument array the driver hands back stays valid until the
+    // node's parameters are set, and is copied out before that; the driver
+    // copies the argument values `args` points at when they are set.
+    unsafe {
+        let mut params: cudarc::driver::sys::CUDA_KERNEL_NODE_PARAMS = core::mem::zeroed();
+        cuda_check(
+            "cuGraphKernelNodeGetParams",
+            cudarc::driver::sys::cuGraphKernelNodeGetParams_v2(node.node, &mut params),
+        )?;
+        let mut args = core::slice::from_raw_parts(params.kernelParams, node.arg_count).to_vec();
+        for (index, ptr) in bound.iter() {
+            args[*index] = ptr as *const u64 as *mut c_void;
+        }
+        if rewritten && let Some((index, info)) = &node.info {
+            args[*index] = info.as_ptr() as *mut c_void;
+        }
+        params.kernelParams = args.as_mut_ptr();
+        params.extra = std::ptr::null_mut();
+
+        cuda_check(
+            "cuGraphExecKernelNodeSetParams",
+            cudarc::driver::sys::cuGraphExecKernelNodeSetParams_v2(exec, node.node, &params),
+        )?;
+        cuda_check(
+            "cuGraphKernelNodeSetParams",
+            cudarc::driver::sys::cuGraphKernelNodeSetParams_v2(node.node, &params),
+        )
+    }
+}
+
 /// Stage `words` into a device buffer, reusing a cached one when a launch has
 /// already staged these exact info words. The info is read-only metadata (no
 /// tensor pointers), so sharing it across launches — even of different kernels
@@ -407,10 +514,12 @@ impl ComputeServer for CudaServer {
             // re-enable the deferred fenced flushes and restore the allocation
             // mode on the way out.
             stream.capturing.end()?;
+            let nodes = core::mem::take(&mut stream.param_nodes);
             // SAFETY: ends the capture begun on this stream and instantiates the
-            // recorded graph into an executable; the intermediate `graph` is freed
-            // whether or not instantiation succeeds, leaving only the `exec` the
-            // returned handle owns.
+            // recorded graph into an executable. The intermediate `graph` is
+            // freed whether or not instantiation succeeds, leaving only the
+            // `exec` the returned handle owns, unless parameter `nodes` still
+            // reference it: then the handle owns it too.
             let exec = unsafe {
                 let mut graph: cudarc::driver::sys::CUgraph = std::ptr::null_mut();
                 cuda_check(
@@ -438,10 +547,35 @@ impl ComputeServer for CudaServer {
                         "cuGraphInstantiateWithFlags",
                         cudarc::driver::sys::cuGraphInstantiateWithFlags(&mut exec, graph, 0),
                     );
-                    cudarc::driver::sys::cuGraphDestroy(graph);
-                    instantiated.map(|_| exec)
+                    if instantiated.is_err() || nodes.is_empty() {
+                        cudarc::driver::sys::cuGraphDestroy(graph);
+                        graph = std::ptr::null_mut();
+                    }
+                    instantiated.map(|_| (exec, graph))
                 })
             };
+            // A parameter no recorded launch used fails the capture, as an
+            // update would silently change nothing.
+            let exec = match exec {
+                Ok((exec, graph)) => match stream.param_marks.finish() {
+                    Ok(params) => Ok(((exec, graph), params)),
+                    Err(err) => {
+                        // SAFETY: both were created above and nothing else
+                        // references them yet.
+                        unsafe {
+                            cudarc::driver::sys::cuGraphExecDestroy(exec);
+                            if !graph.is_null() {
+                                cudarc::driver::sys::cuGraphDestroy(graph);
+                            }
+                        }
+                        Err(err)
+                    }
+                },
+                Err(err) => {
+                    stream.param_marks.clear();
+                    Err(err)
+                }
+            };
             // Pin every buffer the graph touched so the pool never reuses that
             // memory for the graph's lifetime — both the GPU slices and the pinned
             // staging slices the recorded info copies still read from on replay.
@@ -455,7 +589,7 @@ impl ComputeServer for CudaServer {
             stream.drop_queue.flush(|| Fence::new(sys));
             stream.drop_queue.flush(|| Fence::new(sys));
             match exec {
-                Ok(exec) => {
+                Ok(((exec, graph), params)) => {
                     // Seal the info-cache entries this capture pinned under the
                     // graph's id, so `graph_destroy` can release them later.
                     stream.info_cache.capture_commit(id);
@@ -476,7 +610,11 @@ impl ComputeServer for CudaServer {
                     }
                     CudaGraph {
                         exec,
+                        graph,
+                        params,
+                        nodes,
                         _retained: retained,
+                        retired: Vec::new(),
                     }
                 }
                 Err(err) => {
@@ -530,6 +668,101 @@ impl ComputeServer for CudaServer {
         }
     }
 
+    fn graph_param(
+        &mut self,
+        value: GraphParamValue,
+        stream_id: StreamId,
+    ) -> Result<GraphParamId, ServerError> {
+        let mut streams = self.streams.resolve(stream_id, [].into_iter(), false)?;
+        let stream = streams.current();
+        if !stream.capturing.is_recording() {
+            return Err(ServerError::graph_state(
+                "graph_param: no graph capture is recording on this stream",
+            ));
+        }
+        stream.param_marks.mark(value)
+    }
+
+    fn graph_update(
+        &mut self,
+        graph: GraphId,
+        updates: Vec<(GraphParamId, GraphParamValue)>,
+        stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        if !self.graphs.contains_key(&graph) {
+            return Err(ServerError::graph_state(
+                "graph_update was given an unknown or already-destroyed graph",
+            ));
+        }
+
+        // Resolve the device pointers of the new bindings before touching the
+        // graph, so a failure leaves it as it was. Resolving them on the
+        // graph's stream orders its later replays after the work that wrote
+        // them on other streams.
+        let (pointers, sys) = {
+            let bindings = updates.iter().filter_map(|(_, value)| match value {
+                GraphParamValue::Binding(binding) => Some(binding),
+                GraphParamValue::Scalar(_) => None,
+            });
+            let mut command = self.command(
+                stream_id,
+                bindings,
+                StreamErrorMode {
+                    ignore: true,
+                    flush: false,
+                },
+            )?;
+            let mut pointers = Vec::new();
+            for (param, value) in updates.iter() {
+                if let GraphParamValue::Binding(binding) = value {
+                    pointers.push((*param, command.resource(binding.clone())?.ptr));
+                }
+            }
+            (pointers, command.streams.current().sys)
+        };
+
+        // An executable update only affects the launches after it, so the
+        // replays already enqueued keep the previous values without a sync;
+        // the bindings they read are retired behind a fence instead.
+        let cuda_graph = self.graphs.get_mut(&graph).expect("looked up above");
+        let replaced = cuda_graph.params.update(&updates)?;
+        cuda_graph.retire(replaced, sys);
+        let exec = cuda_graph.exec;
+        for node in cuda_graph.nodes.iter_mut() {
+            // SAFETY: `exec` was instantiated from the graph holding the node,
+            // which the `CudaGraph` keeps alive.
+            unsafe { update_kernel_node(exec, node, &updates, &pointers)? };
+        }
+        Ok(())
+    }
+
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        self.unsafe_set_current();
+        Ok(self.streams.record_event(stream_id))
+    }
+
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        self.unsafe_set_current();
+        self.streams.wait_event(event, stream_id)
+    }
+
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        self.unsafe_set_current();
+        self.streams.query_event(event)
+    }
+
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        self.unsafe_set_current();
+        match self.streams.synchronize_event(event) {
+            Ok(event) => Box::pin(async move { event.wait_sync() }),
+            Err(err) => Box::pin(async { Err(err) }),
+        }
+    }
+
+    fn event_destroy(&mut self, event: EventId) {
+        self.streams.destroy_event(event);
+    }
+
     fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
         let command = self.command_no_inputs(
             stream_id,
@@ -1060,6 +1293,26 @@ impl CudaServer {
             return Ok(());
         }
 
+        // Locate the graph parameters marked on a recording stream in this
+        // launch's arguments, before they are resolved.
+        let params = {
+            let stream = command.streams.current();
+            match stream.capturing.is_recording() {
+                true => stream.param_marks.sites(&bindings)?,
+                false => Vec::new(),
+            }
+        };
+        let scalar_params = params
+            .iter()
+            .any(|site| matches!(site.target, GraphParamTarget::Scalar { .. }));
+        // Without grid constants the scalars live in an info buffer that
+        // launches with the same info share, so one cannot be rewritten alone.
+        if scalar_params && !grid_constants {
+            return Err(ServerError::graph_state(
+                "scalar graph parameters need grid constant support on this device",
+            ));
+        }
+
         let count = match count {
             CubeCount::Static(x, y, z) => (x, y, z),
             // TODO: CUDA doesn't have an exact equivalent of dynamic dispatch. Instead, kernels are free to launch other kernels.
@@ -1137,9 +1390,24 @@ impl CudaServer {
             resources.push(command.resource(binding.binding())?.binding);
         }
         resources.extend(info_const);
+        let arg_count = resources.len();
 
         command.kernel(kernel_id, kernel, count, &mut resources, logger)?;
 
+        if !params.is_empty() {
+            let stream = command.streams.current();
+            // SAFETY: the stream is recording, and the launch above was the
+            // last operation captured on it.
+            let node = unsafe { captured_kernel_node(stream.sys)? };
+            stream.param_nodes.push(KernelNodeParams {
+                node,
+                sites: params,
+                arg_count,
+                // The info constant is the last kernel argument.
+                info: scalar_params.then(|| (arg_count - 1, bindings.info.data.clone())),
+            });
+        }
+
         Ok(())
     }
 
@@ -1350,13 +1618,13 @@ fn elem_to_tensor_map_type(ty: ElemType) -> CUtensorMapDataType {
         },
         ElemType::Int(kind) => match kind {
             // UInt is fine because zero bits and size is the same between both
-            IntKind::I8 => CU_TENSOR_MAP_DATA_TYPE_UINT8,
+            IntKind::I4x2 | IntKind::I8 => CU_TENSOR_MAP_DATA_TYPE_UINT8,
             IntKind::I16 => CU_TENSOR_MAP_DATA_TYPE_UINT16,
             IntKind::I32 => CU_TENSOR_MAP_DATA_TYPE_INT32,
             IntKind::I64 => CU_TENSOR_MAP_DATA_TYPE_INT64,
         },
         ElemType::UInt(kind) => match kind {
-            UIntKind::U8 => CU_TENSOR_MAP_DATA_TYPE_UINT8,
+            UIntKind::U4x2 | UIntKind::U8 => CU_TENSOR_MAP_DATA_TYPE_UINT8,
             UIntKind::U16 => CU_TENSOR_MAP_DATA_TYPE_UINT16,
             UIntKind::U32 => CU_TENSOR_MAP_DATA_TYPE_UINT32,
             UIntKind::U64 => CU_TENSOR_MAP_DATA_TYPE_UINT64,
@@ -1,4 +1,5 @@
 use crate::compute::{
+    graph::KernelNodeParams,
     storage::{
         cpu::{PINNED_MEMORY_ALIGNMENT, PinnedMemoryStorage},
         gpu::GpuStorage,
@@ -8,7 +9,7 @@ use crate::compute::{
 use cubecl_core::{
     MemoryConfiguration,
     ir::MemoryDeviceProperties,
-    server::{BufferBinding, Handle, ServerError},
+    server::{BufferBinding, GraphParamMarks, Handle, ServerError},
 };
 use cubecl_runtime::{
     config::streaming::StreamPriority,
@@ -40,6 +41,12 @@ pub struct Stream {
     /// the capture lifecycle, so during graph capture every buffer is cached
     /// and none is evicted mid-capture. See [`StreamCaptureState::cache_mode`].
     pub info_cache: MetadataInfoCache<Handle>,
+    /// The graph parameters marked since `begin_capture`, matched against
+    /// each launch recorded on this stream.
+    pub param_marks: GraphParamMarks,
+    /// The kernel nodes of the current capture holding a graph parameter,
+    /// moved into the [`CudaGraph`](super::graph::CudaGraph) at `end_capture`.
+    pub(crate) param_nodes: Vec<KernelNodeParams>,
 }
 
 impl drop_queue::Fence for Fence {
@@ -176,6 +183,8 @@ impl EventStreamBackend for CudaStreamBackend {
             drop_queue: Default::default(),
             capturing: StreamCaptureState::NoCapture,
             info_cache: MetadataInfoCache::new(MetadataCachePolicy::default()),
+            param_marks: GraphParamMarks::default(),
+            param_nodes: Vec::new(),
         }
     }
 
@@ -191,6 +200,14 @@ impl EventStreamBackend for CudaStreamBackend {
         event.wait_sync()
     }
 
+    fn wait_event_shared(stream: &mut Self::Stream, event: &Self::Event) {
+        event.wait_async_shared(stream.sys);
+    }
+
+    fn is_event_complete(event: &Self::Event) -> bool {
+        event.is_complete()
+    }
+
     fn handle_cursor(stream: &Self::Stream, binding: &BufferBinding) -> u64 {
         // The slice cursor the sync logic compares against the origin stream's `last_synced`
         // to decide whether to wait. A freed/reallocated slice falls back to `u64::MAX`,
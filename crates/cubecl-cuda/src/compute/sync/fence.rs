@@ -1,6 +1,6 @@
 use cubecl_core::server::ServerError;
 use cubecl_environment::backtrace::BackTrace;
-use cudarc::driver::sys::{CUevent_flags, CUevent_st, CUevent_wait_flags, CUstream_st};
+use cudarc::driver::sys::{CUevent_flags, CUevent_st, CUevent_wait_flags, CUresult, CUstream_st};
 
 /// A fence is simply an [event](CUevent_st) created on a [stream](CUevent_st) that you can wait
 /// until completion.
@@ -84,4 +84,24 @@ impl Fence {
             cudarc::driver::result::event::destroy(self.event).unwrap();
         }
     }
+
+    /// Like [`Self::wait_async`], without destroying the event, so it can be waited on again.
+    pub fn wait_async_shared(&self, stream: *mut CUstream_st) {
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, kept alive by `&self`.
+        // `stream` must be a valid CUDA stream.
+        unsafe {
+            cudarc::driver::result::stream::wait_event(
+                stream,
+                self.event,
+                CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+            .unwrap();
+        }
+    }
+
+    /// Whether the [Fence] was reached, without blocking.
+    pub fn is_complete(&self) -> bool {
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, kept alive by `&self`.
+        unsafe { cudarc::driver::sys::cuEventQuery(self.event) == CUresult::CUDA_SUCCESS }
+    }
 }
@@ -8,6 +8,8 @@ fn main() {
         stream_local: { feature = "std" },
         // Filesystem and environment access for config loading and caching.
         std_io: { all(feature = "std", any(target_os = "windows", target_os = "linux", target_os = "macos", target_os = "android")) },
+        // Memory-mapped file bytes. `memmap2` is declared for non-wasm targets only.
+        mmap: { all(feature = "mmap", std_io) },
         // The `SQLite` persistence backend. `cache` pulls in `rusqlite`, which is
         // declared for non-wasm targets only, so the feature alone is not enough
         // to gate the module: enabling `cache` on wasm must compile to nothing
@@ -371,6 +371,37 @@ impl Bytes {
         }
     }
 
+    /// Creates bytes from a read-only memory map of `size` bytes of a file at `offset`.
+    ///
+    /// Unlike [`Self::from_file`], reading never copies: the mapped pages are handed out
+    /// as they are, so a backend uploads straight from the page cache. Mutating copies the
+    /// range into a private buffer first, the file is never written. The offset doesn't
+    /// need to be page aligned, but [`Self::align`] is then only as large as it allows.
+    ///
+    /// Returns an error if the file can't be opened or mapped, or is shorter than
+    /// `offset + size`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the returned bytes, or any clone,
+    /// split or view of them, are alive.
+    #[cfg(mmap)]
+    pub unsafe fn from_file_mmap<P: AsRef<std::path::Path>>(
+        file: P,
+        size: u64,
+        offset: u64,
+    ) -> std::io::Result<Self> {
+        // SAFETY: upheld by the caller.
+        let controller = unsafe {
+            crate::bytes::mmap::MmapAllocationController::new(file.as_ref(), size, offset)
+        }?;
+
+        Ok(Self {
+            controller: Box::new(controller),
+            len: size as usize,
+        })
+    }
+
     /// Creates bytes from a shared [`bytes::Bytes`] buffer (zero-copy).
     ///
     /// This is useful for zero-copy tensor loading from:
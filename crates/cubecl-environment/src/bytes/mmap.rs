@@ -0,0 +1,274 @@
+//! Allocation controller over a read-only memory map of a file.
+//!
+//! Unlike the [file controller](super::file), which copies the file range into an
+//! in-memory buffer on first access, [`MmapAllocationController`] hands out the mapped
+//! pages directly: reading never copies, and the pages are loaded by the OS as they are
+//! touched. Uploading a multi-gigabyte weight file therefore goes from the page cache to
+//! the device without a second host-side copy of the whole range.
+//!
+//! The mapping is shared behind an [`Arc`], so cloning, splitting and viewing are cheap.
+//! Mutating copies the mapped range into a private buffer first (copy-on-write); the
+//! file itself is never written.
+
+use super::{
+    AccessError, AccessPolicy, AllocationController, AllocationProperty, SplitError,
+    default_controller::{MAX_ALIGN, NativeAllocationController},
+};
+use memmap2::{Mmap, MmapOptions};
+use spin::Once;
+use std::{boxed::Box, fs::File, io, mem::MaybeUninit, path::Path, sync::Arc};
+
+/// Allocation controller reading straight from a read-only memory map.
+///
+/// The controller views `[start, start + len)` of the map. Offsets into the file don't
+/// need to be page aligned: the map is placed on the page boundary below and the data
+/// starts as far into it.
+pub(crate) struct MmapAllocationController {
+    map: Arc<Mmap>,
+    /// Offset, in bytes, of this view into `map`.
+    start: usize,
+    /// Length, in bytes, of this view.
+    len: usize,
+    /// Lazily initialized private buffer (copy-on-write).
+    controller: Once<Box<dyn AllocationController>>,
+}
+
+impl MmapAllocationController {
+    /// Map `size` bytes of `file` starting at `offset`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the map or any view of it is alive,
+    /// see [`Mmap`].
+    pub unsafe fn new(file: &Path, size: u64, offset: u64) -> io::Result<Self> {
+        let len = usize::try_from(size)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "size exceeds usize"))?;
+
+        let file = File::open(file)?;
+        let file_len = file.metadata()?.len();
+        if offset.checked_add(size).is_none_or(|end| end > file_len) {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "mapped range exceeds the file length",
+            ));
+        }
+
+        // SAFETY: upheld by the caller.
+        let map = unsafe { MmapOptions::new().offset(offset).len(len).map(&file)? };
+
+        Ok(Self::from_map(Arc::new(map), 0, len))
+    }
+
+    fn from_map(map: Arc<Mmap>, start: usize, len: usize) -> Self {
+        debug_assert!(
+            start + len <= map.len(),
+            "mapped view must stay within the bounds of the map"
+        );
+        Self {
+            map,
+            start,
+            len,
+            controller: Once::new(),
+        }
+    }
+
+    /// The mapped view, valid as long as no copy-on-write has occurred.
+    fn view(&self) -> &[u8] {
+        &self.map[self.start..self.start + self.len]
+    }
+
+    /// Copy the mapped view into a private, writable native allocation on first call.
+    fn init_mutable(&self) -> &dyn AllocationController {
+        &**self.controller.call_once(|| {
+            Box::new(
+                NativeAllocationController::alloc_with_data(self.view(), MAX_ALIGN)
+                    .expect("failed to allocate copy-on-write buffer for mapped bytes"),
+            ) as Box<dyn AllocationController>
+        })
+    }
+}
+
+impl AllocationController for MmapAllocationController {
+    fn alloc_align(&self) -> usize {
+        if self.controller.is_completed() {
+            return MAX_ALIGN;
+        }
+
+        // Pages are aligned, the data only as much as its offset into the first one.
+        let address = self.view().as_ptr() as usize;
+        1 << address.trailing_zeros().min(MAX_ALIGN.trailing_zeros())
+    }
+
+    fn property(&self) -> AllocationProperty {
+        AllocationProperty::File
+    }
+
+    // The view length, known without touching the pages.
+    fn capacity(&self) -> usize {
+        self.len
+    }
+
+    fn memory(&self, policy: AccessPolicy) -> Result<&[MaybeUninit<u8>], AccessError> {
+        match self.controller.get() {
+            Some(controller) => controller.memory(policy),
+            None => {
+                // Reading the map is always zero-copy, so no policy check is needed.
+                let slice = self.view();
+                // SAFETY: `&[u8]` and `&[MaybeUninit<u8>]` share a layout, and every
+                // byte of the map is initialized.
+                Ok(unsafe { core::slice::from_raw_parts(slice.as_ptr().cast(), slice.len()) })
+            }
+        }
+    }
+
+    unsafe fn memory_mut(
+        &mut self,
+        policy: AccessPolicy,
+    ) -> Result<&mut [MaybeUninit<u8>], AccessError> {
+        // The map is read-only, writing requires a private copy first.
+        if !self.controller.is_completed() && !policy.copy_allowed() {
+            return Err(AccessError::WouldCopy);
+        }
+        self.init_mutable();
+
+        // SAFETY: `init_mutable` guarantees the private controller is set, and `&mut self` is
+        // exclusive.
+        let controller = self
+            .controller
+            .get_mut()
+            .expect("controller must be set after init_mutable");
+        unsafe { controller.memory_mut(policy) }
+    }
+
+    fn split(
+        &mut self,
+        offset: usize,
+    ) -> Result<(Box<dyn AllocationController>, Box<dyn AllocationController>), SplitError> {
+        if self.controller.is_completed() {
+            // After copy-on-write the private buffer no longer matches the map.
+            return Err(SplitError::Unsupported);
+        }
+        // Use `>` (not `>=`) to allow boundary splits where one side is empty.
+        if offset > self.len {
+            return Err(SplitError::InvalidOffset);
+        }
+
+        let left = Self::from_map(self.map.clone(), self.start, offset);
+        let right = Self::from_map(self.map.clone(), self.start + offset, self.len - offset);
+
+        Ok((Box::new(left), Box::new(right)))
+    }
+
+    fn view(&self, start: usize, end: usize) -> Option<Box<dyn AllocationController>> {
+        if self.controller.is_completed() || start > end || end > self.len {
+            return None;
+        }
+
+        Some(Box::new(Self::from_map(
+            self.map.clone(),
+            self.start + start,
+            end - start,
+        )))
+    }
+
+    fn duplicate(&self) -> Option<Box<dyn AllocationController>> {
+        if self.controller.is_completed() {
+            return None;
+        }
+
+        Some(Box::new(Self::from_map(
+            self.map.clone(),
+            self.start,
+            self.len,
+        )))
+    }
+
+    unsafe fn copy_into(&self, buf: &mut [u8]) {
+        let memory = self
+            .memory(AccessPolicy::default())
+            .expect("mmap: host access never fails");
+        let copy_len = buf.len().min(memory.len());
+        // SAFETY: By construction, every byte of the memory is initialized.
+        let data = unsafe { core::slice::from_raw_parts(memory.as_ptr().cast(), copy_len) };
+        buf[..copy_len].copy_from_slice(data);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(miri))]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::super::{AllocationProperty, Bytes, Reader, SplitPolicy};
+    use std::{io::Write, path::PathBuf, vec::Vec};
+
+    #[test_log::test]
+    fn test_read_is_zero_copy() {
+        let (path, bytes, dir) = with_data((0..250).collect());
+        let mapped = unsafe { Bytes::from_file_mmap(&path, bytes.len() as u64, 0) }.unwrap();
+
+        assert!(matches!(mapped.property(), AllocationProperty::File));
+        assert_eq!(mapped.read(Reader::new().no_copy()).unwrap(), &bytes[..]);
+        core::mem::drop(dir);
+    }
+
+    #[test_log::test]
+    fn test_unaligned_offset() {
+        let elems = (0..10_000u32).map(|i| i as u8).collect();
+        let (path, bytes, dir) = with_data(elems);
+        // Past the first page, and not on a page boundary.
+        let offset = 4096 + 13;
+        let mapped = unsafe { Bytes::from_file_mmap(&path, 100, offset as u64) }.unwrap();
+
+        assert_eq!(&mapped[..], &bytes[offset..offset + 100]);
+        assert_eq!(mapped.align(), 1);
+        core::mem::drop(dir);
+    }
+
+    #[test_log::test]
+    fn test_range_past_the_end_fails() {
+        let (path, bytes, dir) = with_data((0..100).collect());
+
+        assert!(unsafe { Bytes::from_file_mmap(&path, bytes.len() as u64, 1) }.is_err());
+        core::mem::drop(dir);
+    }
+
+    #[test_log::test]
+    fn test_split_and_view_share_the_map() {
+        let (path, bytes, dir) = with_data((0..250).collect());
+        let mapped = unsafe { Bytes::from_file_mmap(&path, bytes.len() as u64, 0) }.unwrap();
+
+        let view = mapped.view(10, 20).unwrap();
+        assert_eq!(view.read(Reader::new().no_copy()).unwrap(), &bytes[10..20]);
+
+        let (left, right) = mapped.split(40, SplitPolicy::Shared).unwrap();
+        assert_eq!(left.read(Reader::new().no_copy()).unwrap(), &bytes[..40]);
+        assert_eq!(right.read(Reader::new().no_copy()).unwrap(), &bytes[40..]);
+        core::mem::drop(dir);
+    }
+
+    #[test_log::test]
+    fn test_mutation_copies_and_leaves_the_file() {
+        let (path, bytes, dir) = with_data((0..250).collect());
+        let mapped = unsafe { Bytes::from_file_mmap(&path, bytes.len() as u64, 0) }.unwrap();
+
+        let mut mutated = mapped.clone();
+        mutated[0] = 5;
+
+        assert_eq!(mutated[0], 5);
+        assert_eq!(&mutated[1..], &bytes[1..]);
+        assert_eq!(&mapped, &bytes);
+        assert_eq!(std::fs::read(&path).unwrap(), &bytes[..]);
+        core::mem::drop(dir);
+    }
+
+    fn with_data(elems: Vec<u8>) -> (PathBuf, Bytes, TempDir) {
+        let bytes = Bytes::from_bytes_vec(elems);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test");
+
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&bytes).unwrap();
+        (path, bytes, dir)
+    }
+}
@@ -1,6 +1,8 @@
 pub(crate) mod default_controller;
 #[cfg(feature = "std")]
 pub(crate) mod file;
+#[cfg(mmap)]
+mod mmap;
 #[cfg(feature = "shared-bytes")]
 mod shared;
 mod shared_arc;
@@ -94,4 +94,23 @@ impl Fence {
 
         Ok(())
     }
+
+    /// Like [`Self::wait_async`], without destroying the event, so it can be waited on again.
+    pub fn wait_async_shared(&self, stream: cubecl_hip_sys::hipStream_t) {
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, kept alive by `&self`.
+        // `stream` must be a valid HIP stream.
+        unsafe {
+            let status = cubecl_hip_sys::hipStreamWaitEvent(stream, self.event, 0);
+            assert_eq!(
+                status, HIP_SUCCESS,
+                "Should successfully wait for stream event"
+            );
+        }
+    }
+
+    /// Whether the [Fence] was reached, without blocking.
+    pub fn is_complete(&self) -> bool {
+        // SAFETY: `self.event` is a valid event created in `Fence::new`, kept alive by `&self`.
+        unsafe { cubecl_hip_sys::hipEventQuery(self.event) == HIP_SUCCESS }
+    }
 }
@@ -22,7 +22,7 @@ use cubecl_runtime::{
     compiler::CubeTask,
     config::{CubeClRuntimeConfig, RuntimeConfig},
     dry_run::LaunchMode,
-    id::GraphId,
+    id::{EventId, GraphId},
     logging::ServerLogger,
     memory_management::{
         InstallMemoryPoolsError, ManagedMemoryHandle, MemoryAllocationMode, MemoryReport,
@@ -520,6 +520,29 @@ impl ComputeServer for HipServer {
         }
     }
 
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        Ok(self.streams.record_event(stream_id))
+    }
+
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        self.streams.wait_event(event, stream_id)
+    }
+
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        self.streams.query_event(event)
+    }
+
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        match self.streams.synchronize_event(event) {
+            Ok(event) => Box::pin(async move { event.wait_sync() }),
+            Err(err) => Box::pin(async { Err(err) }),
+        }
+    }
+
+    fn event_destroy(&mut self, event: EventId) {
+        self.streams.destroy_event(event);
+    }
+
     fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
         let command = self.command_no_inputs(
             stream_id,
@@ -798,7 +821,9 @@ impl HipServer {
             return Ok(());
         }
 
-        let KernelArguments { resources, info } = bindings;
+        let KernelArguments {
+            resources, info, ..
+        } = bindings;
 
         let info_handle = info_buffer(&mut command, info.data)?;
 
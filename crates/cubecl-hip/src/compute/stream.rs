@@ -163,6 +163,14 @@ impl EventStreamBackend for HipStreamBackend {
         event.wait_sync()
     }
 
+    fn wait_event_shared(stream: &mut Self::Stream, event: &Self::Event) {
+        event.wait_async_shared(stream.sys);
+    }
+
+    fn is_event_complete(event: &Self::Event) -> bool {
+        event.is_complete()
+    }
+
     fn handle_cursor(stream: &Self::Stream, binding: &BufferBinding) -> u64 {
         // The slice cursor the sync logic compares against the origin stream's `last_synced`
         // to decide whether to wait. A freed/reallocated slice falls back to `u64::MAX`,
@@ -1,7 +1,78 @@
 use cubecl_macros_internal::{cube_op, op_traits};
+use derive_more::From;
+use derive_new::new;
+use pliron::derive::{format, pliron_attr};
 
 use crate::{CanMaterialize, dialect::ptr_value_ty, prelude::*};
 
+/// Memory ordering of an atomic operation, with the same meaning as
+/// [`core::sync::atomic::Ordering`].
+#[format]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord, Default)]
+pub enum MemoryOrdering {
+    /// The ordering the backend has always given atomics that don't specify one. Relaxed
+    /// everywhere except SPIR-V, where loads acquire, stores release and read-modify-writes do
+    /// both.
+    #[default]
+    BackendDefault,
+    Relaxed,
+    Acquire,
+    Release,
+    AcqRel,
+    SeqCst,
+}
+
+impl MemoryOrdering {
+    /// Whether the operation acquires the writes released by other units.
+    pub fn is_acquire(self) -> bool {
+        matches!(self, Self::Acquire | Self::AcqRel | Self::SeqCst)
+    }
+
+    /// Whether the operation releases the writes made before it.
+    pub fn is_release(self) -> bool {
+        matches!(self, Self::Release | Self::AcqRel | Self::SeqCst)
+    }
+
+    /// The ordering to lower with, `default` being the backend's ordering for this operation.
+    pub fn or(self, default: Self) -> Self {
+        match self {
+            Self::BackendDefault => default,
+            other => other,
+        }
+    }
+
+    /// The ordering of a failed compare exchange, which only loads and so can't release.
+    pub fn failure(self) -> Self {
+        match self {
+            Self::Release => Self::Relaxed,
+            Self::AcqRel => Self::Acquire,
+            other => other,
+        }
+    }
+}
+
+/// Set of units an atomic operation is atomic and ordered with respect to. Like
+/// [`SyncScope`](super::synchronization::SyncScope), this is a minimum, and backends without
+/// fine-grained scopes use the smallest one they support that includes it.
+#[format]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord, Default)]
+pub enum AtomicScope {
+    Plane,
+    Cube,
+    #[default]
+    Device,
+    /// Includes the host and other devices sharing the memory.
+    System,
+}
+
+#[pliron_attr(name = "atomic.ordering", format = "$0", verifier = "succ")]
+#[derive(new, From, PartialEq, Eq, Clone, Debug, Hash, PartialOrd, Ord)]
+pub struct MemoryOrderingAttr(pub MemoryOrdering);
+
+#[pliron_attr(name = "atomic.scope", format = "$0", verifier = "succ")]
+#[derive(new, From, PartialEq, Eq, Clone, Debug, Hash, PartialOrd, Ord)]
+pub struct AtomicScopeAttr(pub AtomicScope);
+
 macro_rules! atomic_binop {
     ($name: literal, $ty: ident) => {
         #[cube_op(name = $name)]
@@ -11,6 +82,8 @@ macro_rules! atomic_binop {
             #[operand(ptr_read, ptr_write)]
             pub ptr: Value,
             pub value: Value,
+            pub ordering: MemoryOrderingAttr,
+            pub scope: AtomicScopeAttr,
         }
     };
 }
@@ -36,6 +109,8 @@ atomic_binop!("atomic.xor", AtomicXorOp);
 pub struct AtomicLoadOp {
     #[operand(ptr_read)]
     pub ptr: Value,
+    pub ordering: MemoryOrderingAttr,
+    pub scope: AtomicScopeAttr,
 }
 
 #[cube_op(name = "atomic.store")]
@@ -45,6 +120,8 @@ pub struct AtomicStoreOp {
     #[operand(ptr_write)]
     pub ptr: Value,
     pub value: Value,
+    pub ordering: MemoryOrderingAttr,
+    pub scope: AtomicScopeAttr,
 }
 
 #[cube_op(name = "atomic.compare_exchange_weak")]
@@ -55,4 +132,6 @@ pub struct AtomicCompareExchangeWeakOp {
     pub ptr: Value,
     pub cmp: Value,
     pub value: Value,
+    pub ordering: MemoryOrderingAttr,
+    pub scope: AtomicScopeAttr,
 }
@@ -0,0 +1,76 @@
+//! Complex number ops. Complex values have no native representation on any target, so these are
+//! lowered to float math on interleaved `[re, im]` pairs before codegen. Addition, subtraction,
+//! negation and casts act on both parts independently and reuse the float ops.
+
+use cubecl_macros_internal::cube_op;
+use pliron::r#type::TypeHandle;
+
+use crate::{
+    CanMaterialize, Pure,
+    dialect::{pure_binop, pure_unop},
+    interfaces::{TriviallyUnrollable, TypedExt},
+    prelude::*,
+    types::{VectorType, scalar::ComplexType},
+};
+
+/// Builds a complex number from its real and imaginary parts.
+#[cube_op(name = "complex.new")]
+#[result_ty(from_inputs = complex_result_ty)]
+#[op_interfaces(SameOperandsType, TriviallyUnrollable)]
+#[op_traits(Pure, CanMaterialize)]
+pub struct ComplexNewOp {
+    pub re: Value,
+    pub im: Value,
+}
+
+#[cube_op(name = "complex.real")]
+#[result_ty(from_inputs = part_result_ty)]
+#[op_interfaces(TriviallyUnrollable)]
+#[op_traits(Pure, CanMaterialize)]
+pub struct ComplexRealOp {
+    pub input: Value,
+}
+
+#[cube_op(name = "complex.imag")]
+#[result_ty(from_inputs = part_result_ty)]
+#[op_interfaces(TriviallyUnrollable)]
+#[op_traits(Pure, CanMaterialize)]
+pub struct ComplexImagOp {
+    pub input: Value,
+}
+
+/// The magnitude of a complex number.
+#[cube_op(name = "complex.abs")]
+#[result_ty(from_inputs = part_result_ty)]
+#[op_interfaces(TriviallyUnrollable)]
+#[op_traits(Pure, CanMaterialize)]
+pub struct ComplexAbsOp {
+    pub input: Value,
+}
+
+pure_binop!("complex.mul", ComplexMulOp);
+pure_binop!("complex.div", ComplexDivOp);
+pure_unop!("complex.conj", ComplexConjOp);
+pure_unop!("complex.exp", ComplexExpOp);
+
+fn complex_result_ty(ctx: &Context, re: &Value, _: &Value) -> TypeHandle {
+    let complex = ComplexType::get(ctx, re.scalar_ty(ctx)).into();
+    with_vector_size(ctx, complex, re.vector_size(ctx))
+}
+
+fn part_result_ty(ctx: &Context, input: &Value) -> TypeHandle {
+    let complex = input.scalar_ty(ctx).deref(ctx);
+    let part = complex
+        .downcast_ref::<ComplexType>()
+        .expect("Should be complex")
+        .inner;
+    with_vector_size(ctx, part, input.vector_size(ctx))
+}
+
+fn with_vector_size(ctx: &Context, elem: TypeHandle, vectorization: usize) -> TypeHandle {
+    if vectorization == 1 {
+        elem
+    } else {
+        VectorType::get(ctx, elem, vectorization).into()
+    }
+}
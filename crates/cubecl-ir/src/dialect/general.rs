@@ -127,7 +127,8 @@ const_eval!(CastOp, {
         let val = inp?.as_const_val(ctx);
         let out_ty = self.get_result(ctx).get_type(ctx).deref(ctx);
         let elem = type_cast::<dyn ScalarType>(&*out_ty)?.elem_type(ctx);
-        Some(val.cast_to(elem).as_attribute(ctx, elem))
+        // Complex values have no constant form, they're always built from their parts
+        (!elem.is_complex()).then(|| val.cast_to(elem).as_attribute(ctx, elem))
     }
 });
 simplify!(CastOp, {
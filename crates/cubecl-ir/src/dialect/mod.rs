@@ -5,6 +5,7 @@ pub mod base;
 pub mod bitwise;
 pub mod branch;
 pub mod cmp;
+pub mod complex;
 pub mod general;
 pub mod math;
 pub mod matrix;
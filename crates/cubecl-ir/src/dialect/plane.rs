@@ -32,26 +32,43 @@ macro_rules! unary_plane_op {
     };
 }
 
+/// Reductions can optionally be restricted to clusters of `cluster_size` consecutive units, each
+/// cluster being reduced independently. The cluster size must be a power of two.
+macro_rules! reduce_plane_op {
+    ($name: literal, $ty: ident) => {
+        #[cube_op(name = $name)]
+        #[result_ty(same_as = input)]
+        #[op_interfaces(TriviallyUnrollable)]
+        #[op_traits(CanMaterialize, NoMemoryEffect)]
+        pub struct $ty {
+            pub input: Value,
+            #[attribute(optional)]
+            pub cluster_size: IndexAttr,
+        }
+        synchronizes!($ty, SyncScope::Plane);
+    };
+}
+
 unary_plane_op!("plane.all", AllOp);
 unary_plane_op!("plane.any", AnyOp);
-unary_plane_op!("plane.i_sum", ISumOp);
-unary_plane_op!("plane.f_sum", FSumOp);
+reduce_plane_op!("plane.i_sum", ISumOp);
+reduce_plane_op!("plane.f_sum", FSumOp);
 unary_plane_op!("plane.inclusive_i_sum", InclusiveISumOp);
 unary_plane_op!("plane.inclusive_f_sum", InclusiveFSumOp);
 unary_plane_op!("plane.exclusive_i_sum", ExclusiveISumOp);
 unary_plane_op!("plane.exclusive_f_sum", ExclusiveFSumOp);
-unary_plane_op!("plane.i_prod", IProdOp);
-unary_plane_op!("plane.f_prod", FProdOp);
+reduce_plane_op!("plane.i_prod", IProdOp);
+reduce_plane_op!("plane.f_prod", FProdOp);
 unary_plane_op!("plane.inclusive_i_prod", InclusiveIProdOp);
 unary_plane_op!("plane.inclusive_f_prod", InclusiveFProdOp);
 unary_plane_op!("plane.exclusive_i_prod", ExclusiveIProdOp);
 unary_plane_op!("plane.exclusive_f_prod", ExclusiveFProdOp);
-unary_plane_op!("plane.s_min", SMinOp);
-unary_plane_op!("plane.u_min", UMinOp);
-unary_plane_op!("plane.f_min", FMinOp);
-unary_plane_op!("plane.s_max", SMaxOp);
-unary_plane_op!("plane.u_max", UMaxOp);
-unary_plane_op!("plane.f_max", FMaxOp);
+reduce_plane_op!("plane.s_min", SMinOp);
+reduce_plane_op!("plane.u_min", UMinOp);
+reduce_plane_op!("plane.f_min", FMinOp);
+reduce_plane_op!("plane.s_max", SMaxOp);
+reduce_plane_op!("plane.u_max", UMaxOp);
+reduce_plane_op!("plane.f_max", FMaxOp);
 
 #[cube_op(name = "plane.ballot")]
 #[result_ty(fixed = ballot_ty(ctx))]
@@ -5,6 +5,7 @@ use alloc::{
 use cubecl_macros_internal::{cube_op, op_traits};
 use pliron::{
     attribute::AttrObj,
+    builtin::types::{IntegerType, Signedness},
     printable::Printable,
     r#type::TypeHandle,
     utils::table::{HMap, SmallSet},
@@ -498,6 +499,17 @@ pub struct FDotOp {
     pub rhs: Value,
 }
 
+/// Dot product of two `u32` words, each holding four packed `i8` lanes, accumulated in `i32`.
+/// Maps to `dp4a` style instructions where the target has them.
+#[cube_op(name = "vector.s_dot_4x8_packed")]
+#[result_ty(fixed = IntegerType::get(ctx, 32, Signedness::Signed).into())]
+#[op_interfaces(SameOperandsType)]
+#[op_traits(CanMaterialize, Pure)]
+pub struct SDot4x8PackedOp {
+    pub lhs: Value,
+    pub rhs: Value,
+}
+
 fn scalar_ty(ctx: &Context, input: &Value) -> TypeHandle {
     input.scalar_ty(ctx)
 }
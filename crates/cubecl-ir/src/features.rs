@@ -1,4 +1,4 @@
-use crate::{AddressType, ElemType, OpaqueType, SemanticType, Type};
+use crate::{AddressType, ElemType, IntKind, OpaqueType, SemanticType, Type, UIntKind};
 use alloc::collections::{BTreeMap, BTreeSet};
 
 use enumset::EnumSetType;
@@ -224,6 +224,20 @@ pub enum Tma {
 impl Features {
     /// Get the usages for a type
     pub fn type_usage(&self, ty: ElemType) -> EnumSet<TypeUsage> {
+        // Complex numbers are lowered to pairs of their part type, so they're supported wherever
+        // the part type is.
+        if let ElemType::Complex(kind) = ty {
+            return self.type_usage(kind.float_kind().into()) - TypeUsage::DotProduct;
+        }
+        // Packed int4 is stored as `u8` and unpacked with shifts, so it can only be loaded, stored
+        // and converted.
+        if matches!(
+            ty,
+            ElemType::Int(IntKind::I4x2) | ElemType::UInt(UIntKind::U4x2)
+        ) {
+            return self.type_usage(UIntKind::U8.into())
+                & (TypeUsage::Conversion | TypeUsage::Buffer);
+        }
         self.types
             .elem
             .get(&ty)
@@ -245,7 +259,16 @@ impl Features {
         match ty.into() {
             Type::Semantic(semantic_type) => self.types.semantic.contains(&semantic_type),
             Type::Opaque(opaque_type) => self.types.opaque.contains(&opaque_type),
-            ty => self.types.elem.contains_key(&ty.elem_type()),
+            ty => match ty.elem_type() {
+                ElemType::Complex(kind) => {
+                    let part = ElemType::Float(kind.float_kind());
+                    self.types.elem.contains_key(&part)
+                }
+                ElemType::Int(IntKind::I4x2) | ElemType::UInt(UIntKind::U4x2) => {
+                    self.types.elem.contains_key(&ElemType::UInt(UIntKind::U8))
+                }
+                elem => self.types.elem.contains_key(&elem),
+            },
         }
     }
 
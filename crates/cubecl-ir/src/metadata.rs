@@ -17,6 +17,7 @@ const STRIDE_OFFSETS: usize = 1;
 pub const METADATA_EXT_LEN: usize = 2;
 
 /// Helper to calculate metadata offsets based on buffer count and position
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Default, Hash)]
 pub struct Metadata {
     num_meta: usize,
@@ -69,6 +70,7 @@ impl Metadata {
 }
 
 /// Helper to calculate info struct fields
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Default, Hash)]
 pub struct Info {
     pub scalars: Vec<SizedInfoField>,
@@ -86,6 +88,7 @@ impl Deref for Info {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Hash)]
 pub struct SizedInfoField {
     pub ty: ElemType,
@@ -19,6 +19,7 @@ use pliron::{
         type_interfaces::FunctionTypeInterface,
         types::{FunctionType, UnitType},
     },
+    combine::Parser,
     context::{AuxDataIndex, Context},
     debug_info::set_operation_result_name,
     dict_key,
@@ -27,8 +28,11 @@ use pliron::{
         inserter::{IRInserter, Inserter},
         listener::DummyListener,
     },
+    irfmt::parsers::spaced,
+    location,
     op::Op,
     operation::Operation,
+    parsable::{self, state_stream_from_iterator},
     printable::Printable,
     r#type::{TypeHandle, Typed, type_cast},
     value::Value,
@@ -128,6 +132,12 @@ pub struct ExpandState {
     pub inv_return_flag: Option<Value>,
     /// Whether the loop is *not* broken. Inverted to save a not on the loop condition.
     pub inv_break_flag: Option<Value>,
+    /// Whether the current loop iteration may have been continued, so what follows the `continue`
+    /// is predicated on `inv_continue_flag`, and the flag has to be reset when the iteration ends.
+    pub may_continue: bool,
+    /// Whether the current loop iteration has *not* been continued. Reset at the end of each
+    /// iteration.
+    pub inv_continue_flag: Option<Value>,
 }
 
 impl Debug for Scope {
@@ -450,10 +460,8 @@ impl Scope {
             ctx: CtxHandle::Rc(ctx),
             inserter: InserterHandle::owned(inserter),
             expand_state: RefCell::new(ExpandState {
-                may_break: false,
-                may_return: false,
                 inv_return_flag: Some(return_flag),
-                inv_break_flag: None,
+                ..Default::default()
             }),
         }
     }
@@ -475,6 +483,59 @@ impl Scope {
         }
     }
 
+    /// Rebuild the root scope of a kernel from its module, as printed by the [`Display`]
+    /// implementation of the scope.
+    ///
+    /// Only the IR is restored: the expansion state (registered types, instruction modes) isn't
+    /// part of the printed module, so the scope can be compiled but shouldn't be expanded into.
+    pub fn parse(settings: KernelSettings, module: &str) -> Result<Self, String> {
+        let mut ctx = Context::default();
+        ctx.set_address_type(settings.address_type);
+
+        let state_stream = state_stream_from_iterator(
+            module.chars(),
+            parsable::State::new(&mut ctx, location::Source::InMemory),
+        );
+        let operation = spaced(Operation::top_level_parser())
+            .parse(state_stream)
+            .map_err(|err| format!("Invalid kernel module: {err}"))?
+            .0;
+
+        let module = operation
+            .as_op::<ModuleOp>(&ctx)
+            .ok_or_else(|| String::from("The kernel IR isn't a module"))?;
+        let module_block = module.get_body(&ctx, 0);
+        let entry_func = module_block
+            .deref(&ctx)
+            .iter(&ctx)
+            .filter_map(|op| op.as_op::<FuncOp>(&ctx))
+            .find(|func| func.get_entrypoint_abi(&ctx).is_some())
+            .ok_or_else(|| String::from("The kernel module has no entry point"))?;
+        let entry_block = entry_func.get_entry_block(&ctx);
+
+        let mut state = GlobalState {
+            reference_arena: Default::default(),
+            module,
+            module_inserter: OpInserter::new_at_block_end(module_block),
+            entry_func,
+            ident_unique_id: Default::default(),
+            typemap: Default::default(),
+            sizemap: Default::default(),
+            modes: Default::default(),
+            target_properties: Default::default(),
+            device_properties: Default::default(),
+            errors: Default::default(),
+        };
+        settings.address_type.register(&mut state);
+        ctx.set_aux_ty(state);
+
+        Ok(Self {
+            ctx: CtxHandle::Rc(Rc::new(UnsafeCell::new(ctx))),
+            inserter: InserterHandle::owned(OpInserter::new_at_block_end(entry_block)),
+            expand_state: Default::default(),
+        })
+    }
+
     /// Create a rewrite scope from an existing context and inserter/rewriter
     pub fn from_context_and_inserter(
         ctx: &mut Context,
@@ -484,12 +545,7 @@ impl Scope {
         Self {
             ctx: CtxHandle::Ref(ctx),
             inserter: InserterHandle::Ref(inserter),
-            expand_state: RefCell::new(ExpandState {
-                may_return: false,
-                may_break: false,
-                inv_return_flag: None,
-                inv_break_flag: None,
-            }),
+            expand_state: RefCell::new(ExpandState::default()),
         }
     }
 
@@ -564,15 +620,39 @@ impl Scope {
 
     pub fn set_break_return(&self, children: &[Scope]) {
         self.set_may_break(children);
+        self.set_may_continue(children);
         self.set_may_return(children);
     }
 
     pub fn set_may_return(&self, children: &[Scope]) {
         let child_may_return = children.iter().any(|scope| scope.expand_state().may_return);
         if child_may_return {
-            self.expand_state_mut().may_return = true;
-            let flag = self.expand_state().inv_return_flag;
-            self.predicate_on_flag(flag.expect("Can't return in rewrite context"));
+            self.predicate_on_return();
+        }
+    }
+
+    /// Predicate everything registered from now on on not having returned.
+    pub fn predicate_on_return(&self) {
+        self.expand_state_mut().may_return = true;
+        let flag = self.expand_state().inv_return_flag;
+        self.predicate_on_flag(flag.expect("Can't return in rewrite context"));
+    }
+
+    /// Predicate everything registered from now on on the loop not being broken.
+    pub fn predicate_on_break(&self) {
+        self.expand_state_mut().may_break = true;
+        let flag = self.expand_state().inv_break_flag;
+        self.predicate_on_flag(flag.expect("Should have break flag"));
+    }
+
+    pub fn set_may_continue(&self, children: &[Scope]) {
+        let child_may_continue = children
+            .iter()
+            .any(|scope| scope.expand_state().may_continue);
+        if child_may_continue {
+            self.expand_state_mut().may_continue = true;
+            let flag = self.expand_state().inv_continue_flag;
+            self.predicate_on_flag(flag.expect("Should have continue flag"));
         }
     }
 
@@ -668,25 +748,26 @@ impl Scope {
             ctx: self.ctx.clone(),
             inserter: InserterHandle::owned(inserter),
             expand_state: RefCell::new(ExpandState {
-                may_break: false,
-                may_return: false,
                 inv_return_flag: self.expand_state().inv_return_flag,
                 inv_break_flag: self.expand_state().inv_break_flag,
+                inv_continue_flag: self.expand_state().inv_continue_flag,
+                ..Default::default()
             }),
         }
     }
 
-    /// Create a child scope with a new break condition.
+    /// Create a child scope with a new break and continue condition.
     pub fn loop_child(&self, inserter: impl Inserter + 'static) -> Self {
         let break_flag = init_bool_flag(self.ctx_mut(), self.inserter(), "inv_break_flag");
+        let continue_flag = init_bool_flag(self.ctx_mut(), self.inserter(), "inv_continue_flag");
         Self {
             ctx: self.ctx.clone(),
             inserter: InserterHandle::owned(inserter),
             expand_state: RefCell::new(ExpandState {
-                may_return: false,
-                may_break: false,
                 inv_return_flag: self.expand_state().inv_return_flag,
                 inv_break_flag: Some(break_flag),
+                inv_continue_flag: Some(continue_flag),
+                ..Default::default()
             }),
         }
     }
@@ -698,14 +779,24 @@ impl Scope {
             ctx: self.ctx.clone(),
             inserter: InserterHandle::owned(inserter),
             expand_state: RefCell::new(ExpandState {
-                may_break: false,
-                may_return: false,
                 inv_return_flag: Some(return_flag),
-                inv_break_flag: None,
+                ..Default::default()
             }),
         }
     }
 
+    /// Create a new return flag at the insertion point, for an inlined function that returns
+    /// early. See [`ExpandState::inv_return_flag`].
+    pub fn create_return_flag(&self) -> Value {
+        init_bool_flag(self.ctx_mut(), self.inserter(), "inv_return_flag")
+    }
+
+    /// Create a new continue flag at the insertion point, for an iteration of an unrolled loop.
+    /// See [`ExpandState::inv_continue_flag`].
+    pub fn create_continue_flag(&self) -> Value {
+        init_bool_flag(self.ctx_mut(), self.inserter(), "inv_continue_flag")
+    }
+
     // Adds a validation error.
     pub fn push_error(&self, msg: impl Into<String>) {
         self.state_mut().errors.push(msg.into());
@@ -5,9 +5,9 @@ use crate::{
 };
 use core::fmt::Display;
 use cubecl_common::{
-    e2m1, e2m1x2, e2m3, e3m2, e4m3, e5m2, flex32,
+    c32, c64, e2m1, e2m1x2, e2m3, e3m2, e4m3, e5m2, flex32, i4x2,
     quant::scheme::{QuantValue, ScaleDtype},
-    tf32, ue8m0,
+    tf32, u4x2, ue8m0,
 };
 use derive_more::{Display, From};
 use half::{bf16, f16};
@@ -72,6 +72,8 @@ impl FloatKind {
 #[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[allow(missing_docs)]
 pub enum IntKind {
+    /// Two 4-bit signed integers packed into a byte
+    I4x2,
     I8,
     I16,
     I32,
@@ -80,12 +82,15 @@ pub enum IntKind {
 
 impl IntKind {
     pub fn to_type(&self, ctx: &Context) -> TypeHandle {
-        IntegerType::get(ctx, self.size_bits() as u32, Signedness::Signed).into()
+        match self {
+            IntKind::I4x2 => Int4x2Type::get(ctx).into(),
+            _ => IntegerType::get(ctx, self.size_bits() as u32, Signedness::Signed).into(),
+        }
     }
 
     pub fn size_bits(&self) -> usize {
         match self {
-            IntKind::I8 => 8,
+            IntKind::I4x2 | IntKind::I8 => 8,
             IntKind::I16 => 16,
             IntKind::I32 => 32,
             IntKind::I64 => 64,
@@ -97,6 +102,8 @@ impl IntKind {
 #[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[allow(missing_docs)]
 pub enum UIntKind {
+    /// Two 4-bit unsigned integers packed into a byte
+    U4x2,
     U8,
     U16,
     U32,
@@ -105,12 +112,15 @@ pub enum UIntKind {
 
 impl UIntKind {
     pub fn to_type(&self, ctx: &Context) -> TypeHandle {
-        IntegerType::get(ctx, self.size_bits() as u32, Signedness::Unsigned).into()
+        match self {
+            UIntKind::U4x2 => UInt4x2Type::get(ctx).into(),
+            _ => IntegerType::get(ctx, self.size_bits() as u32, Signedness::Unsigned).into(),
+        }
     }
 
     pub fn size_bits(&self) -> usize {
         match self {
-            UIntKind::U8 => 8,
+            UIntKind::U4x2 | UIntKind::U8 => 8,
             UIntKind::U16 => 16,
             UIntKind::U32 => 32,
             UIntKind::U64 => 64,
@@ -118,6 +128,29 @@ impl UIntKind {
     }
 }
 
+/// Complex numbers, stored as an interleaved pair of floats
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(missing_docs)]
+pub enum ComplexKind {
+    C32,
+    C64,
+}
+
+impl ComplexKind {
+    pub fn to_type(&self, ctx: &Context) -> TypeHandle {
+        ComplexType::get(ctx, self.float_kind().to_type(ctx)).into()
+    }
+
+    /// The kind of the real and imaginary parts
+    pub fn float_kind(&self) -> FloatKind {
+        match self {
+            ComplexKind::C32 => FloatKind::F32,
+            ComplexKind::C64 => FloatKind::F64,
+        }
+    }
+}
+
 /// Conceptual element type, not necessarily the physical type used in the code
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, PartialOrd, Ord, From)]
@@ -127,6 +160,7 @@ pub enum ElemType {
     Float(FloatKind),
     Int(IntKind),
     UInt(UIntKind),
+    Complex(ComplexKind),
     Bool,
 }
 
@@ -163,6 +197,7 @@ impl ElemType {
             QuantValue::E4M3 => Self::Float(FloatKind::E4M3),
             QuantValue::E2M1 => Self::Float(FloatKind::E2M1),
             QuantValue::Q8F | QuantValue::Q8S => Self::Int(IntKind::I8),
+            QuantValue::Q4F | QuantValue::Q4S => Self::Int(IntKind::I4x2),
             other => panic!("Unsupported quant value {other:?}"),
         }
     }
@@ -173,6 +208,7 @@ impl ElemType {
             ElemType::Float(float_kind) => float_kind.to_type(ctx),
             ElemType::Int(int_kind) => int_kind.to_type(ctx),
             ElemType::UInt(uint_kind) => uint_kind.to_type(ctx),
+            ElemType::Complex(complex_kind) => complex_kind.to_type(ctx),
             ElemType::Bool => BoolType::get(ctx).into(),
         }
     }
@@ -220,17 +256,23 @@ impl ElemType {
                 FloatKind::TF32 => core::mem::size_of::<f32>(),
             },
             ElemType::Int(kind) => match kind {
+                IntKind::I4x2 => core::mem::size_of::<i4x2>(),
                 IntKind::I8 => core::mem::size_of::<i8>(),
                 IntKind::I16 => core::mem::size_of::<i16>(),
                 IntKind::I32 => core::mem::size_of::<i32>(),
                 IntKind::I64 => core::mem::size_of::<i64>(),
             },
             ElemType::UInt(kind) => match kind {
+                UIntKind::U4x2 => core::mem::size_of::<u4x2>(),
                 UIntKind::U8 => core::mem::size_of::<u8>(),
                 UIntKind::U16 => core::mem::size_of::<u16>(),
                 UIntKind::U32 => core::mem::size_of::<u32>(),
                 UIntKind::U64 => core::mem::size_of::<u64>(),
             },
+            ElemType::Complex(kind) => match kind {
+                ComplexKind::C32 => core::mem::size_of::<c32>(),
+                ComplexKind::C64 => core::mem::size_of::<c64>(),
+            },
             ElemType::Bool => core::mem::size_of::<bool>(),
         }
     }
@@ -254,7 +296,9 @@ impl ElemType {
                 | FloatKind::TF32 => self.size() * 8,
                 FloatKind::E2M1 => 4,
             },
-            ElemType::Int(_) | ElemType::UInt(_) | ElemType::Bool => self.size() * 8,
+            ElemType::Int(_) | ElemType::UInt(_) | ElemType::Complex(_) | ElemType::Bool => {
+                self.size() * 8
+            }
         }
     }
 
@@ -285,6 +329,10 @@ impl ElemType {
         matches!(self, ElemType::Bool)
     }
 
+    pub fn is_complex(&self) -> bool {
+        matches!(self, ElemType::Complex(_))
+    }
+
     pub fn as_float(&self) -> Option<FloatKind> {
         match self {
             ElemType::Float(kind) => Some(*kind),
@@ -313,6 +361,7 @@ impl ElemType {
             }
             .into(),
             ElemType::Int(kind) => match kind {
+                IntKind::I4x2 => i4x2::MAX as i64,
                 IntKind::I8 => i8::MAX as i64,
                 IntKind::I16 => i16::MAX as i64,
                 IntKind::I32 => i32::MAX as i64,
@@ -320,12 +369,14 @@ impl ElemType {
             }
             .into(),
             ElemType::UInt(kind) => match kind {
+                UIntKind::U4x2 => u4x2::MAX as u64,
                 UIntKind::U8 => u8::MAX as u64,
                 UIntKind::U16 => u16::MAX as u64,
                 UIntKind::U32 => u32::MAX as u64,
                 UIntKind::U64 => u64::MAX,
             }
             .into(),
+            ElemType::Complex(_) => panic!("Complex numbers aren't ordered"),
             ElemType::Bool => true.into(),
         };
 
@@ -350,6 +401,7 @@ impl ElemType {
             }
             .into(),
             ElemType::Int(kind) => match kind {
+                IntKind::I4x2 => i4x2::MIN as i64,
                 IntKind::I8 => i8::MIN as i64,
                 IntKind::I16 => i16::MIN as i64,
                 IntKind::I32 => i32::MIN as i64,
@@ -357,12 +409,14 @@ impl ElemType {
             }
             .into(),
             ElemType::UInt(kind) => match kind {
+                UIntKind::U4x2 => u4x2::MIN as u64,
                 UIntKind::U8 => u8::MIN as u64,
                 UIntKind::U16 => u16::MIN as u64,
                 UIntKind::U32 => u32::MIN as u64,
                 UIntKind::U64 => u64::MIN,
             }
             .into(),
+            ElemType::Complex(_) => panic!("Complex numbers aren't ordered"),
             ElemType::Bool => false.into(),
         };
 
@@ -384,6 +438,7 @@ impl ElemType {
                 FloatKind::Flex32 | FloatKind::F32 | FloatKind::TF32 => f32::EPSILON.into(),
                 FloatKind::F64 => f64::EPSILON,
             },
+            ElemType::Complex(kind) => ElemType::Float(kind.float_kind()).epsilon(),
             ElemType::Index | ElemType::Int(_) | ElemType::UInt(_) => 1.0, // step of 1
             ElemType::Bool => 1.0,
         }
@@ -553,17 +608,23 @@ impl Display for ElemType {
                 FloatKind::F64 => f.write_str("f64"),
             },
             Self::Int(kind) => match kind {
+                IntKind::I4x2 => f.write_str("i4x2"),
                 IntKind::I8 => f.write_str("i8"),
                 IntKind::I16 => f.write_str("i16"),
                 IntKind::I32 => f.write_str("i32"),
                 IntKind::I64 => f.write_str("i64"),
             },
             Self::UInt(kind) => match kind {
+                UIntKind::U4x2 => f.write_str("u4x2"),
                 UIntKind::U8 => f.write_str("u8"),
                 UIntKind::U16 => f.write_str("u16"),
                 UIntKind::U32 => f.write_str("u32"),
                 UIntKind::U64 => f.write_str("u64"),
             },
+            Self::Complex(kind) => match kind {
+                ComplexKind::C32 => f.write_str("c32"),
+                ComplexKind::C64 => f.write_str("c64"),
+            },
             Self::Bool => f.write_str("bool"),
         }
     }
@@ -666,6 +727,18 @@ impl From<e2m1x2> for ExpandValue {
     }
 }
 
+impl From<i4x2> for ExpandValue {
+    fn from(_value: i4x2) -> Self {
+        unimplemented!("Can't currently construct i4x2")
+    }
+}
+
+impl From<u4x2> for ExpandValue {
+    fn from(_value: u4x2) -> Self {
+        unimplemented!("Can't currently construct u4x2")
+    }
+}
+
 impl From<e2m3> for ExpandValue {
     fn from(_value: e2m3) -> Self {
         unimplemented!("Can't currently construct fp6")
@@ -678,6 +751,18 @@ impl From<e3m2> for ExpandValue {
     }
 }
 
+impl From<c32> for ExpandValue {
+    fn from(_value: c32) -> Self {
+        unimplemented!("Complex constants must be constructed from their parts")
+    }
+}
+
+impl From<c64> for ExpandValue {
+    fn from(_value: c64) -> Self {
+        unimplemented!("Complex constants must be constructed from their parts")
+    }
+}
+
 impl From<i8> for ConstantValue {
     fn from(value: i8) -> Self {
         ConstantValue::Int(value as i64)
@@ -10,6 +10,7 @@ use pliron::{
     },
     context::Context,
     derive::{pliron_type, type_interface_impl},
+    r#type::TypeHandle,
     parsable::{IntoParseResult, ParseResult, StateStream},
     printable,
     utils::apfloat::{self, GetSemantics, Semantics, float_parse, single_to_f32},
@@ -17,9 +18,9 @@ use pliron::{
 use rustc_apfloat::ieee::{self, IeeeFloat, NonfiniteBehavior};
 
 use crate::{
-    ContextExt, ElemType, FloatKind, IntKind, UIntKind, aligned,
+    ComplexKind, ContextExt, ElemType, FloatKind, IntKind, UIntKind, aligned,
     apfloat::{APFloat, APFloatType, apfloat_type},
-    interfaces::{AlignedType, MaybePackedType, ScalarType, SizedType, not_packed},
+    interfaces::{AlignedType, MaybePackedType, ScalarType, SizedType, TypedExt, not_packed},
     scalar, sized,
 };
 
@@ -323,6 +324,34 @@ impl ScalarType for Float4E2M1x2Type {
     }
 }
 
+macro_rules! int4x2_type {
+    ($name: literal, $ty: ident, $kind: expr) => {
+        #[pliron_type(name = $name, format = "", generate_get = true, verifier = "succ")]
+        #[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+        pub struct $ty;
+        scalar!($ty);
+        aligned!($ty, 1);
+        sized!($ty, 1);
+
+        #[type_interface_impl]
+        impl MaybePackedType for $ty {
+            fn packing_factor(&self, _ctx: &Context) -> usize {
+                2
+            }
+        }
+
+        #[type_interface_impl]
+        impl ScalarType for $ty {
+            fn elem_type(&self, _ctx: &Context) -> ElemType {
+                $kind.into()
+            }
+        }
+    };
+}
+
+int4x2_type!("cube.i4x2", Int4x2Type, IntKind::I4x2);
+int4x2_type!("cube.u4x2", UInt4x2Type, UIntKind::U4x2);
+
 #[pliron_type(
     name = "cube.bool",
     format = "",
@@ -341,3 +370,46 @@ impl ScalarType for BoolType {
         ElemType::Bool
     }
 }
+
+/// A complex number, stored as an interleaved pair of `inner`. Lowered to a pair of floats before
+/// reaching any backend.
+#[pliron_type(
+    name = "cube.complex",
+    format = "`<` $inner `>`",
+    generate_get = true,
+    verifier = "succ"
+)]
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ComplexType {
+    pub inner: TypeHandle,
+}
+scalar!(ComplexType);
+not_packed!(ComplexType);
+
+#[type_interface_impl]
+impl AlignedType for ComplexType {
+    fn align(&self, ctx: &Context) -> usize {
+        self.inner.align(ctx) * 2
+    }
+}
+
+#[type_interface_impl]
+impl SizedType for ComplexType {
+    fn size(&self, ctx: &Context) -> usize {
+        self.inner.size(ctx) * 2
+    }
+}
+
+#[type_interface_impl]
+impl ScalarType for ComplexType {
+    fn elem_type(&self, ctx: &Context) -> ElemType {
+        let inner = self.inner.deref(ctx);
+        if inner.is::<Float32Type>() {
+            ComplexKind::C32.into()
+        } else if inner.is::<Float64Type>() {
+            ComplexKind::C64.into()
+        } else {
+            unreachable!("Complex parts must be `f32` or `f64`")
+        }
+    }
+}
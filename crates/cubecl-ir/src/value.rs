@@ -353,6 +353,8 @@ impl ConstantValue {
             }
             .into(),
             ElemType::Int(kind) => match kind {
+                // Packed constants hold a single element, splatted on use
+                IntKind::I4x2 => ((self.as_i64() as i8) << 4 >> 4) as i64,
                 IntKind::I8 => self.as_i64() as i8 as i64,
                 IntKind::I16 => self.as_i64() as i16 as i64,
                 IntKind::I32 => self.as_i64() as i32 as i64,
@@ -360,12 +362,16 @@ impl ConstantValue {
             }
             .into(),
             ElemType::UInt(kind) => match kind {
+                UIntKind::U4x2 => self.as_u64() & 0xF,
                 UIntKind::U8 => self.as_u64() as u8 as u64,
                 UIntKind::U16 => self.as_u64() as u16 as u64,
                 UIntKind::U32 => self.as_u64() as u32 as u64,
                 UIntKind::U64 => self.as_u64(),
             }
             .into(),
+            ElemType::Complex(_) => {
+                unimplemented!("Complex constants must be constructed from their parts")
+            }
             ElemType::Bool => self.as_bool().into(),
         }
     }
@@ -88,7 +88,15 @@ pub enum Expression {
     },
     Asm(AsmExpression),
     Continue(Span),
-    Return(Span),
+    Return {
+        expr: Option<Box<Expression>>,
+        span: Span,
+    },
+    /// `?` on a runtime `Option` or `RuntimeResult`
+    Try {
+        expr: Box<Expression>,
+        span: Span,
+    },
     ForLoop {
         range: Box<Expression>,
         unroll: Option<Box<Expression>>,
@@ -294,19 +302,36 @@ impl Expression {
         }
     }
 
+    /// Whether control flow never continues past this expression.
+    pub fn diverges(&self) -> bool {
+        matches!(
+            self,
+            Expression::Break | Expression::Continue(_) | Expression::Return { .. }
+        )
+    }
+
     pub fn needs_terminator(&self) -> bool {
         match self {
-            Expression::If { then_block, .. } => then_block.ret.is_some(),
-            Expression::Block(block) => block.ret.is_some(),
+            Expression::If { then_block, .. } => then_block.has_value(),
+            Expression::Block(block) => block.has_value(),
             Expression::ForLoop { .. } => false,
             Expression::WhileLoop { .. } => false,
             Expression::Loop { .. } => false,
             Expression::VerbatimTerminated { .. } => false,
+            expr if expr.diverges() => false,
             _ => true,
         }
     }
 }
 
+impl Block {
+    /// Whether the block evaluates to a value, rather than to nothing or by leaving the function
+    /// or loop.
+    pub fn has_value(&self) -> bool {
+        self.ret.as_ref().is_some_and(|ret| !ret.diverges())
+    }
+}
+
 pub fn is_intrinsic(path: &Path) -> bool {
     // Add both possible import paths
     let intrinsic_paths = [
@@ -1,8 +1,8 @@
 use core::iter;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote};
-use syn::Ident;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Ident, Index, Type};
 
 use crate::{
     parse::cube_type::{CubeTypeEnum, CubeTypeVariant, VariantKind},
@@ -11,7 +11,7 @@ use crate::{
 
 impl CubeTypeEnum {
     pub fn generate_runtime(&self, with_launch: bool) -> TokenStream {
-        if let Err(err) = self.validate() {
+        if let Err(err) = self.validate(with_launch) {
             return err.into_compile_error();
         }
 
@@ -42,27 +42,24 @@ impl CubeTypeEnum {
         }
     }
 
-    fn validate(&self) -> Result<(), syn::Error> {
-        let types = self
-            .variants
-            .iter()
-            .filter_map(|v| match v.kind {
-                VariantKind::Named => Some(Err(syn::Error::new(
+    fn validate(&self, with_launch: bool) -> Result<(), syn::Error> {
+        for v in self.variants.iter() {
+            match v.kind {
+                VariantKind::Named => Err(syn::Error::new(
                     self.ident.span(),
                     "Named enum fields are not supported for runtime enums",
-                ))),
-                VariantKind::Unnamed if v.fields.len() > 1 => Some(Err(syn::Error::new(
+                ))?,
+                VariantKind::Unnamed if v.fields.len() > 1 => Err(syn::Error::new(
                     self.ident.span(),
                     "Only single value is supported for runtime enums",
-                ))),
-                VariantKind::Unnamed => Some(Ok(v.fields.iter().next().unwrap().ty.clone())),
-                VariantKind::Empty => None,
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        if types.len() > 1 {
+                ))?,
+                _ => {}
+            }
+        }
+        if with_launch && self.value_tys().len() > 1 {
             Err(syn::Error::new(
                 self.ident.span(),
-                "Only one value type is allowed for runtime enums",
+                "Only one value type is allowed for launchable runtime enums",
             ))
         } else {
             Ok(())
@@ -104,6 +101,8 @@ impl CubeTypeEnum {
         let name_expand = &self.name_expand;
         let (generics, generic_names, where_clause) = self.generics.split_for_impl();
 
+        let variant_values = self.variant_values_impl();
+        let value_tys = self.value_tys();
         let constructors = if self.with_constructors {
             let new_variant_functions = self.variants.iter().map(|v| {
                 v.new_variant_function_runtime(
@@ -111,6 +110,7 @@ impl CubeTypeEnum {
                     name_expand,
                     &generic_names,
                     self.value_ty(),
+                    &value_tys,
                 )
             });
 
@@ -168,19 +168,72 @@ impl CubeTypeEnum {
             }
 
             #constructors
+
+            #variant_values
+        }
+    }
+
+    /// The distinct value types of the variants, in declaration order. Variants sharing a type
+    /// share the same runtime value.
+    fn value_tys(&self) -> Vec<Type> {
+        let mut tys: Vec<Type> = Vec::new();
+        for v in self.variants.iter() {
+            if let VariantKind::Unnamed = v.kind {
+                let ty = v.fields.iter().next().unwrap().ty.clone();
+                if !tys.iter().any(|it| same_ty(it, &ty)) {
+                    tys.push(ty);
+                }
+            }
         }
+        tys
     }
 
     fn value_ty(&self) -> TokenStream {
-        self.variants
-            .iter()
-            .find_map(|v| match v.kind {
-                VariantKind::Named => unimplemented!(),
-                VariantKind::Unnamed => Some(v.fields.iter().next().unwrap().ty.clone()),
-                VariantKind::Empty => None,
+        match self.value_tys().as_slice() {
+            [] => quote![()],
+            [ty] => quote![#ty],
+            tys => quote![(#(#tys,)*)],
+        }
+    }
+
+    /// Generates `__variant_value_{Variant}`, returning the projection from the runtime value to
+    /// the value of that variant. Used to bind the value in runtime `match` arms.
+    fn variant_values_impl(&self) -> TokenStream {
+        let cube_type = prelude_type("CubeType");
+        let cube_enum = prelude_type("CubeEnum");
+
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+
+        let value_tys = self.value_tys();
+        let functions = self.variants.iter().filter_map(|v| {
+            let VariantKind::Unnamed = v.kind else {
+                return None;
+            };
+            let ty = &v.fields.iter().next().unwrap().ty;
+            let function = format_ident!("__variant_value_{}", v.ident);
+            let project = match value_tys.len() {
+                1 => quote![|value| value],
+                _ => {
+                    let index = Index::from(v.value_index(&value_tys));
+                    quote![|value| value.#index]
+                }
+            };
+
+            Some(quote! {
+                #[doc(hidden)]
+                pub fn #function(&self) -> fn(<Self as #cube_enum>::RuntimeValue) -> <#ty as #cube_type>::ExpandType {
+                    #project
+                }
             })
-            .map(|ty| quote![#ty])
-            .unwrap_or_else(|| quote![()])
+        });
+
+        quote! {
+            #[allow(non_snake_case)]
+            impl #generics #name_expand #generic_names #where_clause {
+                #(#functions)*
+            }
+        }
     }
 
     fn cube_type_impl_runtime(&self) -> proc_macro2::TokenStream {
@@ -492,6 +545,7 @@ impl CubeTypeVariant {
         ident_ty_expand: &Ident,
         generics: &syn::TypeGenerics,
         value_ty: TokenStream,
+        value_tys: &[Type],
     ) -> TokenStream {
         let scope = prelude_type("Scope");
         let cube_type = prelude_type("CubeType");
@@ -506,16 +560,27 @@ impl CubeTypeVariant {
             }
             VariantKind::Unnamed => {
                 let ty = self.fields.iter().next().unwrap().ty.clone();
+                let (scope_arg, value) = match value_tys.len() {
+                    1 => (quote![_], quote![value]),
+                    _ => {
+                        let own = self.value_index(value_tys);
+                        let values = value_tys.iter().enumerate().map(|(i, ty)| match i == own {
+                            true => quote![value],
+                            false => quote![<#ty as #into_runtime>::__expand_runtime_method(Default::default(), scope)],
+                        });
+                        (quote![scope], quote![(#(#values,)*)])
+                    }
+                };
 
                 quote! {
                     pub fn #base_function(value: #ty) -> Self {
                         cubecl::unexpanded!()
                     }
 
-                    pub fn #expand_function(_: &#scope, value: <#ty as #cube_type>::ExpandType) -> #ident_ty_expand #generics {
+                    pub fn #expand_function(#scope_arg: &#scope, value: <#ty as #cube_type>::ExpandType) -> #ident_ty_expand #generics {
                         #ident_ty_expand #generics {
                             discriminant: #index.into(),
-                            value
+                            value: #value
                         }
                     }
                 }
@@ -536,4 +601,14 @@ impl CubeTypeVariant {
             }
         }
     }
+
+    /// The index of this variant's value in the runtime value tuple.
+    fn value_index(&self, value_tys: &[Type]) -> usize {
+        let ty = &self.fields.iter().next().unwrap().ty;
+        value_tys.iter().position(|it| same_ty(it, ty)).unwrap()
+    }
+}
+
+fn same_ty(a: &Type, b: &Type) -> bool {
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
 }
@@ -273,11 +273,44 @@ impl Expression {
                     return;
                 }
             }
-            Expression::Continue(span) => error!(*span, "Continue not supported yet"),
-            Expression::Return(span) => error!(
-                *span,
-                "Return not supported yet. Consider using the terminate!() macro instead."
-            ),
+            Expression::Continue(_) => {
+                let path = frontend_path();
+                // Like break, continue terminates the current closure scope
+                quote! {
+                    #path::branch::continue_expand(scope);
+                    return;
+                }
+            }
+            Expression::Return { expr, span } => {
+                if !context.returns_early {
+                    return error!(*span, "`return` isn't supported here");
+                }
+                let value = expr
+                    .as_ref()
+                    .map(|expr| into_expand(expr.to_tokens(context)))
+                    .unwrap_or_else(|| quote![()]);
+                quote! {
+                    __fn_return.ret(scope, #value);
+                    return;
+                }
+            }
+            Expression::Try { expr, span } => {
+                if !context.returns_early {
+                    return error!(*span, "`?` isn't supported here");
+                }
+                let path = frontend_path();
+                let expr = into_expand(expr.to_tokens(context));
+                quote_spanned! {*span=>
+                    {
+                        let (__try_returns, __try_output, __try_residual) =
+                            #path::TryExpand::__expand_branch_method(#expr, scope);
+                        #path::branch::if_expand(scope, __try_returns, |scope| {
+                            __fn_return.ret_residual(scope, __try_residual);
+                        });
+                        __try_output
+                    }
+                }
+            }
             Expression::Cast { from, to } => {
                 let cast = prelude_type("Cast");
                 let from = into_expand(from.to_tokens(context));
@@ -340,7 +373,7 @@ impl Expression {
                 condition,
                 then_block,
                 else_branch: Some(else_branch),
-            } if then_block.ret.is_some() && else_branch.needs_terminator() => {
+            } if then_block.has_value() && else_branch.needs_terminator() => {
                 let path = frontend_path();
                 let condition = into_expand(condition.to_tokens(context));
                 let then_block = then_block.to_tokens(context);
@@ -594,8 +627,9 @@ impl Expression {
                         let discriminant = format_ident!("_disc_{i}");
                         let block = match inner_pat(pat) {
                             Some(inner) => {
+                                let project = format_ident!("_value_{i}");
                                 quote! {{
-                                    let #inner = value;
+                                    let #inner = #project(value);
                                     #block
                                 }}
                             }
@@ -609,7 +643,15 @@ impl Expression {
                     let discriminants = arms.iter().enumerate().map(|(i, (pat, _))| {
                         let name = variant_name(pat).expect("Already checked");
                         let ident = format_ident!("_disc_{i}");
-                        quote![let #ident = #expr.discriminant_of_value(#name);]
+                        let project = inner_pat(pat).map(|_| {
+                            let project = format_ident!("_value_{i}");
+                            let variant_value = format_ident!("__variant_value_{name}");
+                            quote![let #project = #expr.#variant_value();]
+                        });
+                        quote! {
+                            let #ident = #expr.discriminant_of_value(#name);
+                            #project
+                        }
                     });
 
                     // Needed so type inference can actually work
@@ -670,8 +712,9 @@ impl Expression {
 
                     let block = match inner_pat(&pat) {
                         Some(inner) => {
+                            let variant_value = format_ident!("__variant_value_{name}");
                             quote! {{
-                                let #inner = #expr.runtime_value();
+                                let #inner = #expr.#variant_value()(#expr.runtime_value());
                                 #block
                             }}
                         }
@@ -737,6 +780,9 @@ impl Expression {
             Expression::PanickingMacro { ident, tokens } => {
                 quote![#ident!(#tokens)]
             }
+            Expression::Terminate if context.returns_early => {
+                quote![__fn_return.terminate(scope);]
+            }
             Expression::Terminate => {
                 quote![cubecl::frontend::branch::return_expand(scope);]
             }
@@ -884,6 +930,32 @@ impl Block {
         }
     }
 
+    /// Expand the body of a function that contains `return`, see
+    /// `cubecl::frontend::branch::fn_return_expand`.
+    pub fn to_tokens_fn_return(&self, context: &mut Context) -> TokenStream {
+        let path = frontend_path();
+        let inner: Vec<_> = self.inner.iter().map(|it| it.to_tokens(context)).collect();
+        let ret = match self.ret.as_ref() {
+            Some(ret) if matches!(**ret, Expression::PanickingMacro { .. }) || ret.diverges() => {
+                ret.to_tokens(context)
+            }
+            Some(ret) => {
+                let ret = into_expand(ret.to_tokens(context));
+                quote![__fn_return.value(scope, #ret);]
+            }
+            None => quote![__fn_return.value(scope, ());],
+        };
+
+        quote! {
+            {
+                #path::branch::fn_return_expand(scope, |scope, __fn_return| {
+                    #(#inner)*
+                    #ret
+                })
+            }
+        }
+    }
+
     pub fn to_tokens_runtime_return(&self, context: &mut Context) -> TokenStream {
         let inner: Vec<_> = self.inner.iter().map(|it| it.to_tokens(context)).collect();
         let ret = if let Some(ret) = self.ret.as_ref() {
@@ -4,7 +4,7 @@ use darling::{
 };
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::{DeriveInput, Index, WhereClause};
+use syn::{DeriveInput, Index, Type, WhereClause};
 
 use crate::{
     generate::bounded_where_clause,
@@ -12,8 +12,8 @@ use crate::{
     paths::{core_type, prelude_type},
 };
 
-impl ToTokens for IntoRuntime {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
+impl IntoRuntime {
+    fn generate(&self) -> syn::Result<TokenStream> {
         let into_expand = prelude_type("IntoExpand");
         let into_runtime = core_type("IntoRuntime");
         let cube_type = prelude_type("CubeType");
@@ -24,12 +24,12 @@ impl ToTokens for IntoRuntime {
         let where_clause = self.where_clause();
 
         let init = match &self.data {
-            Data::Enum(_) if self.runtime_variants.is_present() => self.init_runtime_enum(),
+            Data::Enum(_) if self.runtime_variants.is_present() => self.init_runtime_enum()?,
             Data::Enum(_) => self.init_comptime_enum(),
             Data::Struct(_) => self.init_struct(),
         };
 
-        tokens.extend(quote! {
+        Ok(quote! {
             impl #generics #into_runtime for #name #generic_names #where_clause {
                 fn __expand_runtime_method(self, scope: &#scope) -> Self::ExpandType {
                     type _Ty #generic_names = <#name #generic_names as #cube_type>::ExpandType;
@@ -44,11 +44,9 @@ impl ToTokens for IntoRuntime {
                     self.__expand_runtime_method(scope)
                 }
             }
-        });
+        })
     }
-}
 
-impl IntoRuntime {
     fn init_struct(&self) -> TokenStream {
         let into_runtime = core_type("IntoRuntime");
 
@@ -117,7 +115,7 @@ impl IntoRuntime {
         }
     }
 
-    fn init_runtime_enum(&self) -> TokenStream {
+    fn init_runtime_enum(&self) -> syn::Result<TokenStream> {
         let into_runtime = core_type("IntoRuntime");
 
         let name = &self.ident;
@@ -126,15 +124,12 @@ impl IntoRuntime {
         let generic_names = generic_names.as_turbofish();
         let variants = self.data.as_ref().take_enum().unwrap();
 
-        let value_ty = variants
-            .iter()
-            .find_map(|v| match v.fields.style {
-                Style::Struct => unimplemented!(),
-                Style::Tuple => Some(v.fields.iter().next().unwrap().ty.clone()),
-                Style::Unit => None,
-            })
-            .map(|ty| quote![#ty])
-            .unwrap_or_else(|| quote![()]);
+        let value_tys = self.runtime_value_tys()?;
+        let value_ty = match value_tys.as_slice() {
+            [] => quote![()],
+            [ty] => quote![#ty],
+            tys => quote![(#(#tys,)*)],
+        };
 
         let discriminants = variants.iter().map(|v| {
             let variant = &v.ident;
@@ -149,7 +144,8 @@ impl IntoRuntime {
 
         let values = variants
             .iter()
-            .map(|variant| self.runtime_variant_value(variant));
+            .map(|variant| self.runtime_variant_value(variant, &value_tys))
+            .collect::<syn::Result<Vec<_>>>()?;
 
         let discriminant = quote! {
             let discriminant = match &self {
@@ -162,27 +158,73 @@ impl IntoRuntime {
             };
         };
 
-        quote! {
+        Ok(quote! {
             #discriminant
             #value
             _Ty {
                 discriminant: discriminant.into(),
                 value: #into_runtime::__expand_runtime_method(value, scope),
             }
+        })
+    }
+
+    /// The distinct value types of the variants, in declaration order. Matches the runtime value
+    /// layout of the `CubeType` derive.
+    fn runtime_value_tys(&self) -> syn::Result<Vec<Type>> {
+        let mut tys: Vec<Type> = Vec::new();
+        for variant in self.data.as_ref().take_enum().unwrap() {
+            match variant.fields.style {
+                Style::Struct => return Err(struct_variant_error(variant)),
+                Style::Tuple => {
+                    let ty = variant.fields.iter().next().unwrap().ty.clone();
+                    let name = ty.to_token_stream().to_string();
+                    if !tys
+                        .iter()
+                        .any(|it| it.to_token_stream().to_string() == name)
+                    {
+                        tys.push(ty);
+                    }
+                }
+                Style::Unit => {}
+            }
         }
+        Ok(tys)
     }
 
-    fn runtime_variant_value(&self, variant: &IntoRuntimeVariant) -> TokenStream {
+    fn runtime_variant_value(
+        &self,
+        variant: &IntoRuntimeVariant,
+        value_tys: &[Type],
+    ) -> syn::Result<TokenStream> {
         let enum_name = &self.ident;
         let variant_name = &variant.ident;
 
-        match variant.fields.style {
+        let value = match variant.fields.style {
+            Style::Tuple if value_tys.len() > 1 => {
+                let ty = variant
+                    .fields
+                    .iter()
+                    .next()
+                    .unwrap()
+                    .ty
+                    .to_token_stream()
+                    .to_string();
+                let values =
+                    value_tys
+                        .iter()
+                        .map(|it| match it.to_token_stream().to_string() == ty {
+                            true => quote![value],
+                            false => quote![Default::default()],
+                        });
+                quote![#enum_name::#variant_name(value) => (#(#values,)*)]
+            }
             Style::Tuple => {
                 quote![#enum_name::#variant_name(value) => value]
             }
-            Style::Struct => unimplemented!(),
+            Style::Struct => return Err(struct_variant_error(variant)),
             Style::Unit => quote![#enum_name::#variant_name => Default::default()],
-        }
+        };
+        Ok(value)
     }
 
     fn where_clause(&self) -> Option<WhereClause> {
@@ -216,7 +258,14 @@ impl IntoRuntime {
     }
 }
 
+fn struct_variant_error(variant: &IntoRuntimeVariant) -> syn::Error {
+    syn::Error::new_spanned(
+        &variant.ident,
+        "struct variants aren't supported in runtime enums",
+    )
+}
+
 pub fn generate_into_runtime(input: &DeriveInput) -> syn::Result<TokenStream> {
     let into_runtime = IntoRuntime::from_derive_input(input)?;
-    Ok(into_runtime.into_token_stream())
+    into_runtime.generate()
 }
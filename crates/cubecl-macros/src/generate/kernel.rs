@@ -37,7 +37,13 @@ impl KernelFn {
             KernelBody::Block(block) => match matches!(sig.returns, KernelReturns::ExpandType(_))
                 && !self.context.is_intrinsic
             {
+                true if self.context.returns_early => &block.to_tokens_fn_return(&mut self.context),
                 true => &block.to_tokens_runtime_return(&mut self.context),
+                false if self.context.returns_early => &syn::Error::new(
+                    self.span,
+                    "`return` and `?` are only supported in functions returning a runtime value",
+                )
+                .into_compile_error(),
                 false => &block.to_tokens(&mut self.context),
             },
             KernelBody::Verbatim(tokens) => tokens,
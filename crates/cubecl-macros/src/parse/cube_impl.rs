@@ -135,6 +135,13 @@ impl CubeImplItem {
         core::mem::swap(&mut func.body, &mut body);
 
         let cfg_debug = cfg!(debug_symbols) && !func.args.no_debug_symbols.is_present();
+        let mut context = Context::new(
+            func.context.return_type.clone(),
+            cfg_debug || func.args.debug_symbols.is_present(),
+            func.context.is_intrinsic,
+        );
+        // The method gets the function's body, `return`s included.
+        context.returns_early = func.context.returns_early;
         KernelFn {
             attrs: func.attrs.clone(),
             vis: func.vis.clone(),
@@ -142,11 +149,7 @@ impl CubeImplItem {
             body,
             full_name: func.full_name.clone(),
             span: func.span,
-            context: Context::new(
-                func.context.return_type.clone(),
-                cfg_debug || func.args.debug_symbols.is_present(),
-                func.context.is_intrinsic,
-            ),
+            context,
             args: func.args.clone(),
             analysis: func.analysis.clone(),
         }
@@ -198,7 +198,22 @@ impl Expression {
                 tokens: quote![#block],
             },
             Expr::Continue(cont) => Expression::Continue(cont.span()),
-            Expr::Return(ret) => Expression::Return(ret.span()),
+            Expr::Return(ret) => {
+                let span = ret.span();
+                if context.in_closure {
+                    return Err(syn::Error::new(
+                        span,
+                        "`return` isn't supported inside closures",
+                    ));
+                }
+                let expr = ret
+                    .expr
+                    .map(|expr| Expression::from_expr(*expr, context))
+                    .transpose()?
+                    .map(Box::new);
+                context.returns_early = true;
+                Expression::Return { expr, span }
+            }
             Expr::ForLoop(for_loop) => expand_for_loop(for_loop, context)?,
             Expr::While(while_loop) => expand_while_loop(while_loop, context)?,
             Expr::Loop(loop_expr) => expand_loop(loop_expr, context)?,
@@ -384,12 +399,15 @@ impl Expression {
             Expr::Verbatim(verbatim) => Expression::Verbatim { tokens: verbatim },
             Expr::Reference(expr_reference) => Self::from_expr_reference(expr_reference, context)?,
             Expr::Closure(expr) => {
-                let (body, scope) = context.in_scope(|ctx| {
+                let in_closure = core::mem::replace(&mut context.in_closure, true);
+                let body = context.in_scope(|ctx| {
                     for arg in expr.inputs.iter() {
                         add_variables_from_pat(arg, ctx);
                     }
                     Expression::from_expr(*expr.body, ctx)
-                })?;
+                });
+                context.in_closure = in_closure;
+                let (body, scope) = body?;
                 let body = Box::new(body);
                 let params = expr.inputs.into_iter().collect();
                 Expression::Closure {
@@ -401,11 +419,24 @@ impl Expression {
 
             Expr::Try(expr) => {
                 let span = expr.span();
-                let expr = Expression::from_expr(*expr.expr, context)?
-                    .as_const(context)
-                    .ok_or_else(|| syn::Error::new(span, "? Operator not supported at runtime"))?;
-                Expression::Verbatim {
-                    tokens: quote_spanned![span=> #expr?],
+                let expr = Expression::from_expr(*expr.expr, context)?;
+                match expr.as_const(context) {
+                    Some(expr) => Expression::Verbatim {
+                        tokens: quote_spanned![span=> #expr?],
+                    },
+                    None if context.in_closure => {
+                        return Err(syn::Error::new(
+                            span,
+                            "`?` on runtime values isn't supported inside closures",
+                        ));
+                    }
+                    None => {
+                        context.returns_early = true;
+                        Expression::Try {
+                            expr: Box::new(expr),
+                            span,
+                        }
+                    }
                 }
             }
             Expr::TryBlock(_) => Err(syn::Error::new_spanned(
@@ -54,6 +54,10 @@ pub struct Context {
     mut_scope_idx: usize,
     pub debug_symbols: bool,
     pub is_intrinsic: bool,
+    /// Whether the function body contains `return`, and must be expanded with its own return flag.
+    pub returns_early: bool,
+    /// Whether we're parsing the body of a closure, where `return` isn't supported.
+    pub in_closure: bool,
 }
 
 impl Context {
@@ -79,6 +83,8 @@ impl Context {
             mut_scope_idx: 0,
             debug_symbols,
             is_intrinsic,
+            returns_early: false,
+            in_closure: false,
         }
     }
 
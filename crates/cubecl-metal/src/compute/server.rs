@@ -22,6 +22,7 @@ use cubecl_runtime::{
     allocator::ContiguousMemoryLayoutPolicy,
     compiler::CubeTask,
     dry_run::LaunchMode,
+    id::EventId,
     logging::ServerLogger,
     memory_management::{InstallMemoryPoolsError, ManagedMemoryHandle},
     server::ComputeServer,
@@ -528,6 +529,29 @@ impl ComputeServer for MetalServer {
         }
     }
 
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        Ok(self.streams.record_event(stream_id))
+    }
+
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        self.streams.wait_event(event, stream_id)
+    }
+
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        self.streams.query_event(event)
+    }
+
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        match self.streams.synchronize_event(event) {
+            Ok(event) => Box::pin(async move { MetalStreamBackend::wait_event_sync(event) }),
+            Err(err) => Box::pin(async { Err(err) }),
+        }
+    }
+
+    fn event_destroy(&mut self, event: EventId) {
+        self.streams.destroy_event(event);
+    }
+
     fn sync(&mut self, stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
         let errors = self.flush_errors(stream_id);
         if !errors.is_empty() {
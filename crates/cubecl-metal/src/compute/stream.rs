@@ -197,7 +197,7 @@ impl MetalEvent {
         Ok(())
     }
 
-    pub fn wait_async(self, stream: &mut MetalStream) {
+    pub fn wait_async(&self, stream: &mut MetalStream) {
         use objc2_metal::{MTLCommandBuffer, MTLCommandEncoder, MTLEvent};
 
         if std::ptr::eq(
@@ -406,6 +406,14 @@ impl EventStreamBackend for MetalStreamBackend {
     fn wait_event_sync(event: Self::Event) -> Result<(), ServerError> {
         event.wait_sync()
     }
+
+    fn wait_event_shared(stream: &mut Self::Stream, event: &Self::Event) {
+        event.wait_async(stream);
+    }
+
+    fn is_event_complete(event: &Self::Event) -> bool {
+        event.is_complete()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,354 @@
+use core::{any::type_name, ops::Deref};
+
+use alloc::string::ToString;
+use cubecl_core::throughput::KernelCost;
+use cubecl_ir::{
+    AddressSpace, ElemType, IntKind,
+    dialect::{
+        branch,
+        memory::{CopyOp, LoadOp, StoreOp},
+        scf,
+    },
+    interfaces::{ConstantAttr, ScalarType, TypedExt},
+    prelude::*,
+    types::PointerType,
+};
+use pliron::{
+    attribute::attr_cast,
+    builtin::ops::ConstantOp,
+    linked_list::ContainsLinkedList,
+    r#type::{Typed, type_cast},
+    value::Value,
+};
+
+/// The static [`KernelCost`] of one unit of a kernel, counted over the ops of a function.
+///
+/// Run after lowering and unrolling, so comptime loops are already flattened and what's left
+/// is close to what the target executes.
+#[derive(Debug, Clone, Default)]
+pub struct StaticCost(pub KernelCost);
+
+impl Deref for StaticCost {
+    type Target = KernelCost;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Analysis for StaticCost {
+    fn name(&self) -> &str {
+        type_name::<Self>()
+    }
+
+    fn compute(op: Ptr<Operation>, ctx: &Context, _analyses: &mut AnalysisManager) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut cost = KernelCost::default();
+        count_regions(ctx, op, &mut cost);
+        Ok(Self(cost))
+    }
+}
+
+/// Count the ops nested in `op`, all regions in sequence.
+fn count_regions(ctx: &Context, op: Ptr<Operation>, cost: &mut KernelCost) {
+    for region in op.regions(ctx) {
+        for block in region.deref(ctx).iter(ctx) {
+            for block_op in block.deref(ctx).iter(ctx) {
+                count_op(ctx, block_op, cost);
+            }
+        }
+    }
+}
+
+fn count_op(ctx: &Context, op: Ptr<Operation>, cost: &mut KernelCost) {
+    let op_id = op.dyn_op(ctx).get_opid().to_string();
+    let (dialect, name) = op_id.split_once('.').unwrap_or(("", op_id.as_str()));
+
+    match (dialect, name) {
+        ("scf" | "branch", "range_loop") => {
+            let mut body = KernelCost::default();
+            count_regions(ctx, op, &mut body);
+            match range_trip_count(ctx, op) {
+                Some(trips) => scale(&mut body, trips),
+                None => cost.dynamic_loops += 1,
+            }
+            add(cost, body);
+        }
+        ("scf" | "branch", "while") => {
+            cost.dynamic_loops += 1;
+            count_regions(ctx, op, cost);
+        }
+        // Never executed.
+        ("branch", "dead_region") => {}
+        ("memory", _) => count_memory(ctx, op, cost),
+        ("atomic", _) => cost.atomics += 1,
+        ("sync", "sync") | ("barrier", _) => cost.barriers += 1,
+        ("plane", _) => cost.plane_ops += 1,
+        ("math" | "bitwise" | "cmp", _) => {
+            let weight = if name == "fma" { 2 } else { 1 };
+            count_arithmetic(ctx, op, weight, cost);
+        }
+        ("vector", "i_sum" | "f_sum") => count_arithmetic(ctx, op, 1, cost),
+        ("vector", "s_dot" | "u_dot" | "f_dot") => count_arithmetic(ctx, op, 2, cost),
+        // Four `i8` lanes hide in each `u32` operand.
+        ("vector", "s_dot_4x8_packed") => {
+            *cost
+                .arithmetic
+                .entry(ElemType::Int(IntKind::I8))
+                .or_default() += 2 * 4;
+        }
+        _ => {
+            // Only the taken alternative of a branch runs, so count the most expensive one.
+            let regions = op.regions(ctx);
+            if regions.len() > 1 {
+                let mut alternatives = Vec::with_capacity(regions.len());
+                for region in regions {
+                    let mut alternative = KernelCost::default();
+                    for block in region.deref(ctx).iter(ctx) {
+                        for block_op in block.deref(ctx).iter(ctx) {
+                            count_op(ctx, block_op, &mut alternative);
+                        }
+                    }
+                    alternatives.push(alternative);
+                }
+                add(cost, most_expensive(alternatives));
+            } else {
+                count_regions(ctx, op, cost);
+            }
+        }
+    }
+}
+
+fn count_memory(ctx: &Context, op: Ptr<Operation>, cost: &mut KernelCost) {
+    if let Some(load) = op.as_op::<LoadOp>(ctx) {
+        let bytes = load.get_result(ctx).size(ctx) as u64;
+        match address_space(ctx, load.ptr(ctx)) {
+            Some(AddressSpace::Global(_)) => cost.global_read_bytes += bytes,
+            Some(AddressSpace::Shared) => cost.shared_read_bytes += bytes,
+            _ => {}
+        }
+    } else if let Some(store) = op.as_op::<StoreOp>(ctx) {
+        let bytes = store.value(ctx).size(ctx) as u64;
+        match address_space(ctx, store.ptr(ctx)) {
+            Some(AddressSpace::Global(_)) => cost.global_write_bytes += bytes,
+            Some(AddressSpace::Shared) => cost.shared_write_bytes += bytes,
+            _ => {}
+        }
+    } else if let Some(copy) = op.as_op::<CopyOp>(ctx) {
+        let source = copy.source(ctx);
+        let bytes = (source.unwrap_ptr(ctx).size(ctx) * copy.len(ctx).0) as u64;
+        match address_space(ctx, source) {
+            Some(AddressSpace::Global(_)) => cost.global_read_bytes += bytes,
+            Some(AddressSpace::Shared) => cost.shared_read_bytes += bytes,
+            _ => {}
+        }
+        match address_space(ctx, copy.destination(ctx)) {
+            Some(AddressSpace::Global(_)) => cost.global_write_bytes += bytes,
+            Some(AddressSpace::Shared) => cost.shared_write_bytes += bytes,
+            _ => {}
+        }
+    }
+}
+
+/// Counts `weight` operations per lane, in the element type of the first operand: the result
+/// of a comparison or a reduction doesn't say what the work was done in.
+fn count_arithmetic(ctx: &Context, op: Ptr<Operation>, weight: u64, cost: &mut KernelCost) {
+    let Some(operand) = op.operands(ctx).first().copied() else {
+        return;
+    };
+    let Some(elem) = operand.try_get_scalar_ty(ctx).and_then(|ty| {
+        let ty = ty.deref(ctx);
+        type_cast::<dyn ScalarType>(&*ty).map(|scalar| scalar.elem_type(ctx))
+    }) else {
+        return;
+    };
+    let lanes = operand.try_get_vector_size(ctx).unwrap_or(1) as u64;
+
+    *cost.arithmetic.entry(elem).or_default() += weight * lanes;
+}
+
+fn address_space(ctx: &Context, ptr: Value) -> Option<AddressSpace> {
+    let ty = ptr.get_type(ctx).deref(ctx);
+    ty.downcast_ref::<PointerType>()
+        .map(|pointer| pointer.address_space)
+}
+
+/// The trip count of a range loop whose bounds and step are all constants.
+fn range_trip_count(ctx: &Context, op: Ptr<Operation>) -> Option<u64> {
+    let (start, end, step) = if let Some(range) = op.as_op::<scf::RangeLoopOp>(ctx) {
+        (range.start(ctx), range.end(ctx), range.step(ctx))
+    } else {
+        let range = op.as_op::<branch::RangeLoopOp>(ctx)?;
+        (range.start(ctx), range.end(ctx), range.step(ctx))
+    };
+
+    let (start, end, step) = (
+        const_u64(ctx, start)?,
+        const_u64(ctx, end)?,
+        const_u64(ctx, step)?,
+    );
+    if step == 0 {
+        return None;
+    }
+
+    Some(end.saturating_sub(start).div_ceil(step))
+}
+
+fn const_u64(ctx: &Context, value: Value) -> Option<u64> {
+    let def_op = value.defining_op()?;
+    let constant = def_op.as_op::<ConstantOp>(ctx)?;
+    let attr = constant.get_value(ctx);
+    let attr = attr_cast::<dyn ConstantAttr>(&*attr)?;
+    attr.as_const_val(ctx).try_as_u64()
+}
+
+fn add(cost: &mut KernelCost, other: KernelCost) {
+    cost.global_read_bytes += other.global_read_bytes;
+    cost.global_write_bytes += other.global_write_bytes;
+    cost.shared_read_bytes += other.shared_read_bytes;
+    cost.shared_write_bytes += other.shared_write_bytes;
+    for (elem, ops) in other.arithmetic {
+        *cost.arithmetic.entry(elem).or_default() += ops;
+    }
+    cost.atomics += other.atomics;
+    cost.barriers += other.barriers;
+    cost.plane_ops += other.plane_ops;
+    cost.dynamic_loops += other.dynamic_loops;
+}
+
+fn scale(cost: &mut KernelCost, factor: u64) {
+    cost.global_read_bytes = cost.global_read_bytes.saturating_mul(factor);
+    cost.global_write_bytes = cost.global_write_bytes.saturating_mul(factor);
+    cost.shared_read_bytes = cost.shared_read_bytes.saturating_mul(factor);
+    cost.shared_write_bytes = cost.shared_write_bytes.saturating_mul(factor);
+    for ops in cost.arithmetic.values_mut() {
+        *ops = ops.saturating_mul(factor);
+    }
+    cost.atomics = cost.atomics.saturating_mul(factor);
+    cost.barriers = cost.barriers.saturating_mul(factor);
+    cost.plane_ops = cost.plane_ops.saturating_mul(factor);
+}
+
+/// The alternative moving the most global bytes, then doing the most arithmetic.
+fn most_expensive(alternatives: Vec<KernelCost>) -> KernelCost {
+    alternatives
+        .into_iter()
+        .max_by_key(|cost| (cost.global_bytes(), cost.arithmetic_ops()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        self as cubecl,
+        ir::{FloatKind, UIntKind, settings::Dim3},
+        prelude::*,
+    };
+
+    #[cube]
+    fn scale_four(input: &[f32], output: &mut [f32]) {
+        for i in 0..4usize {
+            output[i] = input[i] * 2.0;
+        }
+    }
+
+    #[cube]
+    fn scale_all(input: &[f32], output: &mut [f32]) {
+        for i in 0..input.len() {
+            output[i] = input[i] * 2.0;
+        }
+    }
+
+    #[cube]
+    fn scale_or_fill(input: &[f32], output: &mut [f32]) {
+        if input[0] > 0.0 {
+            output[0] = input[1] * input[2] + input[3];
+        } else {
+            output[0] = 1.0;
+        }
+    }
+
+    #[cube]
+    fn packed_dot(input: &[u32], output: &mut [i32]) {
+        output[0] = dot4_i8_packed(input[0], input[1]);
+    }
+
+    /// The cost of `kernel` expanded over an input and an output buffer.
+    fn cost_of<I: CubePrimitive, O: CubePrimitive>(
+        kernel: impl FnOnce(&Scope, &'static NativeExpand<[I]>, &'static mut NativeExpand<[O]>),
+    ) -> KernelCost {
+        let mut builder = KernelBuilder::new(KernelSettings::new(
+            Dim3::new_single(),
+            ExecutionMode::Checked,
+            AddressType::U32,
+        ));
+        builder
+            .scope
+            .register_type::<usize>(ElemType::UInt(UIntKind::U32));
+
+        let arg = BufferCompilationArg { inplace: None };
+        let input = <&'static [I] as LaunchArg>::expand(&arg, &mut builder);
+        let output = <&'static mut [O] as LaunchArg>::expand(&arg, &mut builder);
+        kernel(&builder.scope, input, output);
+
+        let definition = builder.build();
+        let entry_func = definition.body.state().entry_func;
+        let ctx = definition.body.into_context().expect("Should be unique");
+        let mut analyses = AnalysisManager::default();
+        let cost = analyses
+            .get_analysis::<StaticCost>(entry_func.get_operation(), &ctx)
+            .expect("Should count");
+        cost.0.clone()
+    }
+
+    fn f32_ops(cost: &KernelCost) -> u64 {
+        let f32 = ElemType::Float(FloatKind::F32);
+        cost.arithmetic.get(&f32).copied().unwrap_or_default()
+    }
+
+    #[test]
+    fn constant_loops_multiply_their_body() {
+        let cost =
+            cost_of::<f32, f32>(|scope, input, output| scale_four::expand(scope, input, output));
+
+        assert_eq!(cost.global_read_bytes, 4 * 4);
+        assert_eq!(cost.global_write_bytes, 4 * 4);
+        assert_eq!(f32_ops(&cost), 4);
+        assert_eq!(cost.dynamic_loops, 0);
+    }
+
+    #[test]
+    fn dynamic_loops_count_their_body_once() {
+        let cost =
+            cost_of::<f32, f32>(|scope, input, output| scale_all::expand(scope, input, output));
+
+        assert_eq!(cost.global_read_bytes, 4);
+        assert_eq!(cost.global_write_bytes, 4);
+        assert_eq!(f32_ops(&cost), 1);
+        assert_eq!(cost.dynamic_loops, 1);
+    }
+
+    #[test]
+    fn branches_count_their_most_expensive_alternative() {
+        let cost =
+            cost_of::<f32, f32>(|scope, input, output| scale_or_fill::expand(scope, input, output));
+
+        // The condition reads one value, the taken branch three more.
+        assert_eq!(cost.global_read_bytes, 4 * 4);
+        assert_eq!(cost.global_write_bytes, 4);
+        // The comparison, a multiply and an add.
+        assert_eq!(f32_ops(&cost), 3);
+    }
+
+    #[test]
+    fn packed_dot_products_count_their_lanes() {
+        let cost =
+            cost_of::<u32, i32>(|scope, input, output| packed_dot::expand(scope, input, output));
+
+        let i8 = ElemType::Int(IntKind::I8);
+        assert_eq!(cost.arithmetic.get(&i8).copied(), Some(8));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cost;
 pub mod liveness;
 pub mod pointer_source;
 pub mod slices;
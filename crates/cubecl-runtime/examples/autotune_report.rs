@@ -0,0 +1,55 @@
+//! Summarize an autotune recorder file.
+//!
+//! ```sh
+//! cargo run -p cubecl-runtime --example autotune_report -- autotune.jsonl --marginal 0.05
+//! ```
+//!
+//! Prints the wins per candidate, every decision with its margin, the candidates that never
+//! won and the decisions within the marginal threshold (5% by default).
+
+use cubecl_runtime::tune::AutotuneReport;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    let mut threshold = 0.05;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--marginal" => {
+                threshold = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .expect("--marginal takes a ratio, e.g. 0.05 for 5%");
+            }
+            _ => path = Some(arg),
+        }
+    }
+
+    let Some(path) = path else {
+        eprintln!("Usage: autotune_report <recorder file> [--marginal <ratio>]");
+        std::process::exit(1);
+    };
+
+    let report = AutotuneReport::from_file(&path)
+        .unwrap_or_else(|err| panic!("Failed to read the recorder file {path}: {err}"));
+
+    println!("{report}");
+
+    println!("Never winning:");
+    for name in report.never_winning() {
+        println!("  {name}");
+    }
+
+    println!("\nMarginal decisions (within {:.1}%):", threshold * 100.0);
+    for key in report.marginal(threshold) {
+        let margin = key.margin().unwrap_or_default();
+        let (runner_up, _) = key.runner_up.as_ref().expect("A margin needs a runner up");
+        println!(
+            "  {} => {} over {runner_up} by {:.1}%",
+            key.key,
+            key.winner,
+            margin * 100.0
+        );
+    }
+}
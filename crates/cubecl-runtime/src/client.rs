@@ -1,29 +1,35 @@
 use crate::{
     config::memory::MemoryPoolsConfig,
-    config::{TypeNameFormatLevel, type_name_format},
-    id::GraphId,
+    config::{TypeNameFormatLevel, streaming::StreamPriority, type_name_format},
+    id::{EventId, GraphId, GraphParamId},
     kernel::KernelMetadata,
-    logging::ProfileLevel,
+    logging::{ProfileLevel, TraceEvent, TraceOp, traced},
     memory_management::{
         InstallMemoryPoolsError, MemoryAllocationMode, MemoryConfiguration, MemoryReport,
         MemoryUsage,
     },
     runtime::Runtime,
     server::{
-        CommunicationId, ComputeServer, CopyDescriptor, CubeCount, Handle, IoError,
-        KernelArguments, MemoryLayout, MemoryLayoutDescriptor, MemoryLayoutPolicy,
+        CommunicationId, ComputeServer, CopyDescriptor, CubeCount, GraphParamValue, Handle,
+        IoError, KernelArguments, MemoryLayout, MemoryLayoutDescriptor, MemoryLayoutPolicy,
         MemoryLayoutStrategy, ProfileError, ReduceOperation, ServerCommunication, ServerError,
         ServerUtilities,
     },
     storage::{ComputeStorage, ManagedResource},
     throughput::{
-        KernelConfig, ThroughputBenchmarker, ThroughputCache, ThroughputKey, ThroughputValue,
+        KernelConfig, KernelCost, MemoryAccess, MemoryCurve, MemoryPoint, RooflineEstimate,
+        ThroughputBenchmarker, ThroughputCache, ThroughputKey, ThroughputMode, ThroughputValue,
+        kernel_costs, working_set_sweep,
     },
 };
 use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
 
 #[cfg(not(target_family = "wasm"))]
 mod lazy;
+#[cfg(std_io)]
+mod record;
+mod upload;
+
 use cubecl_common::{
     bytes::{AllocationProperty, Bytes},
     device::{Device, DeviceId},
@@ -34,6 +40,9 @@ use cubecl_environment::backtrace::BackTrace;
 use cubecl_environment::future::DynFut;
 use cubecl_ir::{DeviceProperties, ElemType, VectorSize, features::Features};
 use cubecl_zspace::Shape;
+#[cfg(std_io)]
+pub use record::*;
+pub use upload::*;
 
 #[allow(unused)]
 use cubecl_common::profile::TimingMethod;
@@ -68,6 +77,13 @@ pub struct ComputeClient<R: Runtime> {
 /// replays, and reads from the same unpinned client — for the whole decode loop.
 /// Refreshing inputs from a client on a different stream races the replay and
 /// silently feeds it stale data.
+///
+/// **Parameters.** Scalars and bindings marked during the capture with
+/// [`graph_scalar_param`](ComputeClient::graph_scalar_param) and
+/// [`graph_binding_param`](ComputeClient::graph_binding_param) can be changed
+/// between replays with [`update`](Graph::update), for the values that change
+/// every iteration (a sequence length, a step index, the buffer a step reads)
+/// without re-capturing the graph.
 pub struct Graph<R: Runtime> {
     inner: Arc<GraphHandle<R>>,
 }
@@ -88,6 +104,8 @@ struct GraphHandle<R: Runtime> {
     id: GraphId,
     device: DeviceHandle<R::Server>,
     stream_id: StreamId,
+    #[cfg(std_io)]
+    utilities: Arc<ServerUtilities<R::Server>>,
 }
 
 impl<R: Runtime> Graph<R> {
@@ -127,12 +145,65 @@ impl<R: Runtime> Graph<R> {
     ///   [`set_stream`](ComputeClient::set_stream), or do everything from the
     ///   one client), so they order against the replay instead of racing it.
     pub unsafe fn replay(&self) {
+        #[cfg(std_io)]
+        self.inner.utilities.recorder.with(|recorder| {
+            recorder.skip("graph replay", "graphs can't be recorded");
+        });
         let id = self.inner.id;
         let stream_id = self.inner.stream_id;
         self.inner
             .device
             .submit(move |server| server.replay(id, stream_id));
     }
+
+    /// Change the parameters in `update` for every replay enqueued after this
+    /// call; replays already enqueued keep the previous values. On CUDA this
+    /// is a graph exec update of the recorded kernel nodes; the software graphs
+    /// rewrite their recorded launches.
+    ///
+    /// A binding swapped in is held by the graph until it is replaced or the
+    /// graph is dropped, so its memory is never reused under a replay. Its size
+    /// must match the captured binding, and a scalar's width the placeholder's:
+    /// the metadata packed with the launch (buffer lengths, shapes) is baked
+    /// into the graph. The batch is applied whole or not at all.
+    pub fn update(&self, update: GraphUpdate) -> Result<(), ServerError> {
+        let id = self.inner.id;
+        let stream_id = self.inner.stream_id;
+        self.inner
+            .device
+            .submit_blocking(move |server| server.graph_update(id, update.values, stream_id))
+            .unwrap_or_resume()
+    }
+}
+
+/// A batch of new parameter values for [`Graph::update`].
+#[derive(Debug, Default)]
+pub struct GraphUpdate {
+    values: Vec<(GraphParamId, GraphParamValue)>,
+}
+
+impl GraphUpdate {
+    /// Create an empty update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `handle` where the graph bound the buffer marked as `param`.
+    pub fn binding(mut self, param: GraphParamId, handle: &Handle) -> Self {
+        self.values
+            .push((param, GraphParamValue::Binding(handle.clone().binding())));
+        self
+    }
+
+    /// Pass `value` where the graph passed the scalar marked as `param`. The
+    /// type must be the one the placeholder was marked with.
+    pub fn scalar<T: bytemuck::Pod>(mut self, param: GraphParamId, value: T) -> Self {
+        self.values.push((
+            param,
+            GraphParamValue::Scalar(bytemuck::bytes_of(&value).to_vec()),
+        ));
+        self
+    }
 }
 
 impl<R: Runtime> Clone for Graph<R> {
@@ -156,6 +227,72 @@ impl<R: Runtime> Drop for GraphHandle<R> {
     }
 }
 
+/// A point in a stream's work, recorded by
+/// [`record_event`](ComputeClient::record_event), that other streams of the same
+/// device can [wait on](ComputeClient::wait_event) without blocking the host.
+///
+/// This makes producer/consumer pipelines across streams explicit, where the
+/// implicit alignment only orders streams sharing a [`Handle`]. An event can be
+/// waited on any number of times; cloning is cheap and the backend event is
+/// released when the last clone drops.
+///
+/// On wgpu, every stream submits to the same in-order queue, so a wait orders nothing more
+/// than the queue already does. On cpu, waiting blocks the server until the event completes.
+pub struct StreamEvent<R: Runtime> {
+    inner: Arc<StreamEventHandle<R>>,
+}
+
+impl<R: Runtime> core::fmt::Debug for StreamEvent<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamEvent")
+            .field("id", &self.inner.id)
+            .finish()
+    }
+}
+
+/// Owner of a backend event. Dropping it ships the release to the server actor.
+struct StreamEventHandle<R: Runtime> {
+    id: EventId,
+    device: DeviceHandle<R::Server>,
+}
+
+impl<R: Runtime> StreamEvent<R> {
+    /// Whether the work recorded before the event has completed, without blocking.
+    pub fn query(&self) -> Result<bool, ServerError> {
+        let id = self.inner.id;
+        self.inner
+            .device
+            .submit_blocking(move |server| server.event_query(id))
+            .unwrap_or_resume()
+    }
+
+    /// Wait on the host for the work recorded before the event, and only that work:
+    /// unlike [`sync`](ComputeClient::sync), later work on the same stream isn't
+    /// waited for.
+    pub fn synchronize(&self) -> DynFut<Result<(), ServerError>> {
+        let id = self.inner.id;
+        self.inner
+            .device
+            .submit_blocking(move |server| server.event_synchronize(id))
+            .unwrap_or_resume()
+    }
+}
+
+impl<R: Runtime> Clone for StreamEvent<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<R: Runtime> Drop for StreamEventHandle<R> {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.device.submit(move |server| server.event_destroy(id));
+    }
+}
+
 impl<R: Runtime> Clone for ComputeClient<R> {
     fn clone(&self) -> Self {
         Self {
@@ -218,10 +355,39 @@ impl<R: Runtime> ComputeClient<R> {
         self.stream_id = Some(stream_id);
     }
 
+    /// Set the priority the work of the current stream is scheduled with, see
+    /// [`ComputeServer::set_stream_priority`].
+    pub fn set_stream_priority(&self, priority: StreamPriority) {
+        let stream_id = self.stream_id();
+        self.device
+            .submit(move |server| server.set_stream_priority(stream_id, priority));
+    }
+
     fn do_read(&self, descriptors: Vec<CopyDescriptor>) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        #[cfg(std_io)]
+        if self.utilities.recorder.is_active() {
+            return self.record_read(descriptors);
+        }
+        self.read_unrecorded(descriptors)
+    }
+
+    /// Read without going through the replay log, if one is being recorded.
+    fn read_unrecorded(
+        &self,
+        descriptors: Vec<CopyDescriptor>,
+    ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
         let stream_id = self.stream_id();
+        let logger = self.utilities.logger.clone();
         self.device
-            .submit_blocking(move |server| server.read(descriptors, stream_id))
+            .submit_blocking(move |server| {
+                let event = logger.trace_activated().then(|| {
+                    let bytes = copy_sizes(descriptors.iter());
+                    TraceEvent::new(TraceOp::Read, "read", stream_id, bytes)
+                });
+                traced(server, &logger, stream_id, event, |server| {
+                    server.read(descriptors, stream_id)
+                })
+            })
             .unwrap_or_resume()
     }
 
@@ -401,10 +567,22 @@ impl<R: Runtime> ComputeClient<R> {
             })
             .collect::<Vec<_>>();
 
+        #[cfg(std_io)]
+        self.utilities
+            .recorder
+            .with(|recorder| recorder.create(&handle_base, &descriptors));
+
         let (size, memory) = (handle_base.size(), handle_base.memory);
+        let logger = self.utilities.logger.clone();
         self.device.submit(move |server| {
             server.initialize_memory(memory, size, stream_id);
-            server.write(descriptors, stream_id);
+            let event = logger.trace_activated().then(|| {
+                let bytes = copy_sizes(descriptors.iter().map(|(descriptor, _)| descriptor));
+                TraceEvent::new(TraceOp::Write, "write", stream_id, bytes)
+            });
+            traced(server, &logger, stream_id, event, |server| {
+                server.write(descriptors, stream_id)
+            });
         });
 
         Ok(layouts)
@@ -435,10 +613,22 @@ impl<R: Runtime> ComputeClient<R> {
             })
             .collect::<Vec<_>>();
 
+        #[cfg(std_io)]
+        self.utilities
+            .recorder
+            .with(|recorder| recorder.create(&handle_base, &descriptors));
+
         let (size, memory) = (handle_base.size(), handle_base.memory);
+        let logger = self.utilities.logger.clone();
         self.device.submit(move |server| {
             server.initialize_memory(memory, size, stream_id);
-            server.write(descriptors, stream_id);
+            let event = logger.trace_activated().then(|| {
+                let bytes = copy_sizes(descriptors.iter().map(|(descriptor, _)| descriptor));
+                TraceEvent::new(TraceOp::Write, "write", stream_id, bytes)
+            });
+            traced(server, &logger, stream_id, event, |server| {
+                server.write(descriptors, stream_id)
+            });
         });
 
         Ok(layouts)
@@ -519,8 +709,19 @@ impl<R: Runtime> ComputeClient<R> {
         let stream_id = self.stream_id();
         let descriptor =
             CopyDescriptor::new(handle.clone().binding(), [data.len()].into(), [1].into(), 1);
+        #[cfg(std_io)]
+        self.utilities
+            .recorder
+            .with(|recorder| recorder.write(&descriptor, &data));
+        let logger = self.utilities.logger.clone();
         self.device.submit(move |server| {
-            server.write(vec![(descriptor, data)], stream_id);
+            let event = logger.trace_activated().then(|| {
+                let bytes = vec![descriptor.handle.size_in_used()];
+                TraceEvent::new(TraceOp::Write, "write", stream_id, bytes)
+            });
+            traced(server, &logger, stream_id, event, |server| {
+                server.write(vec![(descriptor, data)], stream_id)
+            });
         });
     }
 
@@ -642,6 +843,11 @@ impl<R: Runtime> ComputeClient<R> {
         let stream_id = self.stream_id();
         let (handle_base, layouts) = self.utilities.layout_policy.apply(stream_id, &descriptors);
 
+        #[cfg(std_io)]
+        self.utilities
+            .recorder
+            .with(|recorder| recorder.empty(&handle_base));
+
         let (size, memory) = (handle_base.size(), handle_base.memory);
         self.device.submit(move |server| {
             server.initialize_memory(memory, size, stream_id);
@@ -674,7 +880,8 @@ impl<R: Runtime> ComputeClient<R> {
     /// Marks the given [Bytes] as being a staging buffer, maybe transferring it to pinned memory
     /// for faster data transfer with compute device.
     ///
-    /// TODO: This blocks the compute queue, so it will drop the compute utilization.
+    /// TODO: This blocks the compute queue, so it will drop the compute utilization. Prefer
+    /// [`Self::upload`] to load large amounts of data.
     pub fn staging<'a, I>(&self, bytes: I, file_only: bool)
     where
         I: Iterator<Item = &'a mut Bytes>,
@@ -725,6 +932,21 @@ impl<R: Runtime> ComputeClient<R> {
             });
     }
 
+    /// Uploads every source to a new resource, in chunks staged through pinned memory.
+    ///
+    /// Sources are uploaded lazily, one ahead of each call to [`Upload::next`], which returns
+    /// a handle once all of its chunks are copied to the device. Reading a chunk from its
+    /// source overlaps with the device copy of the previous one, and file or memory-mapped
+    /// [`Bytes`] are read chunk by chunk rather than all at once, so the host never holds a
+    /// full copy of a large source. See [`Upload`] for the details.
+    pub fn upload<I: IntoIterator<Item = Bytes>>(
+        &self,
+        sources: I,
+        options: UploadOptions,
+    ) -> Upload<R, I::IntoIter> {
+        Upload::new(self.clone(), sources.into_iter(), options)
+    }
+
     /// Transfer data from one client to another
     #[cfg_attr(
         feature = "tracing",
@@ -803,6 +1025,12 @@ impl<R: Runtime> ComputeClient<R> {
         let src = src.binding();
         let dst = dst.binding();
 
+        // Written outside of the replay log, read back again on its next use.
+        #[cfg(std_io)]
+        self.utilities
+            .recorder
+            .with(|recorder| recorder.forget(&dst));
+
         self.ensure_init_collective(device_ids.clone());
 
         self.device.submit(move |server| {
@@ -839,10 +1067,16 @@ impl<R: Runtime> ComputeClient<R> {
         self.ensure_init_collective(device_ids.clone());
         dst_server.ensure_init_collective(device_ids);
 
+        let logger = self.utilities.logger.clone();
         self.device.submit(move |server_src| {
-            server_src
-                .send(src_descriptor, dtype, stream_id_src, device_id_dst)
-                .unwrap()
+            let event = logger.trace_activated().then(|| {
+                let bytes = vec![src_descriptor.handle.size_in_used()];
+                TraceEvent::new(TraceOp::Copy, "send", stream_id_src, bytes)
+            });
+            traced(server_src, &logger, stream_id_src, event, |server| {
+                server.send(src_descriptor, dtype, stream_id_src, device_id_dst)
+            })
+            .unwrap()
         });
 
         dst_server.device.submit(move |server_dst| {
@@ -888,6 +1122,9 @@ impl<R: Runtime> ComputeClient<R> {
         // the time it reaches the server thread, that context is gone.
         let launch_mode = crate::dry_run::launch_mode();
 
+        #[cfg(std_io)]
+        self.record_launch(&kernel, &count, &bindings);
+
         let level = self.utilities.logger.profile_level();
 
         match level {
@@ -895,7 +1132,13 @@ impl<R: Runtime> ComputeClient<R> {
                 let utilities = self.utilities.clone();
                 self.device.submit(move |state| {
                     let name = kernel.name();
-                    unsafe { state.launch(kernel, count, bindings, stream_id, launch_mode) };
+                    let event = utilities
+                        .logger
+                        .trace_activated()
+                        .then(|| launch_event(&kernel, stream_id, &count, &bindings));
+                    traced(state, &utilities.logger, stream_id, event, |state| unsafe {
+                        state.launch(kernel, count, bindings, stream_id, launch_mode)
+                    });
 
                     if matches!(level, Some(ProfileLevel::ExecutionOnly)) {
                         let info = type_name_format(name, TypeNameFormatLevel::Balanced);
@@ -907,19 +1150,25 @@ impl<R: Runtime> ComputeClient<R> {
                 let name = kernel.name();
                 let kernel_id = kernel.id();
                 let context = self.device.clone();
+                let logger = self.utilities.logger.clone();
                 let count_moved = count.clone();
                 let (result, profile) = self
                     .profile(
                         move || {
                             context
-                                .submit_blocking(move |state| unsafe {
-                                    state.launch(
-                                        kernel,
-                                        count_moved,
-                                        bindings,
-                                        stream_id,
-                                        launch_mode,
-                                    )
+                                .submit_blocking(move |state| {
+                                    let event = logger.trace_activated().then(|| {
+                                        launch_event(&kernel, stream_id, &count_moved, &bindings)
+                                    });
+                                    traced(state, &logger, stream_id, event, |state| unsafe {
+                                        state.launch(
+                                            kernel,
+                                            count_moved,
+                                            bindings,
+                                            stream_id,
+                                            launch_mode,
+                                        )
+                                    })
                                 })
                                 .unwrap_or_resume()
                         },
@@ -985,12 +1234,49 @@ impl<R: Runtime> ComputeClient<R> {
     ///
     /// Returns an error on backends without graph support.
     pub fn start_capture(&self) -> Result<(), ServerError> {
+        #[cfg(std_io)]
+        self.utilities.recorder.with(|recorder| {
+            recorder.skip("graph capture", "graphs can't be recorded");
+        });
         let stream_id = self.stream_id();
         self.device
             .submit_blocking(move |server| server.begin_capture(stream_id))
             .unwrap_or_resume()
     }
 
+    /// Mark the buffer of `handle` as a parameter of the graph recording on
+    /// this client's stream: every launch recorded from now on that binds it
+    /// can be rebound with [`Graph::update`]. Call it between
+    /// [`start_capture`](Self::start_capture) and the launches using the buffer.
+    pub fn graph_binding_param(&self, handle: &Handle) -> Result<GraphParamId, ServerError> {
+        let stream_id = self.stream_id();
+        let value = GraphParamValue::Binding(handle.clone().binding());
+        self.device
+            .submit_blocking(move |server| server.graph_param(value, stream_id))
+            .unwrap_or_resume()
+    }
+
+    /// Mark a scalar argument as a parameter of the graph recording on this
+    /// client's stream, so [`Graph::update`] can change it. Pass `placeholder`
+    /// as the argument of the launches recorded from now on, with the type the
+    /// kernel receives it as.
+    ///
+    /// Prefer passing it as an `InputScalar` marked with the returned id
+    /// (`InputScalar::graph_param`), which records exactly where the launch
+    /// packed it. An unmarked argument is matched by value among the launch's
+    /// scalars, so pick a placeholder no other scalar of those launches takes
+    /// (a launch with two matches fails the capture).
+    pub fn graph_scalar_param<T: bytemuck::Pod>(
+        &self,
+        placeholder: T,
+    ) -> Result<GraphParamId, ServerError> {
+        let stream_id = self.stream_id();
+        let value = GraphParamValue::Scalar(bytemuck::bytes_of(&placeholder).to_vec());
+        self.device
+            .submit_blocking(move |server| server.graph_param(value, stream_id))
+            .unwrap_or_resume()
+    }
+
     /// Stop recording and return the captured graph, ready to
     /// [`replay`](Graph::replay).
     pub fn stop_capture(&self) -> Result<Graph<R>, ServerError> {
@@ -1005,17 +1291,58 @@ impl<R: Runtime> ComputeClient<R> {
                 id,
                 device: self.device.clone(),
                 stream_id,
+                #[cfg(std_io)]
+                utilities: self.utilities.clone(),
+            }),
+        })
+    }
+
+    /// Record an event after the work enqueued so far on this client's stream.
+    pub fn record_event(&self) -> Result<StreamEvent<R>, ServerError> {
+        let stream_id = self.stream_id();
+        let id = self
+            .device
+            .submit_blocking(move |server| server.event_record(stream_id))
+            .unwrap_or_resume()?;
+
+        Ok(StreamEvent {
+            inner: Arc::new(StreamEventHandle {
+                id,
+                device: self.device.clone(),
             }),
         })
     }
 
+    /// Make the work enqueued on this client's stream from now on wait for `event`.
+    ///
+    /// The host isn't blocked. Events are only known to the device that recorded
+    /// them, waiting on one from another device fails.
+    pub fn wait_event(&self, event: &StreamEvent<R>) -> Result<(), ServerError> {
+        let stream_id = self.stream_id();
+        let id = event.inner.id;
+        self.device
+            .submit_blocking(move |server| server.event_wait(id, stream_id))
+            .unwrap_or_resume()
+    }
+
     /// Wait for the completion of every task in the server.
     pub fn sync(&self) -> DynFut<Result<(), ServerError>> {
         let stream_id = self.stream_id();
 
+        let logger = self.utilities.logger.clone();
         let fut = self
             .device
-            .submit_blocking(move |server| server.sync(stream_id))
+            .submit_blocking(move |server| {
+                traced(
+                    server,
+                    &logger,
+                    stream_id,
+                    logger
+                        .trace_activated()
+                        .then(|| TraceEvent::new(TraceOp::Sync, "sync", stream_id, Vec::new())),
+                    |server| server.sync(stream_id),
+                )
+            })
             .unwrap_or_resume();
 
         self.utilities.logger.profile_summary();
@@ -1366,4 +1693,104 @@ impl<R: Runtime> ComputeClient<R> {
         let mut throughputs = ThroughputBenchmarker::new(cache);
         throughputs.measure(key, kernel_config)
     }
+
+    /// The memory curve of `access` from the points already measured on this device, without
+    /// measuring anything. `None` until at least one point was probed, see
+    /// `cubecl_std::throughput::measure_memory_curve`.
+    pub fn cached_memory_curve(&self, access: MemoryAccess) -> Option<MemoryCurve> {
+        let cache = ThroughputCache::get_for_device(&self.device_key());
+        let cache = cache.lock();
+        let cap = access.working_set_cap(self.properties().memory.max_page_size);
+
+        let points = working_set_sweep(cap).into_iter().filter_map(|bytes| {
+            let key = ThroughputKey {
+                mode: ThroughputMode::MemoryWorkingSet { access, bytes },
+            };
+            let value = *cache.get(&key)?;
+            Some(MemoryPoint { bytes, value })
+        });
+        let curve = MemoryCurve::new(access, points);
+
+        (!curve.points().is_empty()).then_some(curve)
+    }
+
+    /// The arithmetic rate of `dtype` already measured on this device, in operations per
+    /// second, without measuring anything.
+    pub fn cached_compute_ceiling(&self, dtype: ElemType) -> Option<f64> {
+        let cache = ThroughputCache::get_for_device(&self.device_key());
+        let key = ThroughputKey {
+            mode: ThroughputMode::ComputeDirect { dtype },
+        };
+        let rate = cache.lock().get(&key)?.ops_per_s();
+
+        (rate.is_finite() && rate > 0.0).then_some(rate)
+    }
+
+    /// The roofline of launching a kernel of the given `cost` over `units` units, held against
+    /// the ceilings already measured on this device. `None` without a cached memory curve.
+    pub fn roofline(&self, cost: &KernelCost, units: u64) -> Option<RooflineEstimate> {
+        let curve = self.cached_memory_curve(MemoryAccess::Copy)?;
+        Some(cost.roofline(units, &curve, |dtype| self.cached_compute_ceiling(dtype)))
+    }
+
+    /// One line per kernel compiled so far with its static [cost](KernelCost), followed by
+    /// where it sits on this device's roofline when the ceilings are cached: the throughput it
+    /// can attain at most, and whether memory or compute caps it.
+    pub fn roofline_report(&self) -> String {
+        let bandwidth = self
+            .cached_memory_curve(MemoryAccess::Copy)
+            .and_then(|curve| curve.ceiling_at(u64::MAX));
+        let mut report = String::new();
+
+        for (name, cost) in kernel_costs() {
+            let name = type_name_format(&name, TypeNameFormatLevel::Balanced);
+            report += &format!("{name}: {cost}\n");
+
+            let peak = cost
+                .dominant_type()
+                .and_then(|dtype| self.cached_compute_ceiling(dtype));
+            let (Some(bandwidth), Some(peak), Some(intensity)) =
+                (bandwidth, peak, cost.intensity())
+            else {
+                continue;
+            };
+
+            let ridge = peak / bandwidth;
+            let (bound, attainable) = if intensity < ridge {
+                ("memory", intensity * bandwidth)
+            } else {
+                ("compute", peak)
+            };
+            report += &format!(
+                "    {bound}-bound, at most {:.2} GOPS (ridge at {ridge:.2} ops/B)\n",
+                attainable / 1e9
+            );
+        }
+
+        report
+    }
+}
+
+/// The sizes of the buffers behind copy descriptors, for the timeline trace.
+fn copy_sizes<'a>(descriptors: impl Iterator<Item = &'a CopyDescriptor>) -> Vec<u64> {
+    descriptors
+        .map(|descriptor| descriptor.handle.size_in_used())
+        .collect()
+}
+
+/// The trace event of a launch, built before the launch consumes its arguments.
+fn launch_event<K: KernelMetadata>(
+    kernel: &K,
+    stream_id: StreamId,
+    count: &CubeCount,
+    bindings: &KernelArguments,
+) -> TraceEvent {
+    let cube_dim = kernel.id().cube_dim;
+    TraceEvent::launch(
+        kernel.name(),
+        stream_id,
+        count,
+        [cube_dim.x, cube_dim.y, cube_dim.z],
+        bindings,
+    )
 }
@@ -0,0 +1,939 @@
+//! Deterministic replay logs of client operations.
+//!
+//! While a [`ComputeClient`] is [recording](ComputeClient::start_recording), every buffer it
+//! allocates or writes, every launch and every read goes to a log file, one JSON [`RecordedOp`]
+//! per line. [`ReplayLog`] loads the file back and re-executes it on a client of any runtime,
+//! comparing what each read returns with what the recording read. A bug that only reproduces
+//! inside a large application can then be reproduced from the log alone.
+//!
+//! Kernels are logged once, with their id, settings, info and expanded IR. The IR is printed in
+//! a form it can be parsed back from, so [`ReplayLog::replay`] compiles every kernel from the
+//! log alone. The expansion already happened on the recording device, so a replay on another
+//! runtime runs the kernels expanded for the recording one.
+//!
+//! A buffer first seen in a launch or a read, because it was created before the recording
+//! started or outside of [`create`](ComputeClient::create)/[`empty`](ComputeClient::empty)/
+//! [`write`](ComputeClient::write) (by an [upload](ComputeClient::upload) or a
+//! [transfer](ComputeClient::to_client), say), is read back and logged as a write of its contents
+//! at that point, so the replay starts from the same bytes. A buffer an
+//! [`all_reduce`](ComputeClient::all_reduce) writes to is forgotten and read back the same way
+//! the next time it is used.
+//!
+//! What can't be logged, a graph capture or replay or a launch binding a tensor map, is logged
+//! as [skipped](RecordedOp::Skipped). A replay fails when it reaches it, rather than running on
+//! buffers the recording changed in a way the log doesn't know about.
+//!
+//! The replay runs every operation on the replaying client's stream, in log order. The streams
+//! the operations were recorded on aren't kept, so a bug that depends on how streams interleave
+//! won't reproduce.
+
+use super::ComputeClient;
+use crate::{
+    compiler::{CompilationError, Compiler, CubeTask},
+    id::KernelId,
+    kernel::{CompiledKernel, KernelDefinition, KernelMetadata},
+    runtime::Runtime,
+    server::{
+        BufferBinding, ComputeServer, CopyDescriptor, CubeCount, Handle, KernelArguments,
+        KernelResource, MetadataBindingInfo, ServerError,
+    },
+};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::sync::atomic::{AtomicBool, Ordering};
+use cubecl_common::bytes::Bytes;
+use cubecl_environment::{
+    backtrace::BackTrace,
+    collections::{HashMap, HashSet},
+    future::DynFut,
+    sync::Mutex,
+};
+use cubecl_ir::{ElemType, Scope, metadata::Info, settings::KernelSettings};
+use cubecl_zspace::{Shape, Strides};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufWriter, Write};
+use std::path::Path;
+
+/// One operation of a replay log, see the [module documentation](self).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RecordedOp {
+    /// The first line of a log.
+    Start {
+        /// The name of the runtime the log was recorded on.
+        runtime: String,
+    },
+    /// A kernel launched for the first time; launches refer to it by index.
+    Kernel(RecordedKernel),
+    /// A buffer allocated, referred to by `memory` in the operations after it.
+    Alloc {
+        /// The id of the buffer in the log.
+        memory: u64,
+        /// The size of the buffer, in bytes.
+        size: u64,
+    },
+    /// Bytes written to a buffer.
+    Write {
+        /// Where the bytes were written.
+        copy: RecordedCopy,
+        /// The bytes written, logged as base64.
+        #[serde(with = "base64_bytes")]
+        data: Bytes,
+    },
+    /// A kernel launch.
+    Launch(RecordedLaunch),
+    /// A read issued. What it returned is logged separately as a [`ReadBack`](Self::ReadBack),
+    /// once it completed.
+    Read {
+        /// The id of the read, shared with its read-back.
+        read: u64,
+        /// The buffers read.
+        copies: Vec<RecordedCopy>,
+    },
+    /// What a read returned, one entry per buffer read.
+    ReadBack {
+        /// The id of the read.
+        read: u64,
+        /// The bytes returned, logged as base64.
+        #[serde(with = "base64_bytes::list")]
+        data: Vec<Bytes>,
+    },
+    /// An operation that couldn't be recorded. A replay fails when it reaches it.
+    Skipped {
+        /// The operation.
+        op: String,
+        /// Why it wasn't recorded.
+        reason: String,
+    },
+}
+
+/// A kernel as logged the first time it was launched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedKernel {
+    /// The index launches refer to the kernel by.
+    pub index: usize,
+    /// The name of the kernel.
+    pub name: String,
+    /// The [kernel id](KernelId), rendered as text.
+    pub id: String,
+    /// The cube dim the kernel was launched with.
+    pub cube_dim: [u32; 3],
+    /// The settings the kernel was expanded with.
+    pub settings: KernelSettings,
+    /// The scalars and metadata the kernel expects.
+    pub info: Info,
+    /// The expanded IR of the kernel, the module [`Scope::parse`] rebuilds it from.
+    pub ir: String,
+}
+
+/// A binding as logged: a buffer of the log and the range of it that is bound.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedBinding {
+    /// The id of the buffer in the log.
+    pub memory: u64,
+    /// Memory offset in bytes.
+    pub offset_start: Option<u64>,
+    /// Memory offset in bytes.
+    pub offset_end: Option<u64>,
+}
+
+/// A copy to or from a buffer, as logged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCopy {
+    /// The range of the buffer copied.
+    pub binding: RecordedBinding,
+    /// Shape of the copy.
+    pub shape: Shape,
+    /// Strides of the copy.
+    pub strides: Strides,
+    /// Size of each element of the copy.
+    pub elem_size: usize,
+}
+
+/// A kernel launch, as logged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedLaunch {
+    /// The [index](RecordedKernel::index) of the kernel launched.
+    pub kernel: usize,
+    /// The cube count of the launch.
+    pub count: RecordedCount,
+    /// The buffers bound, in order.
+    pub bindings: Vec<RecordedBinding>,
+    /// The packed scalars and metadata.
+    pub info: Vec<u64>,
+    /// Length of the scalars at the start of `info`.
+    #[serde(default)]
+    pub scalars_len: usize,
+    /// Start of the dynamically sized portion of `info`.
+    pub dynamic_metadata_offset: usize,
+}
+
+/// A cube count, as logged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RecordedCount {
+    /// A known count of x, y, z cubes.
+    Static([u32; 3]),
+    /// A count read from this buffer at launch time.
+    Dynamic(RecordedBinding),
+}
+
+/// The recording state shared by every client of a device.
+///
+/// Checked on every allocation, write, launch and read, so the flag keeps the check down to an
+/// atomic load when nothing is being recorded.
+pub(crate) struct RecorderSlot {
+    active: AtomicBool,
+    recorder: Mutex<Option<OpRecorder>>,
+}
+
+impl Default for RecorderSlot {
+    fn default() -> Self {
+        Self {
+            active: AtomicBool::new(false),
+            recorder: Mutex::new(None),
+        }
+    }
+}
+
+impl core::fmt::Debug for RecorderSlot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecorderSlot")
+            .field("active", &self.active.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl RecorderSlot {
+    /// Whether operations are being recorded.
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Run `func` on the recorder, `None` when nothing is being recorded.
+    pub(crate) fn with<T>(&self, func: impl FnOnce(&mut OpRecorder) -> T) -> Option<T> {
+        if !self.is_active() {
+            return None;
+        }
+        self.recorder.lock().as_mut().map(func)
+    }
+}
+
+/// Writes the operations of a recording to its log.
+pub(crate) struct OpRecorder {
+    writer: BufWriter<std::fs::File>,
+    kernels: HashMap<KernelId, usize>,
+    buffers: HashSet<u64>,
+    next_read: u64,
+    /// The first write to the log that failed, reported by
+    /// [`stop_recording`](ComputeClient::stop_recording).
+    error: Option<std::io::Error>,
+}
+
+impl OpRecorder {
+    fn push(&mut self, op: &RecordedOp) {
+        if self.error.is_some() {
+            return;
+        }
+        let result = serde_json::to_writer(&mut self.writer, op)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+    }
+
+    fn alloc(&mut self, memory: u64, size: u64) {
+        if self.buffers.insert(memory) {
+            self.push(&RecordedOp::Alloc { memory, size });
+        }
+    }
+
+    /// Log a buffer allocated with `writes` as its initial contents.
+    pub(crate) fn create(&mut self, handle: &Handle, writes: &[(CopyDescriptor, Bytes)]) {
+        self.alloc(memory_id(&handle.clone().binding()), handle.size());
+        for (descriptor, data) in writes {
+            self.write(descriptor, data);
+        }
+    }
+
+    /// Log a buffer allocated without contents.
+    pub(crate) fn empty(&mut self, handle: &Handle) {
+        self.alloc(memory_id(&handle.clone().binding()), handle.size());
+    }
+
+    /// Log bytes written to a buffer.
+    pub(crate) fn write(&mut self, descriptor: &CopyDescriptor, data: &Bytes) {
+        self.alloc(memory_id(&descriptor.handle), descriptor.handle.size());
+        self.push(&RecordedOp::Write {
+            copy: RecordedCopy::new(descriptor),
+            data: data.clone(),
+        });
+    }
+
+    /// Forget a buffer written outside of the log, so it is read back the next time it is used.
+    pub(crate) fn forget(&mut self, binding: &BufferBinding) {
+        self.buffers.remove(&memory_id(binding));
+    }
+
+    /// Log an operation the log can't replay.
+    pub(crate) fn skip(&mut self, op: impl Into<String>, reason: impl Into<String>) {
+        self.push(&RecordedOp::Skipped {
+            op: op.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// The buffers among `bindings` the log hasn't seen yet, as bindings over the whole buffer.
+    fn unseen<'a>(&self, bindings: impl Iterator<Item = &'a BufferBinding>) -> Vec<BufferBinding> {
+        let mut unseen = Vec::new();
+        for binding in bindings {
+            let memory = memory_id(binding);
+            if !self.buffers.contains(&memory)
+                && !unseen.iter().any(|seen| memory_id(seen) == memory)
+            {
+                unseen.push(BufferBinding {
+                    offset_start: None,
+                    offset_end: None,
+                    ..binding.clone()
+                });
+            }
+        }
+        unseen
+    }
+
+    /// Log the contents of buffers read back before their first use.
+    fn snapshots(&mut self, snapshots: Vec<(CopyDescriptor, Result<Vec<Bytes>, ServerError>)>) {
+        for (descriptor, data) in snapshots {
+            match data {
+                Ok(mut data) => self.write(&descriptor, &data.remove(0)),
+                Err(err) => self.skip(
+                    format!("contents of buffer {}", memory_id(&descriptor.handle)),
+                    format!("reading it back failed: {err}"),
+                ),
+            }
+        }
+    }
+
+    fn launch<C: Compiler>(
+        &mut self,
+        kernel: &dyn CubeTask<C>,
+        count: &CubeCount,
+        bindings: &KernelArguments,
+    ) {
+        let mut resources = Vec::with_capacity(bindings.resources.len());
+        for resource in bindings.resources.iter() {
+            match resource {
+                KernelResource::Buffer(binding) => resources.push(RecordedBinding::new(binding)),
+                KernelResource::TensorMap(_) => {
+                    self.skip(
+                        format!("launch of {}", kernel.name()),
+                        "tensor map bindings can't be recorded",
+                    );
+                    return;
+                }
+            }
+        }
+
+        let id = kernel.id();
+        let next = self.kernels.len();
+        let index = *self.kernels.entry(id.clone()).or_insert(next);
+        if index == next {
+            let cube_dim = id.cube_dim;
+            let definition = kernel.define();
+            self.push(&RecordedOp::Kernel(RecordedKernel {
+                index,
+                name: kernel.name().into(),
+                id: format!("{id}"),
+                cube_dim: [cube_dim.x, cube_dim.y, cube_dim.z],
+                ir: format!("{}", definition.body),
+                settings: definition.settings,
+                info: definition.info,
+            }));
+        }
+
+        let count = match count {
+            CubeCount::Static(x, y, z) => RecordedCount::Static([*x, *y, *z]),
+            CubeCount::Dynamic(binding) => RecordedCount::Dynamic(RecordedBinding::new(binding)),
+        };
+        self.push(&RecordedOp::Launch(RecordedLaunch {
+            kernel: index,
+            count,
+            bindings: resources,
+            info: bindings.info.data.clone(),
+            scalars_len: bindings.info.scalars_len,
+            dynamic_metadata_offset: bindings.info.dynamic_metadata_offset,
+        }));
+    }
+
+    /// Log a read issued, returning the id its read-back is logged under.
+    pub(crate) fn read(&mut self, descriptors: &[CopyDescriptor]) -> u64 {
+        let read = self.next_read;
+        self.next_read += 1;
+        self.push(&RecordedOp::Read {
+            read,
+            copies: descriptors.iter().map(RecordedCopy::new).collect(),
+        });
+        read
+    }
+
+    /// Log what a read returned.
+    pub(crate) fn read_back(&mut self, read: u64, data: &[Bytes]) {
+        self.push(&RecordedOp::ReadBack {
+            read,
+            data: data.to_vec(),
+        });
+    }
+}
+
+impl RecordedBinding {
+    fn new(binding: &BufferBinding) -> Self {
+        Self {
+            memory: memory_id(binding),
+            offset_start: binding.offset_start,
+            offset_end: binding.offset_end,
+        }
+    }
+
+    fn resolve(&self, buffers: &HashMap<u64, Handle>) -> Option<BufferBinding> {
+        let mut handle = buffers.get(&self.memory)?.clone();
+        if let Some(offset) = self.offset_start {
+            handle = handle.offset_start(offset);
+        }
+        if let Some(offset) = self.offset_end {
+            handle = handle.offset_end(offset);
+        }
+        Some(handle.binding())
+    }
+}
+
+impl RecordedCopy {
+    fn new(descriptor: &CopyDescriptor) -> Self {
+        Self {
+            binding: RecordedBinding::new(&descriptor.handle),
+            shape: descriptor.shape.clone(),
+            strides: descriptor.strides.clone(),
+            elem_size: descriptor.elem_size,
+        }
+    }
+
+    fn resolve(&self, buffers: &HashMap<u64, Handle>) -> Option<CopyDescriptor> {
+        Some(CopyDescriptor::new(
+            self.binding.resolve(buffers)?,
+            self.shape.clone(),
+            self.strides.clone(),
+            self.elem_size,
+        ))
+    }
+}
+
+fn memory_id(binding: &BufferBinding) -> u64 {
+    binding.memory.descriptor().id.value as u64
+}
+
+/// A replay log read back from a file, see the [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct ReplayLog {
+    ops: Vec<RecordedOp>,
+}
+
+/// What a [replay](ReplayLog::replay) found.
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// The number of launches replayed.
+    pub launches: usize,
+    /// The number of reads replayed and compared.
+    pub reads: usize,
+    /// The reads that returned other bytes than in the recording.
+    pub mismatches: Vec<ReadMismatch>,
+}
+
+impl ReplayReport {
+    /// Whether every replayed read returned the recorded bytes.
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// A buffer read during a replay that returned other bytes than in the recording.
+///
+/// The comparison is bitwise. Both sides are kept, so a caller replaying on another runtime can
+/// compare floats with a tolerance instead.
+#[derive(Debug, Clone)]
+pub struct ReadMismatch {
+    /// The id of the read in the log.
+    pub read: u64,
+    /// The position of the buffer in the read.
+    pub index: usize,
+    /// The offset of the first byte that differs. A buffer read on one side only is empty on
+    /// the other, so it differs at offset 0.
+    pub first_difference: usize,
+    /// The bytes the recording read.
+    pub recorded: Bytes,
+    /// The bytes the replay read.
+    pub replayed: Bytes,
+}
+
+impl ReplayLog {
+    /// Load a log file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Load a log from any reader, one JSON operation per line.
+    ///
+    /// A line that isn't an operation fails the load: an operation missing from the log would
+    /// make the replay diverge from the recording.
+    pub fn from_reader(reader: impl BufRead) -> std::io::Result<Self> {
+        let mut ops = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            ops.push(serde_json::from_str(&line).map_err(std::io::Error::from)?);
+        }
+
+        Ok(Self { ops })
+    }
+
+    /// The operations of the log, in order.
+    pub fn ops(&self) -> &[RecordedOp] {
+        &self.ops
+    }
+
+    /// The kernels the log launches.
+    pub fn kernels(&self) -> impl Iterator<Item = &RecordedKernel> {
+        self.ops.iter().filter_map(|op| match op {
+            RecordedOp::Kernel(kernel) => Some(kernel),
+            _ => None,
+        })
+    }
+
+    /// Re-execute the log on `client`, comparing every read with the recording.
+    ///
+    /// Every kernel is compiled from the IR logged with it. The replay fails at the first
+    /// operation the recording [skipped](RecordedOp::Skipped), or that it can't run: a kernel
+    /// whose IR doesn't parse, or a buffer the log never allocated.
+    pub fn replay<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+    ) -> Result<ReplayReport, ServerError> {
+        self.replay_with(client, |_| None)
+    }
+
+    /// [Replay](Self::replay) the log, running the kernel `resolve` provides for a
+    /// [`RecordedKernel`] instead of the one compiled from its IR, to try a fix on the recorded
+    /// operations for instance.
+    ///
+    /// `resolve` is called once per launch. The logged IR is compiled when it returns `None`.
+    pub fn replay_with<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+        mut resolve: impl FnMut(&RecordedKernel) -> Option<Box<dyn CubeTask<R::Compiler>>>,
+    ) -> Result<ReplayReport, ServerError> {
+        let mut report = ReplayReport::default();
+        let mut buffers = HashMap::<u64, Handle>::new();
+        let mut kernels = HashMap::<usize, ReplayKernel<'_>>::new();
+        let mut reads = HashMap::<u64, Vec<Bytes>>::new();
+
+        for op in self.ops.iter() {
+            match op {
+                RecordedOp::Start { .. } => {}
+                RecordedOp::Kernel(kernel) => {
+                    // Parsed once here, so a launch fails with the parse error rather than
+                    // panicking in the server.
+                    let invalid = Scope::parse(kernel.settings.clone(), &kernel.ir).err();
+                    kernels.insert(kernel.index, ReplayKernel { kernel, invalid });
+                }
+                RecordedOp::Alloc { memory, size } => {
+                    buffers.insert(*memory, client.empty(*size as usize));
+                }
+                RecordedOp::Write { copy, data } => {
+                    let descriptor = copy.resolve(&buffers).ok_or_else(|| {
+                        diverged(format!(
+                            "write to buffer {}, never allocated",
+                            copy.binding.memory
+                        ))
+                    })?;
+                    client.replay_write(descriptor, data.clone());
+                }
+                RecordedOp::Launch(launch) => {
+                    let (kernel, count, bindings) =
+                        replay_launch(launch, &kernels, &buffers, &mut resolve)
+                            .map_err(diverged)?;
+                    client.launch(kernel, count, bindings);
+                    report.launches += 1;
+                }
+                RecordedOp::Read { read, copies } => {
+                    let descriptors = copies
+                        .iter()
+                        .map(|copy| copy.resolve(&buffers))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| {
+                            diverged(format!("read {read}, of a buffer never allocated"))
+                        })?;
+                    let data = cubecl_environment::future::reader::read_sync(
+                        client.read_unrecorded(descriptors),
+                    )?;
+                    reads.insert(*read, data);
+                }
+                RecordedOp::ReadBack { read, data } => {
+                    let Some(replayed) = reads.remove(read) else {
+                        continue;
+                    };
+                    report.reads += 1;
+                    // A buffer only one side read compares against nothing, so a replay that
+                    // returns another number of buffers is a mismatch as well.
+                    let count = data.len().max(replayed.len());
+                    let mut replayed = replayed.into_iter();
+                    for index in 0..count {
+                        let recorded = data
+                            .get(index)
+                            .cloned()
+                            .unwrap_or_else(|| Bytes::from_bytes_vec(Vec::new()));
+                        let replayed = replayed
+                            .next()
+                            .unwrap_or_else(|| Bytes::from_bytes_vec(Vec::new()));
+                        let first_difference = recorded
+                            .iter()
+                            .zip(replayed.iter())
+                            .position(|(lhs, rhs)| lhs != rhs)
+                            .or_else(|| {
+                                (recorded.len() != replayed.len())
+                                    .then_some(recorded.len().min(replayed.len()))
+                            });
+                        if let Some(first_difference) = first_difference {
+                            report.mismatches.push(ReadMismatch {
+                                read: *read,
+                                index,
+                                first_difference,
+                                recorded,
+                                replayed,
+                            });
+                        }
+                    }
+                }
+                RecordedOp::Skipped { op, reason } => {
+                    return Err(diverged(format!("{op} wasn't recorded: {reason}")));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn diverged(reason: String) -> ServerError {
+    ServerError::Generic {
+        reason: format!("The replay can't follow the recording: {reason}"),
+        backtrace: BackTrace::capture(),
+    }
+}
+
+/// A kernel of the log being replayed, with why its IR doesn't parse if it doesn't.
+struct ReplayKernel<'a> {
+    kernel: &'a RecordedKernel,
+    invalid: Option<String>,
+}
+
+type ReplayedLaunch<C> = (Box<dyn CubeTask<C>>, CubeCount, KernelArguments);
+
+fn replay_launch<C: Compiler>(
+    launch: &RecordedLaunch,
+    kernels: &HashMap<usize, ReplayKernel<'_>>,
+    buffers: &HashMap<u64, Handle>,
+    resolve: &mut impl FnMut(&RecordedKernel) -> Option<Box<dyn CubeTask<C>>>,
+) -> Result<ReplayedLaunch<C>, String> {
+    let ReplayKernel {
+        kernel: recorded,
+        invalid,
+    } = kernels
+        .get(&launch.kernel)
+        .ok_or_else(|| format!("launch of kernel {}, never logged", launch.kernel))?;
+    let kernel = match (resolve(recorded), invalid) {
+        (Some(kernel), _) => kernel,
+        (None, None) => Box::new(ReplayedKernel::new(recorded)),
+        (None, Some(err)) => return Err(format!("launch of {}: {err}", recorded.name)),
+    };
+    let unallocated = || format!("launch of {}, on a buffer never allocated", recorded.name);
+
+    let count = match &launch.count {
+        RecordedCount::Static([x, y, z]) => CubeCount::Static(*x, *y, *z),
+        RecordedCount::Dynamic(binding) => {
+            CubeCount::Dynamic(binding.resolve(buffers).ok_or_else(unallocated)?)
+        }
+    };
+    let mut bindings = KernelArguments {
+        resources: Vec::with_capacity(launch.bindings.len()),
+        info: MetadataBindingInfo::new(
+            launch.info.clone(),
+            launch.scalars_len,
+            launch.dynamic_metadata_offset,
+        ),
+        marked_scalars: Vec::new(),
+    };
+    for binding in launch.bindings.iter() {
+        let binding = binding.resolve(buffers).ok_or_else(unallocated)?;
+        bindings.resources.push(KernelResource::Buffer(binding));
+    }
+
+    Ok((kernel, count, bindings))
+}
+
+/// A kernel compiled from the IR of its [log entry](RecordedKernel).
+struct ReplayedKernel {
+    settings: KernelSettings,
+    info: Info,
+    ir: String,
+}
+
+impl ReplayedKernel {
+    fn new(kernel: &RecordedKernel) -> Self {
+        Self {
+            settings: kernel.settings.clone(),
+            info: kernel.info.clone(),
+            ir: kernel.ir.clone(),
+        }
+    }
+}
+
+impl KernelMetadata for ReplayedKernel {
+    fn id(&self) -> KernelId {
+        // Keyed by the IR itself: the logged id only names the kernel type of the recording.
+        KernelId::new::<Self>()
+            .info(self.ir.clone())
+            .cube_dim(self.settings.cube_dim)
+            .mode(self.settings.execution_mode)
+            .address_type(self.settings.address_type)
+    }
+
+    fn address_type(&self) -> ElemType {
+        self.settings.address_type.unsigned_type()
+    }
+}
+
+impl<C: Compiler> CubeTask<C> for ReplayedKernel {
+    fn define(&self) -> KernelDefinition {
+        KernelDefinition {
+            body: Scope::parse(self.settings.clone(), &self.ir)
+                .expect("Should have been parsed when the kernel was logged"),
+            info: self.info.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+
+    fn compile(
+        &self,
+        definition: KernelDefinition,
+        compiler: &mut C,
+        compilation_options: &C::CompilationOptions,
+    ) -> Result<CompiledKernel<C>, CompilationError> {
+        let entrypoint_name = definition.settings.kernel_name.clone();
+        let cube_dim = definition.settings.cube_dim.into();
+        let repr = compiler.compile(definition, compilation_options)?;
+
+        Ok(CompiledKernel {
+            entrypoint_name,
+            debug_name: None,
+            source: repr.to_string(),
+            cost: compiler.kernel_cost(&repr),
+            repr: Some(repr),
+            cube_dim,
+            debug_info: None,
+        })
+    }
+}
+
+impl<R: Runtime> ComputeClient<R> {
+    /// Start recording every operation of this device's clients to a replay log at `path`, see
+    /// [`ReplayLog`].
+    ///
+    /// Recording is slow: every buffer written and read is copied into the log, and the first
+    /// launch of a buffer created before the recording started reads it back. It is a debugging
+    /// tool, not something to leave on.
+    pub fn start_recording(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut slot = self.utilities.recorder.recorder.lock();
+        if slot.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "a recording is already in progress on this device",
+            ));
+        }
+
+        let mut recorder = OpRecorder {
+            writer: BufWriter::new(std::fs::File::create(path)?),
+            kernels: HashMap::new(),
+            buffers: HashSet::new(),
+            next_read: 0,
+            error: None,
+        };
+        recorder.push(&RecordedOp::Start {
+            runtime: R::name(self).into(),
+        });
+        if let Some(err) = recorder.error.take() {
+            return Err(err);
+        }
+
+        *slot = Some(recorder);
+        self.utilities
+            .recorder
+            .active
+            .store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Stop the recording started with [`start_recording`](Self::start_recording), flushing the
+    /// log.
+    ///
+    /// Returns the first error writing the log ran into, if any. Does nothing when nothing is
+    /// being recorded.
+    pub fn stop_recording(&self) -> std::io::Result<()> {
+        self.utilities
+            .recorder
+            .active
+            .store(false, Ordering::Release);
+        let Some(mut recorder) = self.utilities.recorder.recorder.lock().take() else {
+            return Ok(());
+        };
+
+        match recorder.error.take() {
+            Some(err) => Err(err),
+            None => recorder.writer.flush(),
+        }
+    }
+
+    /// Log a launch, reading back the buffers it binds that the log hasn't seen yet first.
+    pub(crate) fn record_launch(
+        &self,
+        kernel: &<R::Server as ComputeServer>::Kernel,
+        count: &CubeCount,
+        bindings: &KernelArguments,
+    ) {
+        let dynamic = match count {
+            CubeCount::Static(..) => None,
+            CubeCount::Dynamic(binding) => Some(binding),
+        };
+        let buffers = bindings.resources.iter().map(|resource| match resource {
+            KernelResource::Buffer(binding) => binding,
+            KernelResource::TensorMap(map) => &map.binding,
+        });
+        let recorder = &self.utilities.recorder;
+        let Some(unseen) = recorder.with(|recorder| recorder.unseen(buffers.chain(dynamic))) else {
+            return;
+        };
+
+        let snapshots = self.snapshot(unseen);
+        recorder.with(|recorder| {
+            recorder.snapshots(snapshots);
+            recorder.launch(kernel.as_ref(), count, bindings);
+        });
+    }
+
+    /// Log a read and, once it completes, what it returned. The buffers the log hasn't seen yet
+    /// are logged first, as for a launch.
+    pub(crate) fn record_read(
+        &self,
+        descriptors: Vec<CopyDescriptor>,
+    ) -> DynFut<Result<Vec<Bytes>, ServerError>> {
+        let recorder = &self.utilities.recorder;
+        let unseen = recorder.with(|recorder| {
+            recorder.unseen(descriptors.iter().map(|descriptor| &descriptor.handle))
+        });
+        let snapshots = self.snapshot(unseen.unwrap_or_default());
+        let Some(read) = recorder.with(|recorder| {
+            recorder.snapshots(snapshots);
+            recorder.read(&descriptors)
+        }) else {
+            return self.read_unrecorded(descriptors);
+        };
+
+        let utilities = self.utilities.clone();
+        let result = self.read_unrecorded(descriptors);
+        Box::pin(async move {
+            let result = result.await;
+            if let Ok(data) = &result {
+                utilities
+                    .recorder
+                    .with(|recorder| recorder.read_back(read, data));
+            }
+            result
+        })
+    }
+
+    /// Read back the whole of each buffer in `unseen`, outside of the recorder's lock: the reads
+    /// go through the device.
+    fn snapshot(
+        &self,
+        unseen: Vec<BufferBinding>,
+    ) -> Vec<(CopyDescriptor, Result<Vec<Bytes>, ServerError>)> {
+        unseen
+            .into_iter()
+            .map(|binding| {
+                let size = binding.size();
+                let descriptor =
+                    CopyDescriptor::new(binding, [size as usize].into(), [1].into(), 1);
+                let data = cubecl_environment::future::reader::read_sync(
+                    self.read_unrecorded(vec![descriptor.clone()]),
+                );
+                (descriptor, data)
+            })
+            .collect()
+    }
+
+    fn replay_write(&self, descriptor: CopyDescriptor, data: Bytes) {
+        let stream_id = self.stream_id();
+        self.device.submit(move |server| {
+            server.write(vec![(descriptor, data)], stream_id);
+        });
+    }
+}
+
+/// Buffer contents as base64 text, so the log doesn't spell every byte out as a JSON number.
+mod base64_bytes {
+    use alloc::{string::String, vec::Vec};
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use cubecl_common::bytes::Bytes;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    fn decoded<E: Error>(text: &str) -> Result<Bytes, E> {
+        STANDARD
+            .decode(text)
+            .map(Bytes::from_bytes_vec)
+            .map_err(|err| E::custom(alloc::format!("invalid base64 buffer contents: {err}")))
+    }
+
+    pub(super) fn serialize<S: Serializer>(data: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(&data[..]))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Bytes, D::Error> {
+        decoded(&String::deserialize(deserializer)?)
+    }
+
+    pub(super) mod list {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(
+            data: &[Bytes],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(data.iter().map(|data| STANDARD.encode(&data[..])))
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Bytes>, D::Error> {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|text| decoded(text))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Chunked host-to-device uploads through pinned staging memory.
+
+use super::ComputeClient;
+use crate::{
+    runtime::Runtime,
+    server::{Handle, ServerError},
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use cubecl_common::bytes::{AllocationProperty, Bytes};
+use cubecl_environment::{
+    backtrace::BackTrace,
+    future::{
+        DynFut, block_on,
+        channel::{Receiver, bounded},
+    },
+};
+
+/// How [`ComputeClient::upload`] splits and stages the data.
+#[derive(Clone, Copy, Debug)]
+pub struct UploadOptions {
+    /// The size of the chunks each source is copied in, in bytes.
+    pub chunk_size: usize,
+    /// How many chunks are staged per request to the server. Two is classic double
+    /// buffering: one chunk is read from the source while the other is copied to the
+    /// device.
+    pub buffers: usize,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 16 * 1024 * 1024,
+            buffers: 2,
+        }
+    }
+}
+
+/// The uploads started by [`ComputeClient::upload`], yielding the handle of each source in
+/// order once all of its chunks have been copied to the device.
+///
+/// Each chunk is read from its source (the disk for file and mapped [`Bytes`]) into pinned
+/// staging memory on the calling thread, then handed to the server as a non-blocking write.
+/// Reading the next chunk thus overlaps with the device copy of the previous one. Staging
+/// memory is reserved [`buffers`](UploadOptions::buffers) chunks at a time, and the next
+/// reservation is requested before the current one is filled, so the server is only waited
+/// on when it falls behind. The staging memory is recycled by the backend once the copies
+/// reading it complete.
+///
+/// The next source is started before waiting on the copies of the one being yielded, so
+/// reading it overlaps with them. The writes are enqueued on the client's stream, so
+/// kernels on other streams keep running.
+pub struct Upload<R: Runtime, I> {
+    client: ComputeClient<R>,
+    sources: I,
+    options: UploadOptions,
+    pending: VecDeque<(Handle, Receiver<DynFut<Result<(), ServerError>>>)>,
+}
+
+impl<R: Runtime, I> Upload<R, I> {
+    pub(super) fn new(client: ComputeClient<R>, sources: I, options: UploadOptions) -> Self {
+        let options = UploadOptions {
+            chunk_size: options.chunk_size.max(1),
+            buffers: options.buffers.max(1),
+        };
+
+        Self {
+            client,
+            sources,
+            options,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn upload(&self, data: Bytes) -> Handle {
+        let handle = self.client.empty(data.len());
+
+        // Already pinned memory is copied from directly, staging it would be a redundant
+        // pinned-to-pinned copy. Device-backed bytes are staged by the write path.
+        if matches!(
+            data.property(),
+            AllocationProperty::Pinned | AllocationProperty::Device
+        ) {
+            self.client.write(&handle, data);
+            return handle;
+        }
+
+        let len = data.len();
+        let chunks = (0..len)
+            .step_by(self.options.chunk_size)
+            .map(|start| (start, (start + self.options.chunk_size).min(len)))
+            .collect::<Vec<_>>();
+        let mut windows = chunks.chunks(self.options.buffers).peekable();
+        let mut reservation = windows.peek().map(|window| self.reserve_staging(window));
+
+        while let Some(window) = windows.next() {
+            let staging = reservation.take().expect("Every window is reserved");
+            // Requested before waiting on the current window, so the server prepares it while
+            // this one is filled.
+            reservation = windows.peek().map(|window| self.reserve_staging(window));
+
+            let Some(staging) = block_on(staging.recv())
+                .ok()
+                .and_then(|staging| staging.ok())
+            else {
+                // The server has no staging memory, let the write path deal with the rest.
+                let start = window[0].0;
+                let rest = slice(&handle, start, len, len);
+                match data.view(start, len) {
+                    Ok(rest_data) => self.client.write(&rest, rest_data),
+                    Err(_) => self
+                        .client
+                        .write(&rest, Bytes::from_bytes_vec(data[start..].to_vec())),
+                }
+                return handle;
+            };
+
+            for (&(start, end), mut buffer) in window.iter().zip(staging) {
+                // A window reads only its range, where the source supports one (files and
+                // maps do), instead of materializing the whole source.
+                match data.view(start, end) {
+                    Ok(chunk) => chunk.copy_into(&mut buffer),
+                    Err(_) => buffer[..].copy_from_slice(&data[start..end]),
+                }
+
+                self.client.write(&slice(&handle, start, end, len), buffer);
+            }
+        }
+
+        handle
+    }
+
+    /// Requests staging memory for a window of chunks, without waiting for the server.
+    fn reserve_staging(
+        &self,
+        window: &[(usize, usize)],
+    ) -> Receiver<Result<Vec<Bytes>, ServerError>> {
+        let stream_id = self.client.stream_id();
+        let sizes = window
+            .iter()
+            .map(|(start, end)| end - start)
+            .collect::<Vec<_>>();
+        let (sender, receiver) = bounded(1);
+
+        self.client.device.submit(move |server| {
+            // The upload may have fallen back to the write path and dropped the receiver.
+            let _ = sender.try_send(server.staging(&sizes, stream_id));
+        });
+
+        receiver
+    }
+
+    /// Requests a future completing with every task enqueued so far on the client's stream,
+    /// the writes of the last upload included, without waiting for the server.
+    fn completion(&self) -> Receiver<DynFut<Result<(), ServerError>>> {
+        let stream_id = self.client.stream_id();
+        let (sender, receiver) = bounded(1);
+
+        self.client.device.submit(move |server| {
+            let _ = sender.try_send(server.sync(stream_id));
+        });
+
+        receiver
+    }
+}
+
+impl<R: Runtime, I: Iterator<Item = Bytes>> Iterator for Upload<R, I> {
+    type Item = Result<Handle, ServerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // One source ahead of the one yielded: it is read while the device copies the other.
+        while self.pending.len() < 2 {
+            let Some(data) = self.sources.next() else {
+                break;
+            };
+            let handle = self.upload(data);
+            self.pending.push_back((handle, self.completion()));
+        }
+
+        let (handle, completion) = self.pending.pop_front()?;
+        let result = block_on(async move {
+            match completion.recv().await {
+                Ok(completion) => completion.await,
+                Err(_) => Err(ServerError::Generic {
+                    reason: "The server dropped the upload before it completed".into(),
+                    backtrace: BackTrace::capture(),
+                }),
+            }
+        });
+
+        Some(result.map(|_| handle))
+    }
+}
+
+/// The `[start, end)` range of a handle of `len` bytes.
+fn slice(handle: &Handle, start: usize, end: usize, len: usize) -> Handle {
+    handle
+        .clone()
+        .offset_start(start as u64)
+        .offset_end((len - end) as u64)
+}
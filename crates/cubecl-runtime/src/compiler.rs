@@ -1,6 +1,7 @@
 use crate::{
     id::KernelId,
     kernel::{CompiledKernel, KernelDefinition, KernelMetadata},
+    throughput::KernelCost,
 };
 use alloc::string::{String, ToString};
 use core::hash::Hash;
@@ -264,4 +265,10 @@ pub trait Compiler: Sync + Send + 'static + Clone + core::fmt::Debug {
     /// The default extension for the runtime's kernel/shader code.
     /// Might change based on which compiler is used.
     fn extension(&self) -> &'static str;
+
+    /// The static cost of a compiled kernel, for compilers that count it while they still hold
+    /// the IR. `None` by default.
+    fn kernel_cost(&self, _kernel: &Self::Representation) -> Option<KernelCost> {
+        None
+    }
 }
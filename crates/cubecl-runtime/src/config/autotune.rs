@@ -33,6 +33,113 @@ pub struct AutotuneConfig {
     /// Sampling budget and elimination thresholds used while benchmarking candidates.
     #[serde(default)]
     pub bench: BenchConfig,
+
+    /// Pacing of the tunable sets tuned in the background, see
+    /// [`TunableSet::with_background`](crate::tune::TunableSet::with_background).
+    #[serde(default)]
+    pub background: BackgroundConfig,
+
+    /// Pinning of tuning decisions, for runs that must pick the same kernels every time.
+    #[serde(default)]
+    pub deterministic: DeterministicConfig,
+}
+
+/// How tuning decisions are kept reproducible across runs.
+///
+/// Timing noise alone can flip a decision between two close candidates, and two kernels for
+/// the same problem rarely produce bit-identical results. Every mode but
+/// [`Disabled`](DeterministicMode::Disabled) also disables the short circuit, which accepts
+/// whichever candidate happens to land under the limit first.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DeterministicConfig {
+    /// The mode.
+    pub mode: DeterministicMode,
+
+    /// The decision file, one JSON decision per line, read by
+    /// [`Pinned`](DeterministicMode::Pinned) and appended to by
+    /// [`Record`](DeterministicMode::Record).
+    #[cfg(std_io)]
+    pub decisions: Option<std::path::PathBuf>,
+
+    /// How much slower than the fastest a candidate may be and still count as tied with it,
+    /// as a ratio: `0.02` ties everything within 2%. Ties go to the candidate registered first.
+    pub tolerance: f64,
+}
+
+impl Default for DeterministicConfig {
+    fn default() -> Self {
+        Self {
+            mode: DeterministicMode::Disabled,
+            #[cfg(std_io)]
+            decisions: None,
+            tolerance: 0.02,
+        }
+    }
+}
+
+impl DeterministicConfig {
+    /// The tie-breaking tolerance in force, `None` when decisions aren't pinned at all.
+    pub fn tie_tolerance(&self) -> Option<f64> {
+        match self.mode {
+            DeterministicMode::Disabled => None,
+            _ => Some(self.tolerance.max(0.0)),
+        }
+    }
+}
+
+/// See [`DeterministicConfig`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DeterministicMode {
+    /// Decisions go to whichever candidate measured fastest (default).
+    #[default]
+    #[serde(rename = "disabled")]
+    Disabled,
+
+    /// Tune as usual, but break ties within the tolerance by registration order.
+    #[serde(rename = "tie-break")]
+    TieBreak,
+
+    /// Tune with tie-breaking and append every decision to the decision file, to be committed
+    /// and replayed with [`Pinned`](Self::Pinned).
+    #[serde(rename = "record")]
+    Record,
+
+    /// Never tune: every decision is read from the decision file, and a key missing from it is
+    /// a hard error rather than a reason to tune.
+    #[serde(rename = "pinned")]
+    Pinned,
+}
+
+/// Controls how often a background tune may take the device away from the caller.
+///
+/// Native only: on wasm a background set is tuned like any other, since its samples can only be
+/// resolved on the browser event loop.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BackgroundConfig {
+    /// Minimum time between the end of one candidate benchmark and the start of the next, in
+    /// milliseconds.
+    ///
+    /// Each benchmark is queued by a call that hit the key being tuned and then shares the
+    /// device with the work that follows it, so this is the share of device time given up to
+    /// tuning: `0` queues the next candidate as soon as the previous one resolved.
+    pub step_interval_ms: u64,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            step_interval_ms: 100,
+        }
+    }
+}
+
+impl BackgroundConfig {
+    /// The minimum time between two candidate benchmarks.
+    pub fn step_interval(&self) -> core::time::Duration {
+        core::time::Duration::from_millis(self.step_interval_ms)
+    }
 }
 
 /// Controls how many samples autotune collects per candidate and when candidates are dropped.
@@ -72,7 +72,7 @@ impl RuntimeConfig for CubeClRuntimeConfig {
     fn override_from_env(mut self) -> Self {
         use super::compilation::CompilationLogLevel;
         use crate::config::{
-            autotune::{AutotuneLevel, AutotuneLogLevel},
+            autotune::{AutotuneLevel, AutotuneLogLevel, DeterministicMode},
             profiling::ProfilingLogLevel,
         };
 
@@ -136,6 +136,10 @@ impl RuntimeConfig for CubeClRuntimeConfig {
             }
         };
 
+        if let Ok(val) = std::env::var("CUBECL_TRACE_FILE") {
+            self.profiling.trace_file = Some(val.into());
+        }
+
         if let Ok(val) = std::env::var("CUBECL_AUTOTUNE_LEVEL") {
             match val.as_str() {
                 "minimal" | "0" => {
@@ -174,6 +178,28 @@ impl RuntimeConfig for CubeClRuntimeConfig {
             self.autotune.bench.adaptive = enabled;
         }
 
+        if let Ok(val) = std::env::var("CUBECL_AUTOTUNE_DETERMINISTIC") {
+            match val.as_str() {
+                "disabled" => {
+                    self.autotune.deterministic.mode = DeterministicMode::Disabled;
+                }
+                "tie-break" => {
+                    self.autotune.deterministic.mode = DeterministicMode::TieBreak;
+                }
+                "record" => {
+                    self.autotune.deterministic.mode = DeterministicMode::Record;
+                }
+                "pinned" => {
+                    self.autotune.deterministic.mode = DeterministicMode::Pinned;
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(val) = std::env::var("CUBECL_AUTOTUNE_DECISIONS") {
+            self.autotune.deterministic.decisions = Some(val.into());
+        }
+
         self
     }
 }
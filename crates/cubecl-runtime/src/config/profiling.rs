@@ -6,6 +6,26 @@ pub struct ProfilingConfig {
     /// Logger configuration for profiling logs, using profiling-specific log levels.
     #[serde(default)]
     pub logger: LoggerConfig<ProfilingLogLevel>,
+
+    /// Where to write a timeline of every launch, read, write, copy and sync, as a Chrome
+    /// trace-event JSON file to open in `chrome://tracing` or Perfetto.
+    ///
+    /// Independent of the [`logger`](Self::logger) level: the summary aggregates per kernel,
+    /// the trace keeps each operation with its stream, timestamps and sizes.
+    #[cfg(std_io)]
+    #[serde(default)]
+    pub trace_file: Option<std::path::PathBuf>,
+}
+
+impl ProfilingConfig {
+    /// Whether operations are recorded on a timeline trace.
+    pub fn trace_enabled(&self) -> bool {
+        #[cfg(std_io)]
+        let enabled = self.trace_file.is_some();
+        #[cfg(not(std_io))]
+        let enabled = false;
+        enabled
+    }
 }
 
 /// Log levels for profiling in `CubeCL`.
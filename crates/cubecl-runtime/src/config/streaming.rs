@@ -15,8 +15,10 @@ pub struct StreamingConfig {
     ///
     /// Backends that expose stream priorities (e.g. CUDA via
     /// `cuStreamCreateWithPriority`) map this to their native range. Backends
-    /// without a notion of stream priority ignore it. The default is
-    /// [`StreamPriority::Default`], which preserves existing behavior.
+    /// scheduling their streams in software (wgpu, cpu) order their work by it,
+    /// see [`SchedulerMultiStream`](crate::stream::scheduler::SchedulerMultiStream).
+    /// Others ignore it. The default is [`StreamPriority::Default`], which
+    /// preserves existing behavior.
     #[serde(default)]
     pub priority: StreamPriority,
     /// How the current stream is derived: `"per-thread"` (default),
@@ -85,6 +85,67 @@ impl Default for GraphId {
     }
 }
 
+/// Identifies a parameter of a captured graph: a scalar or binding marked with
+/// [`graph_param`](crate::server::ComputeServer::graph_param) while recording,
+/// and updated on the graph with
+/// [`graph_update`](crate::server::ComputeServer::graph_update).
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, PartialOrd, Ord)]
+pub struct GraphParamId {
+    value: u64,
+}
+
+impl GraphParamId {
+    /// Allocate a fresh, process-unique parameter id.
+    pub fn new() -> Self {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let value = COUNTER.fetch_add(1, Ordering::Relaxed);
+        if value == u64::MAX {
+            core::panic!("Graph parameter ID overflowed");
+        }
+        Self { value }
+    }
+}
+
+impl Default for GraphParamId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identifies a backend-owned event recorded on a stream.
+///
+/// Like a [`GraphId`], the event itself stays in the backend's registry:
+/// [`event_record`](crate::server::ComputeServer::event_record) returns this id,
+/// and the other event calls take it back to look the event up.
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, PartialOrd, Ord)]
+pub struct EventId {
+    value: u64,
+}
+
+impl EventId {
+    /// Allocate a fresh, process-unique event id.
+    pub fn new() -> Self {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let value = COUNTER.fetch_add(1, Ordering::Relaxed);
+        if value == u64::MAX {
+            core::panic!("Event ID overflowed");
+        }
+        Self { value }
+    }
+}
+
+impl Default for EventId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Kernel unique identifier.
 #[derive(Clone, PartialEq, Eq)]
 pub struct KernelId {
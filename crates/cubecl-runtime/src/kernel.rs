@@ -23,6 +23,7 @@ use crate::{
     config::{CubeClRuntimeConfig, RuntimeConfig, compilation::CompilationLogLevel},
     id::KernelId,
     server::CubeDim,
+    throughput::{KernelCost, register_kernel_cost},
 };
 
 /// Implement this trait to create a [kernel definition](KernelDefinition).
@@ -113,6 +114,8 @@ pub struct CompiledKernel<C: Compiler> {
     pub cube_dim: CubeDim,
     /// Extra debugging information about the compiled kernel.
     pub debug_info: Option<DebugInformation>,
+    /// Static cost of the compiled kernel, when the compiler counts it.
+    pub cost: Option<KernelCost>,
 }
 
 /// Extra debugging information about the compiled kernel.
@@ -167,6 +170,12 @@ impl<C: Compiler, K: CubeKernel> CubeTask<C> for KernelTask<C, K> {
         let cube_dim = gpu_ir.settings.cube_dim.into();
         let lower_level_ir = compiler.compile(gpu_ir, compilation_options)?;
 
+        let cost = compiler.kernel_cost(&lower_level_ir);
+        if let Some(cost) = &cost {
+            let name = self.kernel_definition.name();
+            register_kernel_cost(self.kernel_definition.id(), name, cost.clone());
+        }
+
         Ok(CompiledKernel {
             entrypoint_name,
             debug_name: Some(core::any::type_name::<K>()),
@@ -174,6 +183,7 @@ impl<C: Compiler, K: CubeKernel> CubeTask<C> for KernelTask<C, K> {
             repr: Some(lower_level_ir),
             cube_dim,
             debug_info: None,
+            cost,
         })
     }
 }
@@ -267,6 +277,10 @@ impl<C: Compiler> CompiledKernel<C> {
             f.write_fmt(format_args!("\nid: {:#?}", info.id))?;
         }
 
+        if let Some(cost) = &self.cost {
+            f.write_fmt(format_args!("\ncost per unit: {cost}"))?;
+        }
+
         f.write_fmt(format_args!(
             "
 source:
@@ -6,11 +6,11 @@ use crate::config::{Logger, compilation::CompilationLogLevel, profiling::Profili
 use alloc::format;
 use alloc::string::String;
 use alloc::string::ToString;
-use cubecl_common::profile::ProfileDuration;
+use cubecl_common::profile::{Instant, ProfileDuration};
 use cubecl_environment::future::channel::{Receiver, Sender};
 use cubecl_environment::future::spawn_detached;
 
-use super::{ProfileLevel, Profiled};
+use super::{ProfileLevel, Profiled, TraceEvent};
 
 enum LogMessage {
     Execution(String),
@@ -19,6 +19,8 @@ enum LogMessage {
     Memory(String),
     Profile(String, ProfileDuration),
     ProfileSummary,
+    Trace(TraceEvent, ProfileDuration),
+    TraceUntimed(TraceEvent, Instant),
 }
 
 /// Server logger.
@@ -30,6 +32,7 @@ pub struct ServerLogger {
     log_streaming: StreamingLogLevel,
     log_channel: Option<Sender<LogMessage>>,
     log_memory: MemoryLogLevel,
+    trace: bool,
 }
 
 impl Default for ServerLogger {
@@ -46,7 +49,8 @@ impl Default for ServerLogger {
             && matches!(
                 logger.config.streaming.logger.level,
                 StreamingLogLevel::Disabled
-            );
+            )
+            && !logger.config.profiling.trace_enabled();
 
         if disabled {
             return Self {
@@ -56,6 +60,7 @@ impl Default for ServerLogger {
                 log_streaming: StreamingLogLevel::Disabled,
                 log_channel: None,
                 log_memory: MemoryLogLevel::Disabled,
+                trace: false,
             };
         }
         let profile_level = match logger.config.profiling.logger.level {
@@ -78,6 +83,15 @@ impl Default for ServerLogger {
         let log_streaming = logger.config.streaming.logger.level;
         let log_memory = logger.config.memory.logger.level;
 
+        // A trace file that can't be created disables the trace rather than the device.
+        #[cfg(std_io)]
+        let trace = match &logger.config.profiling.trace_file {
+            Some(path) => super::open_trace(path),
+            None => false,
+        };
+        #[cfg(not(std_io))]
+        let trace = false;
+
         let (send, rec) = cubecl_environment::future::channel::unbounded();
 
         // Spawn the logger as a detached task.
@@ -96,6 +110,7 @@ impl Default for ServerLogger {
             log_streaming,
             log_memory,
             log_channel: Some(send),
+            trace,
         }
     }
 }
@@ -181,6 +196,32 @@ impl ServerLogger {
         }
     }
 
+    /// Returns true if operations are recorded on the timeline trace.
+    pub fn trace_activated(&self) -> bool {
+        self.trace
+    }
+
+    /// Record an operation on the timeline trace, once its duration resolves.
+    pub fn register_trace(&self, event: TraceEvent, duration: ProfileDuration) {
+        if let Some(channel) = &self.log_channel
+            && self.trace
+        {
+            // Channel will never be full, don't care if it's closed.
+            let _ = channel.try_send(LogMessage::Trace(event, duration));
+        }
+    }
+
+    /// Record an operation whose duration can't be measured on the timeline trace, as an
+    /// instant event at `at`.
+    pub fn register_trace_untimed(&self, event: TraceEvent, at: Instant) {
+        if let Some(channel) = &self.log_channel
+            && self.trace
+        {
+            // Channel will never be full, don't care if it's closed.
+            let _ = channel.try_send(LogMessage::TraceUntimed(event, at));
+        }
+    }
+
     /// Show the profiling summary if activated and reset its state.
     pub fn profile_summary(&self) {
         if let Some(channel) = &self.log_channel
@@ -220,6 +261,17 @@ impl AsyncLogger {
                 LogMessage::Execution(name) => {
                     self.logger.log_profiling(&format!("Executing {name}"));
                 }
+                #[cfg_attr(not(std_io), allow(unused_variables))]
+                LogMessage::Trace(event, profile) => {
+                    let ticks = profile.resolve().await;
+                    #[cfg(std_io)]
+                    super::write_trace(&event, &ticks);
+                }
+                #[cfg_attr(not(std_io), allow(unused_variables))]
+                LogMessage::TraceUntimed(event, at) => {
+                    #[cfg(std_io)]
+                    super::write_trace_untimed(&event, at);
+                }
                 LogMessage::ProfileSummary => {
                     if !self.profiled.is_empty() {
                         self.logger.log_profiling(&self.profiled);
@@ -0,0 +1,356 @@
+use alloc::{string::String, vec::Vec};
+use cubecl_common::profile::Instant;
+use cubecl_environment::stream::StreamId;
+
+use crate::server::{ComputeServer, CubeCount, KernelArguments, KernelResource};
+
+use super::ServerLogger;
+
+/// The kind of operation a [`TraceEvent`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOp {
+    /// A kernel launch.
+    Launch,
+    /// A read from device memory.
+    Read,
+    /// A write to device memory.
+    Write,
+    /// A transfer between two devices.
+    Copy,
+    /// A stream synchronization.
+    Sync,
+}
+
+impl TraceOp {
+    /// The category of the operation in the trace.
+    pub fn category(&self) -> &'static str {
+        match self {
+            TraceOp::Launch => "launch",
+            TraceOp::Read => "read",
+            TraceOp::Write => "write",
+            TraceOp::Copy => "copy",
+            TraceOp::Sync => "sync",
+        }
+    }
+}
+
+/// One operation recorded on the timeline, see
+/// [`ProfilingConfig::trace_file`](crate::config::profiling::ProfilingConfig::trace_file).
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// The kind of operation.
+    pub op: TraceOp,
+    /// The kernel name for a launch, the operation otherwise.
+    pub name: String,
+    /// The stream the operation ran on, one track per stream in the trace.
+    pub stream_id: StreamId,
+    /// The cube count of a launch, `None` when it isn't known on the host.
+    pub cube_count: Option<[u32; 3]>,
+    /// The cube dim of a launch.
+    pub cube_dim: Option<[u32; 3]>,
+    /// The size of every buffer the operation touched, in bytes.
+    pub bytes: Vec<u64>,
+}
+
+impl TraceEvent {
+    /// An operation that isn't a launch.
+    pub fn new(op: TraceOp, name: impl Into<String>, stream_id: StreamId, bytes: Vec<u64>) -> Self {
+        Self {
+            op,
+            name: name.into(),
+            stream_id,
+            cube_count: None,
+            cube_dim: None,
+            bytes,
+        }
+    }
+
+    /// A kernel launch.
+    pub fn launch(
+        name: impl Into<String>,
+        stream_id: StreamId,
+        count: &CubeCount,
+        cube_dim: [u32; 3],
+        bindings: &KernelArguments,
+    ) -> Self {
+        let cube_count = match count {
+            CubeCount::Static(x, y, z) => Some([*x, *y, *z]),
+            CubeCount::Dynamic(_) => None,
+        };
+        let bytes = bindings
+            .resources
+            .iter()
+            .filter_map(|resource| match resource {
+                KernelResource::Buffer(binding) => Some(binding.size_in_used()),
+                KernelResource::TensorMap(_) => None,
+            })
+            .collect();
+
+        Self {
+            op: TraceOp::Launch,
+            name: name.into(),
+            stream_id,
+            cube_count,
+            cube_dim: Some(cube_dim),
+            bytes,
+        }
+    }
+}
+
+/// Run `op` on the server, and record it on the timeline when given an `event`.
+///
+/// Callers build the event only when [`ServerLogger::trace_activated`], so that an untraced
+/// operation costs nothing more than the operation itself.
+///
+/// The operation is bracketed by the server's own profiling, so its timestamps come from the
+/// device timers where the backend has them and from a
+/// [`TimestampProfiler`](crate::timestamp_profiler::TimestampProfiler) elsewhere. The latter
+/// synchronizes the stream at both ends to time the operation alone, which serializes the
+/// work of that stream while tracing: other streams still overlap with it.
+///
+/// A stream that can't be profiled, e.g. while a graph captures it, still gets its operations
+/// on the timeline, as instant events at the time the host issued them.
+pub(crate) fn traced<S: ComputeServer, O>(
+    server: &mut S,
+    logger: &ServerLogger,
+    stream_id: StreamId,
+    event: Option<TraceEvent>,
+    op: impl FnOnce(&mut S) -> O,
+) -> O {
+    let Some(event) = event else {
+        return op(server);
+    };
+
+    let Ok(token) = server.start_profile(stream_id) else {
+        logger.register_trace_untimed(event, Instant::now());
+        return op(server);
+    };
+    let out = op(server);
+
+    // A failed profile only loses the event, never the operation.
+    if let Ok(duration) = server.end_profile(stream_id, token) {
+        logger.register_trace(event, duration);
+    }
+
+    out
+}
+
+#[cfg(std_io)]
+pub(crate) use writer::*;
+
+#[cfg(std_io)]
+mod writer {
+    use super::TraceEvent;
+    use alloc::format;
+    use cubecl_common::profile::{Instant, ProfileTicks};
+    use cubecl_environment::collections::HashSet;
+    use cubecl_environment::sync::Mutex;
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+        path::Path,
+    };
+
+    /// Shared by every device, so they all land in one file on a common clock.
+    static TRACE: Mutex<Option<TraceWriter>> = Mutex::new(None);
+
+    /// Writes the Chrome trace-event JSON array format, one event per line.
+    ///
+    /// The format allows leaving the array unterminated, which both `chrome://tracing` and
+    /// Perfetto accept: the file is valid after every event, so a process that crashes or never
+    /// shuts its devices down still leaves a readable trace behind.
+    struct TraceWriter {
+        writer: BufWriter<File>,
+        epoch: Instant,
+        streams: HashSet<u64>,
+    }
+
+    /// Open the trace file, truncating it, unless a device already did. Returns whether the
+    /// trace is open: a file that can't be created is logged and leaves tracing disabled.
+    pub(crate) fn open_trace(path: &Path) -> bool {
+        let mut trace = TRACE.lock();
+        if trace.is_some() {
+            return true;
+        }
+
+        match TraceWriter::create(path) {
+            Ok(writer) => {
+                *trace = Some(writer);
+                true
+            }
+            Err(err) => {
+                log::warn!(
+                    "Can't create the trace file {}, tracing is disabled: {err}",
+                    path.display()
+                );
+                false
+            }
+        }
+    }
+
+    /// Append a resolved event to the trace file.
+    pub(crate) fn write_trace(event: &TraceEvent, ticks: &ProfileTicks) {
+        if let Some(trace) = TRACE.lock().as_mut() {
+            trace.span(event, ticks);
+        }
+    }
+
+    /// Append an event without a duration to the trace file.
+    pub(crate) fn write_trace_untimed(event: &TraceEvent, at: Instant) {
+        if let Some(trace) = TRACE.lock().as_mut() {
+            trace.instant(event, at);
+        }
+    }
+
+    impl TraceWriter {
+        fn create(path: &Path) -> std::io::Result<Self> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(writer, "[")?;
+            writer.flush()?;
+
+            Ok(Self {
+                writer,
+                epoch: Instant::now(),
+                streams: HashSet::new(),
+            })
+        }
+
+        /// A complete event, spanning the measured duration of the operation.
+        fn span(&mut self, event: &TraceEvent, ticks: &ProfileTicks) {
+            let mut value = self.event(event);
+            value["ph"] = "X".into();
+            value["ts"] = (ticks.start_duration_since(self.epoch).as_secs_f64() * 1e6).into();
+            value["dur"] = (ticks.duration().as_secs_f64() * 1e6).into();
+            self.write(&value);
+        }
+
+        /// An instant event, scoped to the track of its stream.
+        fn instant(&mut self, event: &TraceEvent, at: Instant) {
+            let mut value = self.event(event);
+            value["ph"] = "i".into();
+            value["s"] = "t".into();
+            value["ts"] = (at.duration_since(self.epoch).as_secs_f64() * 1e6).into();
+            self.write(&value);
+        }
+
+        /// The fields shared by every kind of event, naming the track of a stream the first
+        /// time it shows up.
+        fn event(&mut self, event: &TraceEvent) -> serde_json::Value {
+            let pid = std::process::id();
+            let tid = event.stream_id.value;
+
+            if self.streams.insert(tid) {
+                let metadata = serde_json::json!({
+                    "name": "thread_name",
+                    "ph": "M",
+                    "pid": pid,
+                    "tid": tid,
+                    "args": { "name": format!("Stream {tid}") },
+                });
+                self.write(&metadata);
+            }
+
+            let mut args = serde_json::Map::new();
+            if let Some(cube_count) = event.cube_count {
+                args.insert("cube_count".into(), cube_count.as_slice().into());
+            }
+            if let Some(cube_dim) = event.cube_dim {
+                args.insert("cube_dim".into(), cube_dim.as_slice().into());
+            }
+            if !event.bytes.is_empty() {
+                args.insert("bytes".into(), event.bytes.as_slice().into());
+            }
+
+            serde_json::json!({
+                "name": event.name,
+                "cat": event.op.category(),
+                "pid": pid,
+                "tid": tid,
+                "args": args,
+            })
+        }
+
+        fn write(&mut self, value: &serde_json::Value) {
+            // A trace that can't be written only loses events, never the operations.
+            let written = writeln!(self.writer, "{value},").and_then(|_| self.writer.flush());
+            if let Err(err) = written {
+                log::warn!("Can't write the trace file: {err}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::logging::TraceOp;
+        use alloc::vec;
+        use alloc::vec::Vec;
+        use core::time::Duration;
+        use cubecl_environment::stream::StreamId;
+
+        fn read_events(path: &Path) -> Vec<serde_json::Value> {
+            let text = std::fs::read_to_string(path).unwrap();
+            let mut lines = text.lines();
+            assert_eq!(lines.next(), Some("["));
+            lines
+                .map(|line| serde_json::from_str(line.trim_end_matches(',')).unwrap())
+                .collect()
+        }
+
+        #[test]
+        fn writes_spans_and_instants_on_stream_tracks() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("nested/trace.json");
+            let mut trace = TraceWriter::create(&path).unwrap();
+            let stream_id = StreamId { value: 7 };
+
+            let start = trace.epoch + Duration::from_micros(10);
+            let ticks = ProfileTicks::from_start_end(start, start + Duration::from_micros(5));
+            let mut launch = TraceEvent::new(TraceOp::Launch, "kernel", stream_id, vec![64]);
+            launch.cube_count = Some([2, 1, 1]);
+            launch.cube_dim = Some([32, 1, 1]);
+            trace.span(&launch, &ticks);
+
+            let sync = TraceEvent::new(TraceOp::Sync, "sync", stream_id, Vec::new());
+            trace.instant(&sync, trace.epoch + Duration::from_micros(20));
+
+            let events = read_events(&path);
+            assert_eq!(events.len(), 3, "{events:?}");
+
+            let metadata = &events[0];
+            assert_eq!(metadata["ph"], "M");
+            assert_eq!(metadata["tid"], 7);
+            assert_eq!(metadata["args"]["name"], "Stream 7");
+
+            let span = &events[1];
+            assert_eq!(span["ph"], "X");
+            assert_eq!(span["name"], "kernel");
+            assert_eq!(span["cat"], "launch");
+            assert_eq!(span["tid"], 7);
+            assert_eq!(span["ts"].as_f64().unwrap().round(), 10.0);
+            assert_eq!(span["dur"].as_f64().unwrap().round(), 5.0);
+            assert_eq!(span["args"]["cube_count"], serde_json::json!([2, 1, 1]));
+            assert_eq!(span["args"]["cube_dim"], serde_json::json!([32, 1, 1]));
+            assert_eq!(span["args"]["bytes"], serde_json::json!([64]));
+
+            let instant = &events[2];
+            assert_eq!(instant["ph"], "i");
+            assert_eq!(instant["cat"], "sync");
+            assert_eq!(instant["ts"].as_f64().unwrap().round(), 20.0);
+            assert!(instant.get("dur").is_none());
+            assert!(instant["args"].as_object().unwrap().is_empty());
+        }
+
+        #[test]
+        fn unwritable_path_fails_to_create() {
+            let dir = tempfile::tempdir().unwrap();
+            let file = dir.path().join("file");
+            std::fs::write(&file, "").unwrap();
+
+            assert!(TraceWriter::create(&file.join("trace.json")).is_err());
+        }
+    }
+}
@@ -2,9 +2,11 @@ use super::Handle;
 use crate::{
     client::ComputeClient,
     compiler::CompilationError,
-    config::{CubeClRuntimeConfig, RuntimeConfig, compilation::BoundsCheckMode},
+    config::{
+        CubeClRuntimeConfig, RuntimeConfig, compilation::BoundsCheckMode, streaming::StreamPriority,
+    },
     dry_run::LaunchMode,
-    id::GraphId,
+    id::{EventId, GraphId, GraphParamId},
     kernel::KernelMetadata,
     logging::ServerLogger,
     memory_management::{
@@ -12,7 +14,7 @@ use crate::{
         MemoryReport, MemoryUsage,
     },
     runtime::Runtime,
-    server::{BufferBinding, KernelResource},
+    server::{BufferBinding, GraphParamValue, KernelResource, MarkedScalar},
     storage::{ComputeStorage, ManagedResource},
     tma::{OobFill, TensorMapFormat, TensorMapInterleave, TensorMapPrefetch, TensorMapSwizzle},
 };
@@ -104,6 +106,9 @@ pub struct ServerUtilities<Server: ComputeServer> {
     pub check_mode: BoundsCheckMode,
     /// A set containing the ids for which the inter-device communication has already been initialized.
     pub initialized_comms: RwLock<HashSet<CommunicationId>>,
+    /// Where operations are recorded while a replay log is being recorded.
+    #[cfg(std_io)]
+    pub(crate) recorder: crate::client::RecorderSlot,
 }
 
 /// Defines how the memory layout is determined.
@@ -167,6 +172,8 @@ impl<S: ComputeServer> ServerUtilities<S> {
             layout_policy: allocator,
             check_mode: CubeClRuntimeConfig::get().compilation.check_mode,
             initialized_comms: RwLock::new(HashSet::default()),
+            #[cfg(std_io)]
+            recorder: Default::default(),
         }
     }
 }
@@ -354,6 +361,21 @@ impl ServerError {
     pub fn graph_capture_unsupported() -> Self {
         Self::graph_state("graph capture is not supported by this backend")
     }
+
+    /// The error the default graph-parameter methods return, for a backend
+    /// that can capture graphs but not update them.
+    pub fn graph_params_unsupported() -> Self {
+        Self::graph_state("graph parameters are not supported by this backend")
+    }
+
+    /// The error the event methods return for an id the backend doesn't know,
+    /// or, by default, for a backend without stream events.
+    pub fn unknown_event(event: EventId) -> Self {
+        Self::Generic {
+            reason: format!("event {event:?} is unknown to this backend"),
+            backtrace: BackTrace::capture(),
+        }
+    }
 }
 
 /// How errors are handled in a stream when executing a task.
@@ -531,6 +553,92 @@ where
         let _ = (graph, stream_id);
     }
 
+    /// Mark `value` as a parameter of the graph recording on `stream_id`, and
+    /// return the id [`graph_update`](ComputeServer::graph_update) takes to
+    /// change it. Every launch recorded after the mark that binds the marked
+    /// buffer, or whose arguments hold the marked scalar, becomes a site of the
+    /// parameter (see [`GraphParamMarks`](crate::server::GraphParamMarks)).
+    ///
+    /// Fails when the stream is not recording. A parameter no recorded launch
+    /// uses fails [`end_capture`](ComputeServer::end_capture) instead.
+    fn graph_param(
+        &mut self,
+        value: GraphParamValue,
+        stream_id: StreamId,
+    ) -> Result<GraphParamId, ServerError> {
+        let _ = (value, stream_id);
+        Err(ServerError::graph_params_unsupported())
+    }
+
+    /// Change parameters of `graph` for the replays enqueued from now on,
+    /// without re-capturing it. A hardware graph patches the parameters of the
+    /// recorded kernel nodes in place (CUDA's graph exec update); a software
+    /// graph rewrites its recorded launches. Replays already enqueued run with
+    /// the previous values.
+    ///
+    /// The whole batch is validated before anything changes: an unknown
+    /// parameter, or a value whose size differs from the captured one (the
+    /// metadata packed alongside is baked into the graph), rejects it all.
+    fn graph_update(
+        &mut self,
+        graph: GraphId,
+        updates: Vec<(GraphParamId, GraphParamValue)>,
+        stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        let _ = (graph, updates, stream_id);
+        Err(ServerError::graph_params_unsupported())
+    }
+
+    /// Record an event after the work currently enqueued on `stream_id`, store it
+    /// in the backend's registry, and return its [`EventId`]. The event completes
+    /// once that work does, and stays usable until
+    /// [`event_destroy`](ComputeServer::event_destroy).
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        let _ = stream_id;
+        Err(ServerError::Generic {
+            reason: "stream events are not supported by this backend".into(),
+            backtrace: BackTrace::capture(),
+        })
+    }
+
+    /// Make the work enqueued on `stream_id` from now on wait for `event`, without
+    /// blocking the host. An event can be waited on any number of times, from any
+    /// stream of the device.
+    ///
+    /// A backend whose streams all submit to a single queue that runs in submission order,
+    /// like wgpu, only has to check that `event` exists: the work it covers was submitted
+    /// when it was recorded, so everything submitted afterwards already runs after it.
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        let _ = stream_id;
+        Err(ServerError::unknown_event(event))
+    }
+
+    /// Whether `event` has completed, without blocking.
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        Err(ServerError::unknown_event(event))
+    }
+
+    /// Wait on the host for `event` to complete. The returned future holds
+    /// everything it needs, so the server isn't blocked while it is awaited.
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        let err = ServerError::unknown_event(event);
+        Box::pin(async move { Err(err) })
+    }
+
+    /// Release `event`. Waits already enqueued on it are unaffected. A no-op by
+    /// default and for an unknown id.
+    fn event_destroy(&mut self, event: EventId) {
+        let _ = event;
+    }
+
+    /// Set the priority the work of `stream_id` is scheduled with, overriding the
+    /// [configured](crate::config::streaming::StreamingConfig::priority) one. A hint:
+    /// backends scheduling their streams in software (wgpu, cpu) order and defer work by
+    /// it, the others ignore it by default.
+    fn set_stream_priority(&mut self, stream_id: StreamId, priority: StreamPriority) {
+        let _ = (stream_id, priority);
+    }
+
     /// Memory usage of the given stream.
     fn memory_usage(&mut self, stream_id: StreamId) -> Result<MemoryUsage, ServerError>;
 
@@ -1032,6 +1140,8 @@ pub struct KernelArguments {
     /// Packed scalars and metadata. First scalars sorted by type, then static metadata,
     /// then dynamic metadata.
     pub info: MetadataBindingInfo,
+    /// The scalars of `info` passed as graph parameters, see [`MarkedScalar`].
+    pub marked_scalars: Vec<MarkedScalar>,
 }
 
 impl core::fmt::Display for KernelArguments {
@@ -1086,6 +1196,8 @@ impl KernelArguments {
 pub struct MetadataBindingInfo {
     /// Scalar and metadata values
     pub data: Vec<u64>,
+    /// Length of the scalars at the start of the info buffer, before the metadata
+    pub scalars_len: usize,
     /// Start of the dynamically sized portion of the metadata, relative to the entire info buffer
     pub dynamic_metadata_offset: usize,
 }
@@ -1093,7 +1205,7 @@ pub struct MetadataBindingInfo {
 impl MetadataBindingInfo {
     /// Create a new binding info for custom data, for externally compiled kernels.
     pub fn custom(data: Vec<u64>) -> Self {
-        Self::new(data, 0)
+        Self::new(data, 0, 0)
     }
 }
 
@@ -0,0 +1,493 @@
+//! Graph parameters: the scalars and bindings of a captured graph that can
+//! change between replays without re-capturing it.
+//!
+//! A parameter is marked on the recording stream with
+//! [`graph_param`](super::ComputeServer::graph_param), from the value the
+//! capture run uses. Every launch recorded after the mark is matched against
+//! it when it is recorded ([`GraphParamMarks::sites`]), which tells the backend
+//! *where* in the launch the parameter lives: which binding, or which bytes of
+//! the packed scalar info. A scalar is best passed to the launch as a
+//! [`MarkedScalar`], which records exactly where it was packed; otherwise its
+//! capture value is looked for among the launch's scalars. At `end_capture` the marks seal into a
+//! [`GraphParamTable`] owned by the graph, which validates each
+//! [`graph_update`](super::ComputeServer::graph_update) before the backend
+//! patches the recorded sites.
+//!
+//! Matching is the same on every backend, so it lives here; what a backend
+//! does with a site — a kernel node update on CUDA, a rewritten recorded launch
+//! on the software graphs — is its own.
+
+use super::{BufferBinding, KernelArguments, KernelResource, ServerError};
+use crate::id::GraphParamId;
+use alloc::{format, vec::Vec};
+
+/// A value a graph parameter takes: the one marked at capture time, or a new
+/// one given to [`graph_update`](super::ComputeServer::graph_update).
+#[derive(Debug, Clone)]
+pub enum GraphParamValue {
+    /// A buffer binding. Marking one makes every binding of the same memory,
+    /// at the same offsets, a site of the parameter.
+    Binding(BufferBinding),
+    /// The bytes of a scalar argument. A launch passing the parameter as a
+    /// [`MarkedScalar`] has that scalar as its site. In a launch that doesn't,
+    /// every occurrence of those exact bytes among the scalars is a site, so
+    /// the value used at capture time acts as a placeholder and must not
+    /// collide with another scalar of the same launch.
+    Scalar(Vec<u8>),
+}
+
+impl GraphParamValue {
+    /// The size of the value in bytes: the bytes a binding covers, or the
+    /// width of a scalar. An update must keep it.
+    pub fn size(&self) -> u64 {
+        match self {
+            GraphParamValue::Binding(binding) => binding.size_in_used(),
+            GraphParamValue::Scalar(bytes) => bytes.len() as u64,
+        }
+    }
+}
+
+/// Where a graph parameter lives in one recorded launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphParamTarget {
+    /// The launch's resource at this index.
+    Binding(usize),
+    /// `len` bytes at byte `offset` of the launch's packed
+    /// [info](super::MetadataBindingInfo).
+    Scalar {
+        /// Offset in bytes from the start of the info data.
+        offset: usize,
+        /// Width of the scalar in bytes.
+        len: usize,
+    },
+}
+
+/// A scalar of a launch passed as a graph parameter, and where the launcher
+/// packed it in the [info](super::MetadataBindingInfo).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkedScalar {
+    /// The parameter.
+    pub param: GraphParamId,
+    /// Offset in bytes from the start of the info data.
+    pub offset: usize,
+    /// Width of the scalar in bytes.
+    pub len: usize,
+}
+
+/// One occurrence of a graph parameter in a recorded launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphParamSite {
+    /// The parameter.
+    pub param: GraphParamId,
+    /// Where it lives in the launch.
+    pub target: GraphParamTarget,
+}
+
+#[derive(Debug)]
+struct GraphParamMark {
+    id: GraphParamId,
+    value: GraphParamValue,
+    matched: bool,
+}
+
+/// The parameters marked on a stream while it records a graph.
+#[derive(Debug, Default)]
+pub struct GraphParamMarks {
+    marks: Vec<GraphParamMark>,
+}
+
+impl GraphParamMarks {
+    /// Mark `value` as a parameter, returning its id.
+    ///
+    /// # Errors
+    ///
+    /// Fails for a scalar that is not 1, 2, 4 or 8 bytes wide: scalars are
+    /// packed at their natural alignment, so no other width can be found.
+    pub fn mark(&mut self, value: GraphParamValue) -> Result<GraphParamId, ServerError> {
+        if let GraphParamValue::Scalar(bytes) = &value
+            && !matches!(bytes.len(), 1 | 2 | 4 | 8)
+        {
+            return Err(ServerError::graph_state(format!(
+                "graph_param: a scalar parameter must be 1, 2, 4 or 8 bytes wide, got {}",
+                bytes.len()
+            )));
+        }
+
+        let id = GraphParamId::new();
+        self.marks.push(GraphParamMark {
+            id,
+            value,
+            matched: false,
+        });
+        Ok(id)
+    }
+
+    /// Whether no parameter is marked, in which case no launch has sites.
+    pub fn is_empty(&self) -> bool {
+        self.marks.is_empty()
+    }
+
+    /// Match one launch's arguments against the marks, returning the sites of
+    /// every marked parameter it uses. Call it for each launch recorded, in
+    /// order, before the arguments are consumed.
+    ///
+    /// A scalar the launch passes as a [`MarkedScalar`] is found where it was
+    /// packed. Otherwise, its capture value is searched among the scalars of
+    /// the info, never in the metadata, at offsets aligned to its width.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a marked scalar occurs more than once in the launch, which
+    /// would make its sites ambiguous, when a scalar is passed with another
+    /// width than it was marked with, or when a marked binding is bound as a
+    /// tensor map, whose descriptor is built from the address at launch time.
+    pub fn sites(&mut self, args: &KernelArguments) -> Result<Vec<GraphParamSite>, ServerError> {
+        let mut sites = Vec::new();
+        if self.marks.is_empty() {
+            return Ok(sites);
+        }
+
+        let info = &args.info;
+        let scalars_end = info.scalars_len.min(info.data.len());
+        let scalar_bytes: &[u8] = bytemuck::cast_slice(&info.data[..scalars_end]);
+
+        for mark in self.marks.iter_mut() {
+            match &mark.value {
+                GraphParamValue::Binding(marked) => {
+                    for (index, resource) in args.resources.iter().enumerate() {
+                        match resource {
+                            KernelResource::Buffer(binding) if same_binding(marked, binding) => {
+                                sites.push(GraphParamSite {
+                                    param: mark.id,
+                                    target: GraphParamTarget::Binding(index),
+                                });
+                            }
+                            KernelResource::TensorMap(map)
+                                if same_binding(marked, &map.binding) =>
+                            {
+                                return Err(ServerError::graph_state(
+                                    "a graph parameter binding cannot be bound as a tensor map",
+                                ));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                GraphParamValue::Scalar(marked) => {
+                    let len = marked.len();
+                    let mut passed = args
+                        .marked_scalars
+                        .iter()
+                        .filter(|scalar| scalar.param == mark.id)
+                        .peekable();
+                    if passed.peek().is_some() {
+                        for scalar in passed {
+                            if scalar.len != len {
+                                return Err(ServerError::graph_state(format!(
+                                    "a scalar graph parameter marked with {len} bytes was \
+                                     passed with {}",
+                                    scalar.len
+                                )));
+                            }
+                            sites.push(GraphParamSite {
+                                param: mark.id,
+                                target: GraphParamTarget::Scalar {
+                                    offset: scalar.offset,
+                                    len,
+                                },
+                            });
+                        }
+                        continue;
+                    }
+
+                    let mut found = scalar_bytes
+                        .chunks_exact(len)
+                        .enumerate()
+                        .filter(|(_, bytes)| *bytes == marked.as_slice())
+                        .map(|(i, _)| i * len);
+                    if let Some(offset) = found.next() {
+                        if found.next().is_some() {
+                            return Err(ServerError::graph_state(
+                                "a scalar graph parameter occurs more than once in a launch; \
+                                 pass it as a marked scalar, or mark it with a placeholder no \
+                                 other scalar shares",
+                            ));
+                        }
+                        sites.push(GraphParamSite {
+                            param: mark.id,
+                            target: GraphParamTarget::Scalar { offset, len },
+                        });
+                    }
+                }
+            }
+        }
+
+        for site in sites.iter() {
+            if let Some(mark) = self.marks.iter_mut().find(|mark| mark.id == site.param) {
+                mark.matched = true;
+            }
+        }
+
+        Ok(sites)
+    }
+
+    /// Seal the marks into the table of the graph being captured, leaving no
+    /// marks behind.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a parameter matched no recorded launch: updating it would
+    /// silently change nothing, which is almost certainly a placeholder that
+    /// was never passed.
+    pub fn finish(&mut self) -> Result<GraphParamTable, ServerError> {
+        let marks = core::mem::take(&mut self.marks);
+        if marks.iter().any(|mark| !mark.matched) {
+            return Err(ServerError::graph_state(
+                "end_capture: a graph parameter is used by none of the recorded launches",
+            ));
+        }
+
+        Ok(GraphParamTable {
+            params: marks
+                .into_iter()
+                .map(|mark| (mark.id, mark.value))
+                .collect(),
+        })
+    }
+
+    /// Drop every mark, for a capture that failed.
+    pub fn clear(&mut self) {
+        self.marks.clear();
+    }
+}
+
+/// The parameters of a captured graph, with their current values.
+///
+/// Holding a binding holds its memory, so a buffer bound to the graph through
+/// an update stays allocated until it is replaced or the graph is dropped.
+#[derive(Debug, Default)]
+pub struct GraphParamTable {
+    params: Vec<(GraphParamId, GraphParamValue)>,
+}
+
+impl GraphParamTable {
+    /// Whether the graph has no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Validate a batch of updates, then store the new values. Call it before
+    /// patching the sites, so a rejected batch changes nothing.
+    ///
+    /// Returns the values replaced. A replaced binding still holds its memory:
+    /// keep it until the replays enqueued before the update are done reading it.
+    ///
+    /// # Errors
+    ///
+    /// Fails for a parameter the graph doesn't have, for a value of another
+    /// kind than the captured one, and for a value of another size.
+    pub fn update(
+        &mut self,
+        updates: &[(GraphParamId, GraphParamValue)],
+    ) -> Result<Vec<GraphParamValue>, ServerError> {
+        for (id, value) in updates {
+            let Some((_, current)) = self.params.iter().find(|(param, _)| param == id) else {
+                return Err(ServerError::graph_state(format!(
+                    "graph_update: {id:?} is not a parameter of this graph"
+                )));
+            };
+            let same_kind = matches!(
+                (current, value),
+                (GraphParamValue::Binding(_), GraphParamValue::Binding(_))
+                    | (GraphParamValue::Scalar(_), GraphParamValue::Scalar(_))
+            );
+            if !same_kind {
+                return Err(ServerError::graph_state(format!(
+                    "graph_update: {id:?} was captured as a {}",
+                    match current {
+                        GraphParamValue::Binding(_) => "binding",
+                        GraphParamValue::Scalar(_) => "scalar",
+                    }
+                )));
+            }
+            if current.size() != value.size() {
+                return Err(ServerError::graph_state(format!(
+                    "graph_update: {id:?} was captured with {} bytes, got {}",
+                    current.size(),
+                    value.size()
+                )));
+            }
+        }
+
+        let mut replaced = Vec::with_capacity(updates.len());
+        for (id, value) in updates {
+            if let Some((_, current)) = self.params.iter_mut().find(|(param, _)| param == id) {
+                replaced.push(core::mem::replace(current, value.clone()));
+            }
+        }
+        Ok(replaced)
+    }
+}
+
+fn same_binding(lhs: &BufferBinding, rhs: &BufferBinding) -> bool {
+    lhs.memory.descriptor().id == rhs.memory.descriptor().id
+        && lhs.offset_start.unwrap_or(0) == rhs.offset_start.unwrap_or(0)
+        && lhs.size_in_used() == rhs.size_in_used()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{Handle, MetadataBindingInfo};
+    use cubecl_environment::stream::StreamId;
+
+    fn args(resources: &[&BufferBinding], data: Vec<u64>) -> KernelArguments {
+        let len = data.len();
+        KernelArguments {
+            resources: resources
+                .iter()
+                .map(|binding| KernelResource::Buffer((*binding).clone()))
+                .collect(),
+            info: MetadataBindingInfo::new(data, len, len),
+            marked_scalars: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bindings_match_every_launch_using_the_memory() {
+        let marked = Handle::new(StreamId::current(), 16).binding();
+        let other = Handle::new(StreamId::current(), 16).binding();
+        let mut marks = GraphParamMarks::default();
+        let id = marks
+            .mark(GraphParamValue::Binding(marked.clone()))
+            .unwrap();
+
+        let sites = marks.sites(&args(&[&other, &marked], vec![])).unwrap();
+        assert_eq!(
+            sites,
+            [GraphParamSite {
+                param: id,
+                target: GraphParamTarget::Binding(1),
+            }]
+        );
+        assert!(marks.sites(&args(&[&other], vec![])).unwrap().is_empty());
+        assert!(marks.finish().unwrap().params.len() == 1);
+    }
+
+    #[test]
+    fn scalars_are_found_by_placeholder() {
+        let mut marks = GraphParamMarks::default();
+        let id = marks
+            .mark(GraphParamValue::Scalar(7u32.to_ne_bytes().to_vec()))
+            .unwrap();
+
+        // Two u32 scalars packed in one word: 3 at bytes 0..4, 7 at 4..8.
+        let word = u64::from_ne_bytes(bytemuck::cast([3u32, 7u32]));
+        let sites = marks.sites(&args(&[], vec![word])).unwrap();
+        assert_eq!(
+            sites,
+            [GraphParamSite {
+                param: id,
+                target: GraphParamTarget::Scalar { offset: 4, len: 4 },
+            }]
+        );
+
+        let ambiguous = u64::from_ne_bytes(bytemuck::cast([7u32, 7u32]));
+        assert!(marks.sites(&args(&[], vec![ambiguous])).is_err());
+    }
+
+    #[test]
+    fn marked_scalars_are_found_where_packed() {
+        let mut marks = GraphParamMarks::default();
+        let id = marks
+            .mark(GraphParamValue::Scalar(7u32.to_ne_bytes().to_vec()))
+            .unwrap();
+
+        // The same value passed twice, only the second time as the parameter.
+        let word = u64::from_ne_bytes(bytemuck::cast([7u32, 7u32]));
+        let mut launch = args(&[], vec![word]);
+        launch.marked_scalars.push(MarkedScalar {
+            param: id,
+            offset: 4,
+            len: 4,
+        });
+        let sites = marks.sites(&launch).unwrap();
+        assert_eq!(
+            sites,
+            [GraphParamSite {
+                param: id,
+                target: GraphParamTarget::Scalar { offset: 4, len: 4 },
+            }]
+        );
+
+        launch.marked_scalars[0].len = 8;
+        assert!(marks.sites(&launch).is_err());
+    }
+
+    #[test]
+    fn placeholders_are_not_searched_in_the_metadata() {
+        let mut marks = GraphParamMarks::default();
+        marks
+            .mark(GraphParamValue::Scalar(7u64.to_ne_bytes().to_vec()))
+            .unwrap();
+
+        // One scalar word, then a metadata word holding the same bytes.
+        let mut launch = args(&[], vec![3, 7]);
+        launch.info.scalars_len = 1;
+        assert!(marks.sites(&launch).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unused_parameters_fail_the_capture() {
+        let mut marks = GraphParamMarks::default();
+        marks
+            .mark(GraphParamValue::Scalar(9u32.to_ne_bytes().to_vec()))
+            .unwrap();
+        marks.sites(&args(&[], vec![1])).unwrap();
+        assert!(marks.finish().is_err());
+        assert!(marks.is_empty(), "a failed finish leaves no marks behind");
+    }
+
+    #[test]
+    fn updates_are_validated_as_a_batch() {
+        let mut marks = GraphParamMarks::default();
+        let scalar = marks
+            .mark(GraphParamValue::Scalar(7u32.to_ne_bytes().to_vec()))
+            .unwrap();
+        marks.sites(&args(&[], vec![7])).unwrap();
+        let mut table = marks.finish().unwrap();
+
+        let wrong_size = [
+            (scalar, GraphParamValue::Scalar(8u32.to_ne_bytes().to_vec())),
+            (scalar, GraphParamValue::Scalar(8u64.to_ne_bytes().to_vec())),
+        ];
+        assert!(table.update(&wrong_size).is_err());
+        let GraphParamValue::Scalar(current) = &table.params[0].1 else {
+            unreachable!()
+        };
+        assert_eq!(
+            current,
+            &7u32.to_ne_bytes(),
+            "a rejected batch changes nothing"
+        );
+
+        let binding = Handle::new(StreamId::current(), 4).binding();
+        assert!(
+            table
+                .update(&[(scalar, GraphParamValue::Binding(binding))])
+                .is_err()
+        );
+        assert!(
+            table
+                .update(&[(GraphParamId::new(), GraphParamValue::Scalar(vec![0; 4]))])
+                .is_err()
+        );
+        let replaced = table
+            .update(&[(scalar, GraphParamValue::Scalar(8u32.to_ne_bytes().to_vec()))])
+            .unwrap();
+        assert!(matches!(
+            &replaced[..],
+            [GraphParamValue::Scalar(bytes)] if bytes[..] == 7u32.to_ne_bytes()
+        ));
+    }
+}
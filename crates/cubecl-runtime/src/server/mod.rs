@@ -1,5 +1,7 @@
 mod base;
+mod graph;
 mod handle;
 
 pub use base::*;
+pub use graph::*;
 pub use handle::*;
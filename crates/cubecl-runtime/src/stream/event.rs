@@ -1,5 +1,6 @@
 use crate::{
     config::streaming::StreamingLogLevel,
+    id::EventId,
     logging::ServerLogger,
     memory_management::{ManagedMemoryId, SharedMemoryBindings},
     server::{BufferBinding, ServerError},
@@ -40,8 +41,19 @@ pub trait EventStreamBackend: 'static {
     fn wait_event(stream: &mut Self::Stream, event: Self::Event);
     /// Wait for the given event synching the CPU.
     fn wait_event_sync(event: Self::Event) -> Result<(), ServerError>;
+    /// Makes the stream wait for the specified event like [`Self::wait_event`], leaving the
+    /// event usable for other waits.
+    fn wait_event_shared(stream: &mut Self::Stream, event: &Self::Event);
+    /// Returns whether the event has completed, without blocking.
+    fn is_event_complete(event: &Self::Event) -> bool;
 }
 
+/// The special stream used for GC.
+const SPECIAL_GC: u8 = 0;
+/// The special stream host synchronization on user events goes through. It only ever waits
+/// on user events, so its flushes complete as soon as the events waited on do.
+const SPECIAL_EVENTS: u8 = 1;
+
 /// Manages multiple streams with synchronization logic based on shared bindings.
 ///
 /// This struct handles the creation and alignment of streams to ensure proper synchronization
@@ -55,6 +67,8 @@ pub struct MultiStream<B: EventStreamBackend> {
     max_streams: usize,
     gc: GcThread<B>,
     shared_bindings_pool: Vec<(ManagedMemoryId, StreamId, u64)>,
+    /// Events recorded on request, see [`MultiStream::record_event`].
+    events: HashMap<EventId, B::Event>,
 }
 
 /// A wrapper around a backend stream that includes synchronization metadata.
@@ -173,7 +187,7 @@ impl<'a, B: EventStreamBackend> Drop for ResolvedStreams<'a, B> {
         let stream = self.streams.get_mut(&self.current);
         let event_origin = B::flush(&mut stream.stream);
 
-        let stream_gc = &mut unsafe { self.streams.get_special(0) }.stream;
+        let stream_gc = &mut unsafe { self.streams.get_special(SPECIAL_GC) }.stream;
         B::wait_event(stream_gc, event_origin);
         let event = B::flush(stream_gc);
 
@@ -194,11 +208,62 @@ impl<B: EventStreamBackend> MultiStream<B> {
     pub fn new(logger: Arc<ServerLogger>, backend: B, max_streams: u8) -> Self {
         let wrapper = EventStreamBackendWrapper { backend };
         Self {
-            streams: StreamPool::new(wrapper, max_streams, 1),
+            streams: StreamPool::new(wrapper, max_streams, 2),
             logger,
             max_streams: max_streams as usize,
             gc: GcThread::new(),
             shared_bindings_pool: Vec::new(),
+            events: HashMap::new(),
+        }
+    }
+
+    /// Records an event after the work enqueued so far on the given stream.
+    ///
+    /// Unlike the events the stream alignment creates internally, it is kept until
+    /// [`Self::destroy_event`], so it can be waited on and queried any number of times.
+    pub fn record_event(&mut self, stream_id: StreamId) -> EventId {
+        let stream = self.streams.get_mut(&stream_id);
+        let event = B::flush(&mut stream.stream);
+        let id = EventId::new();
+        self.events.insert(id, event);
+        id
+    }
+
+    /// Makes the given stream wait for a recorded event.
+    pub fn wait_event(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        let event = self
+            .events
+            .get(&event)
+            .ok_or_else(|| ServerError::unknown_event(event))?;
+        let stream = self.streams.get_mut(&stream_id);
+        B::wait_event_shared(&mut stream.stream, event);
+        Ok(())
+    }
+
+    /// Whether a recorded event has completed.
+    pub fn query_event(&self, event: EventId) -> Result<bool, ServerError> {
+        self.events
+            .get(&event)
+            .map(B::is_event_complete)
+            .ok_or_else(|| ServerError::unknown_event(event))
+    }
+
+    /// An owned event completing with a recorded one, for the host to
+    /// [wait on](EventStreamBackend::wait_event_sync) outside the server.
+    pub fn synchronize_event(&mut self, event: EventId) -> Result<B::Event, ServerError> {
+        let event = self
+            .events
+            .get(&event)
+            .ok_or_else(|| ServerError::unknown_event(event))?;
+        let stream = &mut unsafe { self.streams.get_special(SPECIAL_EVENTS) }.stream;
+        B::wait_event_shared(stream, event);
+        Ok(B::flush(stream))
+    }
+
+    /// Releases a recorded event once it completes. Unknown ids are ignored.
+    pub fn destroy_event(&mut self, event: EventId) {
+        if let Some(event) = self.events.remove(&event) {
+            self.gc.register(GcTask::new((), event));
         }
     }
 
@@ -608,6 +673,29 @@ mod tests {
         }
     }
 
+    #[test_log::test]
+    fn test_recorded_event_outlives_waits() {
+        let logger = Arc::new(ServerLogger::default());
+        let stream_1 = StreamId { value: 1 };
+        let stream_2 = StreamId { value: 2 };
+
+        let gate = Arc::new(AtomicBool::new(false));
+        let mut ms = MultiStream::new(logger, GatedBackend { gate: gate.clone() }, MAX_STREAMS);
+
+        let event = ms.record_event(stream_1);
+        ms.wait_event(event, stream_2).unwrap();
+        ms.wait_event(event, stream_2).unwrap();
+        assert!(!ms.query_event(event).unwrap());
+
+        gate.store(true, Ordering::Release);
+        assert!(ms.query_event(event).unwrap());
+        GatedBackend::wait_event_sync(ms.synchronize_event(event).unwrap()).unwrap();
+
+        ms.destroy_event(event);
+        assert!(ms.query_event(event).is_err());
+        assert!(ms.wait_event(event, stream_2).is_err());
+    }
+
     fn handle(stream: StreamId) -> BufferBinding {
         Handle::new(stream, 10).binding()
     }
@@ -661,6 +749,12 @@ mod tests {
             Ok(())
         }
 
+        fn wait_event_shared(_stream: &mut Self::Stream, _event: &Self::Event) {}
+
+        fn is_event_complete(event: &Self::Event) -> bool {
+            event.gate.load(Ordering::Acquire)
+        }
+
         fn handle_cursor(_stream: &Self::Stream, _handle: &BufferBinding) -> u64 {
             0
         }
@@ -688,6 +782,12 @@ mod tests {
             Ok(())
         }
 
+        fn wait_event_shared(_stream: &mut Self::Stream, _event: &Self::Event) {}
+
+        fn is_event_complete(_event: &Self::Event) -> bool {
+            true
+        }
+
         fn handle_cursor(_stream: &Self::Stream, _handle: &BufferBinding) -> u64 {
             0
         }
@@ -1,9 +1,10 @@
 use crate::{
-    config::streaming::StreamingLogLevel,
+    config::streaming::{StreamPriority, StreamingLogLevel},
     logging::ServerLogger,
     stream::{StreamFactory, StreamPool},
 };
 use alloc::{format, sync::Arc, vec, vec::Vec};
+use core::cmp::Reverse;
 use cubecl_environment::stream::StreamId;
 
 /// Defines a trait for a scheduler stream backend, specifying the types and behavior for task scheduling.
@@ -32,7 +33,17 @@ pub trait SchedulerStreamBackend {
     }
 }
 
+/// How many times [`SchedulerMultiStreamOptions::max_tasks`] a [low-priority](StreamPriority::Low)
+/// stream may queue while higher-priority work is pending, before it is executed anyway.
+const LOW_PRIORITY_DEFERRAL: usize = 4;
+
 /// Represents a multi-stream scheduler that manages task execution across multiple streams.
+///
+/// Each stream has a [`StreamPriority`]. When streams are executed together, higher-priority
+/// streams are enqueued first, and a stream reaching its task limit executes the pending work
+/// of higher-priority streams ahead of its own. Under that contention, low-priority streams are
+/// deferred: they keep queueing up to four times the task limit. Ordering within a stream, and
+/// between streams sharing bindings, is unaffected.
 #[derive(Debug)]
 pub struct SchedulerMultiStream<B: SchedulerStreamBackend> {
     /// Pool of streams managed by the scheduler.
@@ -41,6 +52,9 @@ pub struct SchedulerMultiStream<B: SchedulerStreamBackend> {
     strategy: SchedulerStrategy,
     /// Maximum number of tasks allowed per stream before execution is triggered.
     max_tasks: usize,
+    /// Whether a stream priority was ever set, so the default path skips the contention
+    /// checks.
+    prioritized: bool,
     /// Server logger.
     pub logger: Arc<ServerLogger>,
 }
@@ -61,6 +75,8 @@ pub struct Stream<B: SchedulerStreamBackend> {
     tasks: Vec<B::Task>,
     /// The backend stream used for task execution.
     stream: B::Stream,
+    /// The priority the stream's tasks are scheduled with.
+    priority: StreamPriority,
 }
 
 impl<B: SchedulerStreamBackend> Stream<B> {
@@ -75,6 +91,8 @@ impl<B: SchedulerStreamBackend> Stream<B> {
 #[derive(Debug)]
 struct SchedulerPoolMarker<B: SchedulerStreamBackend> {
     backend: B,
+    /// The priority new streams start with.
+    priority: StreamPriority,
 }
 
 impl<B: SchedulerStreamBackend> StreamFactory for SchedulerPoolMarker<B> {
@@ -87,6 +105,7 @@ impl<B: SchedulerStreamBackend> StreamFactory for SchedulerPoolMarker<B> {
             tasks: Vec::new(),
             // Uses the backend's factory to create a new stream.
             stream: self.backend.factory().create(),
+            priority: self.priority,
         }
     }
 }
@@ -100,6 +119,8 @@ pub struct SchedulerMultiStreamOptions {
     pub max_tasks: usize,
     /// The scheduling strategy to use.
     pub strategy: SchedulerStrategy,
+    /// The priority streams start with, until [`SchedulerMultiStream::set_priority`].
+    pub priority: StreamPriority,
 }
 
 impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
@@ -110,9 +131,17 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
         options: SchedulerMultiStreamOptions,
     ) -> Self {
         Self {
-            pool: StreamPool::new(SchedulerPoolMarker { backend }, options.max_streams, 0),
+            pool: StreamPool::new(
+                SchedulerPoolMarker {
+                    backend,
+                    priority: options.priority,
+                },
+                options.max_streams,
+                0,
+            ),
             max_tasks: options.max_tasks,
             strategy: options.strategy,
+            prioritized: false,
             logger,
         }
     }
@@ -130,6 +159,12 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
         &mut self.pool.factory_mut().backend
     }
 
+    /// Sets the priority the tasks of a stream are scheduled with, see [`SchedulerMultiStream`].
+    pub fn set_priority(&mut self, stream_id: StreamId, priority: StreamPriority) {
+        self.pool.get_mut(&stream_id).priority = priority;
+        self.prioritized = true;
+    }
+
     /// Read-only iterator over initialized backend streams.
     pub fn streams(&self) -> impl Iterator<Item = &B::Stream> {
         self.pool.streams().map(|s| &s.stream)
@@ -149,10 +184,40 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
         let current = self.pool.get_mut(&stream_id);
         current.tasks.push(task);
 
+        let num_tasks = current.tasks.len();
+        let priority = current.priority;
+
         // If the task queue exceeds the maximum, execute the stream.
-        if current.tasks.len() >= self.max_tasks {
+        if num_tasks < self.max_tasks {
+            return;
+        }
+        if !self.prioritized {
             self.execute_streams(vec![stream_id]);
+            return;
+        }
+
+        // Pending higher-priority work runs first, and holds low-priority work back.
+        let mut to_execute = self.pending_above(priority);
+        if priority == StreamPriority::Low
+            && !to_execute.is_empty()
+            && num_tasks < self.max_tasks * LOW_PRIORITY_DEFERRAL
+        {
+            return;
         }
+        to_execute.push(stream_id);
+        self.execute_streams(to_execute);
+    }
+
+    /// The streams with pending tasks and a higher priority than `priority`.
+    fn pending_above(&mut self, priority: StreamPriority) -> Vec<StreamId> {
+        let stream_ids = self.pool.stream_ids().collect::<Vec<_>>();
+        stream_ids
+            .into_iter()
+            .filter(|stream_id| {
+                let stream = self.pool.get_mut(stream_id);
+                !stream.tasks.is_empty() && rank(stream.priority) > rank(priority)
+            })
+            .collect()
     }
 
     /// Aligns streams by flushing tasks from streams that conflict with the given bindings.
@@ -218,6 +283,7 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
                 tasks: tasks.into_iter(),
                 num_tasks,
                 stream_index: index,
+                priority: stream.priority,
             });
         }
 
@@ -226,6 +292,10 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
             return;
         }
 
+        // Higher-priority streams are enqueued first. The sort is stable, so streams of the
+        // same priority keep the requested order.
+        schedules.sort_by_key(|schedule| Reverse(rank(schedule.priority)));
+
         // Execute schedules based on the configured strategy. Interleaving is
         // suspended while any involved stream requires isolation; the
         // sequential path keeps every task on the stream that owns it.
@@ -257,6 +327,9 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
     /// flushing all other streams first and flushing the execution stream at the end.
     /// This way, we ensure that most tasks are actually interleaved on the real compute queue
     /// shared across all streams.
+    ///
+    /// Only streams of the same priority are interleaved: every task of a higher-priority
+    /// stream is enqueued before those of lower-priority ones.
     fn execute_schedules_interleave(&mut self, mut schedules: Vec<Schedule<B>>) {
         // Makes sure the tasks are ordered on the compute queue.
         for schedule in schedules.iter_mut().skip(1) {
@@ -267,19 +340,22 @@ impl<B: SchedulerStreamBackend> SchedulerMultiStream<B> {
         let execution_index = schedules.first().expect("At least one stream").stream_index;
         let stream = unsafe { self.pool.get_mut_index(execution_index) };
 
-        // Find the maximum number of tasks across all schedules.
-        let num_tasks_max = schedules
-            .iter()
-            .map(|s| s.num_tasks)
-            .max()
-            .expect("At least one schedule");
-
-        // Iterate through tasks, interleaving them across streams.
-        for _ in 0..num_tasks_max {
-            for schedule in schedules.iter_mut() {
-                // If there are tasks remaining in the schedule, enqueue the next one.
-                if let Some(task) = schedule.tasks.next() {
-                    B::enqueue(task, &mut stream.stream);
+        // Schedules are sorted by priority, so each group is contiguous.
+        for group in schedules.chunk_by_mut(|a, b| a.priority == b.priority) {
+            // Find the maximum number of tasks across the group's schedules.
+            let num_tasks_max = group
+                .iter()
+                .map(|s| s.num_tasks)
+                .max()
+                .expect("At least one schedule");
+
+            // Iterate through tasks, interleaving them across streams.
+            for _ in 0..num_tasks_max {
+                for schedule in group.iter_mut() {
+                    // If there are tasks remaining in the schedule, enqueue the next one.
+                    if let Some(task) = schedule.tasks.next() {
+                        B::enqueue(task, &mut stream.stream);
+                    }
                 }
             }
         }
@@ -297,4 +373,166 @@ struct Schedule<B: SchedulerStreamBackend> {
     num_tasks: usize,
     // Index of the stream in the pool.
     stream_index: usize,
+    // Priority of the stream.
+    priority: StreamPriority,
+}
+
+/// Scheduling rank of a priority, higher ranks are executed first.
+fn rank(priority: StreamPriority) -> u8 {
+    match priority {
+        StreamPriority::Low => 0,
+        StreamPriority::Default => 1,
+        StreamPriority::High => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    const MAX_STREAMS: u8 = 4;
+    const MAX_TASKS: usize = 2;
+
+    #[test_log::test]
+    fn test_higher_priority_enqueued_first() {
+        let (mut scheduler, log) = scheduler(16);
+        let [low, high_1, high_2] = [1, 2, 3].map(|value| StreamId { value });
+        scheduler.set_priority(low, StreamPriority::Low);
+        scheduler.set_priority(high_1, StreamPriority::High);
+        scheduler.set_priority(high_2, StreamPriority::High);
+
+        for task in [10, 11] {
+            scheduler.register(low, task, &[]);
+        }
+        for task in [20, 21] {
+            scheduler.register(high_1, task, &[]);
+        }
+        for task in [30, 31] {
+            scheduler.register(high_2, task, &[]);
+        }
+        scheduler.execute_streams(vec![low, high_1, high_2]);
+
+        // Streams of the same priority are still interleaved.
+        assert_eq!(*log.lock().unwrap(), [20, 30, 21, 31, 10, 11]);
+    }
+
+    #[test_log::test]
+    fn test_pending_higher_priority_executes_first() {
+        let (mut scheduler, log) = scheduler(MAX_TASKS);
+        let [default, high] = [1, 2].map(|value| StreamId { value });
+        scheduler.set_priority(high, StreamPriority::High);
+
+        scheduler.register(high, 20, &[]);
+        scheduler.register(default, 10, &[]);
+        assert!(log.lock().unwrap().is_empty());
+
+        scheduler.register(default, 11, &[]);
+        assert_eq!(*log.lock().unwrap(), [20, 10, 11]);
+    }
+
+    #[test_log::test]
+    fn test_low_priority_deferred_under_contention() {
+        let (mut scheduler, log) = scheduler(MAX_TASKS);
+        let [low, high] = [1, 2].map(|value| StreamId { value });
+        scheduler.set_priority(low, StreamPriority::Low);
+        scheduler.set_priority(high, StreamPriority::High);
+
+        scheduler.register(high, 20, &[]);
+        let limit = MAX_TASKS * LOW_PRIORITY_DEFERRAL;
+        for task in 0..limit as u32 - 1 {
+            scheduler.register(low, task, &[]);
+        }
+        assert!(log.lock().unwrap().is_empty());
+
+        scheduler.register(low, limit as u32 - 1, &[]);
+        let expected = core::iter::once(20)
+            .chain(0..limit as u32)
+            .collect::<Vec<_>>();
+        assert_eq!(*log.lock().unwrap(), expected);
+    }
+
+    #[test_log::test]
+    fn test_low_priority_not_deferred_without_contention() {
+        let (mut scheduler, log) = scheduler(MAX_TASKS);
+        let low = StreamId { value: 1 };
+        scheduler.set_priority(low, StreamPriority::Low);
+
+        scheduler.register(low, 10, &[]);
+        scheduler.register(low, 11, &[]);
+        assert_eq!(*log.lock().unwrap(), [10, 11]);
+    }
+
+    #[test_log::test]
+    fn test_shared_binding_flushes_deferred_stream() {
+        let (mut scheduler, log) = scheduler(MAX_TASKS);
+        let [low, high] = [1, 2].map(|value| StreamId { value });
+        scheduler.set_priority(low, StreamPriority::Low);
+        scheduler.set_priority(high, StreamPriority::High);
+
+        scheduler.register(high, 20, &[]);
+        scheduler.register(low, 10, &[]);
+        scheduler.register(low, 11, &[]);
+        assert!(log.lock().unwrap().is_empty());
+
+        // A task on the high-priority stream reading the low-priority stream's output must
+        // still wait for it.
+        scheduler.register(high, 21, &[low]);
+        scheduler.execute_streams(vec![high]);
+        assert_eq!(*log.lock().unwrap(), [10, 11, 20, 21]);
+    }
+
+    fn scheduler(max_tasks: usize) -> (SchedulerMultiStream<TestBackend>, Log) {
+        let log = Log::default();
+        let scheduler = SchedulerMultiStream::new(
+            Arc::new(ServerLogger::default()),
+            TestBackend { log: log.clone() },
+            SchedulerMultiStreamOptions {
+                max_streams: MAX_STREAMS,
+                max_tasks,
+                strategy: SchedulerStrategy::Interleave,
+                priority: StreamPriority::Default,
+            },
+        );
+        (scheduler, log)
+    }
+
+    /// The tasks in the order they reached the device.
+    type Log = Arc<Mutex<Vec<u32>>>;
+
+    #[derive(Debug)]
+    struct TestBackend {
+        log: Log,
+    }
+
+    #[derive(Debug)]
+    struct TestStream {
+        log: Log,
+    }
+
+    impl StreamFactory for TestBackend {
+        type Stream = TestStream;
+
+        fn create(&mut self) -> Self::Stream {
+            TestStream {
+                log: self.log.clone(),
+            }
+        }
+    }
+
+    impl SchedulerStreamBackend for TestBackend {
+        type Task = u32;
+        type Stream = TestStream;
+        type Factory = Self;
+
+        fn enqueue(task: Self::Task, stream: &mut Self::Stream) {
+            stream.log.lock().unwrap().push(task);
+        }
+
+        fn flush(_stream: &mut Self::Stream) {}
+
+        fn factory(&mut self) -> &mut Self::Factory {
+            self
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::throughput::{CmmaDims, ComputeCmmaConfig};
 use alloc::{format, string::String};
 use core::time::Duration;
-use cubecl_ir::{ElemType, FloatKind};
+use cubecl_ir::{ElemType, FloatKind, IntKind};
 
 /// Bytes per buffer of the single-size memory probes, [`ThroughputMode::Memory`]
 /// and [`ThroughputMode::MemoryRead`]. Clamped to the device's maximum
@@ -37,6 +37,13 @@ impl MemoryAccess {
     pub const fn default_working_set(&self) -> u64 {
         DEFAULT_BUFFER_BYTES * self.buffers()
     }
+
+    /// The largest working set this access can be probed at on a device that
+    /// allocates at most `max_alloc` bytes at once: as much as one buffer can
+    /// hold, times the buffers the access touches.
+    pub fn working_set_cap(&self, max_alloc: u64) -> u64 {
+        DEFAULT_BUFFER_BYTES.min(max_alloc) * self.buffers()
+    }
 }
 
 /// Represents the mode of a throughput computation.
@@ -55,6 +62,34 @@ pub enum ThroughputMode {
         /// The configuration of the CMMA operation.
         config: ComputeCmmaConfig,
     },
+    /// Packed integer dot products accumulated into 32-bit integers, the ceiling for
+    /// quantized kernels that don't go through CMMA. `ops_count` counts a multiply-add
+    /// as two operations, like the other compute modes.
+    ComputeDot {
+        /// How the operands are packed.
+        packing: DotPacking,
+    },
+    /// Scalar fma at a fixed vector width.
+    ///
+    /// [`ComputeDirect`](Self::ComputeDirect) uses whatever width the device prefers for
+    /// IO, which says nothing about packed half precision math: `f16` and `bf16` are only
+    /// at full rate two lanes at a time on most hardware, and at half rate or worse one
+    /// lane at a time.
+    ComputeVector {
+        /// The data type of the computation.
+        dtype: ElemType,
+        /// The number of lanes of every fma.
+        vector_size: usize,
+    },
+    /// Special function throughput, which runs on its own units at a fraction of the fma
+    /// rate: the ceiling for softmax, normalization and activation heavy kernels.
+    /// `ops_count` counts one operation per function evaluated.
+    ComputeSpecial {
+        /// The data type of the computation.
+        dtype: ElemType,
+        /// The function evaluated.
+        function: SpecialFunction,
+    },
     /// Memory input reads and output writes — a copy, at the default working
     /// set. `ops_count` counts both directions, so this is total traffic across
     /// the memory interface.
@@ -107,11 +142,48 @@ impl ThroughputMode {
                 Some((MemoryAccess::Read, MemoryAccess::Read.default_working_set()))
             }
             Self::MemoryWorkingSet { access, bytes } => Some((*access, *bytes)),
-            Self::ComputeDirect { .. } | Self::ComputeCmma { .. } | Self::Launch => None,
+            Self::ComputeDirect { .. }
+            | Self::ComputeCmma { .. }
+            | Self::ComputeDot { .. }
+            | Self::ComputeVector { .. }
+            | Self::ComputeSpecial { .. }
+            | Self::Launch => None,
+        }
+    }
+}
+
+/// How the operands of a [`ThroughputMode::ComputeDot`] are packed.
+#[derive(Eq, PartialEq, Clone, Hash, Debug, Copy)]
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+pub enum DotPacking {
+    /// Four 8-bit integers per 32-bit word.
+    Int8x4,
+    /// Eight 4-bit integers per 32-bit word, unpacked to two words of 8-bit lanes by
+    /// masking before the dot products: outside of matrix units, no device has a native
+    /// 4-bit dot, so the unpacking is part of the real cost.
+    Int4x8,
+}
+
+impl DotPacking {
+    /// The multiply-adds retired per 32-bit word of each operand.
+    pub const fn macs_per_word(&self) -> usize {
+        match self {
+            Self::Int8x4 => 4,
+            Self::Int4x8 => 8,
         }
     }
 }
 
+/// The function of a [`ThroughputMode::ComputeSpecial`].
+#[derive(Eq, PartialEq, Clone, Hash, Debug, Copy)]
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecialFunction {
+    /// `exp(x)`.
+    Exp,
+    /// `1 / sqrt(x)`.
+    InverseSqrt,
+}
+
 /// Represents a key/configuration used to identify the throughput of a computation.
 #[derive(Eq, PartialEq, Clone, Hash, Debug, Copy)]
 #[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
@@ -128,6 +200,10 @@ impl ThroughputKey {
         match self.mode {
             ThroughputMode::ComputeDirect { dtype } => dtype,
             ThroughputMode::ComputeCmma { dtype, .. } => dtype,
+            ThroughputMode::ComputeVector { dtype, .. } => dtype,
+            ThroughputMode::ComputeSpecial { dtype, .. } => dtype,
+            // Both packings multiply 8-bit lanes.
+            ThroughputMode::ComputeDot { .. } => ElemType::Int(IntKind::I8),
             // For memory and launch throughput, we use a default element type (F32).
             ThroughputMode::Memory
             | ThroughputMode::MemoryRead
@@ -182,9 +258,11 @@ impl ThroughputValue {
     /// Formats the throughput value as a clean human-readable string.
     pub fn format(&self, key: &ThroughputKey) -> String {
         let (mut val_per_s, unit) = match key.mode {
-            ThroughputMode::ComputeDirect { .. } | ThroughputMode::ComputeCmma { .. } => {
-                (self.ops_per_s(), "OPS")
-            }
+            ThroughputMode::ComputeDirect { .. }
+            | ThroughputMode::ComputeCmma { .. }
+            | ThroughputMode::ComputeDot { .. }
+            | ThroughputMode::ComputeVector { .. }
+            | ThroughputMode::ComputeSpecial { .. } => (self.ops_per_s(), "OPS"),
             ThroughputMode::Memory
             | ThroughputMode::MemoryRead
             | ThroughputMode::MemoryWorkingSet { .. } => (self.bytes_per_s(key), "bytes"),
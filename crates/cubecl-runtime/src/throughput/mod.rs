@@ -3,8 +3,10 @@ mod benchmarker;
 mod cache;
 mod cmma;
 mod curve;
+mod roofline;
 pub use base::*;
 pub use benchmarker::*;
 pub use cache::*;
 pub use cmma::*;
 pub use curve::*;
+pub use roofline::*;
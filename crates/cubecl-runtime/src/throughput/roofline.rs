@@ -0,0 +1,273 @@
+//! What a kernel costs, counted from its compiled IR rather than measured.
+//!
+//! A [`KernelCost`] says how many bytes one unit moves and how many operations
+//! it performs. Held against the device ceilings — a [`MemoryCurve`] for the
+//! traffic and a compute rate per element type for the arithmetic — it gives a
+//! roofline: the least time the kernel can take, and which side of the device
+//! limits it. No launch is needed, only the probes the throughput cache already
+//! holds.
+
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::{fmt::Display, time::Duration};
+use cubecl_environment::{collections::HashMap, sync::Mutex};
+use cubecl_ir::ElemType;
+
+use crate::{id::KernelId, throughput::MemoryCurve};
+
+/// The static cost of one unit of a kernel, counted over its compiled IR.
+///
+/// Loops with a trip count known at compile time multiply their body, the others
+/// count it once and are tallied in [`dynamic_loops`](Self::dynamic_loops), so
+/// the figures are a lower bound whenever that isn't zero. Of the alternatives of
+/// a branch, the most expensive is counted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KernelCost {
+    /// Bytes loaded from global memory.
+    pub global_read_bytes: u64,
+    /// Bytes stored to global memory.
+    pub global_write_bytes: u64,
+    /// Bytes loaded from shared memory.
+    pub shared_read_bytes: u64,
+    /// Bytes stored to shared memory.
+    pub shared_write_bytes: u64,
+    /// Arithmetic operations by element type, one per lane, with an fma or a dot
+    /// product step counting as two like the compute probes do.
+    pub arithmetic: BTreeMap<ElemType, u64>,
+    /// Atomic operations on any memory.
+    pub atomics: u64,
+    /// Synchronization barriers.
+    pub barriers: u64,
+    /// Plane (warp) operations.
+    pub plane_ops: u64,
+    /// Loops whose trip count isn't known at compile time.
+    pub dynamic_loops: u64,
+}
+
+impl KernelCost {
+    /// Bytes moved across the global memory interface.
+    pub fn global_bytes(&self) -> u64 {
+        self.global_read_bytes + self.global_write_bytes
+    }
+
+    /// Arithmetic operations of every type.
+    pub fn arithmetic_ops(&self) -> u64 {
+        self.arithmetic.values().sum()
+    }
+
+    /// Arithmetic operations per byte of global traffic, `None` for a kernel that
+    /// doesn't touch global memory.
+    pub fn intensity(&self) -> Option<f64> {
+        match self.global_bytes() {
+            0 => None,
+            bytes => Some(self.arithmetic_ops() as f64 / bytes as f64),
+        }
+    }
+
+    /// The element type most of the arithmetic is done in.
+    pub fn dominant_type(&self) -> Option<ElemType> {
+        self.arithmetic
+            .iter()
+            .max_by_key(|(_, ops)| **ops)
+            .map(|(elem, _)| *elem)
+    }
+
+    /// The roofline of a launch of `units` units.
+    ///
+    /// The memory side asks `memory` for the ceiling of the launch's whole working
+    /// set, so a small launch is held to what the device reaches at that size. The
+    /// compute side sums the time of every element type `compute` has a rate for,
+    /// in operations per second; types it has none for are left out.
+    pub fn roofline(
+        &self,
+        units: u64,
+        memory: &MemoryCurve,
+        compute: impl Fn(ElemType) -> Option<f64>,
+    ) -> RooflineEstimate {
+        let bytes = self.global_bytes().saturating_mul(units);
+        let memory = match bytes {
+            0 => Some(Duration::ZERO),
+            bytes => memory
+                .ceiling_at(bytes)
+                .map(|rate| Duration::from_secs_f64(bytes as f64 / rate)),
+        };
+
+        let mut compute_time = None;
+        for (elem, ops) in &self.arithmetic {
+            let Some(rate) = compute(*elem).filter(|rate| rate.is_finite() && *rate > 0.0) else {
+                continue;
+            };
+            let time = (*ops).saturating_mul(units) as f64 / rate;
+            *compute_time.get_or_insert(Duration::ZERO) += Duration::from_secs_f64(time);
+        }
+
+        RooflineEstimate {
+            memory,
+            compute: compute_time,
+        }
+    }
+}
+
+impl Display for KernelCost {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "global {} B read / {} B written, shared {} B read / {} B written",
+            self.global_read_bytes,
+            self.global_write_bytes,
+            self.shared_read_bytes,
+            self.shared_write_bytes
+        )?;
+
+        let ops = self
+            .arithmetic
+            .iter()
+            .map(|(elem, ops)| format!("{ops} {elem}"))
+            .collect::<Vec<_>>();
+        if !ops.is_empty() {
+            write!(f, ", ops {}", ops.join(" + "))?;
+        }
+        if let Some(intensity) = self.intensity() {
+            write!(f, ", {intensity:.2} ops/B")?;
+        }
+        if self.atomics > 0 {
+            write!(f, ", {} atomics", self.atomics)?;
+        }
+        if self.barriers > 0 {
+            write!(f, ", {} barriers", self.barriers)?;
+        }
+        if self.plane_ops > 0 {
+            write!(f, ", {} plane ops", self.plane_ops)?;
+        }
+        if self.dynamic_loops > 0 {
+            write!(f, ", {} loops of unknown trip count", self.dynamic_loops)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Which side of the device limits a kernel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RooflineBound {
+    /// The kernel can't move its bytes faster than the memory interface allows.
+    Memory,
+    /// The kernel can't do its arithmetic faster than the compute units allow.
+    Compute,
+}
+
+/// The least time a launch can take on each side of the roofline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RooflineEstimate {
+    /// The time to move the launch's global traffic, `None` without a memory ceiling.
+    pub memory: Option<Duration>,
+    /// The time to do the launch's arithmetic, `None` without a compute ceiling for
+    /// any of its element types.
+    pub compute: Option<Duration>,
+}
+
+impl RooflineEstimate {
+    /// The side that takes longer, `None` unless both are known.
+    pub fn bound(&self) -> Option<RooflineBound> {
+        match (self.memory?, self.compute?) {
+            (memory, compute) if memory >= compute => Some(RooflineBound::Memory),
+            _ => Some(RooflineBound::Compute),
+        }
+    }
+
+    /// The least time the launch can take: the slower side of those known.
+    pub fn time(&self) -> Option<Duration> {
+        match (self.memory, self.compute) {
+            (Some(memory), Some(compute)) => Some(memory.max(compute)),
+            (memory, compute) => memory.or(compute),
+        }
+    }
+}
+
+/// Costs of the kernels compiled so far, by kernel id.
+static KERNEL_COSTS: Mutex<Option<HashMap<KernelId, (String, KernelCost)>>> = Mutex::new(None);
+
+/// Remember the cost of a compiled kernel, see [`kernel_costs`].
+pub(crate) fn register_kernel_cost(id: KernelId, name: &str, cost: KernelCost) {
+    let mut costs = KERNEL_COSTS.lock();
+    costs
+        .get_or_insert_with(HashMap::new)
+        .insert(id, (name.into(), cost));
+}
+
+/// The name and cost of every kernel compiled in this process by a compiler that
+/// analyzes them, sorted by name.
+pub fn kernel_costs() -> Vec<(String, KernelCost)> {
+    let costs = KERNEL_COSTS.lock();
+    let mut costs = costs
+        .iter()
+        .flat_map(|costs| costs.values().cloned())
+        .collect::<Vec<_>>();
+    costs.sort_by(|a, b| a.0.cmp(&b.0));
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::throughput::{MemoryAccess, MemoryPoint, ThroughputValue};
+    use cubecl_ir::FloatKind;
+
+    const F32: ElemType = ElemType::Float(FloatKind::F32);
+    const GB: u64 = 1_000_000_000;
+
+    /// A flat curve at `bytes_per_s`.
+    fn flat_curve(bytes_per_s: f64) -> MemoryCurve {
+        MemoryCurve::new(
+            MemoryAccess::Copy,
+            [MemoryPoint {
+                bytes: GB,
+                value: ThroughputValue {
+                    ops_count: (GB / 4) as usize,
+                    duration: Duration::from_secs_f64(GB as f64 / bytes_per_s),
+                },
+            }],
+        )
+    }
+
+    fn assert_close(time: Option<Duration>, secs: f64) {
+        let time = time.expect("Should have an estimate").as_secs_f64();
+        assert!((time - secs).abs() < 1e-6, "{time} != {secs}");
+    }
+
+    fn cost(bytes: u64, ops: u64) -> KernelCost {
+        KernelCost {
+            global_read_bytes: bytes,
+            arithmetic: [(F32, ops)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn low_intensity_is_memory_bound() {
+        let cost = cost(8, 2);
+        let estimate = cost.roofline(GB, &flat_curve(1e12), |_| Some(1e13));
+
+        assert_eq!(cost.intensity(), Some(0.25));
+        assert_eq!(estimate.bound(), Some(RooflineBound::Memory));
+        assert_close(estimate.time(), 0.008);
+    }
+
+    #[test]
+    fn high_intensity_is_compute_bound() {
+        let cost = cost(8, 800);
+        let estimate = cost.roofline(GB, &flat_curve(1e12), |_| Some(1e13));
+
+        assert_eq!(estimate.bound(), Some(RooflineBound::Compute));
+        assert_close(estimate.time(), 0.08);
+    }
+
+    #[test]
+    fn types_without_a_ceiling_are_left_out() {
+        let cost = cost(8, 800);
+        let estimate = cost.roofline(GB, &flat_curve(1e12), |_| None);
+
+        assert_eq!(estimate.compute, None);
+        assert_eq!(estimate.bound(), None);
+        assert_eq!(estimate.time(), estimate.memory);
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use cubecl_common::profile::Instant;
+use cubecl_environment::future::DynFut;
+
+use crate::tune::{AutotuneLogContext, AutotuneLoggerExt, AutotuneResult, TunePlan};
+
+/// Tuning state for a key of a set tuned in the background (see
+/// [`TunableSet::with_background`](crate::tune::TunableSet::with_background)).
+///
+/// Benchmarks borrow the caller's inputs, so they can't be handed to another thread. Instead
+/// the tune is spread across the calls that hit the key: each one runs the default tunable,
+/// and when a step is due it also queues the samples of the next candidate. The call never
+/// waits for those samples: the device runs them once it is done with the work queued before,
+/// and a later call picks up the result once they resolved.
+#[derive(Debug)]
+pub(crate) struct BackgroundTune {
+    /// Drives the candidate order exactly like a blocking tune: a batch that produced a
+    /// valid result ends the tune, a batch that failed entirely moves on to the next one.
+    pub(crate) plan: TunePlan,
+    /// Candidates of the current batch still to be benchmarked.
+    pub(crate) queue: Vec<usize>,
+    /// Whether any candidate of the current batch produced a valid result.
+    pub(crate) batch_success: bool,
+    /// Results in tunable order, `Skip` for the candidates not benchmarked (yet).
+    pub(crate) results: Vec<AutotuneResult>,
+    /// The benchmark queued by the last step, until its samples resolve.
+    pub(crate) in_flight: Option<InFlight>,
+    /// Whether [`wait_background`](crate::tune::Tuner::wait_background) took the benchmark
+    /// in flight to block on it outside the lock. No other step may start until it completes.
+    pub(crate) waiting: bool,
+    /// Whether a result ended the tune, see [`record`](Self::record).
+    pub(crate) finished: bool,
+    pub(crate) last_step: Option<Instant>,
+    pub(crate) limit: Option<Duration>,
+    /// Whether a candidate under [`limit`](Self::limit) ends the tune on the spot.
+    pub(crate) short_circuit: bool,
+    #[cfg(autotune_persistence)]
+    pub(crate) bounds: Option<crate::tune::Bounds>,
+    pub(crate) log_context: Option<AutotuneLogContext>,
+}
+
+/// A candidate whose samples are queued on the device but not resolved yet.
+pub(crate) struct InFlight {
+    pub(crate) index: usize,
+    pub(crate) name: String,
+    pub(crate) started: Instant,
+    pub(crate) result: DynFut<AutotuneResult>,
+}
+
+impl core::fmt::Debug for InFlight {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InFlight")
+            .field("index", &self.index)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What the caller of a background tune should do with the key it asked about.
+pub(crate) enum BackgroundStep {
+    /// Nothing to benchmark on this call: run the default tunable.
+    Wait,
+    /// Queue the samples of this candidate, then hand them over with
+    /// [`BackgroundTune::launched`].
+    Bench(usize),
+    /// Every batch is exhausted or one succeeded: commit the results.
+    Done,
+}
+
+impl BackgroundTune {
+    pub(crate) fn new(
+        plan: TunePlan,
+        results: Vec<AutotuneResult>,
+        limit: Option<Duration>,
+        short_circuit: bool,
+        #[cfg(autotune_persistence)] bounds: Option<crate::tune::Bounds>,
+        log_context: Option<AutotuneLogContext>,
+    ) -> Self {
+        Self {
+            plan,
+            queue: Vec::new(),
+            batch_success: false,
+            results,
+            in_flight: None,
+            waiting: false,
+            finished: false,
+            last_step: None,
+            limit,
+            short_circuit,
+            #[cfg(autotune_persistence)]
+            bounds,
+            log_context,
+        }
+    }
+
+    /// Pick the next step, given the minimum time between two benchmarks.
+    ///
+    /// The very first call never benchmarks: it is the one that found the key untuned, and
+    /// the whole point of the mode is that it returns as fast as the default allows. No step is
+    /// due either while a benchmark is in flight, the interval starts once it resolved.
+    pub(crate) fn next_step(&mut self, interval: Duration) -> BackgroundStep {
+        if self.poll_in_flight() || self.finished {
+            return BackgroundStep::Done;
+        }
+        if self.in_flight.is_some() || self.waiting {
+            return BackgroundStep::Wait;
+        }
+
+        let Some(last) = self.last_step else {
+            self.last_step = Some(Instant::now());
+            return BackgroundStep::Wait;
+        };
+
+        if last.elapsed() < interval {
+            return BackgroundStep::Wait;
+        }
+
+        if self.queue.is_empty() {
+            if self.batch_success {
+                return BackgroundStep::Done;
+            }
+
+            // Reversed, so candidates are popped in the order the plan returned them.
+            let mut batch = self.plan.next();
+            if batch.is_empty() {
+                return BackgroundStep::Done;
+            }
+            batch.reverse();
+            self.queue = batch;
+        }
+
+        self.last_step = Some(Instant::now());
+        match self.queue.pop() {
+            Some(index) => BackgroundStep::Bench(index),
+            None => BackgroundStep::Done,
+        }
+    }
+
+    /// Hand over the samples queued for a candidate returned by [`next_step`](Self::next_step).
+    ///
+    /// Returns whether the tune is over, which can only be the case when the samples already
+    /// resolved, e.g. with system timing.
+    pub(crate) fn launched(&mut self, in_flight: InFlight) -> bool {
+        self.in_flight = Some(in_flight);
+        self.poll_in_flight()
+    }
+
+    /// Check on the benchmark in flight without blocking, recording its result once the samples
+    /// resolved. Returns whether that result ended the tune.
+    pub(crate) fn poll_in_flight(&mut self) -> bool {
+        let Some(in_flight) = self.in_flight.as_mut() else {
+            return false;
+        };
+
+        let mut context = Context::from_waker(futures_util::task::noop_waker_ref());
+        let Poll::Ready(result) = in_flight.result.as_mut().poll(&mut context) else {
+            return false;
+        };
+
+        let in_flight = self.in_flight.take().expect("Checked above");
+        self.complete(in_flight.index, in_flight.name, in_flight.started, result)
+    }
+
+    /// Take the benchmark in flight to block on it, keeping the key busy until its result is
+    /// handed back with [`complete`](Self::complete).
+    pub(crate) fn take_in_flight(&mut self) -> Option<InFlight> {
+        let in_flight = self.in_flight.take()?;
+        self.waiting = true;
+        Some(in_flight)
+    }
+
+    /// Record the result of a candidate returned by [`next_step`](Self::next_step), along with
+    /// its tuning step. Returns whether the tune is over, see [`record`](Self::record).
+    pub(crate) fn complete(
+        &mut self,
+        index: usize,
+        name: String,
+        started: Instant,
+        result: AutotuneResult,
+    ) -> bool {
+        self.waiting = false;
+        self.log_context
+            .push_tuning_step(name.clone(), started.elapsed());
+
+        let finished = self.record(index, result);
+        if finished && !self.queue.is_empty() {
+            self.log_context.push_short_circuit(name);
+        }
+
+        finished
+    }
+
+    /// Store the result of a benchmark returned by [`next_step`](Self::next_step).
+    ///
+    /// Returns whether the tune is over: the batch is done and one of its candidates
+    /// succeeded, or this result reaches the time limit, like the short circuit of a
+    /// blocking tune.
+    pub(crate) fn record(&mut self, index: usize, result: AutotuneResult) -> bool {
+        let close_enough = match (&result.outcome, self.limit) {
+            (Ok(outcome), Some(limit)) => self.short_circuit && outcome.computation.median <= limit,
+            _ => false,
+        };
+
+        self.batch_success |= result.outcome.is_ok();
+        self.results[index] = result;
+        // The step ended now, not when it started: the interval is idle time between steps.
+        self.last_step = Some(Instant::now());
+
+        self.finished = close_enough || (self.queue.is_empty() && self.batch_success);
+        self.finished
+    }
+
+    /// Whether any candidate produced a valid result, i.e. whether there is a winner to commit.
+    pub(crate) fn any_success(&self) -> bool {
+        self.results.iter().any(|result| result.outcome.is_ok())
+    }
+}
@@ -0,0 +1,248 @@
+//! Reproducible tuning decisions, see
+//! [`DeterministicConfig`](crate::config::autotune::DeterministicConfig).
+
+use crate::tune::AutotuneResult;
+
+#[cfg(std_io)]
+pub(crate) use decisions::*;
+
+/// Settle a decision on the candidate registered first among those tied with the winner, i.e.
+/// measured no more than `tolerance` slower than `fastest_index`.
+///
+/// Timing noise reorders close candidates from one run to the next; registration order doesn't
+/// move. A winner without a measurement of its own is returned as is, there is nothing to
+/// compare against.
+pub(crate) fn break_ties(
+    results: &[AutotuneResult],
+    fastest_index: usize,
+    tolerance: f64,
+) -> usize {
+    let outcomes = || {
+        results
+            .iter()
+            .filter_map(|result| result.outcome.as_ref().ok())
+    };
+
+    let Some(winner) = outcomes().find(|outcome| outcome.index == fastest_index) else {
+        return fastest_index;
+    };
+    let ceiling = winner.computation.median.as_secs_f64() * (1.0 + tolerance);
+
+    outcomes()
+        .filter(|outcome| outcome.computation.median.as_secs_f64() <= ceiling)
+        .map(|outcome| outcome.index)
+        .min()
+        .unwrap_or(fastest_index)
+}
+
+#[cfg(std_io)]
+mod decisions {
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use cubecl_environment::collections::HashMap;
+    use cubecl_environment::sync::Mutex;
+    use std::io::{BufRead, Write};
+    use std::path::{Path, PathBuf};
+
+    use crate::config::autotune::{DeterministicConfig, DeterministicMode};
+    use crate::tune::{AutotuneError, AutotuneKey};
+
+    /// One line of a decision file.
+    ///
+    /// The candidate is stored by name rather than by index, so reordering a
+    /// [`TunableSet`](crate::tune::TunableSet) doesn't silently repoint a committed decision,
+    /// and removing the winner fails loudly instead.
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Decision {
+        /// The name of the tuner, as passed to [`Tuner::new`](crate::tune::Tuner::new).
+        tuner: String,
+        key: serde_json::Value,
+        name: String,
+    }
+
+    /// Decisions by tuner and JSON-rendered key.
+    type Decisions = HashMap<(String, String), String>;
+
+    /// Pinned decisions of every decision file read so far, loaded on first use.
+    static PINNED: Mutex<Option<HashMap<PathBuf, Decisions>>> = Mutex::new(None);
+
+    /// Decisions this process appended to every decision file, so a key resolved on every call
+    /// is written once rather than once per call.
+    static RECORDED: Mutex<Option<HashMap<PathBuf, Decisions>>> = Mutex::new(None);
+
+    /// The decision file of `config`, if it is in `mode`.
+    fn decision_file(
+        config: &DeterministicConfig,
+        mode: DeterministicMode,
+    ) -> Result<Option<&Path>, AutotuneError> {
+        if config.mode != mode {
+            return Ok(None);
+        }
+
+        match &config.decisions {
+            Some(path) => Ok(Some(path)),
+            None => Err(AutotuneError::InvalidDecisionFile {
+                context: format!(
+                    "deterministic autotune mode {mode:?} needs a decision file, set \
+                     `autotune.deterministic.decisions` or CUBECL_AUTOTUNE_DECISIONS"
+                ),
+            }),
+        }
+    }
+
+    /// The name of the candidate pinned for `key`, when decisions are
+    /// [`Pinned`](DeterministicMode::Pinned), `None` otherwise.
+    ///
+    /// Fails with [`MissingPinnedDecision`](AutotuneError::MissingPinnedDecision) when decisions
+    /// are pinned and the file has no decision for `key`.
+    pub(crate) fn pinned_decision<K: AutotuneKey>(
+        config: &DeterministicConfig,
+        tuner: &str,
+        key: &K,
+    ) -> Result<Option<String>, AutotuneError> {
+        let Some(path) = decision_file(config, DeterministicMode::Pinned)? else {
+            return Ok(None);
+        };
+        // Rendered through a `Value` like the keys of the file, whose objects sort their fields.
+        let rendered = serde_json::to_value(key)
+            .expect("Autotune keys serialize to JSON")
+            .to_string();
+
+        let mut pinned = PINNED.lock();
+        let files = pinned.get_or_insert_with(HashMap::new);
+        // Only a file that could be read is kept, so fixing it takes effect on the next call.
+        if !files.contains_key(path) {
+            files.insert(path.to_path_buf(), load(path)?);
+        }
+
+        match files[path].get(&(tuner.to_string(), rendered)) {
+            Some(name) => Ok(Some(name.clone())),
+            None => Err(AutotuneError::MissingPinnedDecision {
+                tuner: tuner.to_string(),
+                key: key.to_string(),
+                reason: format!("{} has no decision for it", path.display()),
+            }),
+        }
+    }
+
+    /// Append a decision to the decision file, when decisions are being
+    /// [`Record`](DeterministicMode::Record)ed. A decision this process already recorded
+    /// isn't written again.
+    pub(crate) fn record_decision<K: AutotuneKey>(
+        config: &DeterministicConfig,
+        tuner: &str,
+        key: &K,
+        name: &str,
+    ) -> Result<(), AutotuneError> {
+        let Some(path) = decision_file(config, DeterministicMode::Record)? else {
+            return Ok(());
+        };
+
+        let decision = Decision {
+            tuner: tuner.to_string(),
+            key: serde_json::to_value(key).expect("Autotune keys serialize to JSON"),
+            name: name.to_string(),
+        };
+
+        // Locked for the write too, so concurrent tuners don't interleave their lines.
+        let mut recorded = RECORDED.lock();
+        let recorded = recorded
+            .get_or_insert_with(HashMap::new)
+            .entry(path.to_path_buf())
+            .or_default();
+        let id = (decision.tuner.clone(), decision.key.to_string());
+        if recorded.get(&id).is_some_and(|previous| previous == name) {
+            return Ok(());
+        }
+
+        let line = serde_json::to_string(&decision).expect("Decisions serialize to JSON");
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .expect("Should be able to open the autotune decision file");
+        writeln!(file, "{line}").expect("Should be able to record the autotune decision");
+
+        recorded.insert(id, decision.name);
+        Ok(())
+    }
+
+    /// Read a decision file. A key decided more than once keeps its last decision, so a file
+    /// recorded over several runs can simply be appended to.
+    fn load(path: &Path) -> Result<Decisions, AutotuneError> {
+        let invalid = |context| AutotuneError::InvalidDecisionFile { context };
+        let file = std::fs::File::open(path)
+            .map_err(|err| invalid(format!("can't open {}: {err}", path.display())))?;
+
+        let mut decisions = HashMap::new();
+        for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line =
+                line.map_err(|err| invalid(format!("can't read {}: {err}", path.display())))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let decision: Decision = serde_json::from_str(&line).map_err(|err| {
+                invalid(format!(
+                    "invalid decision at {}:{}: {err}",
+                    path.display(),
+                    number + 1
+                ))
+            })?;
+            // Rendered the way `pinned_decision` renders a key, whatever spacing the file uses.
+            let key = decision.key.to_string();
+            decisions.insert((decision.tuner, key), decision.name);
+        }
+
+        Ok(decisions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tune::{AutotuneError, AutotuneOutcome};
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+    use cubecl_common::benchmark::{BenchmarkComputations, BenchmarkDurations, TimingMethod};
+
+    fn results(medians: &[Option<u64>]) -> Vec<AutotuneResult> {
+        medians
+            .iter()
+            .enumerate()
+            .map(|(index, micros)| match micros {
+                Some(micros) => AutotuneResult::success(AutotuneOutcome::new(
+                    index.to_string(),
+                    index,
+                    BenchmarkComputations::new(&BenchmarkDurations::from_durations(
+                        TimingMethod::System,
+                        alloc::vec![Duration::from_micros(*micros)],
+                    )),
+                )),
+                None => AutotuneResult::error(AutotuneError::Skip {
+                    name: index.to_string(),
+                }),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ties_go_to_the_first_registered() {
+        let results = results(&[Some(101), Some(100), Some(150)]);
+
+        assert_eq!(break_ties(&results, 1, 0.02), 0);
+        assert_eq!(break_ties(&results, 1, 0.0), 1);
+    }
+
+    #[test]
+    fn failed_candidates_never_win_a_tie() {
+        let results = results(&[None, Some(105), Some(100)]);
+
+        assert_eq!(break_ties(&results, 2, 0.1), 1);
+        assert_eq!(break_ties(&results, 0, 0.1), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{AutotuneKey, AutotuneOutput, TunableSet, TuneInputs, Tuner};
+use super::{AutotuneError, AutotuneKey, AutotuneOutput, TunableSet, TuneInputs, Tuner};
 #[cfg(feature = "autotune-checks")]
 use crate::tune::AutotuneLoggerExt;
 use crate::{client::ComputeClient, runtime::Runtime, tune::TuneCacheResult};
@@ -91,6 +91,21 @@ where
         content
     }
 
+    /// Block until the benchmark a background tune of `key` has in flight on the tuner of `id`
+    /// resolves, see [`Tuner::wait_background`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_background(&self, id: &ID, key: &AK) {
+        let tuner = self
+            .state
+            .lock()
+            .as_ref()
+            .and_then(|state| state.get(id).cloned());
+
+        if let Some(tuner) = tuner {
+            tuner.wait_background(key);
+        }
+    }
+
     /// Clear the autotune state.
     pub fn clear(&self) {
         if let Some(s) = self.state.lock().as_mut() {
@@ -120,13 +135,18 @@ where
 
     /// Execute the fastest operation in a [`TunableSet`], triggering a tuning pass on
     /// the first call for a given key.
+    ///
+    /// Fails when decisions are
+    /// [deterministic](crate::config::autotune::DeterministicConfig) and the decision file
+    /// can't serve or record the key, e.g. with
+    /// [`MissingPinnedDecision`](AutotuneError::MissingPinnedDecision).
     pub fn execute<'a, R: Runtime, I: TuneInputs, Out>(
         &self,
         id: &ID,
         client: &ComputeClient<R>,
         operations: Arc<TunableSet<AK, I, Out>>,
         inputs: <I as TuneInputs>::At<'a>,
-    ) -> Out
+    ) -> Result<Out, AutotuneError>
     where
         <I as TuneInputs>::At<'a>: Clone + Send,
         Out: AutotuneOutput,
@@ -155,10 +175,29 @@ where
         // `fastest` also resets the tuner cache if the environment switched, so
         // a miss here falls through to `check_tune`, which re-hydrates.
         if let TuneCacheResult::Hit { fastest_index } = tuner.fastest(&key) {
-            return operations
+            return Ok(operations
                 .fastest(fastest_index)
                 .execute(inputs)
-                .expect("Should run when selected by autotune.");
+                .expect("Should run when selected by autotune."));
+        }
+
+        // A set tuned in the background never blocks on a tuning pass: it runs the default
+        // until the winner is known, and lends a call to the tune now and then.
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(default_index) = operations.background_default() {
+            let index = tuner.check_tune_background::<R, I, Out>(
+                &key,
+                &inputs,
+                &operations,
+                default_index,
+                client,
+                log_context,
+            )?;
+
+            return Ok(operations
+                .fastest(index)
+                .execute(inputs)
+                .expect("Should run when selected by autotune."));
         }
 
         let fastest = tuner.check_tune::<R, I, Out>(
@@ -168,14 +207,14 @@ where
             || operations.compute_checksum(),
             client,
             log_context,
-        );
+        )?;
 
         // Run the execution depending on the cache state.
         match fastest {
-            TuneCacheResult::Hit { fastest_index } => operations
+            TuneCacheResult::Hit { fastest_index } => Ok(operations
                 .fastest(fastest_index)
                 .execute(inputs)
-                .expect("Should run when selected by autotune."),
+                .expect("Should run when selected by autotune.")),
             TuneCacheResult::Unchecked | TuneCacheResult::Miss => {
                 panic!(
                     "Somehow we STILL didn't check a tuning checksum or start tuning, something has gone wrong."
@@ -185,7 +224,7 @@ where
                 // Still waiting (e.g. on wasm). Try all operations as a fallback.
                 for i in 0..operations.len() {
                     if let Ok(output) = operations.fastest(i).execute(inputs.clone()) {
-                        return output;
+                        return Ok(output);
                     }
                 }
                 panic!("All autotune operations failed, no viable operation found.");
@@ -16,7 +16,7 @@
 //!             .with(Tunable::new("k2", |(lhs, rhs)| kernel_2(lhs, rhs)))
 //!     });
 //!
-//!     TUNER.execute(&device_id, &lhs.client, tunables, (lhs, rhs));
+//!     TUNER.execute(&device_id, &lhs.client, tunables, (lhs, rhs)).unwrap()
 //! }
 //! ```
 //!
@@ -26,18 +26,26 @@
 //! See [`TuneInputs`] for the borrowed-inputs story, and [`Tunable::new`] for why its
 //! HRTB bound is spelled out directly (closure inference).
 
+// Background tuning steps resolve their samples inline, which only the native driver can do.
+#[cfg(not(target_family = "wasm"))]
+mod background;
 mod base;
 mod bounds_generator;
+mod deterministic;
 mod input_generator;
 mod key_generator;
 mod local;
 mod log;
 mod operation;
+// Reads recorder files back, which only exist with a filesystem.
+#[cfg(std_io)]
+mod replay;
 // Both are the adaptive strategy, which only the native driver can run.
 #[cfg(not(target_family = "wasm"))]
 mod sampler;
 #[cfg(not(target_family = "wasm"))]
 mod schedule;
+mod search_space;
 mod tune_benchmark;
 mod tune_cache;
 mod tune_inputs;
@@ -51,6 +59,9 @@ pub use key_generator::*;
 pub use local::*;
 pub use log::*;
 pub use operation::*;
+#[cfg(std_io)]
+pub use replay::*;
+pub use search_space::*;
 pub use tune_benchmark::*;
 pub use tune_cache::*;
 pub use tune_inputs::*;
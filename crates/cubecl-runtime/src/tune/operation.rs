@@ -4,10 +4,11 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::fmt::{Debug, Display};
 use core::hash::Hash;
+use core::time::Duration;
 
 use alloc::format;
 
-use crate::tune::{Bounds, BoundsGenerator};
+use crate::tune::{Bounds, BoundsGenerator, Halving, SearchSpace, SearchStrategy, TuneParams};
 
 use super::{
     AutotuneError, input_generator::InputGenerator, key_generator::KeyGenerator,
@@ -28,6 +29,12 @@ type TuneDelegate<I, Out> =
 pub struct TuneFn<I: TuneInputs, Out> {
     pub(crate) name: String,
     func: Box<TuneDelegate<I, Out>>,
+    /// The config this function runs, when it was generated from a [`SearchSpace`].
+    #[new(default)]
+    pub(crate) params: Option<TuneParams>,
+    /// The successive halving search this function takes part in, if any.
+    #[new(default)]
+    pub(crate) halving: Option<Halving>,
 }
 
 impl<I: TuneInputs, Out: 'static> TuneFn<I, Out> {
@@ -35,6 +42,16 @@ impl<I: TuneInputs, Out: 'static> TuneFn<I, Out> {
     pub fn execute<'a>(&self, inputs: <I as TuneInputs>::At<'a>) -> Result<Out, AutotuneError> {
         (self.func)(inputs)
     }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The config this function runs, when it was generated from a [`SearchSpace`].
+    pub fn params(&self) -> Option<&TuneParams> {
+        self.params.as_ref()
+    }
 }
 
 /// A set of candidate tunable functions for autotune, sharing a key generator and an
@@ -45,6 +62,10 @@ pub struct TunableSet<K: AutotuneKey, F: TuneInputs, Output: 'static> {
     input_gen: Arc<dyn InputGenerator<K, F> + Send + Sync>,
     bounds_gen: Option<Arc<dyn BoundsGenerator<K, F> + Send + Sync>>,
     short_circuit: bool,
+    background_default: Option<usize>,
+    background_interval: Option<Duration>,
+    /// The number of [`with_space`](Self::with_space) calls so far, identifying each space.
+    spaces: usize,
 }
 
 impl<K: AutotuneKey, F: TuneInputs, Output: 'static> TunableSet<K, F, Output> {
@@ -66,6 +87,9 @@ impl<K: AutotuneKey, F: TuneInputs, Output: 'static> TunableSet<K, F, Output> {
             key_gen: Arc::new(key_gen),
             bounds_gen: None,
             short_circuit: true,
+            background_default: None,
+            background_interval: None,
+            spaces: 0,
         }
     }
 
@@ -81,6 +105,67 @@ impl<K: AutotuneKey, F: TuneInputs, Output: 'static> TunableSet<K, F, Output> {
         self
     }
 
+    /// Register one tunable per config of `space` picked by `strategy`, all running `func`
+    /// with the config they were generated for.
+    ///
+    /// The tunables are named `name[param=value,...]`, and the winner's config is recorded
+    /// with its [`AutotuneOutcome`](crate::tune::AutotuneOutcome), in memory and in the
+    /// persistent cache. Read it back with [`params`](Self::params).
+    ///
+    /// With [`SearchStrategy::SuccessiveHalving`], the configs of this space race among
+    /// themselves, and only the last one standing competes with the rest of its batch on the
+    /// full sample budget. Other tunables of the set, generated or not, are unaffected.
+    pub fn with_space<Func, Err>(
+        mut self,
+        name: &str,
+        space: SearchSpace,
+        strategy: SearchStrategy,
+        func: Func,
+    ) -> Self
+    where
+        Err: Into<String> + 'static,
+        Func: for<'a> Fn(&TuneParams, <F as TuneInputs>::At<'a>) -> Result<Output, Err>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let halving = match strategy {
+            SearchStrategy::SuccessiveHalving { eta } => Some(Halving {
+                space: self.spaces,
+                eta,
+            }),
+            _ => None,
+        };
+        self.spaces += 1;
+
+        let func = Arc::new(func);
+        for params in strategy.select(&space) {
+            let func = func.clone();
+            let config = params.clone();
+            let mut tunable = Tunable::new(&format!("{name}[{params}]"), move |inputs| {
+                func(&config, inputs)
+            });
+            tunable.function.params = Some(params);
+            tunable.function.halving = halving;
+            self.tunables.push(tunable);
+        }
+
+        self
+    }
+
+    /// Whether a [successive halving](SearchStrategy::SuccessiveHalving) search was
+    /// registered.
+    pub(crate) fn has_halving(&self) -> bool {
+        self.tunables
+            .iter()
+            .any(|tunable| tunable.function.halving.is_some())
+    }
+
+    /// The config of the tunable at `index`, when it was generated from a [`SearchSpace`].
+    pub fn params(&self, index: usize) -> Option<&TuneParams> {
+        self.tunables[index].function.params()
+    }
+
     /// Sets the autotune bounds for this set.
     pub fn with_bounds(mut self, bounds: Arc<dyn BoundsGenerator<K, F> + Send + Sync>) -> Self {
         self.bounds_gen = Some(bounds);
@@ -98,6 +183,43 @@ impl<K: AutotuneKey, F: TuneInputs, Output: 'static> TunableSet<K, F, Output> {
         self.short_circuit
     }
 
+    /// Tune this set in the background: a key that isn't tuned yet runs the tunable at
+    /// `default_index` right away instead of blocking on a tuning pass.
+    ///
+    /// The candidates are then benchmarked one at a time, each queued by a later call for the
+    /// same key and paced by [`BackgroundConfig`](crate::config::autotune::BackgroundConfig).
+    /// No call waits for a benchmark to run: its samples go through the device after the work
+    /// queued before them, and the call following their resolution records them. The winner
+    /// replaces the default once the tune settles, the same way a blocking tune
+    /// does: when a batch of candidates produced a valid result, or earlier when a candidate reaches
+    /// the [bounds](Self::with_bounds) limit, so candidates of later batches may never be
+    /// measured. Results go through the same in-memory and persistent caches as a blocking
+    /// tune, so a key tuned in the background is a plain cache hit in later processes.
+    ///
+    /// Native only; on wasm the set is tuned like any other.
+    pub fn with_background(mut self, default_index: usize) -> Self {
+        self.background_default = Some(default_index);
+        self
+    }
+
+    /// Override the minimum time between two background steps of this set, see
+    /// [`BackgroundConfig::step_interval_ms`](crate::config::autotune::BackgroundConfig::step_interval_ms).
+    pub fn with_background_interval(mut self, interval: Duration) -> Self {
+        self.background_interval = Some(interval);
+        self
+    }
+
+    /// The minimum time between two background steps, when this set overrides the config.
+    pub(crate) fn background_interval(&self) -> Option<Duration> {
+        self.background_interval
+    }
+
+    /// The tunable run while a key is tuned in the background, if this set is tuned in the
+    /// background at all.
+    pub fn background_default(&self) -> Option<usize> {
+        self.background_default
+    }
+
     /// All candidate operations in this set, in registration order.
     pub fn autotunables(&self) -> impl Iterator<Item = &TuneFn<F, Output>> {
         self.tunables.iter().map(|tunable| &tunable.function)
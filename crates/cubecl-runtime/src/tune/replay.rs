@@ -0,0 +1,361 @@
+//! Reading [`AutotuneRecord`]s back, after the fact.
+//!
+//! The recorder writes one record per tuning decision; this module turns a recorder file into
+//! a per-key view of how each decision was made, so dead candidates can be pruned from a
+//! [`TunableSet`](crate::tune::TunableSet) based on data, and into seed entries for a tune
+//! cache, so a fresh machine doesn't have to tune what another one already did.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::tune::{
+    AutotuneError, AutotuneKey, AutotuneOutcome, AutotuneRecord, AutotuneResult,
+    PersistentCacheValue, TuneCache,
+};
+
+/// A record as read back, with its key left untyped: a recorder file mixes the keys of every
+/// tuner in the process.
+pub type RecordedDecision = AutotuneRecord<'static, serde_json::Value>;
+
+/// How one key was decided, as reported by [`AutotuneReport`].
+#[derive(Debug, Clone)]
+pub struct KeyReport {
+    /// The key, rendered as JSON.
+    pub key: String,
+    /// The name of the winning candidate.
+    pub winner: String,
+    /// The median time of the winner.
+    pub winner_time: Duration,
+    /// The fastest other candidate that produced a measurement, with its median time.
+    pub runner_up: Option<(String, Duration)>,
+    /// Every candidate that was benchmarked or skipped for this key.
+    pub candidates: Vec<String>,
+}
+
+impl KeyReport {
+    /// How much slower the runner up was, relative to the winner: `0.1` means 10% slower.
+    ///
+    /// `None` when the winner was the only candidate measured, where there is no margin to
+    /// speak of.
+    pub fn margin(&self) -> Option<f64> {
+        let (_, runner_up) = self.runner_up.as_ref()?;
+        let winner = self.winner_time.as_secs_f64();
+        if winner == 0.0 {
+            return None;
+        }
+
+        Some(runner_up.as_secs_f64() / winner - 1.0)
+    }
+}
+
+/// An analysis of a recorder file, see the [module documentation](self).
+#[derive(Debug, Default)]
+pub struct AutotuneReport {
+    records: Vec<RecordedDecision>,
+    keys: Vec<KeyReport>,
+    skipped_lines: usize,
+}
+
+impl AutotuneReport {
+    /// Load a recorder file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Load records from any reader, one JSON record per line.
+    ///
+    /// Lines that aren't records are skipped rather than failing the whole load: the recorder
+    /// can share its sink with other output, and writes an error object in place of a record it
+    /// couldn't serialize. See [`skipped_lines`](Self::skipped_lines).
+    pub fn from_reader(reader: impl BufRead) -> std::io::Result<Self> {
+        let mut records = Vec::new();
+        let mut skipped_lines = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<RecordedDecision>(&line) {
+                Ok(record) => records.push(record),
+                Err(_) => skipped_lines += 1,
+            }
+        }
+
+        Ok(Self::from_records(records, skipped_lines))
+    }
+
+    fn from_records(records: Vec<RecordedDecision>, skipped_lines: usize) -> Self {
+        // A key tuned more than once (several processes, or a cache that was disabled) is
+        // reported on its latest decision.
+        let mut latest = BTreeMap::new();
+        for record in records.iter() {
+            if let Some(report) = key_report(record) {
+                latest.insert(report.key.clone(), report);
+            }
+        }
+
+        Self {
+            records,
+            keys: latest.into_values().collect(),
+            skipped_lines,
+        }
+    }
+
+    /// Every record loaded, in file order.
+    pub fn records(&self) -> &[RecordedDecision] {
+        &self.records
+    }
+
+    /// One report per distinct key, sorted by key.
+    pub fn keys(&self) -> &[KeyReport] {
+        &self.keys
+    }
+
+    /// How many non-empty lines weren't records.
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    /// How many keys each candidate won, for every candidate seen.
+    pub fn wins(&self) -> BTreeMap<String, usize> {
+        let mut wins = BTreeMap::new();
+        for report in self.keys.iter() {
+            for candidate in report.candidates.iter() {
+                wins.entry(candidate.clone()).or_insert(0);
+            }
+            *wins.entry(report.winner.clone()).or_insert(0) += 1;
+        }
+        wins
+    }
+
+    /// The candidates that took part in at least one decision and never won any: the ones a
+    /// [`TunableSet`](crate::tune::TunableSet) can likely do without, for the keys recorded.
+    pub fn never_winning(&self) -> Vec<String> {
+        self.wins()
+            .into_iter()
+            .filter(|(_, wins)| *wins == 0)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// The keys whose runner up was within `threshold` of the winner (`0.05` for 5%), closest
+    /// first. These are the decisions timing noise can flip from one run to the next.
+    pub fn marginal(&self, threshold: f64) -> Vec<&KeyReport> {
+        let mut marginal: Vec<(&KeyReport, f64)> = self
+            .keys
+            .iter()
+            .filter_map(|report| report.margin().map(|margin| (report, margin)))
+            .filter(|(_, margin)| *margin <= threshold)
+            .collect();
+
+        marginal.sort_by(|a, b| a.1.total_cmp(&b.1));
+        marginal.into_iter().map(|(report, _)| report).collect()
+    }
+
+    /// Write the latest decision of every recorded key of type `K` to the persistent tune
+    /// cache of the tuner `name` on `device_id`, as [`Tuner::new`](crate::tune::Tuner::new)
+    /// opens it (a [`LocalTuner`](crate::tune::LocalTuner) names its tuner after its module
+    /// path, `::` replaced by `-`).
+    ///
+    /// `checksum` is the [`compute_checksum`](crate::tune::TunableSet::compute_checksum) of the
+    /// set the records were tuned with: records don't carry it, and a seeded entry with the
+    /// wrong one is discarded on first use rather than trusted. Keys that don't decode as `K`
+    /// belong to another tuner and are left out.
+    ///
+    /// Returns how many entries were written: a key the cache already holds with the same
+    /// decision, or with another one it keeps, isn't counted.
+    pub fn seed_cache<K: AutotuneKey>(&self, name: &str, device_id: &str, checksum: &str) -> usize {
+        let mut cache = TuneCache::<K>::new(name, device_id);
+        let mut latest = BTreeMap::new();
+
+        for record in self.records.iter() {
+            let Ok(key) = serde_json::from_value::<K>(record.key.clone().into_owned()) else {
+                continue;
+            };
+            latest.insert(record.key.to_string(), (key, record));
+        }
+
+        let mut seeded = 0;
+        for (_, (key, record)) in latest {
+            let written = cache.persistent_cache_insert(
+                key,
+                checksum.to_string(),
+                PersistentCacheValue {
+                    fastest_index: record.fastest_index,
+                    results: record.results.to_vec(),
+                    bounds: record
+                        .log_context
+                        .as_ref()
+                        .and_then(|context| context.bounds.clone()),
+                    limit: record
+                        .log_context
+                        .as_ref()
+                        .and_then(|context| context.limit),
+                },
+            );
+            seeded += usize::from(written);
+        }
+
+        seeded
+    }
+}
+
+impl core::fmt::Display for AutotuneReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "{} records, {} keys ({} lines skipped)",
+            self.records.len(),
+            self.keys.len(),
+            self.skipped_lines
+        )?;
+
+        writeln!(f, "\nWins per candidate:")?;
+        for (name, wins) in self.wins() {
+            writeln!(f, "  {name:<48} {wins}")?;
+        }
+
+        writeln!(f, "\nDecisions:")?;
+        for report in self.keys.iter() {
+            match report.margin() {
+                Some(margin) => writeln!(
+                    f,
+                    "  {} => {} ({:?}), margin {:.1}%",
+                    report.key,
+                    report.winner,
+                    report.winner_time,
+                    margin * 100.0
+                )?,
+                None => writeln!(
+                    f,
+                    "  {} => {} ({:?}), unopposed",
+                    report.key, report.winner, report.winner_time
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn key_report(record: &RecordedDecision) -> Option<KeyReport> {
+    let outcomes: Vec<&AutotuneOutcome> = record
+        .results
+        .iter()
+        .filter_map(|result| result.outcome.as_ref().ok())
+        .collect();
+
+    let winner = outcomes
+        .iter()
+        .find(|outcome| outcome.index == record.fastest_index)?;
+    let runner_up = outcomes
+        .iter()
+        .filter(|outcome| outcome.index != winner.index)
+        .min_by_key(|outcome| outcome.computation.median)
+        .map(|outcome| (outcome.name.clone(), outcome.computation.median));
+
+    let mut candidates = BTreeSet::new();
+    for result in record.results.iter() {
+        if let Some(name) = candidate_name(result) {
+            candidates.insert(name.to_string());
+        }
+    }
+
+    Some(KeyReport {
+        key: record.key.to_string(),
+        winner: winner.name.clone(),
+        winner_time: winner.computation.median,
+        runner_up,
+        candidates: candidates.into_iter().collect(),
+    })
+}
+
+/// The candidate a result belongs to, when the result says: the errors that aren't about a
+/// single candidate carry no name.
+fn candidate_name(result: &AutotuneResult) -> Option<&str> {
+    match &result.outcome {
+        Ok(outcome) => Some(&outcome.name),
+        Err(AutotuneError::Unknown { name, .. })
+        | Err(AutotuneError::InvalidSamples { name })
+        | Err(AutotuneError::Skip { name }) => Some(name),
+        Err(AutotuneError::NoValidKernelFound { .. })
+        | Err(AutotuneError::MissingPinnedDecision { .. })
+        | Err(AutotuneError::InvalidDecisionFile { .. })
+        | Err(AutotuneError::Launch(_)) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    fn outcome(name: &str, index: usize, micros: u64) -> String {
+        let time = format!("{{\"secs\":0,\"nanos\":{}}}", micros * 1000);
+        format!(
+            "{{\"outcome\":{{\"Ok\":{{\"name\":\"{name}\",\"index\":{index},\"computation\":{{\"mean\":{time},\"median\":{time},\"variance\":{time},\"min\":{time},\"max\":{time}}}}}}}}}"
+        )
+    }
+
+    fn record(key: &str, fastest_index: usize, results: &[String]) -> String {
+        format!(
+            "{{\"key\":\"{key}\",\"fastest_index\":{fastest_index},\"fastest_time\":{{\"secs\":0,\"nanos\":0}},\"results\":[{}],\"log_context\":null,\"checks\":null}}",
+            results.join(",")
+        )
+    }
+
+    fn report() -> AutotuneReport {
+        let lines = [
+            record("a", 0, &[outcome("fast", 0, 100), outcome("slow", 1, 300)]),
+            String::from("not a record"),
+            record("b", 0, &[outcome("fast", 0, 100), outcome("slow", 1, 102)]),
+            String::from("{\"error\": \"Failed to serialize the autotune record\"}"),
+            record(
+                "c",
+                2,
+                &[
+                    outcome("other", 2, 50),
+                    outcome("fast", 0, 60),
+                    String::from("{\"outcome\":{\"Err\":{\"Skip\":{\"name\":\"never\"}}}}"),
+                ],
+            ),
+        ];
+
+        AutotuneReport::from_reader(lines.join("\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn reports_winners_and_skips_foreign_lines() {
+        let report = report();
+
+        assert_eq!(report.records().len(), 3);
+        assert_eq!(report.skipped_lines(), 2);
+
+        let winners: Vec<&str> = report.keys().iter().map(|k| k.winner.as_str()).collect();
+        assert_eq!(winners, ["fast", "fast", "other"]);
+    }
+
+    #[test]
+    fn finds_candidates_that_never_win() {
+        assert_eq!(report().never_winning(), ["never", "slow"]);
+    }
+
+    #[test]
+    fn flags_marginal_decisions() {
+        let report = report();
+        let marginal: Vec<&str> = report
+            .marginal(0.05)
+            .into_iter()
+            .map(|k| k.key.as_str())
+            .collect();
+
+        assert_eq!(marginal, ["\"b\""]);
+    }
+}
@@ -0,0 +1,361 @@
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+/// The value of a single tuning parameter.
+///
+/// Kept to integers and flags: every kernel config we explore (tile sizes, stages, vector
+/// sizes, toggles) is one of the two, and a closed set keeps the persisted form readable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ParamValue {
+    /// An integer parameter.
+    Int(i64),
+    /// A boolean parameter.
+    Bool(bool),
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParamValue::Int(value) => write!(f, "{value}"),
+            ParamValue::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// A Rust type a tuning parameter can be declared and read back as.
+pub trait ParamType: Copy {
+    /// Convert into the untyped value stored in [`TuneParams`].
+    fn into_value(self) -> ParamValue;
+    /// Convert back from the untyped value, `None` when it doesn't fit.
+    fn from_value(value: ParamValue) -> Option<Self>;
+}
+
+macro_rules! impl_param_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ParamType for $ty {
+                fn into_value(self) -> ParamValue {
+                    ParamValue::Int(self as i64)
+                }
+
+                fn from_value(value: ParamValue) -> Option<Self> {
+                    match value {
+                        ParamValue::Int(value) => <$ty>::try_from(value).ok(),
+                        ParamValue::Bool(_) => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_param_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl ParamType for bool {
+    fn into_value(self) -> ParamValue {
+        ParamValue::Bool(self)
+    }
+
+    fn from_value(value: ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Bool(value) => Some(value),
+            ParamValue::Int(_) => None,
+        }
+    }
+}
+
+/// One point of a [`SearchSpace`]: a value for every declared parameter, in declaration order.
+///
+/// Stored with each [`AutotuneOutcome`](crate::tune::AutotuneOutcome), so the persistent cache
+/// records which config won and not just its index.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct TuneParams {
+    values: Vec<(String, ParamValue)>,
+}
+
+impl TuneParams {
+    /// Read a parameter.
+    ///
+    /// # Panics
+    ///
+    /// If the parameter wasn't declared or doesn't fit in `T`: both are bugs in the tunable,
+    /// which declared the space it reads from.
+    pub fn get<T: ParamType>(&self, name: &str) -> T {
+        self.try_get(name).unwrap_or_else(|| {
+            panic!(
+                "Tuning parameter {name} is missing or has the wrong type in {self} ({})",
+                core::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Read a parameter, `None` when it wasn't declared or doesn't fit in `T`.
+    pub fn try_get<T: ParamType>(&self, name: &str) -> Option<T> {
+        self.values
+            .iter()
+            .find(|(param, _)| param == name)
+            .and_then(|(_, value)| T::from_value(*value))
+    }
+
+    /// Every parameter with its value, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ParamValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+impl Display for TuneParams {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+type Constraint = Arc<dyn Fn(&TuneParams) -> bool + Send + Sync>;
+
+/// The typed parameters of a kernel config and the constraints between them.
+///
+/// ```ignore
+/// let space = SearchSpace::new()
+///     .param("tile_m", [16u32, 32, 64, 128])
+///     .param("stages", 1u32..=3)
+///     .param("vector_size", [1u32, 2, 4])
+///     .constraint(|p| p.get::<u32>("tile_m") * p.get::<u32>("stages") <= 256);
+/// ```
+///
+/// Registered on a [`TunableSet`](crate::tune::TunableSet) with
+/// [`with_space`](crate::tune::TunableSet::with_space), which explores it with a
+/// [`SearchStrategy`].
+#[derive(Clone, Default)]
+pub struct SearchSpace {
+    params: Vec<(String, Vec<ParamValue>)>,
+    constraints: Vec<Constraint>,
+}
+
+impl core::fmt::Debug for SearchSpace {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SearchSpace")
+            .field("params", &self.params)
+            .field("constraints", &self.constraints.len())
+            .finish()
+    }
+}
+
+impl SearchSpace {
+    /// Create an empty search space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a parameter and the values it ranges over, in exploration order.
+    pub fn param<T: ParamType>(mut self, name: &str, values: impl IntoIterator<Item = T>) -> Self {
+        let values = values.into_iter().map(T::into_value).collect();
+        self.params.push((name.to_string(), values));
+        self
+    }
+
+    /// Only keep the configs for which `valid` returns `true`.
+    pub fn constraint(
+        mut self,
+        valid: impl Fn(&TuneParams) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.constraints.push(Arc::new(valid));
+        self
+    }
+
+    /// Every config satisfying the constraints, the last parameter varying fastest.
+    ///
+    /// The order is stable for a given declaration: the tuner caches the winner by its
+    /// position, so the same space has to enumerate the same way in every process.
+    pub fn configs(&self) -> Vec<TuneParams> {
+        let mut configs = alloc::vec![TuneParams::default()];
+
+        for (name, values) in self.params.iter() {
+            configs = configs
+                .into_iter()
+                .flat_map(|config| {
+                    values.iter().map(move |value| {
+                        let mut config = config.clone();
+                        config.values.push((name.clone(), *value));
+                        config
+                    })
+                })
+                .collect();
+        }
+
+        configs.retain(|config| self.constraints.iter().all(|valid| valid(config)));
+        configs
+    }
+}
+
+/// How a [`SearchSpace`] is explored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchStrategy {
+    /// Benchmark every config (default).
+    #[default]
+    Exhaustive,
+    /// Benchmark `samples` configs picked at random.
+    ///
+    /// The pick is seeded, so every process explores the same configs and a cached winner
+    /// keeps pointing at the same one.
+    Random {
+        /// How many configs to benchmark.
+        samples: usize,
+        /// The seed of the pick.
+        seed: u64,
+    },
+    /// Benchmark every config on a single sample, keep the fastest `1 / eta`, and repeat with
+    /// `eta` times more samples until one config is left or the sample budget is reached.
+    ///
+    /// Far cheaper than [`Exhaustive`](Self::Exhaustive) on large spaces, at the cost of
+    /// dropping a config on little evidence in the first rounds.
+    SuccessiveHalving {
+        /// The reduction factor between two rounds, read as at least `2`.
+        eta: usize,
+    },
+}
+
+impl SearchStrategy {
+    /// The configs of `space` this strategy benchmarks, in the order they are registered.
+    pub(crate) fn select(&self, space: &SearchSpace) -> Vec<TuneParams> {
+        let configs = space.configs();
+
+        match *self {
+            SearchStrategy::Exhaustive | SearchStrategy::SuccessiveHalving { .. } => configs,
+            SearchStrategy::Random { samples, seed } => sample_configs(configs, samples, seed),
+        }
+    }
+}
+
+/// Keep `samples` of `configs`, chosen by a partial Fisher-Yates shuffle seeded by `seed`.
+/// The kept configs stay in enumeration order, which keeps the registered names readable.
+fn sample_configs(configs: Vec<TuneParams>, samples: usize, seed: u64) -> Vec<TuneParams> {
+    if samples >= configs.len() {
+        return configs;
+    }
+
+    let mut indices: Vec<usize> = (0..configs.len()).collect();
+    let mut state = seed;
+    for i in 0..samples {
+        let j = i + (splitmix64(&mut state) % (indices.len() - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(samples);
+    indices.sort_unstable();
+
+    let mut configs: Vec<Option<TuneParams>> = configs.into_iter().map(Some).collect();
+    indices
+        .into_iter()
+        .map(|index| configs[index].take().expect("Indices are unique"))
+        .collect()
+}
+
+/// A tiny, portable PRNG: the pick has to be identical on every platform and every version of
+/// every dependency, since cached winners are stored by position.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// The successive halving search a tunable takes part in, see
+/// [`TunableSet::with_space`](crate::tune::TunableSet::with_space).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Halving {
+    /// Which space of the set generated the tunable.
+    pub(crate) space: usize,
+    /// The reduction factor between two rounds.
+    pub(crate) eta: usize,
+}
+
+/// Pick the survivors of a successive halving round: the fastest `ceil(len / eta)` of the
+/// candidates that produced a measurement, given as `(index, median)`.
+pub(crate) fn halving_survivors(
+    mut measured: Vec<(usize, core::time::Duration)>,
+    eta: usize,
+) -> Vec<usize> {
+    let eta = eta.max(2);
+    let keep = measured.len().div_ceil(eta).max(1);
+
+    measured.sort_by_key(|(_, median)| *median);
+    measured.truncate(keep);
+    measured.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use core::time::Duration;
+
+    fn space() -> SearchSpace {
+        SearchSpace::new()
+            .param("tile", [16u32, 32, 64])
+            .param("stages", 1u32..=2)
+            .constraint(|p| p.get::<u32>("tile") * p.get::<u32>("stages") <= 64)
+    }
+
+    #[test]
+    fn enumerates_the_product_under_constraints() {
+        let configs: Vec<String> = space().configs().iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            configs,
+            vec![
+                "tile=16,stages=1",
+                "tile=16,stages=2",
+                "tile=32,stages=1",
+                "tile=32,stages=2",
+                "tile=64,stages=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_parameters_back_typed() {
+        let config = &space().configs()[1];
+
+        assert_eq!(config.get::<usize>("tile"), 16);
+        assert_eq!(config.get::<u8>("stages"), 2);
+        assert_eq!(config.try_get::<bool>("stages"), None);
+        assert_eq!(config.try_get::<u32>("missing"), None);
+    }
+
+    #[test]
+    fn random_pick_is_stable_and_bounded() {
+        let strategy = SearchStrategy::Random {
+            samples: 3,
+            seed: 42,
+        };
+
+        let first = strategy.select(&space());
+        let second = strategy.select(&space());
+
+        assert_eq!(first.len(), 3);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn halving_keeps_the_fastest_fraction() {
+        let measured = vec![
+            (0, Duration::from_micros(40)),
+            (1, Duration::from_micros(10)),
+            (2, Duration::from_micros(30)),
+            (3, Duration::from_micros(20)),
+            (4, Duration::from_micros(50)),
+        ];
+
+        assert_eq!(halving_survivors(measured.clone(), 2), vec![1, 3, 2]);
+        assert_eq!(halving_survivors(measured, 4), vec![1, 3]);
+    }
+}
@@ -27,12 +27,31 @@ pub fn tune_benchmark<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
     operation: &TuneFn<F, Out>,
     inputs: <F as TuneInputs>::At<'a>,
     client: ComputeClient<R>,
+) -> Result<Vec<ProfileDuration>, AutotuneError> {
+    // The same budget the adaptive scheduler reads. This pass takes the ceiling: with no
+    // elimination, there is nothing for a smaller budget to buy, and a candidate that stops early
+    // here would just be measured on less evidence than its rivals.
+    let (_, num_samples) = crate::config::CubeClRuntimeConfig::get()
+        .autotune
+        .bench
+        .samples();
+
+    tune_benchmark_samples(operation, inputs, client, num_samples)
+}
+
+/// [`tune_benchmark`] with an explicit sample count, for the strategies that grow the budget
+/// of a candidate over several passes.
+pub(crate) fn tune_benchmark_samples<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
+    operation: &TuneFn<F, Out>,
+    inputs: <F as TuneInputs>::At<'a>,
+    client: ComputeClient<R>,
+    num_samples: usize,
 ) -> Result<Vec<ProfileDuration>, AutotuneError> {
     // `scoped` holds exclusive device access for the whole benchmark loop and
     // accepts non-`'static` closures.
     client
         .clone()
-        .exclusive(move || profile_exclusive(operation, inputs, client))
+        .exclusive(move || profile_exclusive(operation, inputs, client, num_samples))
         .map_err(|err| AutotuneError::Unknown {
             name: operation.name.to_string(),
             err: err.to_string(),
@@ -81,6 +100,7 @@ fn profile_exclusive<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
     operation: &TuneFn<F, Out>,
     inputs: <F as TuneInputs>::At<'a>,
     client: ComputeClient<R>,
+    num_samples: usize,
 ) -> Result<Vec<ProfileDuration>, AutotuneError> {
     // These launches are the measurement, so they run even inside a dry run:
     // that mode exists to skip the *workload*, not the tuning it is there to
@@ -95,13 +115,6 @@ fn profile_exclusive<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
 
     warmup(operation, inputs.clone(), client.clone())?;
 
-    // The same budget the adaptive scheduler reads. This pass takes the ceiling: with no
-    // elimination, there is nothing for a smaller budget to buy, and a candidate that stops early
-    // here would just be measured on less evidence than its rivals.
-    let (_, num_samples) = crate::config::CubeClRuntimeConfig::get()
-        .autotune
-        .bench
-        .samples();
     let mut durations = Vec::new();
 
     for _ in 0..num_samples {
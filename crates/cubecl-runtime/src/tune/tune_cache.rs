@@ -1,4 +1,6 @@
 #[cfg(autotune_persistence)]
+use alloc::string::ToString;
+#[cfg(autotune_persistence)]
 use alloc::vec::Vec;
 
 #[cfg(autotune_persistence)]
@@ -65,6 +67,19 @@ pub struct PersistentCacheValue {
     pub limit: Option<core::time::Duration>,
 }
 
+#[cfg(autotune_persistence)]
+impl PersistentCacheValue {
+    /// The config of the winner, when it was generated from a
+    /// [`SearchSpace`](crate::tune::SearchSpace).
+    pub fn params(&self) -> Option<&crate::tune::TuneParams> {
+        self.results
+            .iter()
+            .filter_map(|result| result.outcome.as_ref().ok())
+            .find(|outcome| outcome.index == self.fastest_index)
+            .and_then(|outcome| outcome.params.as_ref())
+    }
+}
+
 #[cfg_attr(autotune_persistence, derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 /// The result of an autotune job.
@@ -328,36 +343,68 @@ impl<K: AutotuneKey> TuneCache<K> {
         delivered
     }
 
+    /// The results persisted for `key` under `checksum`, read through the store.
+    pub(crate) fn persistent_results(
+        &mut self,
+        key: &K,
+        checksum: &str,
+    ) -> Option<Vec<AutotuneResult>> {
+        let key = PersistentCacheKey {
+            key: key.clone(),
+            checksum: checksum.to_string(),
+        };
+
+        self.persistent_cache
+            .as_mut()?
+            .get_mut(&key)
+            .map(|value| value.results.clone())
+    }
+
+    /// Write a result to the persistent cache. Returns whether an entry was written, `false`
+    /// when the cache already held this exact entry or refused it.
     pub(crate) fn persistent_cache_insert(
         &mut self,
         key: K,
         checksum: String,
         value: PersistentCacheValue,
-    ) {
+    ) -> bool {
         let Some(persistent_cache) = self.persistent_cache.as_mut() else {
-            return;
+            return false;
+        };
+
+        // The store reports an identical entry as stored, although nothing was written.
+        let key = PersistentCacheKey { key, checksum };
+        if persistent_cache
+            .get_mut(&key)
+            .is_some_and(|existing| *existing == value)
+        {
+            return false;
+        }
+
+        let Err(err) = persistent_cache.insert(key, value) else {
+            return true;
         };
 
-        if let Err(err) = persistent_cache.insert(PersistentCacheKey { key, checksum }, value) {
-            match err {
-                StoreError::DuplicatedKey {
-                    key,
-                    value_previous,
-                    value_updated,
-                } => log::warn!(
-                    "Autotune the same function multiple times for key {key:?} => old {value_previous:?}, new {value_updated:?}"
-                ),
-                // Another process sharing the cache root tuned this key first.
-                // Routine with N training processes on a cold cache, and both
-                // results are valid, so it stays quiet: warning here would
-                // print a full result payload per key on every cold start.
-                StoreError::KeyOutOfSync { key, .. } => {
-                    log::debug!("Autotune result for key {key:?} was already stored concurrently")
-                }
-                StoreError::Backend { key, error } => log::warn!(
-                    "Autotune result for key {key:?} could not be stored, it will be retuned: {error}"
-                ),
+        match err {
+            StoreError::DuplicatedKey {
+                key,
+                value_previous,
+                value_updated,
+            } => log::warn!(
+                "Autotune the same function multiple times for key {key:?} => old {value_previous:?}, new {value_updated:?}"
+            ),
+            // Another process sharing the cache root tuned this key first.
+            // Routine with N training processes on a cold cache, and both
+            // results are valid, so it stays quiet: warning here would
+            // print a full result payload per key on every cold start.
+            StoreError::KeyOutOfSync { key, .. } => {
+                log::debug!("Autotune result for key {key:?} was already stored concurrently")
             }
+            StoreError::Backend { key, error } => log::warn!(
+                "Autotune result for key {key:?} could not be stored, it will be retuned: {error}"
+            ),
         }
+
+        false
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(not(target_family = "wasm"))]
+use alloc::boxed::Box;
 #[cfg(std_io)]
 use alloc::format;
 use alloc::sync::Arc;
@@ -15,14 +17,22 @@ use cubecl_common::benchmark::{BenchmarkComputations, BenchmarkDurations};
 use crate::config::Logger;
 #[cfg(std_io)]
 use crate::config::autotune::AutotuneLogLevel;
+use crate::config::autotune::DeterministicConfig;
 use crate::server::LaunchError;
 use crate::tune::{AutotuneLoggerExt, AutotuneResult, TimeBound, TuneCache, tune_benchmark};
+#[cfg(not(target_family = "wasm"))]
+use crate::tune::{halving_survivors, tune_benchmark::tune_benchmark_samples};
 use crate::{client::ComputeClient, runtime::Runtime};
 use cubecl_environment::config::RuntimeConfig;
 
+#[cfg(not(target_family = "wasm"))]
+use super::background::{BackgroundStep, BackgroundTune, InFlight};
 use super::{
-    AutotuneKey, AutotuneOutput, TunableSet, TuneCacheResult, TuneFn, TuneInputs, TunePlan,
+    AutotuneKey, AutotuneOutput, TunableSet, TuneCacheResult, TuneFn, TuneInputs, TuneParams,
+    TunePlan,
 };
+#[cfg(not(target_family = "wasm"))]
+use cubecl_environment::collections::HashMap;
 
 #[derive(Debug)]
 /// Runs autotune benchmarks for a single device and caches the results.
@@ -33,6 +43,15 @@ use super::{
 pub struct Tuner<K: AutotuneKey> {
     cache: Arc<Mutex<TuneCache<K>>>,
     logger: Arc<Mutex<Logger>>,
+    /// Identifies the tuner in decision files, see
+    /// [`DeterministicConfig`](crate::config::autotune::DeterministicConfig).
+    #[cfg(std_io)]
+    name: String,
+    /// How decisions are kept reproducible, see [`with_deterministic`](Self::with_deterministic).
+    deterministic: DeterministicConfig,
+    /// Keys being tuned in the background, always locked after [`Self::cache`].
+    #[cfg(not(target_family = "wasm"))]
+    background: Mutex<HashMap<K, BackgroundTune>>,
 }
 
 /// The measured outcome for a given autotune invocation.
@@ -45,6 +64,13 @@ pub struct AutotuneOutcome {
     pub index: usize,
     /// The computation benchmark results.
     pub computation: BenchmarkComputations,
+    /// The config of the tunable, when it was generated from a
+    /// [`SearchSpace`](crate::tune::SearchSpace).
+    ///
+    /// Defaulted, so entries written before this field existed still decode.
+    #[new(default)]
+    #[cfg_attr(autotune_persistence, serde(default))]
+    pub params: Option<TuneParams>,
 }
 
 impl core::fmt::Display for AutotuneOutcome {
@@ -91,6 +117,25 @@ pub enum AutotuneError {
         /// The name of the skipped kernel.
         name: String,
     },
+    /// Decisions are [pinned](crate::config::autotune::DeterministicMode::Pinned), and the
+    /// decision file has no usable decision for the key. Tuning it anyway would defeat the
+    /// point of pinning, which is that nothing gets measured.
+    #[display("No pinned autotune decision for key {key} of tuner {tuner}: {reason}")]
+    MissingPinnedDecision {
+        /// The name of the tuner.
+        tuner: String,
+        /// The key without a decision.
+        key: String,
+        /// Why the decision is missing.
+        reason: String,
+    },
+    /// The decision file of a [deterministic](crate::config::autotune::DeterministicConfig)
+    /// tune is missing from the config, or can't be read.
+    #[display("Invalid autotune decision file: {context}")]
+    InvalidDecisionFile {
+        /// What's wrong with the file.
+        context: String,
+    },
 
     /// An error happened when launching a kernel.
     Launch(LaunchError),
@@ -135,6 +180,7 @@ struct TuneJob<'t, 'i, K: AutotuneKey, F: TuneInputs, Out> {
     #[cfg(autotune_persistence)]
     checksum: String,
     log_context: Option<crate::tune::AutotuneLogContext>,
+    tie_tolerance: Option<f64>,
 }
 
 impl<K: AutotuneKey, F: TuneInputs, Out> TuneJob<'_, '_, K, F, Out> {
@@ -142,11 +188,17 @@ impl<K: AutotuneKey, F: TuneInputs, Out> TuneJob<'_, '_, K, F, Out> {
         TuneRequest {
             key: self.key,
             results: self.results,
+            params: self
+                .autotunables
+                .iter()
+                .map(|tunable| tunable.params.clone())
+                .collect(),
             #[cfg(autotune_persistence)]
             checksum: self.checksum,
             log_context: self.log_context,
             pending,
             decided,
+            tie_tolerance: self.tie_tolerance,
             #[cfg(autotune_persistence)]
             limit: self.limit,
             #[cfg(autotune_persistence)]
@@ -160,6 +212,8 @@ impl<K: AutotuneKey, F: TuneInputs, Out> TuneJob<'_, '_, K, F, Out> {
 struct TuneRequest<K: AutotuneKey> {
     key: K,
     results: Vec<AutotuneResult>,
+    /// The config of every tunable, in tunable order.
+    params: Vec<Option<TuneParams>>,
     #[cfg(autotune_persistence)]
     checksum: String,
     log_context: Option<crate::tune::AutotuneLogContext>,
@@ -167,6 +221,8 @@ struct TuneRequest<K: AutotuneKey> {
     /// The winner, when the strategy already picked one. `None` means the results are all
     /// comparable and the fastest is whichever scores best.
     decided: Option<usize>,
+    /// See [`DeterministicConfig::tie_tolerance`].
+    tie_tolerance: Option<f64>,
     #[cfg(autotune_persistence)]
     limit: Option<Duration>,
     #[cfg(autotune_persistence)]
@@ -182,9 +238,25 @@ impl<K: AutotuneKey> Tuner<K> {
         Self {
             cache: Arc::new(Mutex::new(TuneCache::new(name, device_id))),
             logger: Arc::new(Mutex::new(Logger::new())),
+            #[cfg(std_io)]
+            name: name.to_string(),
+            deterministic: crate::config::CubeClRuntimeConfig::get()
+                .autotune
+                .deterministic
+                .clone(),
+            #[cfg(not(target_family = "wasm"))]
+            background: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Keep the decisions of this tuner reproducible as `config` says, rather than as the
+    /// [`deterministic`](crate::config::autotune::AutotuneConfig::deterministic) section of
+    /// the runtime config does.
+    pub fn with_deterministic(mut self, config: DeterministicConfig) -> Self {
+        self.deterministic = config;
+        self
+    }
+
     /// Fetch the fastest autotune operation index for an autotune key.
     ///
     /// This resets the cache when the environment switched but does not
@@ -209,51 +281,37 @@ impl<K: AutotuneKey> Tuner<K> {
 
     /// Check the cache, validate checksums if needed, and kick off a tuning job if the
     /// key is a miss. Returns the resolved cache state.
+    ///
+    /// Fails when decisions are [deterministic](DeterministicConfig) and the decision file
+    /// can't serve or record the key.
     pub fn check_tune<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
         &self,
         key: &K,
         inputs: &F::At<'a>,
         tunables: &TunableSet<K, F, Out>,
-        #[cfg_attr(not(autotune_persistence), allow(unused))] checksum: impl FnOnce() -> String
-        + Send
-        + Sync,
+        checksum: impl FnOnce() -> String + Send + Sync,
         client: &ComputeClient<R>,
         mut log_context: Option<crate::tune::AutotuneLogContext>,
-    ) -> TuneCacheResult
+    ) -> Result<TuneCacheResult, AutotuneError>
     where
         <F as TuneInputs>::At<'a>: Clone + Send,
     {
+        #[cfg(std_io)]
+        if let Some(fastest_index) = self.pinned(key, tunables)? {
+            return Ok(TuneCacheResult::Hit { fastest_index });
+        }
+
         {
             let mut cache = self.cache.lock();
-            #[cfg(autotune_persistence)]
-            cache.reset_if_environment_switched();
-            let cur = cache.fastest(key);
-
-            // Browser hydration is asynchronous, so persistent entries may
-            // have arrived after construction. Ingest them before starting a
-            // redundant tune.
-            #[cfg(autotune_persistence)]
-            let cur = if matches!(cur, TuneCacheResult::Miss) {
-                cache.sync_persistent();
-                cache.fastest(key)
-            } else {
-                cur
-            };
-
-            #[cfg(autotune_persistence)]
-            let cur = if matches!(cur, TuneCacheResult::Unchecked) {
-                let mut log = self.logger.lock();
-                let checksum = checksum();
-                if let AutotuneLogLevel::Full = log.log_level_autotune() {
-                    log.log_autotune(&format!("validate checksum key={key}, checksum={checksum}"));
-                }
-                cache.validate_checksum(key, &checksum)
-            } else {
-                cur
-            };
+            let cur = self.lookup(&mut cache, key, checksum);
 
             match cur {
-                TuneCacheResult::Hit { .. } | TuneCacheResult::Pending => return cur,
+                TuneCacheResult::Hit { fastest_index } => {
+                    #[cfg(std_io)]
+                    self.record(key, tunables, fastest_index)?;
+                    return Ok(cur);
+                }
+                TuneCacheResult::Pending => return Ok(cur),
                 TuneCacheResult::Miss | TuneCacheResult::Unchecked => {
                     cache.mark_pending(key.clone())
                 }
@@ -280,7 +338,7 @@ impl<K: AutotuneKey> Tuner<K> {
         // Fast path: single tunable, no benchmarking needed.
         if results.len() == 1 {
             self.cache.lock().cache_insert(key.clone(), 0);
-            return TuneCacheResult::Hit { fastest_index: 0 };
+            return Ok(TuneCacheResult::Hit { fastest_index: 0 });
         }
 
         let test_inputs = tunables.generate_inputs(key, inputs);
@@ -294,11 +352,13 @@ impl<K: AutotuneKey> Tuner<K> {
         // The slowest median duration still considered close enough to peak throughput.
         // Only used on native, where a benchmark can be resolved inline to exit early.
         #[cfg(not(target_family = "wasm"))]
-        let short_circuit = limit.is_some()
-            && tunables.is_short_circuit_enabled()
-            && !crate::config::CubeClRuntimeConfig::get()
-                .autotune
-                .disable_short_circuit;
+        let short_circuit = {
+            let config = crate::config::CubeClRuntimeConfig::get();
+            limit.is_some()
+                && tunables.is_short_circuit_enabled()
+                && !config.autotune.disable_short_circuit
+                && self.deterministic.tie_tolerance().is_none()
+        };
 
         let job = TuneJob {
             key: key.clone(),
@@ -315,18 +375,361 @@ impl<K: AutotuneKey> Tuner<K> {
             #[cfg(autotune_persistence)]
             checksum,
             log_context,
+            tie_tolerance: self.deterministic.tie_tolerance(),
         };
 
         #[cfg(not(target_family = "wasm"))]
-        if crate::config::CubeClRuntimeConfig::get()
+        let result = if tunables.has_halving() {
+            self.tune_halving(job, client)
+        } else if crate::config::CubeClRuntimeConfig::get()
             .autotune
             .bench
             .adaptive
         {
-            return self.tune_adaptive(job, client);
+            self.tune_adaptive(job, client)
+        } else {
+            self.tune_fixed_samples(job, client)
+        };
+        #[cfg(target_family = "wasm")]
+        let result = self.tune_fixed_samples(job, client);
+
+        #[cfg(std_io)]
+        if let TuneCacheResult::Hit { fastest_index } = result {
+            self.record(key, tunables, fastest_index)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Check the cache for a set tuned in the background, and advance the background tune of
+    /// the key when a step is due. Returns the index of the tunable to execute: the cached
+    /// winner once there is one, `default_index` until then.
+    ///
+    /// Fails when decisions are [deterministic](DeterministicConfig) and the decision file
+    /// can't serve or record the key.
+    ///
+    /// Unlike [`check_tune`](Self::check_tune), this never waits for a benchmark: a due step
+    /// only queues the samples of one candidate, see [`TunableSet::with_background`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn check_tune_background<'a, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
+        &self,
+        key: &K,
+        inputs: &F::At<'a>,
+        tunables: &TunableSet<K, F, Out>,
+        default_index: usize,
+        client: &ComputeClient<R>,
+        mut log_context: Option<crate::tune::AutotuneLogContext>,
+    ) -> Result<usize, AutotuneError>
+    where
+        <F as TuneInputs>::At<'a>: Clone + Send,
+    {
+        #[cfg(std_io)]
+        if let Some(fastest_index) = self.pinned(key, tunables)? {
+            return Ok(fastest_index);
+        }
+
+        let config = crate::config::CubeClRuntimeConfig::get();
+        let interval = tunables
+            .background_interval()
+            .unwrap_or_else(|| config.autotune.background.step_interval());
+
+        let step = {
+            let mut cache = self.cache.lock();
+            let cur = self.lookup(&mut cache, key, || tunables.compute_checksum());
+
+            if let TuneCacheResult::Hit { fastest_index } = cur {
+                #[cfg(std_io)]
+                self.record(key, tunables, fastest_index)?;
+                return Ok(fastest_index);
+            }
+
+            let mut background = self.background.lock();
+            if !background.contains_key(key) {
+                // Another caller is committing the winner, or a blocking tune is in flight.
+                if let TuneCacheResult::Pending = cur {
+                    return Ok(default_index);
+                }
+
+                if tunables.len() == 1 {
+                    cache.cache_insert(key.clone(), 0);
+                    return Ok(0);
+                }
+
+                log::info!("Tuning {key} in the background");
+                cache.mark_pending(key.clone());
+
+                let results = tunables
+                    .autotunables()
+                    .map(|a| {
+                        AutotuneResult::error(AutotuneError::Skip {
+                            name: a.name.to_string(),
+                        })
+                    })
+                    .collect();
+                let bounds = tunables.bounds(key, inputs);
+                let limit = bounds.as_ref().and_then(|bounds| bounds.time_limit());
+                let short_circuit = limit.is_some()
+                    && tunables.is_short_circuit_enabled()
+                    && !config.autotune.disable_short_circuit
+                    && self.deterministic.tie_tolerance().is_none();
+
+                log_context.set_bounds(bounds.clone());
+                log_context.set_limit(limit);
+
+                background.insert(
+                    key.clone(),
+                    BackgroundTune::new(
+                        tunables.plan(key),
+                        results,
+                        limit,
+                        short_circuit,
+                        #[cfg(autotune_persistence)]
+                        bounds,
+                        log_context,
+                    ),
+                );
+            }
+
+            background
+                .get_mut(key)
+                .expect("Inserted above when missing")
+                .next_step(interval)
+        };
+
+        let index = match step {
+            BackgroundStep::Wait => return Ok(default_index),
+            BackgroundStep::Done => return self.commit_background(key, tunables, default_index),
+            BackgroundStep::Bench(index) => index,
+        };
+
+        // Queueing the samples holds exclusive device access, but nothing here waits for them
+        // to run: they resolve on the device's own time, and a later call records them.
+        let op = tunables.fastest(index);
+        let started = cubecl_common::profile::Instant::now();
+        let test_inputs = tunables.generate_inputs(key, inputs);
+        let launched = tune_benchmark(op, test_inputs, client.clone());
+
+        let finished = {
+            let mut background = self.background.lock();
+            let Some(state) = background.get_mut(key) else {
+                return Ok(default_index);
+            };
+
+            match launched {
+                Ok(profiles) => state.launched(InFlight {
+                    index,
+                    name: op.name.clone(),
+                    started,
+                    result: Box::pin(resolve_bench(PendingBench {
+                        index,
+                        name: op.name.clone(),
+                        profiles,
+                        launch: None,
+                    })),
+                }),
+                Err(err) => {
+                    state.complete(index, op.name.clone(), started, AutotuneResult::error(err))
+                }
+            }
+        };
+
+        if finished {
+            self.commit_background(key, tunables, default_index)
+        } else {
+            Ok(default_index)
+        }
+    }
+
+    /// Block until the benchmark queued by the background tune of `key` resolves, and record
+    /// its result. Returns right away when the key has nothing in flight.
+    ///
+    /// Background steps never wait for the device, so a result is otherwise only picked up by
+    /// a later call for the key. This gives a synchronization point to whoever needs the tune
+    /// to make progress, e.g. a warmup phase or a test.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_background(&self, key: &K) {
+        let in_flight = self
+            .background
+            .lock()
+            .get_mut(key)
+            .and_then(|state| state.take_in_flight());
+        let Some(in_flight) = in_flight else {
+            return;
+        };
+
+        // The state stays marked as waiting, so a concurrent call for the key can't queue the
+        // next candidate while this one runs and skew both timings.
+        let result = cubecl_environment::future::block_on(in_flight.result);
+
+        if let Some(state) = self.background.lock().get_mut(key) {
+            state.complete(in_flight.index, in_flight.name, in_flight.started, result);
+        }
+    }
+
+    /// Commit a finished background tune to the caches, returning the winner.
+    #[cfg(not(target_family = "wasm"))]
+    fn commit_background<F: TuneInputs, Out: AutotuneOutput>(
+        &self,
+        key: &K,
+        #[cfg_attr(not(autotune_persistence), allow(unused))] tunables: &TunableSet<K, F, Out>,
+        default_index: usize,
+    ) -> Result<usize, AutotuneError> {
+        // Whoever removes the state commits it; a concurrent caller finds the key pending
+        // without a state and keeps running the default until the winner lands.
+        let Some(state) = self.background.lock().remove(key) else {
+            return Ok(default_index);
+        };
+
+        if !state.any_success() {
+            // Nothing was measured, yet the default kept serving every call: keep it for this
+            // process rather than panicking on a key that works, and re-tune in the next one.
+            log::warn!("No candidate could be benchmarked for {key}, keeping the default");
+            self.cache.lock().cache_insert(key.clone(), default_index);
+            return Ok(default_index);
+        }
+
+        let request = TuneRequest {
+            key: key.clone(),
+            results: state.results,
+            params: tunables
+                .autotunables()
+                .map(|tunable| tunable.params.clone())
+                .collect(),
+            #[cfg(autotune_persistence)]
+            checksum: tunables.compute_checksum(),
+            log_context: state.log_context,
+            pending: Vec::new(),
+            decided: None,
+            tie_tolerance: self.deterministic.tie_tolerance(),
+            #[cfg(autotune_persistence)]
+            limit: state.limit,
+            #[cfg(autotune_persistence)]
+            bounds: state.bounds,
+        };
+
+        match cubecl_environment::future::block_on(process_request(
+            request,
+            &self.cache,
+            &self.logger,
+        )) {
+            TuneCacheResult::Hit { fastest_index } => {
+                #[cfg(std_io)]
+                self.record(key, tunables, fastest_index)?;
+                Ok(fastest_index)
+            }
+            _ => Ok(default_index),
+        }
+    }
+
+    /// The index of the candidate the decision file pins for `key`, when decisions are
+    /// [pinned](crate::config::autotune::DeterministicMode::Pinned). Cached like a tuned
+    /// decision, so later calls take the fast path.
+    ///
+    /// Fails with [`MissingPinnedDecision`](AutotuneError::MissingPinnedDecision) when the key
+    /// has no decision, or its decision names a candidate the set doesn't have.
+    #[cfg(std_io)]
+    fn pinned<F: TuneInputs, Out: AutotuneOutput>(
+        &self,
+        key: &K,
+        tunables: &TunableSet<K, F, Out>,
+    ) -> Result<Option<usize>, AutotuneError> {
+        // Nothing to decide, and nothing worth requiring a decision file entry for.
+        if tunables.len() == 1 {
+            return Ok(None);
         }
 
-        self.tune_fixed_samples(job, client)
+        let Some(name) =
+            super::deterministic::pinned_decision(&self.deterministic, &self.name, key)?
+        else {
+            return Ok(None);
+        };
+        let fastest_index = tunables
+            .autotunables()
+            .position(|tunable| tunable.name == name)
+            .ok_or_else(|| AutotuneError::MissingPinnedDecision {
+                tuner: self.name.clone(),
+                key: key.to_string(),
+                reason: format!("the decision pins {name}, which the set doesn't have"),
+            })?;
+
+        self.cache.lock().cache_insert(key.clone(), fastest_index);
+        Ok(Some(fastest_index))
+    }
+
+    /// Append the decision for `key` to the decision file, when decisions are
+    /// [recorded](crate::config::autotune::DeterministicMode::Record).
+    ///
+    /// Every resolved decision goes through here, tuned or served from the persistent cache: a
+    /// pinned run asks for every key it meets, however the recording run decided it.
+    #[cfg(std_io)]
+    fn record<F: TuneInputs, Out: AutotuneOutput>(
+        &self,
+        key: &K,
+        tunables: &TunableSet<K, F, Out>,
+        fastest_index: usize,
+    ) -> Result<(), AutotuneError> {
+        // Never pinned, see `pinned`.
+        if tunables.len() == 1 {
+            return Ok(());
+        }
+
+        super::deterministic::record_decision(
+            &self.deterministic,
+            &self.name,
+            key,
+            &tunables.fastest(fastest_index).name,
+        )
+    }
+
+    /// Resolve the cached state of a key: reset on an environment switch, ingest entries the
+    /// persistent store delivered since the last lookup, and validate an unchecked checksum.
+    #[cfg_attr(not(autotune_persistence), allow(clippy::let_and_return))]
+    fn lookup(
+        &self,
+        cache: &mut TuneCache<K>,
+        key: &K,
+        #[cfg_attr(not(autotune_persistence), allow(unused))] checksum: impl FnOnce() -> String,
+    ) -> TuneCacheResult {
+        #[cfg(autotune_persistence)]
+        cache.reset_if_environment_switched();
+        let cur = cache.fastest(key);
+
+        // Browser hydration is asynchronous, so persistent entries may
+        // have arrived after construction. Ingest them before starting a
+        // redundant tune.
+        #[cfg(autotune_persistence)]
+        let cur = if matches!(cur, TuneCacheResult::Miss) {
+            cache.sync_persistent();
+            cache.fastest(key)
+        } else {
+            cur
+        };
+
+        #[cfg(autotune_persistence)]
+        if matches!(cur, TuneCacheResult::Unchecked) {
+            let mut log = self.logger.lock();
+            let checksum = checksum();
+            if let AutotuneLogLevel::Full = log.log_level_autotune() {
+                log.log_autotune(&format!("validate checksum key={key}, checksum={checksum}"));
+            }
+            let validated = cache.validate_checksum(key, &checksum);
+
+            // Persisted without tie-breaking, or with another tolerance: settle it again from
+            // the results it was decided on, like a fresh tune would be.
+            if let TuneCacheResult::Hit { fastest_index } = validated
+                && let Some(tolerance) = self.deterministic.tie_tolerance()
+                && let Some(results) = cache.persistent_results(key, &checksum)
+            {
+                let fastest_index =
+                    super::deterministic::break_ties(&results, fastest_index, tolerance);
+                cache.cache_insert(key.clone(), fastest_index);
+                return TuneCacheResult::Hit { fastest_index };
+            }
+
+            return validated;
+        }
+
+        cur
     }
 
     /// Round robin the candidates, eliminating them as the evidence allows. Native only: the
@@ -371,6 +774,178 @@ impl<K: AutotuneKey> Tuner<K> {
         cubecl_environment::future::block_on(process_request(request, &self.cache, &self.logger))
     }
 
+    /// Benchmark the candidates through successive halving rounds, see
+    /// [`SearchStrategy::SuccessiveHalving`](crate::tune::SearchStrategy::SuccessiveHalving).
+    /// Native only, like the adaptive strategy: each round is resolved before the survivors of
+    /// the next one can be picked.
+    ///
+    /// The configs of each halving space race among themselves, and only the last one standing
+    /// is measured on the full sample budget, like every candidate that isn't part of such a
+    /// space. The winner is picked among those full measurements, which also honor the limit of
+    /// the set's bounds like the fixed-count pass does.
+    #[cfg(not(target_family = "wasm"))]
+    fn tune_halving<'i, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
+        &self,
+        mut job: TuneJob<'_, 'i, K, F, Out>,
+        client: &ComputeClient<R>,
+    ) -> TuneCacheResult
+    where
+        <F as TuneInputs>::At<'i>: Clone + Send,
+    {
+        let (_, max_samples) = crate::config::CubeClRuntimeConfig::get()
+            .autotune
+            .bench
+            .samples();
+        let mut decided = None;
+
+        // Like the other strategies, the plan moves on to the next batch only when a whole
+        // batch failed.
+        while decided.is_none() {
+            let batch = job.plan.next();
+
+            if batch.is_empty() {
+                let key = &job.key;
+                panic!(
+                    "Can't execute the autotune plan for key: {key:?}\n - plan: {:?}\n - results: {:?}",
+                    job.plan, job.results
+                );
+            }
+
+            // Each space takes the place of its first config in the batch, so candidates are
+            // still measured in plan order and a short circuit skips everything after it.
+            let mut entries: Vec<(Option<usize>, Vec<usize>)> = Vec::new();
+            for index in batch {
+                match job.autotunables[index].halving {
+                    Some(halving) => match entries
+                        .iter_mut()
+                        .find(|(_, members)| job.autotunables[members[0]].halving == Some(halving))
+                    {
+                        Some((_, members)) => members.push(index),
+                        None => entries.push((Some(halving.eta), alloc::vec![index])),
+                    },
+                    None => entries.push((None, alloc::vec![index])),
+                }
+            }
+
+            let mut fastest: Option<(usize, u64)> = None;
+            for (eta, members) in entries {
+                let (index, samples) = match eta {
+                    None => (members[0], None),
+                    Some(eta) => match Self::halving_rounds(&mut job, client, members, eta) {
+                        Some(finalist) => finalist,
+                        None => continue,
+                    },
+                };
+
+                if samples != Some(max_samples) {
+                    Self::measure(&mut job, client, index, max_samples);
+                }
+
+                let Ok(outcome) = &job.results[index].outcome else {
+                    continue;
+                };
+
+                // short_circuit is only true when limit.is_some() => unwrap is fine.
+                if job.short_circuit && outcome.computation.median <= job.limit.unwrap() {
+                    let name = job.autotunables[index].name.to_string();
+                    job.log_context.push_short_circuit(name);
+                    fastest = Some((index, 0));
+                    break;
+                }
+
+                let score = outcome.computation.score();
+                if fastest.is_none_or(|(_, best)| score < best) {
+                    fastest = Some((index, score));
+                }
+            }
+
+            decided = fastest.map(|(index, _)| index);
+        }
+
+        // Eliminated candidates keep the results of the round that eliminated them, measured on
+        // fewer samples than the contenders, so the winner is decided here rather than
+        // re-derived by comparing scores.
+        let request = job.into_request(Vec::new(), decided);
+
+        cubecl_environment::future::block_on(process_request(request, &self.cache, &self.logger))
+    }
+
+    /// Race the configs of one halving space through rounds of growing sample counts. Returns
+    /// the last config standing with the sample count of its last measurement, `None` when no
+    /// config could be measured at all.
+    #[cfg(not(target_family = "wasm"))]
+    fn halving_rounds<'i, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
+        job: &mut TuneJob<'_, 'i, K, F, Out>,
+        client: &ComputeClient<R>,
+        mut survivors: Vec<usize>,
+        eta: usize,
+    ) -> Option<(usize, Option<usize>)>
+    where
+        <F as TuneInputs>::At<'i>: Clone + Send,
+    {
+        let (_, max_samples) = crate::config::CubeClRuntimeConfig::get()
+            .autotune
+            .bench
+            .samples();
+        let mut samples = 1;
+
+        loop {
+            let mut measured = Vec::new();
+
+            for &index in survivors.iter() {
+                Self::measure(job, client, index, samples);
+
+                if let Ok(outcome) = &job.results[index].outcome {
+                    measured.push((index, outcome.computation.median));
+                }
+            }
+
+            if measured.is_empty() {
+                return None;
+            }
+
+            survivors = halving_survivors(measured, eta);
+            if survivors.len() == 1 || samples >= max_samples {
+                return survivors.first().map(|index| (*index, Some(samples)));
+            }
+            samples = (samples * eta.max(2)).min(max_samples);
+        }
+    }
+
+    /// Benchmark a single candidate on `samples` samples, waiting for them to resolve.
+    #[cfg(not(target_family = "wasm"))]
+    fn measure<'i, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
+        job: &mut TuneJob<'_, 'i, K, F, Out>,
+        client: &ComputeClient<R>,
+        index: usize,
+        samples: usize,
+    ) where
+        <F as TuneInputs>::At<'i>: Clone + Send,
+    {
+        let op = job.autotunables[index];
+        let start_time = job
+            .log_context
+            .is_some()
+            .then(cubecl_common::profile::Instant::now);
+
+        let result =
+            match tune_benchmark_samples(op, job.test_inputs.clone(), client.clone(), samples) {
+                Ok(profiles) => cubecl_environment::future::block_on(resolve_bench(PendingBench {
+                    index,
+                    name: op.name.clone(),
+                    profiles,
+                    launch: None,
+                })),
+                Err(err) => AutotuneResult::error(err),
+            };
+
+        if let Some(start) = start_time {
+            job.log_context
+                .push_tuning_step(op.name.to_string(), start.elapsed());
+        }
+        job.results[index] = result;
+    }
+
     /// Benchmark every candidate with a fixed sample count, resolving the samples afterwards.
     /// This is the only strategy available on wasm, where nothing can be awaited inline.
     fn tune_fixed_samples<'i, R: Runtime, F: TuneInputs, Out: AutotuneOutput>(
@@ -541,11 +1116,13 @@ async fn process_request<K: AutotuneKey>(
     let TuneRequest {
         key,
         mut results,
+        params,
         #[cfg(autotune_persistence)]
         checksum,
         mut log_context,
         pending,
         decided,
+        tie_tolerance,
         #[cfg(autotune_persistence)]
         limit,
         #[cfg(autotune_persistence)]
@@ -579,6 +1156,14 @@ async fn process_request<K: AutotuneKey>(
         results[index] = result;
     }
 
+    // Candidates are measured by name; attach the config each one ran, so the logged and
+    // persisted results say which point of a search space won.
+    for result in results.iter_mut() {
+        if let Ok(outcome) = result.outcome.as_mut() {
+            outcome.params = params[outcome.index].clone();
+        }
+    }
+
     // Read before the sort, which reorders `results` out of tunable order. A
     // decided candidate whose own outcome is an error is one `Schedule::run_plan`
     // picked with nothing measured — the tune executed but could not be timed.
@@ -602,7 +1187,7 @@ async fn process_request<K: AutotuneKey>(
     // The sort above orders what gets logged and persisted. It does not pick the winner when the
     // strategy already did: a scheduler that eliminates candidates leaves results built from
     // different sample counts behind, and `score` reads a short sample set as a stable one.
-    let fastest_index = match decided {
+    let mut fastest_index = match decided {
         Some(index) => index,
         None => {
             results
@@ -615,6 +1200,11 @@ async fn process_request<K: AutotuneKey>(
         }
     };
 
+    // Timing noise alone can flip a close decision from one run to the next.
+    if let Some(tolerance) = tie_tolerance {
+        fastest_index = super::deterministic::break_ties(&results, fastest_index, tolerance);
+    }
+
     {
         log_context.log_result(&mut logger.lock(), &key, &results);
         // In-memory regardless: without it this key re-tunes on every call, and
@@ -27,7 +27,14 @@ use cubecl_runtime::{
     timestamp_profiler::TimestampProfiler,
 };
 use cubecl_zspace::{Shape, Strides};
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// How many staging buffers every dummy server has handed out, so tests can tell the staged
+/// upload path from the fallback write path.
+pub static STAGING_BUFFERS: AtomicUsize = AtomicUsize::new(0);
 
 /// The dummy server is used to test the cubecl-runtime infrastructure.
 /// It uses simple memory management with a bytes storage on CPU, without asynchronous tasks.
@@ -97,6 +104,7 @@ impl CubeTask<DummyCompiler> for KernelTask {
             repr: Some(self.clone()),
             cube_dim: CubeDim::new_single(),
             debug_info: None,
+            cost: None,
         })
     }
 }
@@ -182,6 +190,18 @@ impl ComputeServer for DummyServer {
         }
     }
 
+    fn staging(
+        &mut self,
+        sizes: &[usize],
+        _stream_id: StreamId,
+    ) -> Result<Vec<Bytes>, ServerError> {
+        STAGING_BUFFERS.fetch_add(sizes.len(), Ordering::Relaxed);
+        Ok(sizes
+            .iter()
+            .map(|size| Bytes::from_bytes_vec(vec![0; *size]))
+            .collect())
+    }
+
     fn sync(&mut self, _stream_id: StreamId) -> DynFut<Result<(), ServerError>> {
         let result = self.take_pending_error();
         Box::pin(async move { result })
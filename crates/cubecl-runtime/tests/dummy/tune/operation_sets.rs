@@ -8,7 +8,10 @@ use std::{
 
 use cubecl_runtime::{
     server::Handle,
-    tune::{AutotuneBound, Bounds, CloneInputGenerator, Tunable, TunableSet},
+    tune::{
+        AutotuneBound, Bounds, CloneInputGenerator, SearchSpace, SearchStrategy, Tunable,
+        TunableSet,
+    },
 };
 
 use crate::dummy::{
@@ -244,3 +247,105 @@ pub fn addition_set_with_failing_compilation(
         op_broken.run(inputs)
     }))
 }
+
+/// Addition set tuned in the background, with the slow+wrong kernel as the default: its output
+/// tells whether a call ran the default or the tuned winner.
+pub fn background_addition_set(
+    client: DummyClient,
+    shapes: Vec<Vec<usize>>,
+    uid: String,
+) -> TestSet {
+    let op_add =
+        OneKernelAutotuneOperation::new(KernelTask::new(DummyElementwiseAddition), client.clone());
+    let op_add_slow = OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyElementwiseAdditionSlowWrong),
+        client.clone(),
+    );
+
+    TestSet::new(
+        move |_input: &Vec<Handle>| {
+            format!("add_background-{uid}-{}", log_shape_input_key(&shapes))
+        },
+        CloneInputGenerator,
+    )
+    .with(Tunable::new("add", move |inputs| op_add.run(inputs)))
+    .with(Tunable::new("add_slow_wrong", move |inputs| {
+        op_add_slow.run(inputs)
+    }))
+    .with_background(1)
+}
+
+/// Addition set generated from a one-parameter search space: `slow` picks the slow+wrong
+/// kernel, so only the fast config produces a real addition.
+pub fn space_addition_set(
+    client: DummyClient,
+    shapes: Vec<Vec<usize>>,
+    uid: String,
+    strategy: SearchStrategy,
+) -> TestSet {
+    let op_add =
+        OneKernelAutotuneOperation::new(KernelTask::new(DummyElementwiseAddition), client.clone());
+    let op_add_slow = OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyElementwiseAdditionSlowWrong),
+        client.clone(),
+    );
+
+    TestSet::new(
+        move |_input: &Vec<Handle>| format!("add_space-{uid}-{}", log_shape_input_key(&shapes)),
+        CloneInputGenerator,
+    )
+    .with_space(
+        "add",
+        SearchSpace::new().param("slow", [true, false]),
+        strategy,
+        move |params, inputs| {
+            if params.get::<bool>("slow") {
+                op_add_slow.run(inputs)
+            } else {
+                op_add.run(inputs)
+            }
+        },
+    )
+}
+
+/// The slow+wrong kernel registered first, then a successive halving space of fast+correct
+/// configs, under the same generous bound as [`bounded_addition_set_slow_first`]: the halving
+/// rounds only race the configs of the space, and the registered kernel still short-circuits.
+pub fn bounded_space_addition_set_slow_first(
+    client: DummyClient,
+    shapes: Vec<Vec<usize>>,
+    uid: String,
+) -> TestSet {
+    let op_add_slow = OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyElementwiseAdditionSlowWrong),
+        client.clone(),
+    );
+    let op_add =
+        OneKernelAutotuneOperation::new(KernelTask::new(DummyElementwiseAddition), client.clone());
+
+    TestSet::new(
+        move |_input: &Vec<Handle>| {
+            format!("add_space_bounded-{uid}-{}", log_shape_input_key(&shapes))
+        },
+        CloneInputGenerator,
+    )
+    .with(Tunable::new("add_slow_wrong", move |inputs| {
+        op_add_slow.run(inputs)
+    }))
+    .with_space(
+        "add",
+        SearchSpace::new().param("unroll", [1u32, 2, 4]),
+        SearchStrategy::SuccessiveHalving { eta: 2 },
+        move |_params, inputs| op_add.run(inputs),
+    )
+    .with_bounds(Arc::new(move |_key: &String, _inputs: &Vec<Handle>| {
+        Bounds {
+            bounds: vec![AutotuneBound {
+                throughput: 1.0,
+                threshold: 1.0,
+                ops_count: 1,
+            }],
+            launch_overhead: Duration::ZERO,
+        }
+    }))
+}
@@ -2,6 +2,9 @@ mod dummy;
 
 use crate::dummy::{DummyDevice, DummyElementwiseAddition, test_client};
 
+use core::sync::atomic::Ordering;
+use cubecl_common::bytes::Bytes;
+use cubecl_runtime::client::UploadOptions;
 use cubecl_runtime::server::CubeCount;
 use cubecl_runtime::server::KernelArguments;
 use cubecl_runtime::{local_tuner, tune::LocalTuner};
@@ -18,6 +21,30 @@ fn created_resource_is_the_same_when_read() {
     assert_eq!(resource, obtained_resource)
 }
 
+#[test_log::test]
+fn uploaded_resources_are_the_same_when_read() {
+    let client = test_client(&DummyDevice);
+    let sources = [Vec::from([0, 1, 2, 3, 4]), Vec::from([5, 6])];
+    let staged = STAGING_BUFFERS.load(Ordering::Relaxed);
+
+    let handles = client
+        .upload(
+            sources.iter().cloned().map(Bytes::from_bytes_vec),
+            UploadOptions {
+                chunk_size: 2,
+                buffers: 2,
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // Three chunks for the first source, one for the second, all through staging memory.
+    assert!(STAGING_BUFFERS.load(Ordering::Relaxed) - staged >= 4);
+    for (handle, source) in handles.into_iter().zip(sources) {
+        assert_eq!(client.read_one(handle).unwrap().to_vec(), source);
+    }
+}
+
 #[test_log::test]
 fn empty_allocates_memory() {
     let client = test_client(&DummyDevice);
@@ -54,6 +81,184 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test_log::test]
+#[cfg(std_io)]
+#[serial_test::serial]
+fn recorded_operations_replay_to_the_same_reads() {
+    use cubecl_runtime::client::{RecordedKernel, RecordedOp, ReplayLog};
+    use cubecl_runtime::compiler::CubeTask;
+
+    let client = test_client(&DummyDevice);
+    // Created before the recording, so the log has to snapshot it at the launch.
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("replay.log");
+    client.start_recording(&path).unwrap();
+
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+    client.launch(
+        Box::new(KernelTask::new(DummyElementwiseAddition)),
+        CubeCount::Static(1, 1, 1),
+        KernelArguments::new().with_buffers(vec![
+            lhs.binding(),
+            rhs.binding(),
+            out.clone().binding(),
+        ]),
+    );
+    assert_eq!(client.read_one(out).unwrap().to_vec(), [4, 5, 6]);
+    client.stop_recording().unwrap();
+
+    let mut text = std::fs::read_to_string(&path).unwrap();
+    // Buffer contents are logged as base64, `[4, 4, 4]` here.
+    assert!(text.contains("\"BAQE\""), "{text}");
+
+    // The dummy kernels run host code the IR doesn't describe, so they're resolved by hand.
+    let resolve = |_: &RecordedKernel| -> Option<Box<dyn CubeTask<DummyCompiler>>> {
+        Some(Box::new(KernelTask::new(DummyElementwiseAddition)))
+    };
+    let log = ReplayLog::from_file(&path).unwrap();
+    assert_eq!(log.kernels().count(), 1);
+    let report = log.replay_with(&client, resolve).unwrap();
+    assert_eq!((report.launches, report.reads), (1, 1));
+    assert!(report.is_match(), "{report:?}");
+
+    // An operation the recording couldn't log stops the replay.
+    let skipped = RecordedOp::Skipped {
+        op: "graph replay".into(),
+        reason: "graphs can't be recorded".into(),
+    };
+    text.push_str(&serde_json::to_string(&skipped).unwrap());
+    let log = ReplayLog::from_reader(text.as_bytes()).unwrap();
+    assert!(log.replay_with(&client, resolve).is_err());
+}
+
+/// Recording writes the decisions served from the persistent cache too, not just the tuned
+/// ones, so a pinned run finds every key the recording run met.
+#[test_log::test]
+#[cfg(std_io)]
+#[serial_test::serial]
+fn autotune_records_cached_decisions_for_pinned_runs() {
+    use cubecl_runtime::config::autotune::{DeterministicConfig, DeterministicMode};
+    use cubecl_runtime::tune::{TuneCacheResult, Tuner};
+
+    let root = tempfile::tempdir().unwrap();
+    cubecl_environment::environment::set_root(root.path());
+    let decisions = root.path().join("decisions.jsonl");
+    let config = |mode| DeterministicConfig {
+        mode,
+        decisions: Some(decisions.clone()),
+        ..Default::default()
+    };
+
+    let client = test_client(&DummyDevice);
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let set = dummy::addition_set(test_client(&DummyDevice), shapes);
+    let handles = vec![
+        client.create_from_slice(&[0, 1, 2]),
+        client.create_from_slice(&[4, 4, 4]),
+        client.empty(3),
+    ];
+    let key = set.generate_key(&handles);
+    let check_tune = |tuner: &Tuner<String>| match tuner
+        .check_tune(
+            &key,
+            &handles,
+            &set,
+            || set.compute_checksum(),
+            &client,
+            None,
+        )
+        .unwrap()
+    {
+        TuneCacheResult::Hit { fastest_index } => fastest_index,
+        other => panic!("Expected a decision, got {other:?}"),
+    };
+
+    // Seed the persistent cache with an ordinary tune.
+    check_tune(&Tuner::new("recorded-decisions", "device0"));
+
+    // A later process serves the key from the persistent cache, and still records it.
+    let recorded = check_tune(
+        &Tuner::new("recorded-decisions", "device0")
+            .with_deterministic(config(DeterministicMode::Record)),
+    );
+
+    // Pinned on another device, so nothing is cached and nothing may be tuned.
+    let pinned = check_tune(
+        &Tuner::new("recorded-decisions", "device1")
+            .with_deterministic(config(DeterministicMode::Pinned)),
+    );
+    assert_eq!(pinned, recorded);
+}
+
+/// A pinned run never tunes: a key the decision file doesn't have is an error, not a reason
+/// to measure.
+#[test_log::test]
+#[cfg(std_io)]
+#[serial_test::serial]
+fn autotune_pinned_without_decision_fails() {
+    use cubecl_runtime::config::autotune::{DeterministicConfig, DeterministicMode};
+    use cubecl_runtime::tune::{AutotuneError, Tuner};
+
+    let root = tempfile::tempdir().unwrap();
+    cubecl_environment::environment::set_root(root.path());
+    let decisions = root.path().join("decisions.jsonl");
+    std::fs::write(&decisions, "").unwrap();
+
+    let client = test_client(&DummyDevice);
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let set = dummy::addition_set(test_client(&DummyDevice), shapes);
+    let handles = vec![
+        client.create_from_slice(&[0, 1, 2]),
+        client.create_from_slice(&[4, 4, 4]),
+        client.empty(3),
+    ];
+    let key = set.generate_key(&handles);
+    let tuner = Tuner::new("missing-decision", "device0").with_deterministic(DeterministicConfig {
+        mode: DeterministicMode::Pinned,
+        decisions: Some(decisions),
+        ..Default::default()
+    });
+
+    let result = tuner.check_tune(
+        &key,
+        &handles,
+        &set,
+        || set.compute_checksum(),
+        &client,
+        None,
+    );
+    assert!(
+        matches!(result, Err(AutotuneError::MissingPinnedDecision { .. })),
+        "{result:?}"
+    );
+}
+
+/// Seeding counts the entries it wrote: a second seed of the same report finds every
+/// decision already cached, and a key decided twice in the report is written once.
+#[test_log::test]
+#[cfg(all(feature = "std", autotune_persistence))]
+#[serial_test::serial]
+fn autotune_report_seeds_only_missing_entries() {
+    use cubecl_runtime::tune::AutotuneReport;
+
+    let root = tempfile::tempdir().unwrap();
+    cubecl_environment::environment::set_root(root.path());
+
+    let record = |key: &str, fastest_index: usize| {
+        format!(
+            "{{\"key\":\"{key}\",\"fastest_index\":{fastest_index},\"fastest_time\":{{\"secs\":0,\"nanos\":0}},\"results\":[],\"log_context\":null,\"checks\":null}}"
+        )
+    };
+    let lines = [record("a", 0), record("b", 1), record("a", 1)].join("\n");
+    let report = AutotuneReport::from_reader(lines.as_bytes()).unwrap();
+
+    assert_eq!(report.seed_cache::<String>("seeding", "device0", "sum"), 2);
+    assert_eq!(report.seed_cache::<String>("seeding", "device0", "sum"), 0);
+}
+
 #[test_log::test]
 #[cfg(feature = "std")]
 #[serial_test::serial]
@@ -72,7 +277,9 @@ fn autotune_basic_addition_execution() {
         let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
         dummy::addition_set(client, shapes)
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let obtained_resource = client.read_one(out).unwrap().to_vec();
 
@@ -99,7 +306,9 @@ fn autotune_basic_multiplication_execution() {
         let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
         dummy::multiplication_set(client, shapes)
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let obtained_resource = client.read_one(out).unwrap().to_vec();
 
@@ -133,42 +342,48 @@ fn autotune_resets_when_the_environment_switches() {
     let key = set.generate_key(&handles);
 
     let tuner: Tuner<String> = Tuner::new("environment-switch", "device0");
-    tuner.check_tune(
-        &key,
-        &handles,
-        &set,
-        || set.compute_checksum(),
-        &client,
-        None,
-    );
+    tuner
+        .check_tune(
+            &key,
+            &handles,
+            &set,
+            || set.compute_checksum(),
+            &client,
+            None,
+        )
+        .unwrap();
     assert!(matches!(tuner.fastest(&key), TuneCacheResult::Hit { .. }));
 
     // The pick was tuned under `first`: after the switch it must not be
     // served, and tuning again fills `second`.
     cubecl_environment::environment::set_root(second.path());
     assert!(matches!(tuner.fastest(&key), TuneCacheResult::Miss));
-    tuner.check_tune(
-        &key,
-        &handles,
-        &set,
-        || set.compute_checksum(),
-        &client,
-        None,
-    );
+    tuner
+        .check_tune(
+            &key,
+            &handles,
+            &set,
+            || set.compute_checksum(),
+            &client,
+            None,
+        )
+        .unwrap();
     assert!(matches!(tuner.fastest(&key), TuneCacheResult::Hit { .. }));
 
     // Switching back serves `first`'s persisted result through hydration and
     // checksum validation, with no third tune.
     cubecl_environment::environment::set_root(first.path());
     assert!(matches!(tuner.fastest(&key), TuneCacheResult::Miss));
-    let rehydrated = tuner.check_tune(
-        &key,
-        &handles,
-        &set,
-        || set.compute_checksum(),
-        &client,
-        None,
-    );
+    let rehydrated = tuner
+        .check_tune(
+            &key,
+            &handles,
+            &set,
+            || set.compute_checksum(),
+            &client,
+            None,
+        )
+        .unwrap();
     assert!(matches!(rehydrated, TuneCacheResult::Hit { .. }));
 }
 
@@ -196,7 +411,9 @@ fn autotune_bounds_short_circuit_accepts_first_within_limit() {
         // first candidate is already "close enough".
         dummy::bounded_addition_set_slow_first(client, shapes, 1.0, 1.0)
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let obtained = client.read_one(out).unwrap().to_vec();
 
@@ -228,7 +445,9 @@ fn autotune_bounds_unreachable_limit_benchmarks_all() {
         // time_limit = (1 / 1e12) / 1.0 ≈ 1ps, below any real median, so nothing qualifies.
         dummy::bounded_addition_set_slow_first(client, shapes, 1e12, 1.0)
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let obtained = client.read_one(out).unwrap().to_vec();
 
@@ -256,7 +475,9 @@ fn autotune_short_circuit_disabled_benchmarks_all() {
         let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
         dummy::bounded_addition_set_no_short_circuit(client, shapes)
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let obtained = client.read_one(out).unwrap().to_vec();
 
@@ -365,7 +586,9 @@ fn autotune_stops_sampling_a_rejected_candidate() {
         let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
         dummy::addition_set_with_rejected_candidate(client, shapes, uid.clone(), calls_set.clone())
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     // The rejected candidate is dropped after its first failure, and the surviving `add`
     // kernel still wins the tuning.
@@ -402,7 +625,9 @@ fn autotune_skips_a_candidate_that_fails_compilation() {
         let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
         dummy::addition_set_with_failing_compilation(client, shapes, uid.clone())
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     // The profile boundary consumed the failure: nothing is left for the next caller,
     // which on this process-global server would be an unrelated test.
@@ -467,7 +692,9 @@ fn autotune_stops_sampling_an_eliminated_candidate() {
             slow_set.clone(),
         )
     });
-    TUNER.execute(&"test".to_string(), &client, test_set, handles);
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
 
     let fast = fast_calls.load(Ordering::Relaxed);
     let slow = slow_calls.load(Ordering::Relaxed);
@@ -493,6 +720,116 @@ fn autotune_stops_sampling_an_eliminated_candidate() {
     assert_eq!(client.read_one(out).unwrap().to_vec(), vec![4, 5, 6]);
 }
 
+/// A set tuned in the background runs its default on the call that finds the key untuned,
+/// queues one candidate per due step afterwards without waiting for it, and serves the winner
+/// once the tune settled.
+#[test_log::test]
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[serial_test::serial]
+fn autotune_in_the_background_swaps_in_the_winner() {
+    static TUNER: LocalTuner<String, String> = local_tuner!("autotune_background");
+
+    let client = test_client(&DummyDevice);
+    let uid = fresh_tune_key_uid();
+    let id = "test".to_string();
+
+    let test_set = TUNER.init(move || {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::background_addition_set(client, shapes, uid.clone())
+            .with_background_interval(core::time::Duration::ZERO)
+    });
+
+    let inputs = || {
+        let lhs = client.create_from_slice(&[0, 1, 2]);
+        let rhs = client.create_from_slice(&[4, 4, 4]);
+        let out = client.empty(3);
+        vec![lhs, rhs, out]
+    };
+    let key = test_set.generate_key(&inputs());
+    let run = || {
+        let handles = inputs();
+        let out = handles[2].clone();
+        TUNER
+            .execute(&id, &client, test_set.clone(), handles)
+            .unwrap();
+        client.read_one(out).unwrap().to_vec()
+    };
+
+    // The first call doesn't queue any benchmark: the slow default copies `lhs`.
+    assert_eq!(run(), vec![0, 1, 2]);
+
+    // One candidate per step, the next one only due once the previous one resolved.
+    for _ in 0..2 {
+        run();
+        TUNER.wait_background(&id, &key);
+    }
+
+    // Every candidate was measured, so the real addition took over.
+    assert_eq!(run(), vec![4, 5, 6]);
+}
+
+/// Tunables generated from a search space are benchmarked like registered ones, and the
+/// successive halving rounds still keep the fastest config.
+#[test_log::test]
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[serial_test::serial]
+fn autotune_search_space_picks_the_fastest_config() {
+    use cubecl_runtime::tune::SearchStrategy;
+
+    static TUNER: LocalTuner<String, String> = local_tuner!("autotune_search_space");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs, rhs, out.clone()];
+
+    let uid = fresh_tune_key_uid();
+    let test_set = TUNER.init(move || {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        let strategy = SearchStrategy::SuccessiveHalving { eta: 2 };
+        dummy::space_addition_set(client, shapes, uid.clone(), strategy)
+    });
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
+
+    // The slow config is eliminated after the first round, leaving the real addition.
+    assert_eq!(client.read_one(out).unwrap().to_vec(), vec![4, 5, 6]);
+}
+
+/// Successive halving only races the configs of its own space: the candidate registered
+/// before it is measured on the full budget, and short-circuits under a generous bound.
+#[test_log::test]
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[serial_test::serial]
+fn autotune_search_space_halving_keeps_the_short_circuit() {
+    static TUNER: LocalTuner<String, String> = local_tuner!("autotune_space_short_circuit");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create_from_slice(&[0, 1, 2]);
+    let rhs = client.create_from_slice(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs, rhs, out.clone()];
+
+    let uid = fresh_tune_key_uid();
+    let test_set = TUNER.init(move || {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::bounded_space_addition_set_slow_first(client, shapes, uid.clone())
+    });
+    TUNER
+        .execute(&"test".to_string(), &client, test_set, handles)
+        .unwrap();
+
+    // The slow+wrong kernel copies lhs -> out: it was accepted before the space was raced.
+    assert_eq!(client.read_one(out).unwrap().to_vec(), vec![0, 1, 2]);
+}
+
 /// A dry run drops an ordinary launch: the server still compiles the kernel,
 /// exactly as it would otherwise, and then never runs it.
 ///
@@ -567,22 +904,26 @@ fn a_dry_run_still_autotunes() {
 
     {
         let _dry_run = DryRun::new();
-        TUNER.execute(
-            &"test".to_string(),
-            &client,
-            test_set.clone(),
-            vec![lhs.clone(), rhs.clone(), out.clone()],
-        );
+        TUNER
+            .execute(
+                &"test".to_string(),
+                &client,
+                test_set.clone(),
+                vec![lhs.clone(), rhs.clone(), out.clone()],
+            )
+            .unwrap();
     }
 
     // Cached now, so this is the fast path: it executes the winner and nothing
     // else.
-    TUNER.execute(
-        &"test".to_string(),
-        &client,
-        test_set,
-        vec![lhs, rhs, out.clone()],
-    );
+    TUNER
+        .execute(
+            &"test".to_string(),
+            &client,
+            test_set,
+            vec![lhs, rhs, out.clone()],
+        )
+        .unwrap();
 
     assert_eq!(
         client.read_one(out).unwrap().to_vec(),
@@ -15,6 +15,9 @@ use cubecl_core::{
     post_processing::{
         bitwise::PromoteBitwisePass,
         checked_io::{CheckedIo, CheckedIoPass},
+        complex::LowerComplexNumbersPass,
+        int4::LowerPackedIntPass,
+        plane::LowerClusteredPlaneOpsPass,
         saturating::LowerSaturatingArithmeticPass,
         unroll::UnrollPass,
     },
@@ -28,12 +31,15 @@ use cubecl_ir::{
     rewrite::{CanonicalizePass, visit_all_ops_of_type_mut},
     settings::{Dim3, KernelSettings},
 };
-use cubecl_opt::passes::{
-    alloc_shared_memory::AllocateSharedMemoryBlockPass,
-    annotate_buffer_visibility::AnnotateGlobalVisibilityPass, mem2reg::Mem2RegPass,
-    simple_cse::SimpleCSEPass, sroa::SROAPass,
+use cubecl_opt::{
+    analyses::cost::StaticCost,
+    passes::{
+        alloc_shared_memory::AllocateSharedMemoryBlockPass,
+        annotate_buffer_visibility::AnnotateGlobalVisibilityPass, mem2reg::Mem2RegPass,
+        simple_cse::SimpleCSEPass, sroa::SROAPass,
+    },
 };
-use cubecl_runtime::compiler::CompilationError;
+use cubecl_runtime::{compiler::CompilationError, throughput::KernelCost};
 use pliron::{
     basic_block::BasicBlock,
     builtin::{
@@ -109,7 +115,7 @@ impl Compiler for SpirvCompiler {
             cube_dim: value.settings.cube_dim,
         });
 
-        let (module, bindings, shared_size) = self.compile_kernel(
+        let (module, bindings, shared_size, cost) = self.compile_kernel(
             &mut ctx,
             module,
             entry_func,
@@ -131,6 +137,7 @@ impl Compiler for SpirvCompiler {
             shared_size,
             immediate_size,
             info_visibility,
+            cost,
         };
 
         #[cfg(feature = "pliron-dump")]
@@ -142,6 +149,10 @@ impl Compiler for SpirvCompiler {
     fn extension(&self) -> &'static str {
         "spv"
     }
+
+    fn kernel_cost(&self, kernel: &Self::Representation) -> Option<KernelCost> {
+        Some(kernel.cost.clone())
+    }
 }
 
 impl Debug for SpirvCompiler {
@@ -158,7 +169,7 @@ impl SpirvCompiler {
         entry_func: FuncOp,
         settings: KernelSettings,
         #[cfg(feature = "pliron-dump")] ir_printing_dir: Option<std::path::PathBuf>,
-    ) -> Result<(Module, Vec<Visibility>, usize), CompilationError> {
+    ) -> Result<(Module, Vec<Visibility>, usize, KernelCost), CompilationError> {
         let entry = entry_func.get_entry_block(ctx);
         let comp_opts = ctx.aux_ty::<WgpuCompilationOptions>();
         let module_op = module.get_operation();
@@ -185,6 +196,8 @@ impl SpirvCompiler {
         let mut passes = OpPass::<ModuleOp, Passes>::default();
 
         let mut func_passes = OpPass::<FuncOp, Passes>::default();
+        func_passes.add_pass(LowerComplexNumbersPass);
+        func_passes.add_pass(LowerPackedIntPass);
         func_passes.add_pass(SROAPass);
         func_passes.add_pass(CheckedIoPass::new(CheckedIo::new(
             settings.execution_mode,
@@ -204,6 +217,10 @@ impl SpirvCompiler {
         func_passes.add_pass(SimpleCSEPass);
         func_passes.add_pass(SimplifyOpsPass::default());
         func_passes.add_pass(PromoteBitwisePass);
+        // Devices without `GroupNonUniformClustered` fall back to shuffles
+        if !comp_opts.vulkan.supports_clustered_plane_ops {
+            func_passes.add_pass(LowerClusteredPlaneOpsPass::default());
+        }
         func_passes.add_pass(LowerOpsSpirvPass::default());
         func_passes.add_pass(DCEPass);
         func_passes.add_pass(SROAPass);
@@ -221,6 +238,12 @@ impl SpirvCompiler {
 
         passes.run(module_op, ctx, &mut analyses).unwrap();
 
+        // Counted before the conversion to SPIR-V ops, which the analysis doesn't know.
+        let cost = analyses
+            .get_analysis::<StaticCost>(entry_func.get_operation(), ctx)?
+            .0
+            .clone();
+
         let bindings = (0..entry.deref(ctx).get_num_arguments()).map(|i| {
             let io = entry_func.get_arg_attr::<BufferIOAttr>(ctx, i, &ATTR_BUFFER_IO);
             match io.expect("Should have IO attr").is_writable() {
@@ -280,7 +303,7 @@ impl SpirvCompiler {
         spirv_module.to_spirv(ctx, &mut builder)?;
         let module = builder.module();
 
-        Ok((module, bindings, shared_size))
+        Ok((module, bindings, shared_size, cost))
     }
 }
 
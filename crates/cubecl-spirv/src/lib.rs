@@ -5,7 +5,7 @@ use std::{
     sync::Arc,
 };
 
-use cubecl_core::prelude::Visibility;
+use cubecl_core::{prelude::Visibility, throughput::KernelCost};
 use rspirv::{binary::Disassemble, dr::Module};
 
 pub mod attributes;
@@ -29,6 +29,10 @@ pub struct SpirvKernel {
     pub shared_size: usize,
     pub immediate_size: Option<usize>,
     pub info_visibility: Visibility,
+    /// The static cost of one unit, counted on the lowered IR. Only known for a kernel compiled
+    /// in this process, it isn't part of the cached kernel.
+    #[serde(skip)]
+    pub cost: KernelCost,
 }
 
 impl Eq for SpirvKernel {}
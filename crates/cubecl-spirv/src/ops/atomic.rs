@@ -1,5 +1,8 @@
 use cubecl_core::{self as cubecl, prelude::*};
-use cubecl_ir::{dialect::atomic, prelude::*};
+use cubecl_ir::{
+    dialect::atomic::{self, AtomicScope, MemoryOrdering},
+    prelude::*,
+};
 use pliron::builtin::types::IntegerType;
 use pliron_spirv::{
     ops,
@@ -86,8 +89,9 @@ macro_rules! atomic_binop_to_spirv_dialect {
                 let op = self.get_operation();
                 let ptr = op.operand(ctx, 0);
                 let value = op.operand(ctx, 1);
-                let scope = ptr_scope(ctx, ptr);
-                let semantics = semantics_rw(ctx, ptr);
+                let scope = atomic_scope(ctx, self.scope(ctx).0, ptr);
+                let ordering = self.ordering(ctx).0.or(MemoryOrdering::AcqRel);
+                let semantics = semantics(ctx, ordering, ptr);
                 let out_ty = ty_to_spirv_dialect(ctx, self.get_result(ctx).get_type(ctx));
                 let new_op = <$new_ty>::new(ctx, out_ty, ptr, scope, semantics, value, $($extra),*);
                 rewriter.append_op(ctx, &new_op);
@@ -174,8 +178,9 @@ impl ToSpirvDialectOp for atomic::AtomicLoadOp {
     ) -> Result<()> {
         let op = self.get_operation();
         let ptr = self.ptr(ctx);
-        let scope = ptr_scope(ctx, ptr);
-        let semantics = semantics_r(ctx, ptr);
+        let scope = atomic_scope(ctx, self.scope(ctx).0, ptr);
+        let ordering = self.ordering(ctx).0.or(MemoryOrdering::Acquire);
+        let semantics = semantics(ctx, ordering, ptr);
         let out_ty = ty_to_spirv_dialect(ctx, self.result_type(ctx));
         let new_op = ops::AtomicLoadOp::new(ctx, out_ty, ptr, scope, semantics);
         rewriter.append_op(ctx, &new_op);
@@ -195,8 +200,9 @@ impl ToSpirvDialectOp for atomic::AtomicStoreOp {
         let op = self.get_operation();
         let ptr = self.ptr(ctx);
         let value = self.value(ctx);
-        let scope = ptr_scope(ctx, ptr);
-        let semantics = semantics_w(ctx, ptr);
+        let scope = atomic_scope(ctx, self.scope(ctx).0, ptr);
+        let ordering = self.ordering(ctx).0.or(MemoryOrdering::Release);
+        let semantics = semantics(ctx, ordering, ptr);
         let new_op = ops::AtomicStoreOp::new(ctx, ptr, scope, semantics, value);
         rewriter.append_op(ctx, &new_op);
         rewriter.replace_operation(ctx, op, new_op.get_operation());
@@ -216,9 +222,10 @@ impl ToSpirvDialectOp for atomic::AtomicCompareExchangeWeakOp {
         let ptr = self.ptr(ctx);
         let value = self.value(ctx);
         let cmp = self.cmp(ctx);
-        let scope = ptr_scope(ctx, ptr);
-        let semantics_succ = semantics_rw(ctx, ptr);
-        let semantics_fail = semantics_r(ctx, ptr);
+        let ordering = self.ordering(ctx).0.or(MemoryOrdering::AcqRel);
+        let scope = atomic_scope(ctx, self.scope(ctx).0, ptr);
+        let semantics_succ = semantics(ctx, ordering, ptr);
+        let semantics_fail = semantics(ctx, ordering.failure(), ptr);
         let out_ty = ty_to_spirv_dialect(ctx, self.result_type(ctx));
         let new_op = ops::AtomicCompareExchangeOp::new(
             ctx,
@@ -241,26 +248,43 @@ impl LowerOp for atomic::AtomicFSubOp {
     fn lower(&self, scope: &cubecl_ir::Scope) -> Vec<Value> {
         let ptr = self.ptr(scope.ctx());
         let value = self.value(scope.ctx());
+        let ordering = self.ordering(scope.ctx()).0;
+        let atomic_scope = self.scope(scope.ctx()).0;
         scope.register_value_type::<T, S>(value);
-        vec![atomic_f_sub::expand::<T, S>(scope, ptr.into(), value.into()).read_value(scope)]
+        let out =
+            atomic_f_sub::expand::<T, S>(scope, ptr.into(), value.into(), ordering, atomic_scope);
+        vec![out.read_value(scope)]
     }
 }
 
 #[cube]
-fn atomic_f_sub<T: Float, N: Size>(ptr: Atomic<Vector<T, N>>, value: Vector<T, N>) -> Vector<T, N> {
-    ptr.fetch_add(-value)
-}
-
-pub fn semantics_r(ctx: &Context, value: Value) -> MemorySemantics {
-    semantics_of(ctx, value) | MemorySemantics::ACQUIRE
+fn atomic_f_sub<T: Float, N: Size>(
+    ptr: Atomic<Vector<T, N>>,
+    value: Vector<T, N>,
+    #[comptime] ordering: MemoryOrdering,
+    #[comptime] atomic_scope: AtomicScope,
+) -> Vector<T, N> {
+    ptr.fetch_add_ordered(-value, ordering, atomic_scope)
 }
 
-pub fn semantics_w(ctx: &Context, value: Value) -> MemorySemantics {
-    semantics_of(ctx, value) | MemorySemantics::RELEASE
+/// Semantics for an atomic with `ordering` on the memory behind `ptr`. Relaxed atomics can't carry
+/// storage class bits, and the Vulkan memory model has no sequentially consistent semantics, so
+/// those are lowered as acquire-release. Unordered atomics keep the semantics they always had,
+/// acquire for loads, release for stores and both for read-modify-writes, which the callers
+/// resolve [`MemoryOrdering::BackendDefault`] to.
+fn semantics(ctx: &Context, ordering: MemoryOrdering, ptr: Value) -> MemorySemantics {
+    let order = match ordering {
+        MemoryOrdering::BackendDefault => unreachable!("Resolved by the caller"),
+        MemoryOrdering::Relaxed => return MemorySemantics::NONE,
+        MemoryOrdering::Acquire => MemorySemantics::ACQUIRE,
+        MemoryOrdering::Release => MemorySemantics::RELEASE,
+        MemoryOrdering::AcqRel | MemoryOrdering::SeqCst => MemorySemantics::ACQUIRE_RELEASE,
+    };
+    semantics_of(ctx, ptr) | order
 }
 
-pub fn semantics_rw(ctx: &Context, value: Value) -> MemorySemantics {
-    semantics_of(ctx, value) | MemorySemantics::ACQUIRE_RELEASE
+pub fn semantics_r(ctx: &Context, value: Value) -> MemorySemantics {
+    semantics_of(ctx, value) | MemorySemantics::ACQUIRE
 }
 
 fn semantics_of(ctx: &Context, value: Value) -> MemorySemantics {
@@ -272,6 +296,20 @@ fn semantics_of(ctx: &Context, value: Value) -> MemorySemantics {
     }
 }
 
+/// Scope of an atomic, narrowed to the memory it accesses. Vulkan has no cross-device scope, so
+/// system scope is the same as device scope.
+fn atomic_scope(ctx: &Context, scope: AtomicScope, ptr: Value) -> Scope {
+    let scope = match scope {
+        AtomicScope::Plane => Scope::Subgroup,
+        AtomicScope::Cube => Scope::Workgroup,
+        AtomicScope::Device | AtomicScope::System => Scope::Device,
+    };
+    match (scope, ptr_scope(ctx, ptr)) {
+        (Scope::Device, Scope::Workgroup) => Scope::Workgroup,
+        (scope, _) => scope,
+    }
+}
+
 fn ptr_scope(ctx: &Context, value: Value) -> Scope {
     let ty = value.get_type(ctx).deref(ctx);
     if let Some(ptr_ty) = ty.downcast_ref::<PointerType>() {
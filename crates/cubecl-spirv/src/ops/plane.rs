@@ -2,9 +2,10 @@ use cubecl_core::{self as cubecl, prelude::*};
 use cubecl_ir::{dialect::plane, interfaces::TypedExt, prelude::*, types::scalar::BoolType};
 use pliron::builtin::ops::ConstantOp;
 use pliron_spirv::ops::{self, ControlBarrierOp};
-use rspirv::spirv::{GroupOperation, MemoryAccess, MemorySemantics, Scope};
+use rspirv::spirv::{Capability, GroupOperation, MemoryAccess, MemorySemantics, Scope};
 
 use crate::{
+    CustomCapabilitiesOp,
     lower::LowerOp,
     ops::{atomic::semantics_r, to_spirv_dialect::ToSpirvDialectOp},
     types::ty_to_spirv_dialect,
@@ -98,6 +99,52 @@ macro_rules! plane_reduce_op_to_spirv_dialect {
     };
 }
 
+macro_rules! plane_clustered_op_to_spirv_dialect {
+    ($ty: ty => $new_ty: ty) => {
+        #[op_interface_impl]
+        impl ToSpirvDialectOp for $ty {
+            fn to_spirv_dialect(
+                &self,
+                ctx: &mut Context,
+                rewriter: &mut DialectConversionRewriter,
+                _operands_info: &OperandsInfo,
+            ) -> Result<()> {
+                let op = self.get_operation();
+                let inp = op.operand(ctx, 0);
+                let (action, cluster_size) = match self.cluster_size(ctx).map(|size| *size) {
+                    Some(size) => {
+                        let size_const = ConstantOp::new(ctx, size.into());
+                        rewriter.append_op(ctx, &size_const);
+                        let size = size_const.get_result(ctx);
+                        (GroupOperation::ClusteredReduce, Some(size))
+                    }
+                    None => (GroupOperation::Reduce, None),
+                };
+                let out_ty = ty_to_spirv_dialect(ctx, self.result_type(ctx));
+                let new_op =
+                    <$new_ty>::new(ctx, out_ty, Scope::Subgroup, action, inp, cluster_size);
+                rewriter.append_op(ctx, &new_op);
+                rewriter.replace_operation(ctx, op, new_op.get_operation());
+
+                Ok(())
+            }
+        }
+
+        // Any of several capabilities enables the op, so the clustered one can't be inferred.
+        // The cluster size is the only operand after the value.
+        #[op_interface_impl]
+        impl CustomCapabilitiesOp for $new_ty {
+            fn custom_capabilities(&self, ctx: &Context) -> Vec<Capability> {
+                if self.get_operation().deref(ctx).get_num_operands() > 1 {
+                    vec![Capability::GroupNonUniformClustered]
+                } else {
+                    vec![]
+                }
+            }
+        }
+    };
+}
+
 plane_binop_to_spirv_dialect!(plane::ShuffleOp => ops::GroupNonUniformShuffleOp);
 plane_binop_to_spirv_dialect!(plane::ShuffleXorOp => ops::GroupNonUniformShuffleXorOp);
 plane_binop_to_spirv_dialect!(plane::ShuffleUpOp => ops::GroupNonUniformShuffleUpOp);
@@ -107,27 +154,27 @@ plane_unop_to_spirv_dialect!(plane::AllOp => ops::GroupNonUniformAllOp);
 plane_unop_to_spirv_dialect!(plane::AnyOp => ops::GroupNonUniformAnyOp);
 plane_unop_to_spirv_dialect!(plane::BallotOp => ops::GroupNonUniformBallotOp);
 
-plane_reduce_op_to_spirv_dialect!(plane::ISumOp => ops::GroupNonUniformIAddOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::FSumOp => ops::GroupNonUniformFAddOp, GroupOperation::Reduce);
+plane_clustered_op_to_spirv_dialect!(plane::ISumOp => ops::GroupNonUniformIAddOp);
+plane_clustered_op_to_spirv_dialect!(plane::FSumOp => ops::GroupNonUniformFAddOp);
 plane_reduce_op_to_spirv_dialect!(plane::InclusiveISumOp => ops::GroupNonUniformIAddOp, GroupOperation::InclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::InclusiveFSumOp => ops::GroupNonUniformFAddOp, GroupOperation::InclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::ExclusiveISumOp => ops::GroupNonUniformIAddOp, GroupOperation::ExclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::ExclusiveFSumOp => ops::GroupNonUniformFAddOp, GroupOperation::ExclusiveScan);
 
-plane_reduce_op_to_spirv_dialect!(plane::IProdOp => ops::GroupNonUniformIMulOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::FProdOp => ops::GroupNonUniformFMulOp, GroupOperation::Reduce);
+plane_clustered_op_to_spirv_dialect!(plane::IProdOp => ops::GroupNonUniformIMulOp);
+plane_clustered_op_to_spirv_dialect!(plane::FProdOp => ops::GroupNonUniformFMulOp);
 plane_reduce_op_to_spirv_dialect!(plane::InclusiveIProdOp => ops::GroupNonUniformIMulOp, GroupOperation::InclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::InclusiveFProdOp => ops::GroupNonUniformFMulOp, GroupOperation::InclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::ExclusiveIProdOp => ops::GroupNonUniformIMulOp, GroupOperation::ExclusiveScan);
 plane_reduce_op_to_spirv_dialect!(plane::ExclusiveFProdOp => ops::GroupNonUniformFMulOp, GroupOperation::ExclusiveScan);
 
-plane_reduce_op_to_spirv_dialect!(plane::SMinOp => ops::GroupNonUniformSMinOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::UMinOp => ops::GroupNonUniformUMinOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::FMinOp => ops::GroupNonUniformFMinOp, GroupOperation::Reduce);
+plane_clustered_op_to_spirv_dialect!(plane::SMinOp => ops::GroupNonUniformSMinOp);
+plane_clustered_op_to_spirv_dialect!(plane::UMinOp => ops::GroupNonUniformUMinOp);
+plane_clustered_op_to_spirv_dialect!(plane::FMinOp => ops::GroupNonUniformFMinOp);
 
-plane_reduce_op_to_spirv_dialect!(plane::SMaxOp => ops::GroupNonUniformSMaxOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::UMaxOp => ops::GroupNonUniformUMaxOp, GroupOperation::Reduce);
-plane_reduce_op_to_spirv_dialect!(plane::FMaxOp => ops::GroupNonUniformFMaxOp, GroupOperation::Reduce);
+plane_clustered_op_to_spirv_dialect!(plane::SMaxOp => ops::GroupNonUniformSMaxOp);
+plane_clustered_op_to_spirv_dialect!(plane::UMaxOp => ops::GroupNonUniformUMaxOp);
+plane_clustered_op_to_spirv_dialect!(plane::FMaxOp => ops::GroupNonUniformFMaxOp);
 
 #[op_interface_impl]
 impl ToSpirvDialectOp for plane::BroadcastOp {
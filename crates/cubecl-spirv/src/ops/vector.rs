@@ -1,11 +1,14 @@
 use cubecl_core::{self as cubecl, define_scalar, define_size, num_traits::One, prelude::*};
 use cubecl_ir::{dialect::vector, interfaces::TypedExt, prelude::*};
+use pliron::builtin::types::IntegerType;
 use pliron_spirv::{
     ext::gl,
     ops::{self, CompositeConstructOp, CompositeExtractOp, CompositeInsertOp},
 };
+use rspirv::spirv::{Capability, PackedVectorFormat};
 
 use crate::{
+    CustomCapabilitiesOp,
     lower::lower_unop,
     ops::{
         base::{binop_to_spirv_dialect, unop_to_spirv_dialect},
@@ -139,6 +142,23 @@ unop_to_spirv_dialect!(vector::NormalizeOp => gl::NormalizeOp);
 binop_to_spirv_dialect!(vector::SDotOp => ops::SDotOp, None);
 binop_to_spirv_dialect!(vector::UDotOp => ops::UDotOp, None);
 binop_to_spirv_dialect!(vector::FDotOp => ops::DotOp);
+binop_to_spirv_dialect!(
+    vector::SDot4x8PackedOp => ops::SDotOp,
+    Some(PackedVectorFormat::PackedVectorFormat4x8Bit)
+);
+
+// Packed operands are scalar words, which the vector input capabilities don't cover.
+#[op_interface_impl]
+impl CustomCapabilitiesOp for ops::SDotOp {
+    fn custom_capabilities(&self, ctx: &Context) -> Vec<Capability> {
+        let lhs = self.get_operation().operand(ctx, 0);
+        if lhs.get_type(ctx).deref(ctx).is::<IntegerType>() {
+            vec![Capability::DotProductInput4x8BitPacked]
+        } else {
+            vec![]
+        }
+    }
+}
 
 lower_unop!(vector::ISumOp, i_vector_sum);
 lower_unop!(vector::FSumOp, f_vector_sum);
@@ -22,5 +22,8 @@ pub mod event;
 /// Throughput utilities.
 pub mod throughput;
 
+#[cfg(feature = "safetensors")]
+pub mod safetensors;
+
 #[cfg(feature = "export_tests")]
 pub mod tests;
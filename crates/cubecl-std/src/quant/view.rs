@@ -514,7 +514,11 @@ pub fn run_with_quant_type<F: RunWithQuantType>(func: F, scheme: QuantScheme) ->
             }
         },
         QuantStore::PackedU32(_) => run_with_q::<F, u32>,
-        QuantStore::PackedNative(_) => run_with_q::<F, e2m1x2>,
+        QuantStore::PackedNative(_) => match scheme.value {
+            QuantValue::E2M1 => run_with_q::<F, e2m1x2>,
+            QuantValue::Q4F | QuantValue::Q4S => run_with_q::<F, i4x2>,
+            other => panic!("{other:?} has no native packed type"),
+        },
     };
     run_q(func, scheme)
 }
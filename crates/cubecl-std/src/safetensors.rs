@@ -0,0 +1,473 @@
+//! Loading and saving tensors in the [safetensors](https://github.com/huggingface/safetensors)
+//! format.
+//!
+//! A file is an 8-byte little-endian header length, a JSON header describing every tensor
+//! (dtype, shape, and byte range) and the raw tensor data. [`SafetensorsFile::open`] only
+//! reads the header: the data of each tensor is handed to the device as file-backed
+//! [`Bytes`], which read their range from disk when the upload needs it, so loading a
+//! subset of the tensors never touches the rest of the file.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use cubecl_common::bytes::Bytes;
+use cubecl_core::{
+    Runtime,
+    ir::{ElemType, FloatKind, IntKind, UIntKind},
+    prelude::ComputeClient,
+    server::MemoryLayout,
+    zspace::Shape,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::tensor::{TensorHandle, into_contiguous, is_contiguous};
+
+/// The key of the free-form string metadata in the header.
+const METADATA_KEY: &str = "__metadata__";
+/// Headers larger than this are rejected rather than allocated.
+const MAX_HEADER_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Error from reading or writing a safetensors file.
+#[derive(Debug, thiserror::Error)]
+pub enum SafetensorsError {
+    /// The file couldn't be read or written.
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    /// The header isn't valid safetensors.
+    #[error("invalid safetensors header: {0}")]
+    InvalidHeader(String),
+    /// The dtype of a tensor has no [`ElemType`] counterpart.
+    #[error("tensor `{name}` has unsupported dtype {dtype}")]
+    UnsupportedDtype {
+        /// The name of the tensor.
+        name: String,
+        /// The dtype, as written in the header or as an [`ElemType`] when saving.
+        dtype: String,
+    },
+    /// No tensor of that name is in the file.
+    #[error("no tensor named `{0}`")]
+    MissingTensor(String),
+}
+
+/// A tensor described in the header of a safetensors file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TensorInfo {
+    /// The name of the tensor.
+    pub name: String,
+    /// The element type of the tensor.
+    pub dtype: ElemType,
+    /// The shape of the tensor.
+    pub shape: Shape,
+    /// The byte offset of the tensor data from the start of the file.
+    pub offset: u64,
+    /// The size of the tensor data in bytes.
+    pub size: u64,
+}
+
+/// An open safetensors file, with its header parsed and its data left on disk.
+#[derive(Clone, Debug)]
+pub struct SafetensorsFile {
+    path: PathBuf,
+    tensors: Vec<TensorInfo>,
+    metadata: BTreeMap<String, String>,
+}
+
+impl SafetensorsFile {
+    /// Read the header of the safetensors file at `path`.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SafetensorsError> {
+        let path = path.into();
+        let mut file = File::open(&path)?;
+        let file_size = file.metadata()?.len();
+        let (tensors, metadata) = read_header(&mut file, file_size)?;
+
+        Ok(Self {
+            path,
+            tensors,
+            metadata,
+        })
+    }
+
+    /// The tensors in the file, in the order of their data.
+    pub fn tensors(&self) -> &[TensorInfo] {
+        &self.tensors
+    }
+
+    /// The tensor named `name`, if the file has one.
+    pub fn tensor(&self, name: &str) -> Option<&TensorInfo> {
+        self.tensors.iter().find(|tensor| tensor.name == name)
+    }
+
+    /// The free-form string metadata of the header.
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// The data of `tensor` as file-backed [`Bytes`], read from disk on first access.
+    pub fn bytes(&self, tensor: &TensorInfo) -> Bytes {
+        Bytes::from_file(&self.path, tensor.size, tensor.offset)
+    }
+
+    /// Create the tensor named `name` on the device of `client`.
+    pub fn load<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+        name: &str,
+    ) -> Result<TensorHandle<R>, SafetensorsError> {
+        let tensor = self
+            .tensor(name)
+            .ok_or_else(|| SafetensorsError::MissingTensor(name.into()))?;
+
+        Ok(self.create(client, tensor))
+    }
+
+    /// Create every tensor of the file on the device of `client`, by name.
+    pub fn load_all<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+    ) -> Vec<(String, TensorHandle<R>)> {
+        self.tensors
+            .iter()
+            .map(|tensor| (tensor.name.clone(), self.create(client, tensor)))
+            .collect()
+    }
+
+    fn create<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+        tensor: &TensorInfo,
+    ) -> TensorHandle<R> {
+        let MemoryLayout { memory, strides } = client.create_tensor(
+            self.bytes(tensor),
+            tensor.shape.clone(),
+            tensor.dtype.size(),
+        );
+
+        TensorHandle::new(memory, tensor.shape.clone(), strides, tensor.dtype)
+    }
+}
+
+/// Write `tensors` to a safetensors file at `path`, with the given header `metadata`.
+///
+/// Each tensor is read from the device lazily, as it is written, so the host only ever holds
+/// one of them at a time. A tensor that isn't contiguous is copied into a contiguous one on the
+/// device first, since safetensors stores the data in row-major order.
+#[cfg(not(target_family = "wasm"))]
+pub fn save<R: Runtime>(
+    client: &ComputeClient<R>,
+    tensors: &[(&str, &TensorHandle<R>)],
+    metadata: &BTreeMap<String, String>,
+    path: impl AsRef<Path>,
+) -> Result<(), SafetensorsError> {
+    let mut offset = 0u64;
+    let infos = tensors
+        .iter()
+        .map(|(name, tensor)| {
+            let size = data_size(tensor.shape(), tensor.dtype).ok_or_else(|| {
+                SafetensorsError::InvalidHeader(format!("size of `{name}` overflows"))
+            })?;
+            let info = TensorInfo {
+                name: (*name).into(),
+                dtype: tensor.dtype,
+                shape: tensor.shape().clone(),
+                offset,
+                size,
+            };
+            offset = offset.checked_add(size).ok_or_else(|| {
+                SafetensorsError::InvalidHeader(format!("offset of `{name}` overflows"))
+            })?;
+            Ok(info)
+        })
+        .collect::<Result<Vec<_>, SafetensorsError>>()?;
+
+    let mut file = BufWriter::new(File::create(path)?);
+    write_header(&mut file, &infos, metadata)?;
+
+    for (_, tensor) in tensors {
+        let tensor = if is_contiguous(tensor.shape(), tensor.strides()) {
+            (*tensor).clone()
+        } else {
+            into_contiguous(client, (*tensor).clone().binding(), tensor.dtype)
+        };
+        let bytes = client.read_lazy(tensor.into_copy_descriptor());
+        file.write_all(&bytes)?;
+    }
+    file.flush()?;
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct HeaderEntry {
+    dtype: String,
+    shape: Vec<usize>,
+    data_offsets: [u64; 2],
+}
+
+type Header = (Vec<TensorInfo>, BTreeMap<String, String>);
+
+/// The size in bytes of the data of a tensor, `None` if it doesn't fit a `u64`.
+fn data_size(shape: &[usize], dtype: ElemType) -> Option<u64> {
+    shape.iter().try_fold(dtype.size() as u64, |size, &dim| {
+        size.checked_mul(dim as u64)
+    })
+}
+
+/// Read the header of a file of `file_size` bytes. Offsets are made absolute and the
+/// tensors sorted by offset.
+///
+/// The header is untrusted input: every size and offset is checked for overflow, and the data
+/// of two tensors may not overlap.
+fn read_header(reader: &mut impl Read, file_size: u64) -> Result<Header, SafetensorsError> {
+    let invalid = |reason: &str| SafetensorsError::InvalidHeader(reason.into());
+
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > MAX_HEADER_SIZE || len + 8 > file_size {
+        return Err(invalid("header length out of range"));
+    }
+
+    let mut header = alloc::vec![0u8; len as usize];
+    reader.read_exact(&mut header)?;
+    let mut entries: BTreeMap<String, serde_json::Value> =
+        serde_json::from_slice(&header).map_err(|err| invalid(&err.to_string()))?;
+
+    let metadata = match entries.remove(METADATA_KEY) {
+        Some(metadata) => {
+            serde_json::from_value(metadata).map_err(|err| invalid(&err.to_string()))?
+        }
+        None => BTreeMap::new(),
+    };
+
+    let data_start = 8 + len;
+    let mut tensors = entries
+        .into_iter()
+        .map(|(name, entry)| {
+            let entry: HeaderEntry =
+                serde_json::from_value(entry).map_err(|err| invalid(&err.to_string()))?;
+            let dtype =
+                elem_type(&entry.dtype).ok_or_else(|| SafetensorsError::UnsupportedDtype {
+                    name: name.clone(),
+                    dtype: entry.dtype.clone(),
+                })?;
+
+            let out_of_range = || invalid(&format!("data offsets of `{name}` out of range"));
+            let [begin, end] = entry.data_offsets;
+            let size = data_size(&entry.shape, dtype).ok_or_else(out_of_range)?;
+            let data_end = data_start.checked_add(end).ok_or_else(out_of_range)?;
+            if end < begin || end - begin != size || data_end > file_size {
+                return Err(out_of_range());
+            }
+
+            Ok(TensorInfo {
+                name,
+                dtype,
+                shape: entry.shape.into(),
+                offset: data_start + begin,
+                size,
+            })
+        })
+        .collect::<Result<Vec<_>, SafetensorsError>>()?;
+    tensors.sort_by_key(|tensor| tensor.offset);
+
+    // Sorted, so overlapping data shows up between neighbors.
+    if let Some(pair) = tensors
+        .windows(2)
+        .find(|pair| pair[0].offset + pair[0].size > pair[1].offset)
+    {
+        return Err(invalid(&format!(
+            "data of `{}` overlaps `{}`",
+            pair[0].name, pair[1].name
+        )));
+    }
+
+    Ok((tensors, metadata))
+}
+
+/// Write the header for `tensors`, whose offsets are relative to the start of the data.
+fn write_header(
+    writer: &mut impl Write,
+    tensors: &[TensorInfo],
+    metadata: &BTreeMap<String, String>,
+) -> Result<(), SafetensorsError> {
+    let mut entries = serde_json::Map::new();
+    if !metadata.is_empty() {
+        entries.insert(METADATA_KEY.into(), serde_json::to_value(metadata).unwrap());
+    }
+    for tensor in tensors {
+        let dtype = dtype(tensor.dtype).ok_or_else(|| SafetensorsError::UnsupportedDtype {
+            name: tensor.name.clone(),
+            dtype: tensor.dtype.to_string(),
+        })?;
+        let entry = HeaderEntry {
+            dtype: dtype.into(),
+            shape: tensor.shape.to_vec(),
+            data_offsets: [tensor.offset, tensor.offset + tensor.size],
+        };
+        entries.insert(tensor.name.clone(), serde_json::to_value(entry).unwrap());
+    }
+
+    let mut header = serde_json::to_vec(&entries).unwrap();
+    // The data starts 8-byte aligned, padded with spaces like the reference writer.
+    header.resize(header.len().next_multiple_of(8), b' ');
+
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(&header)?;
+    Ok(())
+}
+
+/// The [`ElemType`] of a safetensors dtype.
+///
+/// The sub-byte `F4` and `F6` dtypes are bit-packed in safetensors, while CubeCL stores
+/// `e2m3`, `e3m2` and `e2m1` one per byte (or two per byte for `e2m1x2`), so they aren't
+/// mapped.
+pub fn elem_type(dtype: &str) -> Option<ElemType> {
+    let elem = match dtype {
+        "BOOL" => ElemType::Bool,
+        "U8" => ElemType::UInt(UIntKind::U8),
+        "U16" => ElemType::UInt(UIntKind::U16),
+        "U32" => ElemType::UInt(UIntKind::U32),
+        "U64" => ElemType::UInt(UIntKind::U64),
+        "I8" => ElemType::Int(IntKind::I8),
+        "I16" => ElemType::Int(IntKind::I16),
+        "I32" => ElemType::Int(IntKind::I32),
+        "I64" => ElemType::Int(IntKind::I64),
+        "F8_E4M3" => ElemType::Float(FloatKind::E4M3),
+        "F8_E5M2" => ElemType::Float(FloatKind::E5M2),
+        "F8_E8M0" => ElemType::Float(FloatKind::UE8M0),
+        "F16" => ElemType::Float(FloatKind::F16),
+        "BF16" => ElemType::Float(FloatKind::BF16),
+        "F32" => ElemType::Float(FloatKind::F32),
+        "F64" => ElemType::Float(FloatKind::F64),
+        _ => return None,
+    };
+    Some(elem)
+}
+
+/// The safetensors dtype of an [`ElemType`], the reverse of [`elem_type`].
+///
+/// `flex32` and `tf32` are stored as 32-bit floats, so they are written as `F32`.
+pub fn dtype(elem: ElemType) -> Option<&'static str> {
+    let dtype = match elem {
+        ElemType::Bool => "BOOL",
+        ElemType::UInt(UIntKind::U8) => "U8",
+        ElemType::UInt(UIntKind::U16) => "U16",
+        ElemType::UInt(UIntKind::U32) => "U32",
+        ElemType::UInt(UIntKind::U64) => "U64",
+        ElemType::Int(IntKind::I8) => "I8",
+        ElemType::Int(IntKind::I16) => "I16",
+        ElemType::Int(IntKind::I32) => "I32",
+        ElemType::Int(IntKind::I64) => "I64",
+        ElemType::Float(FloatKind::E4M3) => "F8_E4M3",
+        ElemType::Float(FloatKind::E5M2) => "F8_E5M2",
+        ElemType::Float(FloatKind::UE8M0) => "F8_E8M0",
+        ElemType::Float(FloatKind::F16) => "F16",
+        ElemType::Float(FloatKind::BF16) => "BF16",
+        ElemType::Float(FloatKind::F32 | FloatKind::Flex32 | FloatKind::TF32) => "F32",
+        ElemType::Float(FloatKind::F64) => "F64",
+        _ => return None,
+    };
+    Some(dtype)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tensor(name: &str, dtype: ElemType, shape: &[usize], offset: u64) -> TensorInfo {
+        TensorInfo {
+            name: name.into(),
+            dtype,
+            shape: shape.into(),
+            offset,
+            size: (shape.iter().product::<usize>() * dtype.size()) as u64,
+        }
+    }
+
+    #[test]
+    fn header_round_trips() {
+        let tensors = [
+            tensor("a", ElemType::Float(FloatKind::BF16), &[2, 3], 0),
+            tensor("b", ElemType::Float(FloatKind::E4M3), &[5], 12),
+        ];
+        let metadata = BTreeMap::from([("format".into(), "pt".into())]);
+
+        let mut file = Vec::new();
+        write_header(&mut file, &tensors, &metadata).unwrap();
+        let data_start = file.len() as u64;
+        assert_eq!(data_start % 8, 0);
+        file.resize(file.len() + 17, 0);
+
+        let (read, read_metadata) = read_header(&mut file.as_slice(), file.len() as u64).unwrap();
+
+        assert_eq!(read_metadata, metadata);
+        assert_eq!(read.len(), 2);
+        for (read, written) in read.iter().zip(&tensors) {
+            assert_eq!(read.offset, written.offset + data_start);
+            assert_eq!(
+                (&read.name, read.dtype, &read.shape),
+                (&written.name, written.dtype, &written.shape)
+            );
+        }
+    }
+
+    #[test]
+    fn data_past_the_end_is_rejected() {
+        let tensors = [tensor("a", ElemType::Float(FloatKind::F32), &[4], 0)];
+
+        let mut file = Vec::new();
+        write_header(&mut file, &tensors, &BTreeMap::new()).unwrap();
+        file.resize(file.len() + 8, 0);
+
+        assert!(matches!(
+            read_header(&mut file.as_slice(), file.len() as u64),
+            Err(SafetensorsError::InvalidHeader(_))
+        ));
+    }
+
+    /// A file with a hand-written `header`, followed by `data_len` bytes of data.
+    fn raw_file(header: &str, data_len: usize) -> Vec<u8> {
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend_from_slice(header.as_bytes());
+        file.resize(file.len() + data_len, 0);
+        file
+    }
+
+    fn assert_invalid(file: &[u8]) {
+        assert!(matches!(
+            read_header(&mut &file[..], file.len() as u64),
+            Err(SafetensorsError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn overflowing_sizes_are_rejected() {
+        let huge = u64::MAX;
+        assert_invalid(&raw_file(
+            &format!(r#"{{"a":{{"dtype":"F32","shape":[{huge},16],"data_offsets":[0,0]}}}}"#),
+            0,
+        ));
+        assert_invalid(&raw_file(
+            &format!(r#"{{"a":{{"dtype":"U8","shape":[{huge}],"data_offsets":[0,{huge}]}}}}"#),
+            0,
+        ));
+    }
+
+    #[test]
+    fn overlapping_data_is_rejected() {
+        assert_invalid(&raw_file(
+            r#"{"a":{"dtype":"F32","shape":[4],"data_offsets":[0,16]},"b":{"dtype":"F32","shape":[4],"data_offsets":[8,24]}}"#,
+            24,
+        ));
+    }
+
+    #[test]
+    fn packed_dtypes_are_unsupported() {
+        assert_eq!(elem_type("F4"), None);
+        assert_eq!(elem_type("F6_E2M3"), None);
+        assert_eq!(dtype(ElemType::Float(FloatKind::E2M1x2)), None);
+    }
+}
@@ -4,6 +4,8 @@ pub use test_log;
 pub mod event;
 pub mod reinterpret_slice;
 pub mod round;
+#[cfg(feature = "safetensors")]
+pub mod safetensors;
 pub mod tensor;
 pub mod trigonometry;
 pub mod view;
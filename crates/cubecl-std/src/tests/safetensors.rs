@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::safetensors::{SafetensorsFile, save};
+use crate::tensor::TensorHandle;
+
+/// Save a contiguous tensor and a transposed view of the same buffer, then load both back and
+/// compare their data with what was saved.
+pub fn test_safetensors_round_trip<R: Runtime>(client: ComputeClient<R>) {
+    let dtype = f32::cube_type();
+    let data: Vec<f32> = (0..6).map(|i| i as f32).collect();
+    let handle = client.create_from_slice(f32::as_bytes(&data));
+    let tensor = TensorHandle::<R>::new_contiguous(vec![2, 3], handle.clone(), dtype);
+    // Not contiguous, so it has to be copied before it can be written.
+    let transposed = TensorHandle::<R>::new(handle, vec![3, 2], vec![1, 3], dtype);
+
+    let path = std::env::temp_dir().join(format!(
+        "cubecl-safetensors-{}.safetensors",
+        std::process::id()
+    ));
+    let metadata = BTreeMap::from([("format".into(), "pt".into())]);
+    save(
+        &client,
+        &[("a", &tensor), ("a_t", &transposed)],
+        &metadata,
+        &path,
+    )
+    .unwrap();
+
+    let file = SafetensorsFile::open(&path).unwrap();
+    assert_eq!(file.metadata(), &metadata);
+    let read = |name: &str| {
+        let tensor = file.load(&client, name).unwrap();
+        assert_eq!(tensor.dtype, dtype);
+        let bytes = client.read_one_unchecked_tensor(tensor.into_copy_descriptor());
+        f32::from_bytes(&bytes).to_vec()
+    };
+    let loaded = read("a");
+    let loaded_t = read("a_t");
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, data);
+    assert_eq!(loaded_t, [0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+}
+
+#[macro_export]
+macro_rules! testgen_safetensors {
+    () => {
+        mod safetensors {
+            use super::*;
+
+            #[$crate::tests::test_log::test]
+            fn test_round_trip() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_std::tests::safetensors::test_safetensors_round_trip::<TestRuntime>(client);
+            }
+        }
+    };
+}
@@ -1,7 +1,7 @@
 use cubecl::prelude::*;
 use cubecl_common::{
-    e2m1, e2m1x2,
-    quant::scheme::{QuantMode, QuantScheme, QuantValue, ScaleDtype},
+    e2m1, e2m1x2, i4x2,
+    quant::scheme::{QuantMode, QuantScheme, QuantStore, QuantValue, ScaleDtype},
 };
 use cubecl_core::ir::{ElemType, FloatKind};
 use cubecl_core::{self as cubecl};
@@ -203,6 +203,61 @@ pub fn test_quantized_per_tensor_fp4<R: Runtime, F: Float + CubeElement>(
     assert_eq!(&actual_float, &float_data);
 }
 
+/// Same values as [`test_quantized_per_tensor_int`], but stored natively as `i4x2` instead of
+/// packed into `u32`.
+pub fn test_quantized_per_tensor_packed_int<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R>,
+    vector_size_values: VectorSize,
+) {
+    if !client.properties().supports_type(i4x2::cube_type()) {
+        return;
+    }
+
+    let vector_size_float = 2 * vector_size_values;
+
+    let scheme = QuantScheme::default()
+        .with_value(QuantValue::Q4F)
+        .with_store(QuantStore::PackedNative(0));
+    let float_data = (-8..=7)
+        .map(|it| F::new(it as f32 * 3.4))
+        .collect::<Vec<_>>();
+
+    let output = client.empty(16 * size_of::<F>());
+    let packed = i4x2::from_slice(&(-8..=7).collect::<Vec<i8>>());
+    let values = client.create_from_slice(i4x2::as_bytes(&packed));
+    let scales = client.create_from_slice(f32::as_bytes(&[3.4]));
+
+    let scales_layout = TestPerTensorScaleLayoutLaunch::new(16);
+
+    let values_view = ViewArg::new_array::<PlainLayout>(
+        unsafe { BufferArg::from_raw_parts(values, packed.len()) },
+        (),
+    );
+    let scales_view = ViewArg::new_array::<TestPerTensorScaleLayout>(
+        unsafe { BufferArg::from_raw_parts(scales, 1) },
+        scales_layout,
+    );
+    let quantized_view =
+        ViewArg::new_quantized(values_view, ScaleBindings::one(scales_view), scheme);
+
+    unsafe {
+        kernel_quantized_view::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::new_single(),
+            CubeDim::new_1d(8),
+            vector_size_float,
+            quantized_view,
+            BufferArg::from_raw_parts(output.clone(), 16),
+            ReadMode::Read,
+        );
+    }
+
+    let actual = client.read_one_unchecked(output);
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(&actual, &float_data);
+}
+
 /// A view built in cube code with the global level's scale already in a register: block scales
 /// are still read per position, the register multiplies in.
 #[cube(launch_unchecked)]
@@ -509,6 +564,19 @@ macro_rules! testgen_quantized_view {
             );
         }
 
+        #[$crate::tests::test_log::test]
+        fn test_quantized_view_per_tensor_packed_int() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_std::tests::view::quantized::test_quantized_per_tensor_packed_int::<
+                TestRuntime,
+                $ty,
+            >(client.clone(), 1);
+            cubecl_std::tests::view::quantized::test_quantized_per_tensor_packed_int::<
+                TestRuntime,
+                $ty,
+            >(client, 4);
+        }
+
         #[$crate::tests::test_log::test]
         fn test_quantized_view_per_tensor_fp4() {
             let client = TestRuntime::client(&Default::default());
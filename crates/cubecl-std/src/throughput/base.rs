@@ -1,17 +1,18 @@
-use cubecl_core::ir::ElemType;
+use cubecl_core::ir::{ElemType, IntKind};
 use cubecl_runtime::{
     client::ComputeClient,
     runtime::Runtime,
     server::CubeDim,
     throughput::{
-        DEFAULT_BUFFER_BYTES, MemoryAccess, MemoryCurve, MemoryPoint, ThroughputKey,
-        ThroughputMode, ThroughputValue, working_set_sweep,
+        MemoryAccess, MemoryCurve, MemoryPoint, ThroughputKey, ThroughputMode, ThroughputValue,
+        working_set_sweep,
     },
     tune::{Bounds, Thresholds, Work, calculate_bounds},
 };
 
 use crate::throughput::{
-    compute_cmma, compute_direct, launch_overhead, memory_direct, memory_read,
+    compute_cmma, compute_direct, compute_dot, compute_special, launch_overhead, memory_direct,
+    memory_read,
 };
 
 /// Measure peak throughput on `device` for each of the given `keys`.
@@ -54,12 +55,9 @@ pub fn measure_memory_curve<R: Runtime>(
     MemoryCurve::new(access, points)
 }
 
-/// The largest working set `access` can be probed at: as much as one buffer can
-/// hold, times the buffers the access touches.
+/// The largest working set `access` can be probed at on the device of `client`.
 fn working_set_cap<R: Runtime>(client: &ComputeClient<R>, access: MemoryAccess) -> u64 {
-    let max_alloc = client.properties().memory.max_page_size;
-
-    DEFAULT_BUFFER_BYTES.min(max_alloc) * access.buffers()
+    access.working_set_cap(client.properties().memory.max_page_size)
 }
 
 /// Computes the peak throughput for a given runtime and key.
@@ -90,6 +88,32 @@ pub fn measure_peak_throughput<R: Runtime>(
             }
             compute_cmma::build_kernel(client, key, cmma_config, launch_config)
         }
+        ThroughputMode::ComputeDot { packing } => {
+            if !client
+                .properties()
+                .supports_type(ElemType::Int(IntKind::I8))
+            {
+                return ThroughputValue::ZERO;
+            }
+            compute_dot::build_kernel(client, key, packing, launch_config)
+        }
+        ThroughputMode::ComputeVector { dtype, vector_size } => {
+            if !client.properties().supports_type(dtype) {
+                return ThroughputValue::ZERO;
+            }
+            // The direct kernel at the requested width rather than the IO-optimized one.
+            let launch_config = LaunchConfig {
+                vector_size,
+                ..launch_config
+            };
+            compute_direct::build_kernel(client, key, launch_config)
+        }
+        ThroughputMode::ComputeSpecial { dtype, function } => {
+            if !client.properties().supports_type(dtype) {
+                return ThroughputValue::ZERO;
+            }
+            compute_special::build_kernel(client, key, function, launch_config)
+        }
         ThroughputMode::Memory
         | ThroughputMode::MemoryRead
         | ThroughputMode::MemoryWorkingSet { .. } => {
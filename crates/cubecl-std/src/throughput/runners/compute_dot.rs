@@ -0,0 +1,83 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_runtime::throughput::{DotPacking, KernelConfig, ThroughputKey};
+
+use crate::throughput::LaunchConfig;
+
+pub fn build_kernel<R: Runtime>(
+    client: &ComputeClient<R>,
+    _key: ThroughputKey,
+    packing: DotPacking,
+    config: LaunchConfig,
+) -> KernelConfig {
+    let client = client.clone();
+
+    let sample = Box::new(move |iterations: usize| {
+        let start = cubecl_common::profile::Instant::now();
+        unsafe {
+            let out = client.empty(size_of::<u32>());
+
+            compute_dot_throughput::launch_unchecked(
+                &client,
+                CubeCount::Static(config.cube_count as u32, 1, 1),
+                CubeDim::new(&client, config.cube_dim),
+                BufferArg::from_raw_parts(out, 1),
+                iterations,
+                packing,
+            )
+        };
+        let _ = cubecl_core::future::block_on(client.sync());
+        start.elapsed()
+    });
+
+    // `CHAINS` independent chains per unit, each retiring one packed word of multiply-adds
+    // (two ops each) per step.
+    let ops_count = 2 * packing.macs_per_word() * CHAINS * config.cube_count * config.cube_dim;
+
+    KernelConfig { sample, ops_count }
+}
+
+/// Independent accumulator chains per unit to hide the dot product latency.
+const CHAINS: usize = 4;
+
+/// The operands are kept as packed 32-bit words and fed to the packed dot product as is, which
+/// accumulates in 32 bits like `dp4a`, so the loop only pays for the dot products and their
+/// accumulation.
+#[cube(launch_unchecked)]
+pub fn compute_dot_throughput(output: &mut [u32], n_iter: usize, #[comptime] packing: DotPacking) {
+    let tid = u32::cast_from(ABSOLUTE_POS);
+    let b = tid ^ 0x5A5A_5A5Au32;
+
+    let mut w0 = tid + 1u32;
+    let mut w1 = tid + 2u32;
+    let mut w2 = tid + 3u32;
+    let mut w3 = tid + 4u32;
+
+    for _ in 0..n_iter {
+        w0 = step(w0, b, packing);
+        w1 = step(w1, b, packing);
+        w2 = step(w2, b, packing);
+        w3 = step(w3, b, packing);
+    }
+
+    if ABSOLUTE_POS == 0 {
+        output[0] = w0 ^ w1 ^ w2 ^ w3;
+    }
+}
+
+/// Multiplies a packed word of lanes with `b` and accumulates the dot product into the word,
+/// chaining the next step on this one.
+#[cube]
+fn step(w: u32, b: u32, #[comptime] packing: DotPacking) -> u32 {
+    let acc = i32::cast_from(w);
+    let acc = match packing {
+        DotPacking::Int8x4 => acc + dot4_i8_packed(w, b),
+        DotPacking::Int4x8 => {
+            // Every other nibble lands in its own byte, as a value in 0..16.
+            let acc = acc + dot4_i8_packed(w & 0x0F0F_0F0Fu32, b & 0x0F0F_0F0Fu32);
+            acc + dot4_i8_packed((w >> 4u32) & 0x0F0F_0F0Fu32, (b >> 4u32) & 0x0F0F_0F0Fu32)
+        }
+    };
+
+    u32::cast_from(acc)
+}
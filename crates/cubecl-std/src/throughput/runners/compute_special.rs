@@ -0,0 +1,93 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_runtime::throughput::{KernelConfig, SpecialFunction, ThroughputKey};
+
+use crate::throughput::LaunchConfig;
+
+pub fn build_kernel<R: Runtime>(
+    client: &ComputeClient<R>,
+    key: ThroughputKey,
+    function: SpecialFunction,
+    config: LaunchConfig,
+) -> KernelConfig {
+    let client = client.clone();
+    let dtype = key.dtype();
+
+    let sample = Box::new(move |iterations: usize| {
+        let start = cubecl_common::profile::Instant::now();
+        unsafe {
+            let out = client.empty(config.vector_size * dtype.size());
+
+            compute_special_throughput::launch_unchecked(
+                &client,
+                CubeCount::Static(config.cube_count as u32, 1, 1),
+                CubeDim::new(&client, config.cube_dim),
+                config.vector_size,
+                BufferArg::from_raw_parts(out, 1),
+                iterations,
+                function,
+                dtype,
+            )
+        };
+        let _ = cubecl_core::future::block_on(client.sync());
+        start.elapsed()
+    });
+
+    // `CHAINS` independent chains per lane, each evaluating the function once per step.
+    let ops_count = CHAINS * config.cube_count * config.cube_dim * config.vector_size;
+
+    KernelConfig { sample, ops_count }
+}
+
+/// Independent chains per lane to hide the latency of the special function units.
+const CHAINS: usize = 4;
+
+#[cube(launch_unchecked)]
+pub fn compute_special_throughput<F: Float, N: Size>(
+    output: &mut [Vector<F, N>],
+    n_iter: usize,
+    #[comptime] function: SpecialFunction,
+    #[define(F)] _dtype: ElemType,
+) {
+    let tid = F::cast_from(ABSOLUTE_POS % 16);
+
+    let mut s0 = Vector::<F, N>::empty();
+    let mut s1 = Vector::<F, N>::empty();
+    let mut s2 = Vector::<F, N>::empty();
+    let mut s3 = Vector::<F, N>::empty();
+
+    // Positive seeds in (0, 1], distinct per lane and chain to prevent folding.
+    let lanes = s0.vector_size();
+    #[unroll]
+    for lane in 0..lanes {
+        let offset = (tid + F::cast_from(lane)) * F::new(0.01);
+        s0.insert(lane, F::new(0.2) + offset);
+        s1.insert(lane, F::new(0.4) + offset);
+        s2.insert(lane, F::new(0.6) + offset);
+        s3.insert(lane, F::new(0.8) + offset);
+    }
+
+    for _ in 0..n_iter {
+        s0 = step(s0, function);
+        s1 = step(s1, function);
+        s2 = step(s2, function);
+        s3 = step(s3, function);
+    }
+
+    let sum = s0 + s1 + s2 + s3;
+
+    if ABSOLUTE_POS == 0 {
+        output[0] = sum;
+    }
+}
+
+/// Evaluates the function once. Both iterations stay finite and positive from a positive
+/// seed, so no lane ends up on a special-cased infinity or NaN that could run faster than
+/// the real thing: `exp(-x)` settles around `0.567`, `1 / sqrt(x)` oscillates around `1`.
+#[cube]
+fn step<F: Float, N: Size>(s: Vector<F, N>, #[comptime] function: SpecialFunction) -> Vector<F, N> {
+    match function {
+        SpecialFunction::Exp => (-s).exp(),
+        SpecialFunction::InverseSqrt => s.inverse_sqrt(),
+    }
+}
@@ -1,5 +1,7 @@
 pub mod compute_cmma;
 pub mod compute_direct;
+pub mod compute_dot;
+pub mod compute_special;
 pub mod launch_overhead;
 pub mod memory_direct;
 pub mod memory_probe;
@@ -22,8 +22,8 @@ use tracel_ash::{
     nv::cooperative_matrix2,
     vk::{
         ComponentTypeKHR, DeviceCreateInfo, DeviceQueueCreateInfo,
-        PhysicalDeviceCooperativeMatrix2PropertiesNV, PhysicalDeviceProperties2, ScopeKHR, TRUE,
-        TaggedStructure,
+        PhysicalDeviceCooperativeMatrix2PropertiesNV, PhysicalDeviceProperties2, ScopeKHR,
+        SubgroupFeatureFlags, TRUE, TaggedStructure,
     },
 };
 use wgpu::{
@@ -377,6 +377,14 @@ fn register_features(
         props.hardware.max_vector_size = long_vector_properties.max_vector_components as usize;
     }
 
+    if let Some(subgroup) = &extended_feat.subgroup_properties
+        && subgroup
+            .supported_operations
+            .contains(SubgroupFeatureFlags::CLUSTERED)
+    {
+        comp_options.vulkan.supports_clustered_plane_ops = true;
+    }
+
     if let Some(maintenance_9) = &extended_feat.maintenance_9
         && maintenance_9.maintenance9 == TRUE
     {
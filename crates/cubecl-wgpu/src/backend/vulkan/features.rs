@@ -40,6 +40,7 @@ pub struct ExtendedFeatures<'a> {
     pub nv_cooperative_matrix2: Option<PhysicalDeviceCooperativeMatrix2FeaturesNV<'a>>,
 
     // Properties
+    pub subgroup_properties: Option<PhysicalDeviceSubgroupProperties<'a>>,
     pub long_vector_properties: Option<PhysicalDeviceShaderLongVectorPropertiesEXT<'a>>,
 
     pub max_spirv_version: (u8, u8),
@@ -142,6 +143,11 @@ impl<'a> ExtendedFeatures<'a> {
             // Properties
             EXT_SHADER_LONG_VECTOR_NAME => long_vector_properties,
         );
+
+        // Core since 1.1, without an extension
+        if version >= API_VERSION_1_1 {
+            self.subgroup_properties = Some(Default::default());
+        }
     }
 
     pub fn add_to_device_create(
@@ -261,6 +267,7 @@ impl<'a> ExtendedFeatures<'a> {
             properties
         }
 
+        properties = push_opt(properties, &mut self.subgroup_properties);
         properties = push_opt(properties, &mut self.long_vector_properties);
 
         unsafe {
@@ -303,6 +310,7 @@ impl<'a> ExtendedFeatures<'a> {
             nv_atomic_float_vector,
             nv_cooperative_matrix2,
             // Properties
+            subgroup_properties,
             long_vector_properties,
         );
     }
@@ -1,8 +1,5 @@
 use cubecl_core::{Compiler, prelude::Visibility, server::KernelArguments};
-use cubecl_core::{
-    WgpuCompilationOptions,
-    ir::{ElemType, UIntKind},
-};
+use cubecl_core::{WgpuCompilationOptions, ir::UIntKind};
 use cubecl_ir::{DeviceProperties, Type};
 use wgpu::Features;
 
@@ -65,9 +62,11 @@ pub fn register_wgsl_features(
     comp_options: &mut WgpuCompilationOptions,
 ) {
     register_types(props, adapter);
-    if props.supports_type(ElemType::UInt(UIntKind::U64)) {
-        comp_options.supports_u64 = true;
-    }
+    // Without native support, 64-bit integers are emulated by the compiler
+    comp_options.supports_u64 = adapter.features().contains(Features::SHADER_INT64);
+    // Same for f32 atomics, which fall back to a compare-exchange loop
+    comp_options.supports_float32_atomics =
+        adapter.features().contains(Features::SHADER_FLOAT32_ATOMIC);
 }
 
 pub fn register_types(props: &mut DeviceProperties, adapter: &wgpu::Adapter) {
@@ -98,22 +97,51 @@ pub fn register_types(props: &mut DeviceProperties, adapter: &wgpu::Adapter) {
         );
     }
 
+    // Storage-only, packed into `u32` words in buffers and widened to `f32` in registers. Math
+    // would run at `f32` precision without rounding back, so only conversions are allowed.
+    for kind in [FloatKind::BF16, FloatKind::E4M3, FloatKind::E5M2] {
+        props.register_type_usage(
+            ElemType::Float(kind),
+            TypeUsage::Conversion | TypeUsage::Buffer,
+        );
+    }
+
     let feats = adapter.features();
 
-    if feats.contains(wgpu::Features::SHADER_INT64) {
-        props.register_type_usage(ElemType::Int(IntKind::I64), TypeUsage::all());
-        props.register_type_usage(ElemType::UInt(UIntKind::U64), TypeUsage::all());
-    }
+    // Emulated as `vec2<u32>` when `SHADER_INT64` isn't available, which only covers scalar
+    // arithmetic. Vectors and plane ops on emulated integers are rejected by the compiler.
+    let int64_usage = if feats.contains(wgpu::Features::SHADER_INT64) {
+        TypeUsage::all()
+    } else {
+        TypeUsage::Conversion | TypeUsage::Arithmetic | TypeUsage::Buffer
+    };
+    props.register_type_usage(ElemType::Int(IntKind::I64), int64_usage);
+    props.register_type_usage(ElemType::UInt(UIntKind::U64), int64_usage);
+
     if feats.contains(wgpu::Features::SHADER_F64) {
         props.register_type_usage(ElemType::Float(FloatKind::F64), TypeUsage::all());
     }
+    // Emulated float atomics go through a compare-exchange loop, so min/max work as well as add
+    let emulated_float_atomic_usage =
+        AtomicUsage::LoadStore | AtomicUsage::Exchange | AtomicUsage::Add | AtomicUsage::MinMax;
+
     if feats.contains(wgpu::Features::SHADER_F16) {
         props.register_type_usage(ElemType::Float(FloatKind::F16), TypeUsage::all());
+        // WGSL has no 16-bit atomics, pairs are always packed into an emulated `atomic<u32>`
+        props.register_atomic_type_usage(
+            Type::atomic(Type::new(ElemType::Float(FloatKind::F16)).with_vector_size(2)),
+            emulated_float_atomic_usage,
+        );
     }
     if feats.contains(wgpu::Features::SHADER_FLOAT32_ATOMIC) {
         props.register_atomic_type_usage(
             Type::atomic(ElemType::Float(FloatKind::F32)),
             AtomicUsage::LoadStore | AtomicUsage::Exchange | AtomicUsage::Add,
         );
+    } else {
+        props.register_atomic_type_usage(
+            Type::atomic(ElemType::Float(FloatKind::F32)),
+            emulated_float_atomic_usage,
+        );
     }
 }
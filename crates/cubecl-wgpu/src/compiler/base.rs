@@ -9,7 +9,7 @@ use cubecl_core::{
 use cubecl_cpp::shared::MslComputeKernel;
 use cubecl_environment::backtrace::BackTrace;
 use cubecl_ir::DeviceProperties;
-use cubecl_runtime::compiler::CompilationError;
+use cubecl_runtime::{compiler::CompilationError, throughput::KernelCost};
 use derive_more::derive::From;
 
 #[cfg(feature = "spirv")]
@@ -156,6 +156,23 @@ impl Compiler for AutoCompiler {
             AutoCompiler::Msl(_) => "msl",
         }
     }
+    fn kernel_cost(&self, kernel: &Self::Representation) -> Option<KernelCost> {
+        match (self, kernel) {
+            (AutoCompiler::Wgsl(compiler), AutoRepresentation::Wgsl(kernel)) => {
+                compiler.kernel_cost(kernel)
+            }
+            #[cfg(feature = "spirv")]
+            (AutoCompiler::SpirV(compiler), AutoRepresentation::SpirV(kernel)) => {
+                compiler.kernel_cost(kernel)
+            }
+            #[cfg(feature = "msl")]
+            (AutoCompiler::Msl(compiler), AutoRepresentation::Msl(kernel)) => {
+                compiler.kernel_cost(kernel)
+            }
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
 }
 
 impl WgpuCompiler for AutoCompiler {
@@ -1,13 +1,22 @@
 use super::shader::ComputeShader;
 use crate::compiler::wgsl::{
-    self, EnableFeaturesPass, builtin::LowerBuiltinsPass, lower::LowerOpsWgslPass,
-    metadata::declare_info, rewrite_args, shared_memory_size,
+    self, EnableFeaturesPass,
+    builtin::LowerBuiltinsPass,
+    float_atomic::find_unsupported_float_atomic,
+    int64::{Int64PolyfillPass, find_unsupported_int64},
+    lower::LowerOpsWgslPass,
+    metadata::declare_info,
+    packed_float::PackedFloatPolyfillPass,
+    rewrite_args, shared_memory_size,
 };
 
 use cubecl_core::{
     WgpuCompilationOptions,
     post_processing::{
         checked_io::{CheckedIo, CheckedIoPass},
+        complex::LowerComplexNumbersPass,
+        int4::LowerPackedIntPass,
+        plane::LowerClusteredPlaneOpsPass,
         saturating::LowerSaturatingArithmeticPass,
         unroll::UnrollPass,
     },
@@ -24,12 +33,16 @@ use cubecl_ir::{
     rewrite::SimplifyOpsPass,
     settings::Dim3,
 };
-use cubecl_opt::passes::{
-    annotate_buffer_visibility::AnnotateGlobalVisibilityPass, simple_cse::SimpleCSEPass,
-    sroa::SROAPass,
+use cubecl_opt::{
+    analyses::cost::StaticCost,
+    passes::{
+        annotate_buffer_visibility::AnnotateGlobalVisibilityPass, simple_cse::SimpleCSEPass,
+        sroa::SROAPass,
+    },
 };
 use cubecl_runtime::compiler::CompilationError;
 use cubecl_runtime::kernel;
+use cubecl_runtime::throughput::KernelCost;
 
 const MAX_VECTOR_SIZE: usize = 4;
 
@@ -62,6 +75,10 @@ impl cubecl_core::Compiler for WgslCompiler {
     fn extension(&self) -> &'static str {
         "wgsl"
     }
+
+    fn kernel_cost(&self, kernel: &Self::Representation) -> Option<KernelCost> {
+        Some(kernel.cost.clone())
+    }
 }
 
 impl WgslCompiler {
@@ -106,6 +123,15 @@ impl WgslCompiler {
 
         verify_operation(module_op, &ctx)?;
 
+        if let Some(reason) = find_unsupported_int64(&ctx, module_op)
+            .or_else(|| find_unsupported_float_atomic(&ctx, module_op))
+        {
+            return Err(CompilationError::Validation {
+                reason,
+                backtrace: BackTrace::capture(),
+            });
+        }
+
         let config = PMConfig {
             #[cfg(feature = "pliron-dump")]
             ir_printing_dir,
@@ -119,6 +145,8 @@ impl WgslCompiler {
         let mut passes = OpPass::<ModuleOp, Passes>::default();
         let mut func_passes = OpPass::<FuncOp, Passes>::default();
 
+        func_passes.add_pass(LowerComplexNumbersPass);
+        func_passes.add_pass(LowerPackedIntPass);
         func_passes.add_pass(SROAPass);
         func_passes.add_pass(CheckedIoPass::new(CheckedIo::new(
             value.settings.execution_mode,
@@ -126,6 +154,8 @@ impl WgslCompiler {
         )));
         func_passes.add_pass(UnrollPass::new(MAX_VECTOR_SIZE));
 
+        // WGSL has no clustered reductions, so clusters always use shuffles
+        func_passes.add_pass(LowerClusteredPlaneOpsPass::default());
         func_passes.add_pass(LowerOpsWgslPass::default());
         func_passes.add_pass(LowerSaturatingArithmeticPass::default());
         func_passes.add_pass(LowerBuiltinsPass);
@@ -147,10 +177,16 @@ impl WgslCompiler {
 
         passes.add_pass(NestedOpsPass::new(func_passes));
         passes.add_pass(AnnotateGlobalVisibilityPass);
+        passes.add_pass(Int64PolyfillPass);
+        passes.add_pass(PackedFloatPolyfillPass);
         passes.add_pass(EnableFeaturesPass);
 
         passes.run(module_op, &mut ctx, &mut analyses).unwrap();
 
+        let cost = analyses
+            .get_analysis::<StaticCost>(entry_func.get_operation(), &ctx)?
+            .0
+            .clone();
         let buffers = rewrite_args(&mut ctx, entry_func);
         declare_info(&mut ctx, entry_func, buffers.len());
         let shared_memory_size = shared_memory_size(&ctx, module_op);
@@ -160,6 +196,7 @@ impl WgslCompiler {
         Ok(ComputeShader {
             buffers,
             shared_memory_size,
+            cost,
             ctx,
         })
     }
@@ -0,0 +1,159 @@
+//! Software float atomics for adapters without `SHADER_FLOAT32_ATOMIC`.
+//!
+//! Emulated float atomics are declared as `atomic<u32>` holding the bit pattern of the value, so
+//! loads, stores and exchanges only need a `bitcast`. Read-modify-write ops load the current bits,
+//! compute the new value in registers and retry with `atomicCompareExchangeWeak` until no other
+//! invocation raced them. WGSL has no 16-bit atomics, so `vec2<f16>` pairs always take this path.
+
+use cubecl_core::WgpuCompilationOptions;
+use cubecl_ir::{interfaces::TypedExt, prelude::*, types::AtomicType};
+
+use crate::compiler::wgsl::{to_wgsl::TypeExtWgsl, value::WgslValue};
+
+/// Whether 32-bit float atomics are emulated for this kernel.
+pub fn emulates_float32_atomics(ctx: &Context) -> bool {
+    !ctx.aux_ty::<WgpuCompilationOptions>()
+        .supports_float32_atomics
+}
+
+/// Whether an atomic holding `value` is stored as the `u32` bit pattern. Only `vec2<f16>` pairs
+/// fill a `u32`, scalar `f16` atomics are rejected by [`find_unsupported_float_atomic`].
+pub fn is_emulated_atomic(ctx: &Context, value: impl Typed) -> bool {
+    let elem = value.scalar_ty(ctx);
+    match elem.is_float16(ctx) {
+        true => value.is_vector_of_size(ctx, 2),
+        false => elem.is_float(ctx) && emulates_float32_atomics(ctx),
+    }
+}
+
+/// Finds an atomic WGSL can't express. `f16` atomics can only be emulated in pairs, since the
+/// smallest atomic is a `u32`.
+pub fn find_unsupported_float_atomic(ctx: &Context, op: Ptr<Operation>) -> Option<String> {
+    let mut found = None;
+    visit_all_values(ctx, &mut found, op, |ctx, found, val| {
+        let ty = val.unwrap_ptr(ctx);
+        let inner = ty
+            .deref(ctx)
+            .downcast_ref::<AtomicType>()
+            .map(|it| it.inner);
+        if let Some(inner) = inner
+            && inner.scalar_ty(ctx).is_float16(ctx)
+            && !inner.is_vector_of_size(ctx, 2)
+        {
+            *found = Some(format!(
+                "`f16` atomics are only supported as `vec2<f16>` pairs in WGSL, found `{}`",
+                ty.disp(ctx)
+            ));
+        }
+    });
+    found
+}
+
+/// Reinterprets `value` as the `u32` stored in an emulated atomic.
+pub fn fmt_to_bits(ctx: &Context, value: Value) -> String {
+    let name = value.name(ctx);
+    match is_emulated_atomic(ctx, value) {
+        true => format!("bitcast<u32>({name})"),
+        false => name.to_string(),
+    }
+}
+
+/// Reinterprets `bits` loaded from an emulated atomic as the type of `out`.
+pub fn fmt_from_bits(ctx: &Context, out: Value, bits: String) -> String {
+    match is_emulated_atomic(ctx, out) {
+        true => format!("bitcast<{}>({bits})", out.get_type(ctx).to_wgsl(ctx)),
+        false => bits,
+    }
+}
+
+/// Formats an atomic read-modify-write. Native atomics call `native_fn`, emulated ones run a
+/// compare-exchange loop that applies `combine(current, value)` to the decoded value.
+pub fn fmt_float_rmw(
+    ctx: &Context,
+    out: Value,
+    ptr: Value,
+    value: Value,
+    native_fn: &str,
+    combine: impl FnOnce(&str, &str) -> String,
+) -> String {
+    let out_left = out.fmt_left(ctx);
+    let ptr = ptr.name(ctx);
+    let value_name = value.name(ctx);
+    if !is_emulated_atomic(ctx, value) {
+        return format!("{out_left} = {native_fn}({ptr}, {value_name});\n");
+    }
+
+    let out_name = out.name(ctx);
+    let ty = value.get_type(ctx).to_wgsl(ctx);
+    let bits = format!("{out_name}_bits");
+    let cas = format!("{out_name}_cas");
+    let new = combine(&format!("bitcast<{ty}>({bits})"), &value_name);
+    format!(
+        "var {bits} = atomicLoad({ptr});
+loop {{
+    let {cas} = atomicCompareExchangeWeak({ptr}, {bits}, bitcast<u32>({new}));
+    if {cas}.exchanged {{
+        break;
+    }}
+    {bits} = {cas}.old_value;
+}}
+{out_left} = bitcast<{ty}>({bits});
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use cubecl_core::{self as cubecl, WgpuCompilationOptions, prelude::*};
+    use cubecl_runtime::compiler::CompilationError;
+    use half::f16;
+    use naga::valid::Capabilities;
+
+    use crate::compiler::wgsl::test_utils::{try_compile_wgsl, validate_wgsl};
+
+    #[cube]
+    fn float_rmw<F: Float, N: Size>(input: &[Vector<F, N>], atomics: &[Atomic<Vector<F, N>>]) {
+        atomics[0].fetch_add(input[0]);
+        atomics[1].fetch_max(input[1]);
+    }
+
+    fn compile_rmw<F: Float, N: Size>() -> Result<String, CompilationError> {
+        let options = WgpuCompilationOptions {
+            supports_float32_atomics: false,
+            ..Default::default()
+        };
+        try_compile_wgsl::<&[Vector<F, N>], &[Atomic<Vector<F, N>>]>(
+            options,
+            |scope, input, atomics| float_rmw::expand::<F, N>(scope, input, atomics),
+        )
+    }
+
+    /// Without `SHADER_FLOAT32_ATOMIC` any native float atomic left in the module fails
+    /// validation.
+    fn no_float_atomics() -> Capabilities {
+        Capabilities::all() - Capabilities::SHADER_FLOAT32_ATOMIC
+    }
+
+    #[test]
+    fn emulated_f32_atomics_validate() {
+        let source = compile_rmw::<f32, Const<1>>().unwrap();
+
+        assert!(source.contains("atomicCompareExchangeWeak"), "{source}");
+        validate_wgsl(&source, no_float_atomics());
+    }
+
+    #[test]
+    fn f16_pair_atomics_validate() {
+        let source = compile_rmw::<f16, Const<2>>().unwrap();
+
+        assert!(source.contains("atomicCompareExchangeWeak"), "{source}");
+        validate_wgsl(&source, no_float_atomics());
+    }
+
+    #[test]
+    fn scalar_f16_atomics_are_rejected() {
+        let err = compile_rmw::<f16, Const<1>>().unwrap_err();
+
+        assert!(err.to_string().contains("vec2<f16>"), "{err}");
+    }
+}
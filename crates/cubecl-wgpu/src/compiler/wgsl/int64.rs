@@ -0,0 +1,400 @@
+//! Software `u64`/`i64` for adapters without `SHADER_INT64`.
+//!
+//! 64-bit integers are emitted as `vec2<u32>`, with the low word in `x` and the high word in `y`.
+//! This has the same layout as a native 64-bit integer, so buffers can be shared with the host
+//! unchanged. Bitwise ops, selects and memory work component-wise, everything else calls into
+//! the helper functions declared by [`Int64PolyfillOp`]. Signed integers are stored as two's
+//! complement and only differ in the helpers that care about the sign.
+//!
+//! Only scalars are supported, since WGSL has no vectors of vectors.
+
+use cubecl_core::WgpuCompilationOptions;
+use cubecl_ir::{interfaces::TypedExt, prelude::*};
+use pliron::builtin::ops::ModuleOp;
+
+use crate::compiler::wgsl::{to_wgsl::wgsl_op, value::WgslValue};
+
+/// Whether 64-bit integers are emulated for this kernel.
+pub fn emulates_int64(ctx: &Context) -> bool {
+    !ctx.aux_ty::<WgpuCompilationOptions>().supports_u64
+}
+
+/// Whether `value` is an emulated 64-bit integer.
+pub fn is_emulated(ctx: &Context, value: impl Typed) -> bool {
+    emulates_int64(ctx) && value.scalar_ty(ctx).is_int_of_width(ctx, 64)
+}
+
+/// Picks the helper matching the signedness of `value`.
+pub fn int64_fn<'a>(
+    ctx: &Context,
+    value: impl Typed,
+    unsigned_fn: &'a str,
+    signed_fn: &'a str,
+) -> &'a str {
+    match value.scalar_ty(ctx).is_signed_int(ctx) {
+        true => signed_fn,
+        false => unsigned_fn,
+    }
+}
+
+/// Formats a binary op, calling `unsigned_fn` or `signed_fn` if the operands are emulated.
+pub fn fmt_binop(
+    ctx: &Context,
+    lhs: Value,
+    rhs: Value,
+    op: &str,
+    unsigned_fn: &str,
+    signed_fn: &str,
+) -> String {
+    let (l, r) = (lhs.name(ctx), rhs.name(ctx));
+    if is_emulated(ctx, lhs) {
+        let func = int64_fn(ctx, lhs, unsigned_fn, signed_fn);
+        format!("{func}({l}, {r})")
+    } else {
+        format!("{l} {op} {r}")
+    }
+}
+
+/// Formats the shift amount of an emulated shift as a `u32`.
+pub fn fmt_shift_amount(ctx: &Context, amount: Value) -> String {
+    if is_emulated(ctx, amount) {
+        format!("{}.x", amount.name(ctx))
+    } else {
+        format!("u32({})", amount.name(ctx))
+    }
+}
+
+/// Formats a cast where the input or output is an emulated 64-bit integer.
+pub fn fmt_cast(ctx: &Context, value: Value, to: TypeHandle, to_wgsl: &str) -> String {
+    let from = value.scalar_ty(ctx);
+    let to_elem = to.scalar_ty(ctx);
+    let name = value.name(ctx);
+    let from_signed = from.is_signed_int(ctx);
+    let prefix = |signed: bool| if signed { "i64" } else { "u64" };
+
+    let is_float = |ty: TypeHandle| !ty.is_int(ctx) && !ty.is_index(ctx) && !ty.is_bool(ctx);
+
+    match (is_emulated(ctx, value), is_emulated(ctx, to)) {
+        // Same representation, signedness is only in the ops
+        (true, true) => name.to_string(),
+        (true, false) if to_elem.is_bool(ctx) => format!("any({name} != vec2<u32>(0u))"),
+        (true, false) if is_float(to_elem) => {
+            format!("{to_wgsl}({}_to_f32({name}))", prefix(from_signed))
+        }
+        // Truncation keeps the low word, `i32(u32)` is a bitcast
+        (true, false) => format!("{to_wgsl}({name}.x)"),
+        (false, true) if is_float(from) => {
+            format!("f32_to_{}(f32({name}))", prefix(to_elem.is_signed_int(ctx)))
+        }
+        (false, true) if from_signed => {
+            format!("vec2<u32>(bitcast<u32>(i32({name})), select(0u, 0xffffffffu, {name} < 0))")
+        }
+        (false, true) => format!("vec2<u32>(u32({name}), 0u)"),
+        (false, false) => unreachable!("Cast doesn't involve emulated integers"),
+    }
+}
+
+/// Ops that can't operate on emulated integers, because the hardware does the arithmetic.
+#[op_interface]
+pub trait NativeInt64Op {
+    verify_op_succ!();
+    fn int64_operand(&self, ctx: &Context) -> Value;
+}
+
+/// Finds uses of emulated integers with no WGSL representation, so they can be rejected up front.
+pub fn find_unsupported_int64(ctx: &Context, op: Ptr<Operation>) -> Option<String> {
+    if !emulates_int64(ctx) {
+        return None;
+    }
+    let mut found = None;
+    visit_all_values(ctx, &mut found, op, |ctx, found, val| {
+        if let Some(elem) = val.try_get_scalar_elem_ty(ctx)
+            && elem.is_int_of_width(ctx, 64)
+            && val.try_get_vector_size(ctx).unwrap_or(1) > 1
+        {
+            *found = Some(format!(
+                "Vectorized 64-bit integers aren't supported without `SHADER_INT64`, found `{}`",
+                val.get_type(ctx).disp(ctx)
+            ));
+        }
+    });
+    visit_all_ops_with_interface::<dyn NativeInt64Op, _>(ctx, &mut found, op, |ctx, found, op| {
+        if is_emulated(ctx, op.int64_operand(ctx)) {
+            *found = Some(
+                "Plane arithmetic on 64-bit integers isn't supported without `SHADER_INT64`"
+                    .to_string(),
+            );
+        }
+    });
+    found
+}
+
+/// Declares the helper functions used by emulated 64-bit integers.
+#[cube_op(name = "wgsl.int64_polyfill")]
+#[result_ty(none)]
+pub struct Int64PolyfillOp {}
+
+wgsl_op!(Int64PolyfillOp, |_, _| INT64_POLYFILL.to_string());
+
+/// Inserts [`Int64PolyfillOp`] if the module uses emulated 64-bit integers. Must run before
+/// [`EnableFeaturesPass`](super::EnableFeaturesPass), since `enable` directives have to come
+/// first.
+pub struct Int64PolyfillPass;
+
+#[pass_name]
+impl Pass for Int64PolyfillPass {
+    fn run(
+        &mut self,
+        op: Ptr<Operation>,
+        ctx: &mut Context,
+        _analyses: &mut AnalysisManager,
+    ) -> Result<PassResult> {
+        let mut res = PassResult::default();
+        if !emulates_int64(ctx) {
+            return Ok(res);
+        }
+
+        let mut used = false;
+        visit_all_values(ctx, &mut used, op, |ctx, used, val| {
+            *used |= val
+                .try_get_scalar_elem_ty(ctx)
+                .is_some_and(|elem| elem.is_int_of_width(ctx, 64));
+        });
+
+        if used {
+            let module_body = op.as_op::<ModuleOp>(ctx).unwrap().get_body(ctx, 0);
+            let polyfill = Int64PolyfillOp::new(ctx);
+            polyfill.get_operation().insert_at_front(module_body, ctx);
+            res.ir_changed = IRStatus::Changed;
+        }
+        Ok(res)
+    }
+}
+
+const INT64_POLYFILL: &str = r#"
+fn u64_add(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let lo = a.x + b.x;
+    return vec2<u32>(lo, a.y + b.y + select(0u, 1u, lo < a.x));
+}
+
+fn u64_sub(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    return vec2<u32>(a.x - b.x, a.y - b.y - select(0u, 1u, a.x < b.x));
+}
+
+fn u64_neg(a: vec2<u32>) -> vec2<u32> {
+    return u64_sub(vec2<u32>(0u), a);
+}
+
+fn i64_abs(a: vec2<u32>) -> vec2<u32> {
+    return select(a, u64_neg(a), bitcast<i32>(a.y) < 0);
+}
+
+fn u32_mul_wide(a: u32, b: u32) -> vec2<u32> {
+    let p0 = (a & 0xffffu) * (b & 0xffffu);
+    let p1 = (a & 0xffffu) * (b >> 16u);
+    let p2 = (a >> 16u) * (b & 0xffffu);
+    let p3 = (a >> 16u) * (b >> 16u);
+    let mid = (p0 >> 16u) + (p1 & 0xffffu) + (p2 & 0xffffu);
+    return vec2<u32>((p0 & 0xffffu) | (mid << 16u), p3 + (p1 >> 16u) + (p2 >> 16u) + (mid >> 16u));
+}
+
+fn u64_mul(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let lo = u32_mul_wide(a.x, b.x);
+    return vec2<u32>(lo.x, lo.y + a.x * b.y + a.y * b.x);
+}
+
+fn u64_eq(a: vec2<u32>, b: vec2<u32>) -> bool {
+    return all(a == b);
+}
+
+fn u64_ne(a: vec2<u32>, b: vec2<u32>) -> bool {
+    return any(a != b);
+}
+
+fn u64_lt(a: vec2<u32>, b: vec2<u32>) -> bool {
+    return a.y < b.y || (a.y == b.y && a.x < b.x);
+}
+
+fn i64_lt(a: vec2<u32>, b: vec2<u32>) -> bool {
+    let a_hi = bitcast<i32>(a.y);
+    let b_hi = bitcast<i32>(b.y);
+    return a_hi < b_hi || (a_hi == b_hi && a.x < b.x);
+}
+
+fn u64_le(a: vec2<u32>, b: vec2<u32>) -> bool { return !u64_lt(b, a); }
+fn u64_gt(a: vec2<u32>, b: vec2<u32>) -> bool { return u64_lt(b, a); }
+fn u64_ge(a: vec2<u32>, b: vec2<u32>) -> bool { return !u64_lt(a, b); }
+fn i64_le(a: vec2<u32>, b: vec2<u32>) -> bool { return !i64_lt(b, a); }
+fn i64_gt(a: vec2<u32>, b: vec2<u32>) -> bool { return i64_lt(b, a); }
+fn i64_ge(a: vec2<u32>, b: vec2<u32>) -> bool { return !i64_lt(a, b); }
+
+fn u64_min(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return select(b, a, u64_lt(a, b)); }
+fn u64_max(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return select(a, b, u64_lt(a, b)); }
+fn i64_min(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return select(b, a, i64_lt(a, b)); }
+fn i64_max(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return select(a, b, i64_lt(a, b)); }
+
+fn u64_clamp(a: vec2<u32>, lo: vec2<u32>, hi: vec2<u32>) -> vec2<u32> {
+    return u64_min(u64_max(a, lo), hi);
+}
+
+fn i64_clamp(a: vec2<u32>, lo: vec2<u32>, hi: vec2<u32>) -> vec2<u32> {
+    return i64_min(i64_max(a, lo), hi);
+}
+
+fn u64_shl(a: vec2<u32>, n: u32) -> vec2<u32> {
+    let s = n & 63u;
+    if s == 0u {
+        return a;
+    }
+    if s >= 32u {
+        return vec2<u32>(0u, a.x << (s - 32u));
+    }
+    return vec2<u32>(a.x << s, (a.y << s) | (a.x >> (32u - s)));
+}
+
+fn u64_shr(a: vec2<u32>, n: u32) -> vec2<u32> {
+    let s = n & 63u;
+    if s == 0u {
+        return a;
+    }
+    if s >= 32u {
+        return vec2<u32>(a.y >> (s - 32u), 0u);
+    }
+    return vec2<u32>((a.x >> s) | (a.y << (32u - s)), a.y >> s);
+}
+
+fn i64_shr(a: vec2<u32>, n: u32) -> vec2<u32> {
+    let s = n & 63u;
+    let hi = bitcast<i32>(a.y);
+    if s == 0u {
+        return a;
+    }
+    if s >= 32u {
+        return vec2<u32>(bitcast<u32>(hi >> (s - 32u)), bitcast<u32>(hi >> 31u));
+    }
+    return vec2<u32>((a.x >> s) | (a.y << (32u - s)), bitcast<u32>(hi >> s));
+}
+
+// Quotient in `xy`, remainder in `zw`
+fn u64_div_rem(n: vec2<u32>, d: vec2<u32>) -> vec4<u32> {
+    if n.y == 0u && d.y == 0u {
+        return vec4<u32>(n.x / d.x, 0u, n.x % d.x, 0u);
+    }
+    var q = vec2<u32>(0u);
+    var r = vec2<u32>(0u);
+    for (var i = 63u; i < 64u; i--) {
+        r = u64_shl(r, 1u);
+        r.x |= (select(n.x, n.y, i >= 32u) >> (i & 31u)) & 1u;
+        if !u64_lt(r, d) {
+            r = u64_sub(r, d);
+            if i >= 32u {
+                q.y |= 1u << (i - 32u);
+            } else {
+                q.x |= 1u << i;
+            }
+        }
+    }
+    return vec4<u32>(q, r);
+}
+
+fn u64_div(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return u64_div_rem(a, b).xy; }
+fn u64_rem(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> { return u64_div_rem(a, b).zw; }
+
+fn i64_div(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let q = u64_div(i64_abs(a), i64_abs(b));
+    return select(q, u64_neg(q), (bitcast<i32>(a.y) < 0) != (bitcast<i32>(b.y) < 0));
+}
+
+fn i64_rem(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let r = u64_rem(i64_abs(a), i64_abs(b));
+    return select(r, u64_neg(r), bitcast<i32>(a.y) < 0);
+}
+
+fn u64_to_f32(a: vec2<u32>) -> f32 {
+    return f32(a.y) * 4294967296.0 + f32(a.x);
+}
+
+fn i64_to_f32(a: vec2<u32>) -> f32 {
+    return select(u64_to_f32(a), -u64_to_f32(u64_neg(a)), bitcast<i32>(a.y) < 0);
+}
+
+fn f32_to_u64(x: f32) -> vec2<u32> {
+    let v = max(x, 0.0);
+    let hi = floor(v / 4294967296.0);
+    return vec2<u32>(u32(v - hi * 4294967296.0), u32(hi));
+}
+
+fn f32_to_i64(x: f32) -> vec2<u32> {
+    // Out of range magnitudes saturate instead of wrapping through the unsigned conversion.
+    if (x >= 9223372036854775808.0) {
+        return vec2<u32>(0xffffffffu, 0x7fffffffu);
+    }
+    if (x <= -9223372036854775808.0) {
+        return vec2<u32>(0u, 0x80000000u);
+    }
+    let m = f32_to_u64(abs(x));
+    return select(m, u64_neg(m), x < 0.0);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use cubecl_core::{self as cubecl, WgpuCompilationOptions, prelude::*};
+    use naga::valid::Capabilities;
+
+    use crate::compiler::wgsl::test_utils::{compile_wgsl, validate_wgsl};
+
+    #[cube]
+    fn unsigned_ops(input: &[u64], output: &mut [u64]) {
+        let a = input[0];
+        let b = input[1];
+        output[0] = a + b;
+        output[1] = a - b;
+        output[2] = a * b;
+        output[3] = a / b;
+        output[4] = a % b;
+        output[5] = select(a < b, a ^ b, a & b);
+    }
+
+    #[cube]
+    fn signed_casts(input: &[f32], output: &mut [i64]) {
+        let a = i64::cast_from(input[0]);
+        let b = i64::cast_from(input[1]);
+        output[0] = a / b;
+        output[1] = a % b;
+        output[2] = -a;
+        output[3] = i64::cast_from(f32::cast_from(a) * 2.0);
+    }
+
+    fn emulated() -> WgpuCompilationOptions {
+        WgpuCompilationOptions {
+            supports_u64: false,
+            ..Default::default()
+        }
+    }
+
+    /// Without `SHADER_INT64` any 64-bit integer left in the module fails validation.
+    fn no_int64() -> Capabilities {
+        Capabilities::all() - Capabilities::SHADER_INT64
+    }
+
+    #[test]
+    fn emulated_unsigned_ops_validate() {
+        let source = compile_wgsl::<&[u64], &mut [u64]>(emulated(), |scope, input, output| {
+            unsigned_ops::expand(scope, input, output)
+        });
+
+        assert!(source.contains("u64_div("), "{source}");
+        validate_wgsl(&source, no_int64());
+    }
+
+    #[test]
+    fn emulated_signed_casts_validate() {
+        let source = compile_wgsl::<&[f32], &mut [i64]>(emulated(), |scope, input, output| {
+            signed_casts::expand(scope, input, output)
+        });
+
+        assert!(source.contains("f32_to_i64("), "{source}");
+        validate_wgsl(&source, no_int64());
+    }
+}
@@ -1,12 +1,18 @@
 pub mod builtin;
 mod compiler;
+pub mod float_atomic;
+pub mod int64;
 pub mod lower;
 pub mod metadata;
 pub mod ops;
+pub mod packed_float;
 pub mod shader;
 pub mod to_wgsl;
 pub mod types;
 pub mod value;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 pub use compiler::*;
 pub(crate) use shader::*;
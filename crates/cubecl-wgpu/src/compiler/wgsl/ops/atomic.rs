@@ -1,38 +1,70 @@
-use cubecl_ir::dialect::atomic::*;
+//! WGSL atomics are always relaxed, and the only fences are the barriers, which can't be used in
+//! non-uniform control flow. The ordering and scope of the atomic ops are ignored here.
+//!
+//! Float atomics the adapter can't do natively are emulated, see
+//! [`float_atomic`](crate::compiler::wgsl::float_atomic).
+
+use cubecl_ir::{dialect::atomic::*, prelude::*};
 
 use crate::compiler::wgsl::{
+    float_atomic::{fmt_float_rmw, fmt_from_bits, fmt_to_bits},
     to_wgsl::{wgsl_op, wgsl_op_with_out},
     value::WgslValue,
 };
 
 wgsl_op_with_out!(AtomicExchangeOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomicExchange({ptr}, {value})")
+    let value = fmt_to_bits(ctx, op.value(ctx));
+    fmt_from_bits(ctx, op.get_result(ctx), format!("atomicExchange({ptr}, {value})"))
 });
 
-wgsl_op_with_out!(AtomicIAddOp, AtomicFAddOp; |op, ctx| {
+wgsl_op_with_out!(AtomicIAddOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
     format!("atomicAdd({ptr}, {value})")
 });
-wgsl_op_with_out!(AtomicISubOp, AtomicFSubOp; |op, ctx| {
+wgsl_op_with_out!(AtomicISubOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
     format!("atomicSub({ptr}, {value})")
 });
 
-wgsl_op_with_out!(AtomicSMaxOp, AtomicUMaxOp, AtomicFMaxOp; |op, ctx| {
+wgsl_op_with_out!(AtomicSMaxOp, AtomicUMaxOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
     format!("atomicMax({ptr}, {value})")
 });
-wgsl_op_with_out!(AtomicSMinOp, AtomicUMinOp, AtomicFMinOp; |op, ctx| {
+wgsl_op_with_out!(AtomicSMinOp, AtomicUMinOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
     format!("atomicMin({ptr}, {value})")
 });
 
+wgsl_op!(AtomicFAddOp, |op, ctx| {
+    let (out, ptr, value) = (op.get_result(ctx), op.ptr(ctx), op.value(ctx));
+    fmt_float_rmw(ctx, out, ptr, value, "atomicAdd", |a, b| {
+        format!("{a} + {b}")
+    })
+});
+wgsl_op!(AtomicFSubOp, |op, ctx| {
+    let (out, ptr, value) = (op.get_result(ctx), op.ptr(ctx), op.value(ctx));
+    fmt_float_rmw(ctx, out, ptr, value, "atomicSub", |a, b| {
+        format!("{a} - {b}")
+    })
+});
+wgsl_op!(AtomicFMaxOp, |op, ctx| {
+    let (out, ptr, value) = (op.get_result(ctx), op.ptr(ctx), op.value(ctx));
+    fmt_float_rmw(ctx, out, ptr, value, "atomicMax", |a, b| {
+        format!("max({a}, {b})")
+    })
+});
+wgsl_op!(AtomicFMinOp, |op, ctx| {
+    let (out, ptr, value) = (op.get_result(ctx), op.ptr(ctx), op.value(ctx));
+    fmt_float_rmw(ctx, out, ptr, value, "atomicMin", |a, b| {
+        format!("min({a}, {b})")
+    })
+});
+
 wgsl_op_with_out!(AtomicAndOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
     let value = op.value(ctx).name(ctx);
@@ -51,17 +83,18 @@ wgsl_op_with_out!(AtomicXorOp; |op, ctx| {
 
 wgsl_op_with_out!(AtomicLoadOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
-    format!("atomicLoad({ptr})")
+    fmt_from_bits(ctx, op.get_result(ctx), format!("atomicLoad({ptr})"))
 });
 wgsl_op!(AtomicStoreOp, |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
+    let value = fmt_to_bits(ctx, op.value(ctx));
     format!("atomicStore({ptr}, {value});\n")
 });
 
 wgsl_op_with_out!(AtomicCompareExchangeWeakOp; |op, ctx| {
     let ptr = op.ptr(ctx).name(ctx);
-    let cmp = op.cmp(ctx).name(ctx);
-    let value = op.value(ctx).name(ctx);
-    format!("atomicCompareExchangeWeak({ptr}, {cmp}, {value}).old_value")
+    let cmp = fmt_to_bits(ctx, op.cmp(ctx));
+    let value = fmt_to_bits(ctx, op.value(ctx));
+    let old = format!("atomicCompareExchangeWeak({ptr}, {cmp}, {value}).old_value");
+    fmt_from_bits(ctx, op.get_result(ctx), old)
 });
@@ -3,6 +3,7 @@ use cubecl_ir::{dialect::bitwise::*, interfaces::TypedExt, prelude::*, types::Ve
 use pliron::builtin::types::{IntegerType, Signedness};
 
 use crate::compiler::wgsl::{
+    int64::{fmt_shift_amount, int64_fn, is_emulated},
     lower::lower_unop,
     to_wgsl::{TypeExtWgsl, wgsl_op_with_out},
 };
@@ -18,12 +19,23 @@ wgsl_op_with_out!(BitwiseXorOp; |op, ctx| {
 });
 
 wgsl_op_with_out!(ShiftLeftOp; |op, ctx| {
-    let u32 = u32_ty(ctx, op.lhs(ctx)).to_wgsl(ctx);
-    format!("{} << {u32}({})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    let lhs = op.lhs(ctx);
+    if is_emulated(ctx, lhs) {
+        let amount = fmt_shift_amount(ctx, op.rhs(ctx));
+        return format!("u64_shl({}, {amount})", lhs.name(ctx));
+    }
+    let u32 = u32_ty(ctx, lhs).to_wgsl(ctx);
+    format!("{} << {u32}({})", lhs.name(ctx), op.rhs(ctx).name(ctx))
 });
 wgsl_op_with_out!(ShiftRightOp; |op, ctx| {
-    let u32 = u32_ty(ctx, op.lhs(ctx)).to_wgsl(ctx);
-    format!("{} >> {u32}({})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    let lhs = op.lhs(ctx);
+    if is_emulated(ctx, lhs) {
+        let amount = fmt_shift_amount(ctx, op.rhs(ctx));
+        let func = int64_fn(ctx, lhs, "u64_shr", "i64_shr");
+        return format!("{func}({}, {amount})", lhs.name(ctx));
+    }
+    let u32 = u32_ty(ctx, lhs).to_wgsl(ctx);
+    format!("{} >> {u32}({})", lhs.name(ctx), op.rhs(ctx).name(ctx))
 });
 
 wgsl_op_with_out!(BitwiseNotOp; |op, ctx| {
@@ -31,11 +43,18 @@ wgsl_op_with_out!(BitwiseNotOp; |op, ctx| {
 });
 
 wgsl_op_with_out!(CountOnesOp; |op, ctx| {
+    let input = op.input(ctx).name(ctx);
+    if is_emulated(ctx, op.input(ctx)) {
+        return format!("countOneBits({input}.x) + countOneBits({input}.y)");
+    }
     let u32 = u32_ty(ctx, op.input(ctx)).to_wgsl(ctx);
-    format!("{u32}(countOneBits({}))", op.input(ctx).name(ctx))
+    format!("{u32}(countOneBits({input}))")
 });
 wgsl_op_with_out!(ReverseBitsOp; |op, ctx| {
-    format!("reverseBits({})", op.input(ctx).name(ctx))
+    match is_emulated(ctx, op.input(ctx)) {
+        true => format!("reverseBits({}).yx", op.input(ctx).name(ctx)),
+        false => format!("reverseBits({})", op.input(ctx).name(ctx)),
+    }
 });
 wgsl_op_with_out!(LeadingZerosBitsOp; |op, ctx| {
     let u32 = u32_ty(ctx, op.input(ctx)).to_wgsl(ctx);
@@ -1,36 +1,54 @@
 use cubecl_ir::dialect::cmp::*;
 
-use crate::compiler::wgsl::to_wgsl::wgsl_op_with_out;
+use crate::compiler::wgsl::{
+    int64::{fmt_binop, int64_fn, is_emulated},
+    to_wgsl::wgsl_op_with_out,
+};
 
 wgsl_op_with_out!(SMinOp, UMinOp, FMinOp; |op, ctx| {
-    format!("min({}, {})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    let lhs = op.lhs(ctx);
+    let func = match is_emulated(ctx, lhs) {
+        true => int64_fn(ctx, lhs, "u64_min", "i64_min"),
+        false => "min",
+    };
+    format!("{func}({}, {})", lhs.name(ctx), op.rhs(ctx).name(ctx))
 });
 
 wgsl_op_with_out!(SMaxOp, UMaxOp, FMaxOp; |op, ctx| {
-    format!("max({}, {})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    let lhs = op.lhs(ctx);
+    let func = match is_emulated(ctx, lhs) {
+        true => int64_fn(ctx, lhs, "u64_max", "i64_max"),
+        false => "max",
+    };
+    format!("{func}({}, {})", lhs.name(ctx), op.rhs(ctx).name(ctx))
 });
 
 wgsl_op_with_out!(SClampOp, UClampOp, FClampOp; |op, ctx| {
+    let input = op.input(ctx);
     let min = op.min(ctx).name(ctx);
     let max = op.max(ctx).name(ctx);
-    format!("clamp({}, {min}, {max})", op.input(ctx).name(ctx))
+    let func = match is_emulated(ctx, input) {
+        true => int64_fn(ctx, input, "u64_clamp", "i64_clamp"),
+        false => "clamp",
+    };
+    format!("{func}({}, {min}, {max})", input.name(ctx))
 });
 
 wgsl_op_with_out!(SLessThanOp, ULessThanOp, FLessThanOp; |op, ctx| {
-    format!("{} < {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "<", "u64_lt", "i64_lt")
 });
 wgsl_op_with_out!(SLessThanOrEqualOp, ULessThanOrEqualOp, FLessThanOrEqualOp; |op, ctx| {
-    format!("{} <= {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "<=", "u64_le", "i64_le")
 });
 wgsl_op_with_out!(SGreaterThanOp, UGreaterThanOp, FGreaterThanOp; |op, ctx| {
-    format!("{} > {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), ">", "u64_gt", "i64_gt")
 });
 wgsl_op_with_out!(SGreaterThanOrEqualOp, UGreaterThanOrEqualOp, FGreaterThanOrEqualOp; |op, ctx| {
-    format!("{} >= {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), ">=", "u64_ge", "i64_ge")
 });
 wgsl_op_with_out!(IEqualOp, FEqualOp, BoolEqualOp; |op, ctx| {
-    format!("{} == {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "==", "u64_eq", "u64_eq")
 });
 wgsl_op_with_out!(INotEqualOp, FNotEqualOp, BoolNotEqualOp; |op, ctx| {
-    format!("{} != {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "!=", "u64_ne", "u64_ne")
 });
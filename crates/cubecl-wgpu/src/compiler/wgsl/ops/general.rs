@@ -15,6 +15,7 @@ use pliron::{
 };
 
 use crate::compiler::wgsl::{
+    int64::{fmt_cast, is_emulated},
     lower::lower_binop,
     to_wgsl::{AttrToWgsl, TypeExtWgsl, wgsl_op, wgsl_op_with_out},
     value::WgslValue,
@@ -76,6 +77,10 @@ impl AttrToWgsl for IntegerAttr {
     fn to_wgsl(&self, ctx: &Context) -> String {
         let ty = self.get_type().to_handle();
         let val = self.value();
+        if is_emulated(ctx, ty) {
+            let bits = val.to_u64();
+            return format!("vec2<u32>({}u, {}u)", bits as u32, (bits >> 32) as u32);
+        }
         // naga can't seem to parse literals > i64::MAX or i64::MIN atm.
         // Work around this by emitting instructions to construct these literals.
         if ty.is_unsigned_int(ctx) && val.to_u64() > i64::MAX as u64 {
@@ -117,6 +122,10 @@ impl AttrToWgsl for ZeroAttr {
 }
 
 pub fn fmt_cast_to(ctx: &Context, value: Value, to: TypeHandle) -> String {
+    if is_emulated(ctx, value) || is_emulated(ctx, to) {
+        return fmt_cast(ctx, value, to, &to.to_wgsl(ctx));
+    }
+
     let from = value.get_type(ctx);
     let from_elem = from.scalar_ty(ctx);
     let to_elem = to.scalar_ty(ctx);
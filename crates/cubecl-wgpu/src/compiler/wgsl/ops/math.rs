@@ -6,12 +6,16 @@ use cubecl_ir::{Scope, dialect::math::*, interfaces::TypedExt, prelude::*, try_c
 use pliron::builtin::types::{IntegerType, Signedness};
 
 use crate::compiler::wgsl::{
+    int64::{fmt_binop, is_emulated},
     lower::{LowerOp, lower_binop, lower_unop},
     to_wgsl::wgsl_op_with_out,
 };
 
 wgsl_op_with_out!(SAbsOp, FAbsOp; |op, ctx| {
-    format!("abs({})", op.input(ctx).name(ctx))
+    match is_emulated(ctx, op.input(ctx)) {
+        true => format!("i64_abs({})", op.input(ctx).name(ctx)),
+        false => format!("abs({})", op.input(ctx).name(ctx)),
+    }
 });
 
 wgsl_op_with_out!(ExpOp; |op, ctx| {
@@ -94,27 +98,30 @@ wgsl_op_with_out!(RsqrtOp; |op, ctx| {
 });
 
 wgsl_op_with_out!(SNegOp, FNegOp; |op, ctx| {
-    format!("-{}", op.input(ctx).name(ctx))
+    match is_emulated(ctx, op.input(ctx)) {
+        true => format!("u64_neg({})", op.input(ctx).name(ctx)),
+        false => format!("-{}", op.input(ctx).name(ctx)),
+    }
 });
 
 wgsl_op_with_out!(IAddOp, FAddOp; |op, ctx| {
-    format!("{} + {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "+", "u64_add", "u64_add")
 });
 
 wgsl_op_with_out!(ISubOp, FSubOp; |op, ctx| {
-    format!("{} - {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "-", "u64_sub", "u64_sub")
 });
 
 wgsl_op_with_out!(IMulOp, FMulOp; |op, ctx| {
-    format!("{} * {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "*", "u64_mul", "u64_mul")
 });
 
 wgsl_op_with_out!(SDivOp, UDivOp, FDivOp; |op, ctx| {
-    format!("{} / {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "/", "u64_div", "i64_div")
 });
 
 wgsl_op_with_out!(SRemOp, URemOp, FRemOp; |op, ctx| {
-    format!("{} % {}", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+    fmt_binop(ctx, op.lhs(ctx), op.rhs(ctx), "%", "u64_rem", "i64_rem")
 });
 
 wgsl_op_with_out!(FmaOp; |op, ctx| {
@@ -8,6 +8,7 @@ use crate::compiler::wgsl::{
     AddressOfOp, GlobalVariableOp,
     lower::LowerOp,
     ops::general::attr_to_wgsl,
+    packed_float::{fmt_packed_index, fmt_packed_load, fmt_packed_store, packed_ptr},
     to_wgsl::{TypeExtWgsl, wgsl_op, wgsl_op_with_out},
     value::WgslValue,
 };
@@ -55,17 +56,39 @@ impl LowerOp for DeclareVariableOp {
     }
 }
 
-wgsl_op_with_out!(IndexOp; |op, ctx| {
-    format!("&(*{})[{}]", op.base(ctx).name(ctx), op.index(ctx).name(ctx))
+wgsl_op!(IndexOp, |op, ctx| {
+    let (out, base, index) = (op.get_result(ctx), op.base(ctx), op.index(ctx));
+    if packed_ptr(ctx, out).is_some() {
+        return fmt_packed_index(ctx, out, base, index);
+    }
+    let out = out.fmt_left(ctx);
+    format!("{out} = &(*{})[{}];\n", base.name(ctx), index.name(ctx))
 });
 
-wgsl_op_with_out!(LoadOp; |op, ctx| format!("*{}", op.ptr(ctx).name(ctx)));
+wgsl_op_with_out!(LoadOp; |op, ctx| {
+    let ptr = op.ptr(ctx);
+    match packed_ptr(ctx, ptr) {
+        Some(_) => fmt_packed_load(ctx, ptr),
+        None => format!("*{}", ptr.name(ctx)),
+    }
+});
 wgsl_op!(StoreOp, |op, ctx| {
-    let ptr = op.ptr(ctx).name(ctx);
-    format!("*{ptr} = {};\n", op.value(ctx).name(ctx))
+    let ptr = op.ptr(ctx);
+    let value = op.value(ctx).name(ctx);
+    match packed_ptr(ctx, ptr) {
+        Some(_) => fmt_packed_store(ctx, ptr, &value.to_string()),
+        None => format!("*{} = {value};\n", ptr.name(ctx)),
+    }
 });
 wgsl_op!(CopyOp, |op, ctx| {
     assert_eq!(op.len(ctx).0, 1, "WGSL doesn't support bulk copy");
-    let dest = op.destination(ctx).name(ctx);
-    format!("*{dest} = *{};\n", op.source(ctx).name(ctx))
+    let (dest, source) = (op.destination(ctx), op.source(ctx));
+    let value = match packed_ptr(ctx, source) {
+        Some(_) => fmt_packed_load(ctx, source),
+        None => format!("*{}", source.name(ctx)),
+    };
+    match packed_ptr(ctx, dest) {
+        Some(_) => fmt_packed_store(ctx, dest, &value),
+        None => format!("*{} = {value};\n", dest.name(ctx)),
+    }
 });
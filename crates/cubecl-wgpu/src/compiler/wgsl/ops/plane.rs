@@ -1,7 +1,7 @@
 use cubecl_core::{self as cubecl, prelude::*};
 use cubecl_ir::{dialect::plane::*, interfaces::TypedExt, prelude::*};
 
-use crate::compiler::wgsl::{lower::LowerOp, to_wgsl::wgsl_op_with_out};
+use crate::compiler::wgsl::{int64::NativeInt64Op, lower::LowerOp, to_wgsl::wgsl_op_with_out};
 
 // wgsl_op_with_out!(ElectOp; |_, _| "subgroupElect()".into());
 
@@ -53,6 +53,23 @@ wgsl_op_with_out!(SMaxOp, UMaxOp, FMaxOp; |op, ctx| {
     format!("subgroupMax({})", op.input(ctx).name(ctx))
 });
 
+macro_rules! native_int64 {
+    ($($ty: ty),*) => {
+        $(
+            #[op_interface_impl]
+            impl NativeInt64Op for $ty {
+                fn int64_operand(&self, ctx: &Context) -> Value {
+                    self.input(ctx)
+                }
+            }
+        )*
+    };
+}
+
+native_int64!(ISumOp, InclusiveISumOp, ExclusiveISumOp);
+native_int64!(IProdOp, InclusiveIProdOp, ExclusiveIProdOp);
+native_int64!(SMinOp, UMinOp, SMaxOp, UMaxOp);
+
 wgsl_op_with_out!(BallotOp; |op, ctx| {
     format!("subgroupBallot({})", op.input(ctx).name(ctx))
 });
@@ -74,6 +74,9 @@ wgsl_op_with_out!(NormalizeOp; |op, ctx| {
 wgsl_op_with_out!(SDotOp, UDotOp, FDotOp; |op, ctx| {
     format!("dot({}, {})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
 });
+wgsl_op_with_out!(SDot4x8PackedOp; |op, ctx| {
+    format!("dot4I8Packed({}, {})", op.lhs(ctx).name(ctx), op.rhs(ctx).name(ctx))
+});
 
 lower_unop!(ISumOp, sum);
 lower_unop!(FSumOp, sum);
@@ -0,0 +1,305 @@
+//! `bf16`, `e4m3` and `e5m2` as storage-only types.
+//!
+//! WGSL has no native type for any of these, so values live in `f32` registers and global buffers
+//! are declared as `array<atomic<u32>>` with two or four elements packed into each word. An element
+//! pointer into a packed buffer is emitted as the buffer pointer plus a separate scalar index
+//! (`{ptr}_idx`), since WGSL can't point into the middle of a word. Loads unpack and widen each
+//! lane, stores narrow with round-to-nearest-even and replace their bits with an `atomicAnd`/
+//! `atomicOr` pair, so invocations writing neighbouring elements of the same word don't race.
+//! Buffers that are only read don't need any of that, they're plain `array<u32>` and loaded
+//! directly.
+//!
+//! Shared memory and local variables aren't packed, they simply hold `f32`.
+
+use cubecl_ir::{
+    AddressSpace,
+    interfaces::TypedExt,
+    prelude::*,
+    types::{
+        PointerType, RuntimeArrayType,
+        scalar::{BFloat16Type, Float8E4M3Type, Float8E5M2Type},
+    },
+};
+use hashbrown::HashSet;
+use pliron::builtin::ops::ModuleOp;
+
+use crate::compiler::wgsl::{to_wgsl::wgsl_op, value::WgslValue};
+
+/// A float type stored packed into `u32` words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackedFloat {
+    BF16,
+    E4M3,
+    E5M2,
+}
+
+impl PackedFloat {
+    /// The packed float type of `ty`'s scalar element, if any.
+    pub fn of(ctx: &Context, ty: impl Typed) -> Option<Self> {
+        let elem = ty.scalar_ty(ctx).deref(ctx);
+        if elem.is::<BFloat16Type>() {
+            Some(PackedFloat::BF16)
+        } else if elem.is::<Float8E4M3Type>() {
+            Some(PackedFloat::E4M3)
+        } else if elem.is::<Float8E5M2Type>() {
+            Some(PackedFloat::E5M2)
+        } else {
+            None
+        }
+    }
+
+    fn bits(&self) -> u32 {
+        match self {
+            PackedFloat::BF16 => 16,
+            PackedFloat::E4M3 | PackedFloat::E5M2 => 8,
+        }
+    }
+
+    fn per_word(&self) -> u32 {
+        32 / self.bits()
+    }
+
+    /// The size of `len` bytes of this type once widened to `f32`.
+    pub fn f32_size(&self, len: usize) -> usize {
+        len * 32 / self.bits() as usize
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PackedFloat::BF16 => "bf16",
+            PackedFloat::E4M3 => "e4m3",
+            PackedFloat::E5M2 => "e5m2",
+        }
+    }
+}
+
+/// The packed float type and element vector size if `ptr` points to an element of a packed
+/// buffer. Pointers to the buffer itself aren't included, they're emitted as usual.
+pub fn packed_ptr(ctx: &Context, ptr: Value) -> Option<(PackedFloat, usize)> {
+    let ptr_ty = ptr.get_type(ctx).deref(ctx);
+    let PointerType {
+        inner,
+        address_space,
+    } = ptr_ty.downcast_ref::<PointerType>()?;
+    if !matches!(address_space, AddressSpace::Global(_))
+        || inner.deref(ctx).is::<RuntimeArrayType>()
+    {
+        return None;
+    }
+    let packed = PackedFloat::of(ctx, *inner)?;
+    Some((packed, inner.vector_size(ctx)))
+}
+
+/// Whether `ty` is a packed buffer, which is declared as `array<atomic<u32>>`, or `array<u32>` if
+/// it's read-only.
+pub fn is_packed_buffer(ctx: &Context, ty: TypeHandle) -> bool {
+    ty.deref(ctx).is::<RuntimeArrayType>() && PackedFloat::of(ctx, ty).is_some()
+}
+
+/// Positions of the packed buffers that are never written, which don't need atomic access.
+#[derive(Clone, Debug, Default)]
+pub struct ReadOnlyPackedBuffers(pub HashSet<usize>);
+
+impl ReadOnlyPackedBuffers {
+    pub fn contains(ctx: &Context, buffer_pos: usize) -> bool {
+        ctx.aux_ty::<Self>().0.contains(&buffer_pos)
+    }
+}
+
+/// Whether the element pointer `ptr` points into a read-only packed buffer.
+fn is_read_only(ctx: &Context, ptr: Value) -> bool {
+    let ptr_ty = ptr.get_type(ctx).deref(ctx);
+    match ptr_ty.downcast_ref::<PointerType>() {
+        Some(PointerType {
+            address_space: AddressSpace::Global(pos),
+            ..
+        }) => ReadOnlyPackedBuffers::contains(ctx, *pos),
+        _ => false,
+    }
+}
+
+/// Formats an index into a packed buffer, which only offsets the scalar index.
+pub fn fmt_packed_index(ctx: &Context, out: Value, base: Value, index: Value) -> String {
+    let (_, vector_size) = packed_ptr(ctx, out).expect("Should be packed");
+    let out = out.name(ctx);
+    let index = index.name(ctx);
+    let base_name = base.name(ctx);
+    let idx = match packed_ptr(ctx, base) {
+        // Indexing a lane of a vector element
+        Some(_) => format!("{base_name}_idx + {index}"),
+        None => format!("{index} * {vector_size}u"),
+    };
+    format!("let {out} = {base_name};\nlet {out}_idx = {idx};\n")
+}
+
+/// Formats an unpacking load from the packed element `ptr`.
+pub fn fmt_packed_load(ctx: &Context, ptr: Value) -> String {
+    let (packed, vector_size) = packed_ptr(ctx, ptr).expect("Should be packed");
+    let (name, bits, per_word) = (packed.name(), packed.bits(), packed.per_word());
+    let read_only = is_read_only(ctx, ptr);
+    let ptr = ptr.name(ctx);
+    let lanes = (0..vector_size).map(|i| {
+        let idx = format!("({ptr}_idx + {i}u)");
+        let word = match read_only {
+            true => format!("(*{ptr})[{idx} / {per_word}u]"),
+            false => format!("atomicLoad(&(*{ptr})[{idx} / {per_word}u])"),
+        };
+        format!("{name}_to_f32(extractBits({word}, {idx} % {per_word}u * {bits}u, {bits}u))")
+    });
+    match vector_size {
+        1 => lanes.collect(),
+        _ => format!(
+            "vec{vector_size}<f32>({})",
+            lanes.collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Formats a packing store of the `f32` expression `value` to the packed element `ptr`.
+pub fn fmt_packed_store(ctx: &Context, ptr: Value, value: &str) -> String {
+    let (packed, vector_size) = packed_ptr(ctx, ptr).expect("Should be packed");
+    let (name, bits, per_word) = (packed.name(), packed.bits(), packed.per_word());
+    let mask = (1u64 << bits) - 1;
+    let ptr = ptr.name(ctx);
+    let mut out = format!("{{\nlet packed_value = {value};\n");
+    for i in 0..vector_size {
+        let lane = match vector_size {
+            1 => "packed_value".to_string(),
+            _ => format!("packed_value[{i}]"),
+        };
+        out.push_str(&format!(
+            "{{
+let idx = {ptr}_idx + {i}u;
+let shift = idx % {per_word}u * {bits}u;
+atomicAnd(&(*{ptr})[idx / {per_word}u], ~({mask}u << shift));
+atomicOr(&(*{ptr})[idx / {per_word}u], f32_to_{name}({lane}) << shift);
+}}
+"
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Declares the conversion functions used by packed floats.
+#[cube_op(name = "wgsl.packed_float_polyfill")]
+#[result_ty(none)]
+pub struct PackedFloatPolyfillOp {}
+
+wgsl_op!(PackedFloatPolyfillOp, |_, _| PACKED_FLOAT_POLYFILL
+    .to_string());
+
+/// Inserts [`PackedFloatPolyfillOp`] if the module uses packed floats. Must run before
+/// [`EnableFeaturesPass`](super::EnableFeaturesPass), since `enable` directives have to come
+/// first.
+pub struct PackedFloatPolyfillPass;
+
+#[pass_name]
+impl Pass for PackedFloatPolyfillPass {
+    fn run(
+        &mut self,
+        op: Ptr<Operation>,
+        ctx: &mut Context,
+        _analyses: &mut AnalysisManager,
+    ) -> Result<PassResult> {
+        let mut res = PassResult::default();
+
+        let mut used = false;
+        visit_all_values(ctx, &mut used, op, |ctx, used, val| {
+            *used |= val
+                .try_get_scalar_elem_ty(ctx)
+                .is_some_and(|elem| PackedFloat::of(ctx, elem).is_some());
+        });
+
+        if used {
+            let module_body = op.as_op::<ModuleOp>(ctx).unwrap().get_body(ctx, 0);
+            let polyfill = PackedFloatPolyfillOp::new(ctx);
+            polyfill.get_operation().insert_at_front(module_body, ctx);
+            res.ir_changed = IRStatus::Changed;
+        }
+        Ok(res)
+    }
+}
+
+/// Non-finite values are built from the input bits, because WGSL rejects NaN and infinity in
+/// constant expressions.
+const PACKED_FLOAT_POLYFILL: &str = r#"
+fn bf16_to_f32(bits: u32) -> f32 {
+    return bitcast<f32>(bits << 16u);
+}
+
+fn f32_to_bf16(value: f32) -> u32 {
+    let bits = bitcast<u32>(value);
+    if (bits & 0x7fffffffu) > 0x7f800000u {
+        return (bits >> 16u) | 0x40u;
+    }
+    return (bits + 0x7fffu + ((bits >> 16u) & 1u)) >> 16u;
+}
+
+fn minifloat_to_f32(bits: u32, exp_bits: u32, man_bits: u32, bias: i32) -> f32 {
+    let man = bits & ((1u << man_bits) - 1u);
+    let exp = i32(extractBits(bits, man_bits, exp_bits));
+    var mag: f32;
+    if exp == 0 {
+        mag = f32(man) * exp2(f32(1 - bias - i32(man_bits)));
+    } else {
+        mag = f32(man | (1u << man_bits)) * exp2(f32(exp - bias - i32(man_bits)));
+    }
+    return select(mag, -mag, (bits & 0x80u) != 0u);
+}
+
+fn minifloat_from_f32(value: f32, man_bits: u32, bias: i32, max_value: f32) -> u32 {
+    let mag = min(abs(value), max_value);
+    let exp = max(i32(extractBits(bitcast<u32>(mag), 23u, 8u)) - 127, 1 - bias);
+    let one = 1u << man_bits;
+    // `round` is round-half-to-even, and the scale is a power of two so the product is exact
+    var man = u32(round(mag * exp2(f32(i32(man_bits) - exp))));
+    var biased = exp + bias;
+    if man >= 2u * one {
+        man = one;
+        biased += 1;
+    }
+    if man < one {
+        return man;
+    }
+    return (u32(biased) << man_bits) | (man - one);
+}
+
+fn e4m3_to_f32(bits: u32) -> f32 {
+    if (bits & 0x7fu) == 0x7fu {
+        return bitcast<f32>(((bits & 0x80u) << 24u) | 0x7fc00000u);
+    }
+    return minifloat_to_f32(bits, 4u, 3u, 7);
+}
+
+// No infinity, out of range values saturate
+fn f32_to_e4m3(value: f32) -> u32 {
+    let bits = bitcast<u32>(value);
+    let sign = (bits >> 24u) & 0x80u;
+    if (bits & 0x7fffffffu) > 0x7f800000u {
+        return sign | 0x7fu;
+    }
+    return sign | minifloat_from_f32(value, 3u, 7, 448.0);
+}
+
+fn e5m2_to_f32(bits: u32) -> f32 {
+    if (bits & 0x7cu) == 0x7cu {
+        let nan = select(0u, 0x400000u, (bits & 0x3u) != 0u);
+        return bitcast<f32>(((bits & 0x80u) << 24u) | 0x7f800000u | nan);
+    }
+    return minifloat_to_f32(bits, 5u, 2u, 15);
+}
+
+fn f32_to_e5m2(value: f32) -> u32 {
+    let bits = bitcast<u32>(value);
+    let sign = (bits >> 24u) & 0x80u;
+    if (bits & 0x7fffffffu) > 0x7f800000u {
+        return sign | 0x7fu;
+    }
+    // Halfway between the largest finite value and the next power of two rounds to infinity
+    if abs(value) >= 61440.0 {
+        return sign | 0x7cu;
+    }
+    return sign | minifloat_from_f32(value, 2u, 15, 57344.0);
+}
+"#;
@@ -1,6 +1,6 @@
 use core::fmt::{self, Display, Write};
 
-use cubecl_core::prelude::Visibility;
+use cubecl_core::{prelude::Visibility, throughput::KernelCost};
 use cubecl_ir::{
     AddressSpace, CanMaterialize, GlobalState, Pure,
     attributes::{
@@ -29,6 +29,7 @@ use pliron::{
 
 use crate::compiler::wgsl::{
     builtin::{ATTR_BUILTIN, BuiltInAttr},
+    packed_float::{PackedFloat, ReadOnlyPackedBuffers, is_packed_buffer},
     to_wgsl::{OpExtWgsl, OpToWgsl, TypeExtWgsl, wgsl_op, wgsl_op_with_out},
     value::WgslValue,
 };
@@ -49,7 +50,15 @@ pub struct GlobalVariableOp {
 impl OpToWgsl for GlobalVariableOp {
     fn to_wgsl(&self, ctx: &Context) -> String {
         let name = self.get_symbol_name(ctx);
-        let ty = self.value_ty(ctx).get_type(ctx).to_wgsl(ctx);
+        let binding = self.buffer_binding(ctx).map(|it| *it);
+        let ty = match binding {
+            Some(BufferBindingAttr { buffer_pos, .. })
+                if ReadOnlyPackedBuffers::contains(ctx, buffer_pos) =>
+            {
+                "array<u32>".to_string()
+            }
+            _ => self.value_ty(ctx).get_type(ctx).to_wgsl(ctx),
+        };
         let addr_space = match self.address_space(ctx).0 {
             AddressSpace::Global(_) => {
                 let io = *self.buffer_io(ctx).expect("Should have IO");
@@ -62,7 +71,7 @@ impl OpToWgsl for GlobalVariableOp {
             AddressSpace::Shared => "workgroup",
             AddressSpace::Local => "function",
         };
-        if let Some(BufferBindingAttr { buffer_pos, .. }) = self.buffer_binding(ctx).map(|it| *it) {
+        if let Some(BufferBindingAttr { buffer_pos, .. }) = binding {
             format!("@group(0) @binding({buffer_pos}) var<{addr_space}> {name}: {ty};\n")
         } else {
             format!("var<{addr_space}> {name}: {ty};\n")
@@ -91,6 +100,7 @@ pub fn rewrite_args(ctx: &mut Context, func: FuncOp) -> Vec<Visibility> {
     rewriter.set_insertion_point_to_block_start(func.get_entry_block(ctx));
 
     let mut buffers = vec![];
+    let mut read_only_packed = ReadOnlyPackedBuffers::default();
     let args = func.get_entry_block(ctx).arguments(ctx);
 
     // Back to front so indices don't shift when args get removed
@@ -111,6 +121,11 @@ pub fn rewrite_args(ctx: &mut Context, func: FuncOp) -> Vec<Visibility> {
             *io
         };
 
+        let value_ty = arg.get_type(ctx).unwrap_ptr(ctx);
+        // Written packed buffers are atomic, read-only ones are plain words
+        if is_packed_buffer(ctx, value_ty) && !io.is_writable() {
+            read_only_packed.0.insert(binding.buffer_pos);
+        }
         if !cfg!(exclusive_memory_only) {
             io = BufferIOAttr::ReadWrite;
         }
@@ -121,7 +136,6 @@ pub fn rewrite_args(ctx: &mut Context, func: FuncOp) -> Vec<Visibility> {
             buffers.insert(0, Visibility::Read);
         }
 
-        let value_ty = arg.get_type(ctx).unwrap_ptr(ctx);
         let var = GlobalVariableOp::new(
             ctx,
             value_ty,
@@ -138,6 +152,7 @@ pub fn rewrite_args(ctx: &mut Context, func: FuncOp) -> Vec<Visibility> {
         func.remove_argument(ctx, i);
     }
 
+    ctx.set_aux_ty(read_only_packed);
     buffers
 }
 
@@ -145,7 +160,12 @@ pub fn shared_memory_size(ctx: &Context, op: Ptr<Operation>) -> usize {
     let mut size = 0;
     visit_all_ops_of_type::<GlobalVariableOp, _>(ctx, &mut size, op, |ctx, size, op| {
         if matches!(op.address_space(ctx).0, AddressSpace::Shared) {
-            *size += op.value_ty(ctx).size(ctx);
+            let value_ty = op.value_ty(ctx).get_type(ctx);
+            // Packed floats aren't packed in shared memory, they're emitted as `f32`
+            *size += match PackedFloat::of(ctx, value_ty) {
+                Some(packed) => packed.f32_size(value_ty.size(ctx)),
+                None => value_ty.size(ctx),
+            };
         }
     });
     size
@@ -211,6 +231,8 @@ impl Pass for EnableFeaturesPass {
 pub struct ComputeShader {
     pub buffers: Vec<Visibility>,
     pub shared_memory_size: usize,
+    /// The static cost of one unit, counted on the lowered IR.
+    pub cost: KernelCost,
     pub ctx: Context,
 }
 
@@ -0,0 +1,59 @@
+use cubecl_core::{
+    Compiler, WgpuCompilationOptions,
+    ir::{ElemType, UIntKind, settings::Dim3},
+    prelude::*,
+};
+
+use cubecl_runtime::compiler::CompilationError;
+
+use super::WgslCompiler;
+
+/// The WGSL source of `kernel` expanded over an input and an output buffer, compiled with
+/// `options` instead of the ones of an adapter.
+pub(crate) fn compile_wgsl<I, O>(
+    options: WgpuCompilationOptions,
+    kernel: impl FnOnce(&Scope, I::ExpandType, O::ExpandType),
+) -> String
+where
+    I: LaunchArg<CompilationArg = BufferCompilationArg>,
+    O: LaunchArg<CompilationArg = BufferCompilationArg>,
+{
+    try_compile_wgsl::<I, O>(options, kernel).expect("Should compile")
+}
+
+/// Like [`compile_wgsl`], for kernels the compiler may reject.
+pub(crate) fn try_compile_wgsl<I, O>(
+    options: WgpuCompilationOptions,
+    kernel: impl FnOnce(&Scope, I::ExpandType, O::ExpandType),
+) -> Result<String, CompilationError>
+where
+    I: LaunchArg<CompilationArg = BufferCompilationArg>,
+    O: LaunchArg<CompilationArg = BufferCompilationArg>,
+{
+    let mut builder = KernelBuilder::new(KernelSettings::new(
+        Dim3::new_single(),
+        ExecutionMode::Checked,
+        AddressType::U32,
+    ));
+    builder
+        .scope
+        .register_type::<usize>(ElemType::UInt(UIntKind::U32));
+
+    let arg = BufferCompilationArg { inplace: None };
+    let input = I::expand(&arg, &mut builder);
+    let output = O::expand(&arg, &mut builder);
+    kernel(&builder.scope, input, output);
+
+    WgslCompiler
+        .compile(builder.build(), &options)
+        .map(|shader| shader.to_string())
+}
+
+/// Parse and validate `source` with naga, for a device limited to `capabilities`.
+pub(crate) fn validate_wgsl(source: &str, capabilities: naga::valid::Capabilities) {
+    let module = naga::front::wgsl::parse_str(source)
+        .unwrap_or_else(|err| panic!("{}\n{source}", err.emit_to_string(source)));
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), capabilities)
+        .validate(&module)
+        .unwrap_or_else(|err| panic!("{err:?}\n{source}"));
+}
@@ -2,12 +2,20 @@ use cubecl_ir::{
     prelude::*,
     types::{
         ArrayType, AtomicType, RuntimeArrayType, VectorType,
-        scalar::{BoolType, Float16Type, Float32Type, Float64Type, FloatFlex32Type, IndexType},
+        scalar::{
+            BFloat16Type, BoolType, Float8E4M3Type, Float8E5M2Type, Float16Type, Float32Type,
+            Float64Type, FloatFlex32Type, IndexType,
+        },
     },
 };
 use pliron::{builtin::types::IntegerType, identifier::Identifier};
 
-use crate::compiler::wgsl::to_wgsl::{TypeExtWgsl, TypeToWgsl};
+use crate::compiler::wgsl::{
+    float_atomic::is_emulated_atomic,
+    int64::emulates_int64,
+    packed_float::PackedFloat,
+    to_wgsl::{TypeExtWgsl, TypeToWgsl},
+};
 
 macro_rules! scalar_ty {
     ($ty: ty, $wgsl: literal) => {
@@ -24,6 +32,10 @@ scalar_ty!(Float16Type, "f16");
 scalar_ty!(Float32Type, "f32");
 scalar_ty!(FloatFlex32Type, "f32");
 scalar_ty!(Float64Type, "f64");
+// Storage-only, values are widened to `f32` and buffers are packed
+scalar_ty!(BFloat16Type, "f32");
+scalar_ty!(Float8E4M3Type, "f32");
+scalar_ty!(Float8E5M2Type, "f32");
 scalar_ty!(BoolType, "bool");
 
 #[type_interface_impl]
@@ -35,7 +47,10 @@ impl TypeToWgsl for IndexType {
 
 #[type_interface_impl]
 impl TypeToWgsl for IntegerType {
-    fn to_wgsl(&self, _ctx: &Context) -> String {
+    fn to_wgsl(&self, ctx: &Context) -> String {
+        if self.width() == 64 && emulates_int64(ctx) {
+            return "vec2<u32>".into();
+        }
         match self.is_signed() {
             true => format!("i{}", self.width()),
             false => format!("u{}", self.width()),
@@ -53,6 +68,9 @@ impl TypeToWgsl for VectorType {
 #[type_interface_impl]
 impl TypeToWgsl for AtomicType {
     fn to_wgsl(&self, ctx: &Context) -> String {
+        if is_emulated_atomic(ctx, self.inner) {
+            return "atomic<u32>".into();
+        }
         format!("atomic<{}>", self.inner.to_wgsl(ctx))
     }
 }
@@ -67,6 +85,9 @@ impl TypeToWgsl for ArrayType {
 #[type_interface_impl]
 impl TypeToWgsl for RuntimeArrayType {
     fn to_wgsl(&self, ctx: &Context) -> String {
+        if PackedFloat::of(ctx, self.inner).is_some() {
+            return "array<atomic<u32>>".into();
+        }
         format!("array<{}>", self.inner.to_wgsl(ctx))
     }
 }
@@ -18,6 +18,7 @@
 
 use crate::WgpuResource;
 use crate::schedule::Addresses;
+use cubecl_core::server::{GraphParamSite, GraphParamTable};
 use cubecl_runtime::memory_management::{ManagedMemoryHandle, SharedMemoryBindings};
 use std::sync::Arc;
 use wgpu::ComputePipeline;
@@ -42,6 +43,9 @@ pub struct WgpuGraph {
     /// where [`WgpuStream::flush`](super::stream::WgpuStream::flush) releases
     /// them on the normal path.
     pub(crate) _shared: SharedMemoryBindings,
+    /// The graph's parameters, patched into the recorded tasks by
+    /// [`WgpuStream::update_graph`](super::stream::WgpuStream::update_graph).
+    pub(crate) params: GraphParamTable,
 }
 
 /// One recorded dispatch, resolved down to what `wgpu` needs at encode time.
@@ -66,6 +70,27 @@ pub(crate) struct ReplayTask {
     /// (Vulkan buffer-address mode, where usage tracking cannot see them).
     pub(crate) transitions: Vec<WgpuResource>,
     pub(crate) dispatch: ReplayDispatch,
+    /// What patching the graph parameters living in this task takes; `None`
+    /// for a task using none.
+    pub(crate) params: Option<Box<TaskParams>>,
+}
+
+/// The state a graph update patches in one recorded task.
+///
+/// A rebound buffer rebuilds the bind group, or in Vulkan buffer-address mode
+/// recomputes the addresses. A rewritten scalar lands in the task's info
+/// uniform, which was staged for the task alone (not through the info cache)
+/// so that no other launch reads the new value.
+#[derive(Debug)]
+pub(crate) struct TaskParams {
+    pub(crate) sites: Vec<GraphParamSite>,
+    /// The launch's buffers in binding order, followed by its info uniform.
+    pub(crate) buffers: Vec<WgpuResource>,
+    /// The uniform holding the addresses of `buffers`, in Vulkan
+    /// buffer-address mode when they are not passed as immediates.
+    pub(crate) address_buffer: Option<WgpuResource>,
+    /// The words of the info uniform, when a scalar parameter lives in it.
+    pub(crate) info: Option<Vec<u64>>,
 }
 
 /// The dispatch shape of a recorded task.
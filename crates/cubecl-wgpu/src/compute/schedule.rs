@@ -6,7 +6,7 @@ use alloc::sync::Arc;
 use cubecl_common::{bytes::Bytes, pool::LeaseHandle, profile::TimingMethod};
 use cubecl_core::{
     CubeCount, MemoryConfiguration,
-    server::{MetadataBindingInfo, StreamErrorMode},
+    server::{GraphParamSite, MetadataBindingInfo, StreamErrorMode},
     zspace::SmallVec,
 };
 use cubecl_ir::MemoryDeviceProperties;
@@ -67,6 +67,9 @@ pub struct BindingsResource {
     /// Which compiler was used. This determines the passing strategy of params.
     /// WGSL and metal use bindings, Vulkan uses buffer addresses sent via a uniform buffer.
     pub compiler_info: CompilerInfo,
+    /// Where the graph parameters marked on a recording stream live in this
+    /// launch; empty outside of a capture.
+    pub params: Vec<GraphParamSite>,
 }
 
 /// Represents a WGPU backend for scheduling tasks on streams.
@@ -175,25 +178,39 @@ impl ScheduledWgpuBackend {
 
 pub type Addresses = SmallVec<[u64; 8]>;
 
+/// The buffer device addresses of `resources`, as passed to a kernel in Vulkan
+/// buffer-address mode.
+pub(crate) fn addresses(resources: &[WgpuResource]) -> Addresses {
+    resources
+        .iter()
+        .map(|it| it.address.unwrap().get() + it.offset)
+        .collect()
+}
+
 impl BindingsResource {
     /// Converts metadata and scalar bindings into WGPU resources for a stream.
+    ///
+    /// `dedicated_info` stages the info into a uniform of the launch's own
+    /// rather than a cached one other launches may share, for a recorded
+    /// launch whose scalars a graph update rewrites in place.
     pub fn into_resources(
         mut self,
         stream: &mut WgpuStream,
+        dedicated_info: bool,
     ) -> (Vec<WgpuResource>, Vec<WgpuResource>, Option<Addresses>) {
-        let info = (!self.info.data.is_empty())
-            .then(|| stream.info_uniform(core::mem::take(&mut self.info.data)));
+        let info = (!self.info.data.is_empty()).then(|| {
+            if dedicated_info {
+                stream.create_uniform(bytemuck::cast_slice(&self.info.data))
+            } else {
+                stream.info_uniform(core::mem::take(&mut self.info.data))
+            }
+        });
         match self.compiler_info {
             CompilerInfo::Vulkan { params_transfer } => {
-                let addresses = self
-                    .resources
-                    .iter()
-                    .chain(info.iter())
-                    .map(|it| it.address.unwrap().get() + it.offset)
-                    .collect::<Addresses>();
                 if let Some(info) = info {
                     self.resources.push(info);
                 }
+                let addresses = addresses(&self.resources);
                 match params_transfer {
                     ParamsTransfer::Immediate => (vec![], self.resources, Some(addresses)),
                     ParamsTransfer::Uniform => {
@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 
 use super::graph::WgpuGraph;
 use super::storage::{WgpuResource, WgpuStorage};
+use super::stream::WgpuEvent;
 use crate::WgpuCompiler;
 use crate::schedule::{BindingsResource, ScheduleTask, ScheduledWgpuBackend};
 use alloc::sync::Arc;
@@ -11,7 +12,9 @@ use cubecl_common::{
     bytes::Bytes,
     profile::{ProfileDuration, TimingMethod},
 };
-use cubecl_core::server::{BufferBinding, KernelResource, StreamErrorMode};
+use cubecl_core::server::{
+    BufferBinding, GraphParamSite, GraphParamValue, KernelResource, StreamErrorMode,
+};
 use cubecl_core::zspace::Shape;
 use cubecl_core::{
     MemoryConfiguration, WgpuCompilationOptions,
@@ -36,9 +39,9 @@ use cubecl_runtime::memory_management::{
 };
 use cubecl_runtime::{
     compiler::{CompilationCache, CubeTask},
-    config::{CubeClRuntimeConfig, RuntimeConfig},
+    config::{CubeClRuntimeConfig, RuntimeConfig, streaming::StreamPriority},
     dry_run::LaunchMode,
-    id::GraphId,
+    id::{EventId, GraphId, GraphParamId},
     logging::ServerLogger,
     memory_management::MemoryAllocationMode,
     server::ComputeServer,
@@ -89,6 +92,8 @@ pub struct WgpuServer<C: WgpuCompiler> {
     /// the client. `end_capture` inserts, `replay` looks up, `graph_destroy`
     /// removes (dropping the [`WgpuGraph`] unpins the buffers it retained).
     graphs: HashMap<GraphId, WgpuGraph>,
+    /// Events recorded on request, keyed by the [`EventId`] handed to the client.
+    events: HashMap<EventId, WgpuEvent>,
     _compiler: PhantomData<C>,
 }
 
@@ -152,6 +157,7 @@ impl<C: WgpuCompiler> WgpuServer<C> {
                     max_streams,
                     max_tasks: tasks_max,
                     strategy: SchedulerStrategy::Interleave,
+                    priority: config.streaming.priority,
                 },
             ),
             #[cfg(feature = "spirv")]
@@ -162,6 +168,7 @@ impl<C: WgpuCompiler> WgpuServer<C> {
             utilities: Arc::new(utilities),
             shared_bindings_pool: LeasePool::with_capacity(tasks_max * max_streams as usize),
             graphs: HashMap::new(),
+            events: HashMap::new(),
             _compiler: PhantomData,
         }
     }
@@ -170,6 +177,7 @@ impl<C: WgpuCompiler> WgpuServer<C> {
         &mut self,
         bindings: KernelArguments,
         compiler_info: CompilerInfo,
+        params: Vec<GraphParamSite>,
     ) -> Result<BindingsResource, IoError> {
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
@@ -190,6 +198,7 @@ impl<C: WgpuCompiler> WgpuServer<C> {
             resources,
             info: bindings.info,
             compiler_info,
+            params,
         })
     }
 
@@ -438,6 +447,23 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             return;
         }
 
+        // Locate the graph parameters marked on a recording stream in this
+        // launch's arguments, before they are resolved.
+        let params = {
+            let stream = self.scheduler.stream(&stream_id);
+            if stream.capturing.is_recording() {
+                match stream.param_marks.sites(&args) {
+                    Ok(params) => params,
+                    Err(err) => {
+                        stream.errors.push(err);
+                        return;
+                    }
+                }
+            } else {
+                Vec::new()
+            }
+        };
+
         self.streams_pool.clear();
         // Reuse a pooled buffer to avoid allocating on every launch; it returns to the pool
         // automatically when the guard drops.
@@ -455,7 +481,7 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             }
         });
 
-        let resources = match self.prepare_bindings(args, compiler_info) {
+        let resources = match self.prepare_bindings(args, compiler_info, params) {
             Ok(val) => val,
             Err(err) => {
                 // We make the stream that would execute the kernel in error.
@@ -500,6 +526,45 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         stream.sync()
     }
 
+    fn event_record(&mut self, stream_id: StreamId) -> Result<EventId, ServerError> {
+        self.scheduler.execute_streams(vec![stream_id]);
+        let event = self.scheduler.stream(&stream_id).record_event()?;
+        let id = EventId::new();
+        self.events.insert(id, event);
+        Ok(id)
+    }
+
+    fn event_wait(&mut self, event: EventId, stream_id: StreamId) -> Result<(), ServerError> {
+        // The event was submitted when recorded, and the queue runs submissions in order:
+        // whatever `stream_id` submits from now on already runs after it.
+        let _ = stream_id;
+        match self.events.contains_key(&event) {
+            true => Ok(()),
+            false => Err(ServerError::unknown_event(event)),
+        }
+    }
+
+    fn event_query(&mut self, event: EventId) -> Result<bool, ServerError> {
+        self.events
+            .get(&event)
+            .map(WgpuEvent::is_complete)
+            .ok_or_else(|| ServerError::unknown_event(event))
+    }
+
+    fn event_synchronize(&mut self, event: EventId) -> DynFut<Result<(), ServerError>> {
+        match self.events.get(&event) {
+            Some(recorded) => recorded.synchronize(),
+            None => {
+                let err = ServerError::unknown_event(event);
+                Box::pin(async move { Err(err) })
+            }
+        }
+    }
+
+    fn event_destroy(&mut self, event: EventId) {
+        self.events.remove(&event);
+    }
+
     fn start_profile(&mut self, stream_id: StreamId) -> Result<ProfilingToken, ServerError> {
         // Recorded launches do not execute, so a profile of the window would
         // measure nothing.
@@ -522,6 +587,10 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         stream.end_profile(token)
     }
 
+    fn set_stream_priority(&mut self, stream_id: StreamId, priority: StreamPriority) {
+        self.scheduler.set_priority(stream_id, priority);
+    }
+
     fn memory_usage(&mut self, stream_id: StreamId) -> Result<MemoryUsage, ServerError> {
         self.scheduler.execute_streams(vec![stream_id]);
         let stream = self.scheduler.stream(&stream_id);
@@ -642,11 +711,19 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         let errors = stream.flush_errors_queue();
         if !errors.is_empty() {
             stream.info_cache.capture_discard();
+            stream.param_marks.clear();
             return Err(ServerError::ServerUnhealthy {
                 errors,
                 backtrace: BackTrace::capture(),
             });
         }
+        let params = match stream.param_marks.finish() {
+            Ok(params) => params,
+            Err(err) => {
+                stream.info_cache.capture_discard();
+                return Err(err);
+            }
+        };
 
         let id = GraphId::new();
         // Seal the info-cache entries this capture pinned under the graph's
@@ -659,6 +736,7 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
                 tasks: recording.tasks,
                 _retained: retained,
                 _shared: recording.shared,
+                params,
             },
         );
         Ok(id)
@@ -712,6 +790,76 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         stream.info_cache.graph_release(graph);
         drop(wgpu_graph);
     }
+
+    fn graph_param(
+        &mut self,
+        value: GraphParamValue,
+        stream_id: StreamId,
+    ) -> Result<GraphParamId, ServerError> {
+        let stream = self.scheduler.stream(&stream_id);
+        if !stream.capturing.is_recording() {
+            return Err(ServerError::graph_state(
+                "graph_param: no graph capture is recording on this stream",
+            ));
+        }
+        stream.param_marks.mark(value)
+    }
+
+    fn graph_update(
+        &mut self,
+        graph: GraphId,
+        updates: Vec<(GraphParamId, GraphParamValue)>,
+        stream_id: StreamId,
+    ) -> Result<(), ServerError> {
+        if !self.graphs.contains_key(&graph) {
+            return Err(ServerError::graph_state(
+                "graph_update was given an unknown or already-destroyed graph",
+            ));
+        }
+
+        // Order the update after the work queued on the graph's stream and on
+        // the streams the new bindings were written from.
+        let mut streams = vec![stream_id];
+        for (_, value) in updates.iter() {
+            if let GraphParamValue::Binding(binding) = value
+                && !streams.contains(&binding.stream)
+            {
+                streams.push(binding.stream);
+            }
+        }
+        self.scheduler.execute_streams(streams);
+
+        // Resolve every new binding before touching the graph, so a failure
+        // leaves it as it was.
+        let mut bindings = Vec::new();
+        for (param, value) in updates.iter() {
+            if let GraphParamValue::Binding(binding) = value {
+                let stream = self.scheduler.stream(&binding.stream);
+                let resource = stream.mem_manage.get_resource(binding.clone())?;
+                bindings.push((*param, resource));
+            }
+        }
+
+        let wgpu_graph = self.graphs.get_mut(&graph).expect("looked up above");
+        let replaced = wgpu_graph.params.update(&updates)?;
+        let stream = self.scheduler.stream(&stream_id);
+        stream.update_graph(wgpu_graph, &updates, &bindings);
+
+        // Like in `graph_destroy`: submit the replays still sitting in the
+        // encoder before the replaced bindings drop, queue ordering does the rest.
+        if replaced
+            .iter()
+            .any(|value| matches!(value, GraphParamValue::Binding(_)))
+        {
+            let _ = stream
+                .flush(StreamErrorMode {
+                    ignore: true,
+                    flush: false,
+                })
+                .ok();
+        }
+        Ok(())
+    }
 }
 
 pub(crate) fn contiguous_strides(shape: &Shape) -> Strides {
@@ -1,5 +1,5 @@
 use super::{
-    graph::{GraphRecording, ReplayDispatch, ReplayTask, WgpuGraph},
+    graph::{GraphRecording, ReplayDispatch, ReplayTask, TaskParams, WgpuGraph},
     mem_manager::WgpuMemManager,
     poll::WgpuPoll,
     timings::{QueryProfiler, TimestampQuerySetBudget},
@@ -7,7 +7,7 @@ use super::{
 use crate::{
     WgpuResource,
     controller::WgpuAllocController,
-    schedule::{Addresses, ScheduleTask},
+    schedule::{Addresses, ScheduleTask, addresses},
 };
 use core::iter;
 #[cfg(renderdoc)]
@@ -18,7 +18,10 @@ use cubecl_common::{
 };
 use cubecl_core::{
     CubeCount, MemoryConfiguration,
-    server::{IoError, ProfileError, ProfilingToken, ServerError, StreamErrorMode},
+    server::{
+        GraphParamMarks, GraphParamSite, GraphParamTarget, GraphParamValue, IoError, ProfileError,
+        ProfilingToken, ServerError, StreamErrorMode,
+    },
     zspace::Shape,
 };
 use cubecl_environment::backtrace::BackTrace;
@@ -27,6 +30,7 @@ use cubecl_environment::future::{self, DynFut};
 use cubecl_environment::sync::Mutex;
 use cubecl_ir::MemoryDeviceProperties;
 use cubecl_runtime::{
+    id::GraphParamId,
     logging::ServerLogger,
     memory_management::{ManagedMemoryHandle, SharedMemoryBindings},
     metadata_cache::{MetadataCachePolicy, MetadataInfoCache},
@@ -35,7 +39,15 @@ use cubecl_runtime::{
 };
 #[cfg(renderdoc)]
 use renderdoc::{RenderDoc, V100};
-use std::{future::Future, num::NonZero, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    num::NonZero,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 use wgpu::ComputePipeline;
 
 #[cfg(renderdoc)]
@@ -51,6 +63,77 @@ enum Timings {
     System(TimestampProfiler),
 }
 
+/// A user event: the queue submission that closed the work enqueued on a stream when it was
+/// recorded.
+///
+/// Every stream submits to the device's single queue, and wgpu runs submissions in order, so
+/// anything submitted after the event is already ordered after it.
+#[derive(Debug, Clone)]
+pub struct WgpuEvent {
+    done: Arc<AtomicBool>,
+    #[cfg(not(target_family = "wasm"))]
+    index: wgpu::SubmissionIndex,
+    #[cfg(not(target_family = "wasm"))]
+    device: wgpu::Device,
+    #[cfg(target_family = "wasm")]
+    queue: wgpu::Queue,
+}
+
+impl WgpuEvent {
+    /// Whether the event has completed, without blocking.
+    pub fn is_complete(&self) -> bool {
+        // Completion callbacks only run when the device is polled. On wasm, the browser
+        // polls on its own.
+        #[cfg(not(target_family = "wasm"))]
+        if let Err(e) = self.device.poll(wgpu::PollType::Poll) {
+            log::warn!("wgpu: event poll failed ({e})");
+        }
+
+        self.done.load(Ordering::Acquire)
+    }
+
+    /// A future completing with the event, holding everything it needs to be awaited
+    /// outside the server.
+    pub fn synchronize(&self) -> DynFut<Result<(), ServerError>> {
+        if self.done.load(Ordering::Acquire) {
+            return Box::pin(async { Ok(()) });
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let device = self.device.clone();
+            let index = self.index.clone();
+            Box::pin(async move {
+                device
+                    .poll(wgpu::PollType::Wait {
+                        submission_index: Some(index),
+                        timeout: None,
+                    })
+                    .map(|_| ())
+                    .map_err(|e| ServerError::Generic {
+                        reason: format!("wgpu: waiting on an event failed ({e})"),
+                        backtrace: BackTrace::capture(),
+                    })
+            })
+        }
+
+        // A submission can't be waited on here, but the work done callback of a later one
+        // only runs once it completes too.
+        #[cfg(target_family = "wasm")]
+        {
+            let queue = self.queue.clone();
+            Box::pin(async move {
+                let (sender, receiver) = cubecl_environment::future::channel::bounded::<()>(1);
+                queue.on_submitted_work_done(move || {
+                    let _ = sender.try_send(());
+                });
+                let _ = receiver.recv().await;
+                Ok(())
+            })
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WgpuStream {
     pub mem_manage: WgpuMemManager,
@@ -88,6 +171,9 @@ pub struct WgpuStream {
     /// The launches recorded since `begin_capture`, drained into a
     /// [`WgpuGraph`] at `end_capture`.
     recording: GraphRecording,
+    /// The graph parameters marked since `begin_capture`, matched against
+    /// each launch the server records.
+    pub(crate) param_marks: GraphParamMarks,
 }
 
 impl WgpuStream {
@@ -164,6 +250,7 @@ impl WgpuStream {
             info_cache: MetadataInfoCache::new(MetadataCachePolicy::new(512, 2048)),
             capturing: StreamCaptureState::NoCapture,
             recording: GraphRecording::default(),
+            param_marks: GraphParamMarks::default(),
         }
     }
 
@@ -216,7 +303,7 @@ impl WgpuStream {
             ScheduleTask::Execute {
                 pipeline,
                 count,
-                resources,
+                mut resources,
                 mut shared_inputs,
             } => {
                 // The capture lifecycle drives the info cache: while a graph is
@@ -234,8 +321,24 @@ impl WgpuStream {
                         .shared
                         .bindings
                         .append(&mut shared_inputs.bindings);
-                    let (resources, custom_handles, addresses) = resources.into_resources(self);
-                    self.record_pipeline(pipeline, &resources, &custom_handles, addresses, &count);
+                    let params = core::mem::take(&mut resources.params);
+                    // A scalar parameter is rewritten in the info uniform, so
+                    // the task keeps the words to patch and a uniform of its own.
+                    let info = params
+                        .iter()
+                        .any(|site| matches!(site.target, GraphParamTarget::Scalar { .. }))
+                        .then(|| resources.info.data.clone());
+                    let (resources, custom_handles, addresses) =
+                        resources.into_resources(self, info.is_some());
+                    self.record_pipeline(
+                        pipeline,
+                        &resources,
+                        &custom_handles,
+                        addresses,
+                        &count,
+                        params,
+                        info,
+                    );
                     return;
                 }
                 // Drain into the stream's pending pins; the handle returns its buffer to the
@@ -243,7 +346,7 @@ impl WgpuStream {
                 self.shared_bindings
                     .bindings
                     .append(&mut shared_inputs.bindings);
-                let (resources, custom_handles, addresses) = resources.into_resources(self);
+                let (resources, custom_handles, addresses) = resources.into_resources(self, false);
                 self.register_pipeline(pipeline, &resources, &custom_handles, addresses, &count);
             }
         }
@@ -492,6 +595,44 @@ impl WgpuStream {
         })
     }
 
+    /// Submits the work enqueued so far and returns an event completing with it.
+    pub(crate) fn record_event(&mut self) -> Result<WgpuEvent, ServerError> {
+        self.reject_while_recording("event_record")?;
+        // Errors stay queued for the next sync, like a launch's.
+        let _ = self
+            .flush(StreamErrorMode {
+                ignore: true,
+                flush: false,
+            })
+            .ok();
+
+        // A flush with no task doesn't submit, and pending `write_buffer` copies only run
+        // at the next submit, so the event always gets a submission of its own.
+        let encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("CubeCL Event Encoder"),
+            });
+        #[cfg_attr(target_family = "wasm", allow(unused_variables))]
+        let index = self.queue.submit([encoder.finish()]);
+
+        let done = Arc::new(AtomicBool::new(false));
+        let signal = done.clone();
+        self.queue.on_submitted_work_done(move || {
+            signal.store(true, Ordering::Release);
+        });
+
+        Ok(WgpuEvent {
+            done,
+            #[cfg(not(target_family = "wasm"))]
+            index,
+            #[cfg(not(target_family = "wasm"))]
+            device: self.device.clone(),
+            #[cfg(target_family = "wasm")]
+            queue: self.queue.clone(),
+        })
+    }
+
     /// Allocates a new empty buffer using the main memory pool.
     pub fn empty(&mut self, size: u64) -> Result<ManagedMemoryHandle, IoError> {
         self.mem_manage.reserve(size)
@@ -748,6 +889,7 @@ impl WgpuStream {
     /// while a capture is recording. Everything a replay needs is resolved
     /// here, once — the bind group is built, the indirect-dispatch buffer is
     /// resolved — so replaying is nothing but re-encoding prebuilt state.
+    #[allow(clippy::too_many_arguments)]
     fn record_pipeline(
         &mut self,
         pipeline: Arc<ComputePipeline>,
@@ -755,28 +897,16 @@ impl WgpuStream {
         custom_resources: &[WgpuResource],
         immediates: Option<Addresses>,
         dispatch: &CubeCount,
+        params: Vec<GraphParamSite>,
+        info: Option<Vec<u64>>,
     ) {
         // An empty dispatch is a no-op on the normal path; record nothing.
         if dispatch.is_empty() {
             return;
         }
 
-        let bind_group = (!resources.is_empty()).then(|| {
-            let entries = resources
-                .iter()
-                .enumerate()
-                .map(|(i, r)| wgpu::BindGroupEntry {
-                    binding: i as u32,
-                    resource: r.as_wgpu_bind_resource(),
-                })
-                .collect::<Vec<_>>();
-            let group_layout = pipeline.get_bind_group_layout(0);
-            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: None,
-                layout: &group_layout,
-                entries: &entries,
-            })
-        });
+        let bind_group =
+            (!resources.is_empty()).then(|| self.replay_bind_group(&pipeline, resources));
 
         let dispatch = match dispatch.clone() {
             CubeCount::Static(x, y, z) => ReplayDispatch::Static(x, y, z),
@@ -791,15 +921,124 @@ impl WgpuStream {
             },
         };
 
+        // In Vulkan buffer-address mode the buffers are the custom resources,
+        // and the bound ones (if any) the address uniform.
+        let params = (!params.is_empty()).then(|| {
+            let vulkan = immediates.is_some() || !custom_resources.is_empty();
+            Box::new(TaskParams {
+                sites: params,
+                buffers: match vulkan {
+                    true => custom_resources.to_vec(),
+                    false => resources.to_vec(),
+                },
+                address_buffer: (vulkan && immediates.is_none()).then(|| resources[0].clone()),
+                info,
+            })
+        });
+
         self.recording.tasks.push(ReplayTask {
             pipeline,
             bind_group,
             immediates,
             transitions: custom_resources.to_vec(),
             dispatch,
+            params,
         });
     }
 
+    /// Build the bind group binding `resources` in order, for a recorded task.
+    fn replay_bind_group(
+        &self,
+        pipeline: &ComputePipeline,
+        resources: &[WgpuResource],
+    ) -> wgpu::BindGroup {
+        let entries = resources
+            .iter()
+            .enumerate()
+            .map(|(i, r)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: r.as_wgpu_bind_resource(),
+            })
+            .collect::<Vec<_>>();
+        let group_layout = pipeline.get_bind_group_layout(0);
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &group_layout,
+            entries: &entries,
+        })
+    }
+
+    /// Patch a validated graph update into the recorded tasks of `graph`.
+    /// `bindings` holds the resolved resource of every binding parameter the
+    /// update changes.
+    ///
+    /// The uniform writes go on the queue, ahead of everything still in the
+    /// encoder, so the replays already encoded are submitted first and keep
+    /// reading the previous values.
+    pub(crate) fn update_graph(
+        &mut self,
+        graph: &mut WgpuGraph,
+        updates: &[(GraphParamId, GraphParamValue)],
+        bindings: &[(GraphParamId, WgpuResource)],
+    ) {
+        let _ = self
+            .flush(StreamErrorMode {
+                ignore: true,
+                flush: false,
+            })
+            .ok();
+
+        for task in graph.tasks.iter_mut() {
+            let Some(params) = task.params.as_deref_mut() else {
+                continue;
+            };
+            let mut rebound = false;
+            let mut rewritten = false;
+
+            for site in params.sites.iter() {
+                match site.target {
+                    GraphParamTarget::Binding(index) => {
+                        if let Some((_, resource)) =
+                            bindings.iter().find(|(param, _)| *param == site.param)
+                        {
+                            params.buffers[index] = resource.clone();
+                            rebound = true;
+                        }
+                    }
+                    GraphParamTarget::Scalar { offset, len } => {
+                        if let (Some(info), Some((_, GraphParamValue::Scalar(bytes)))) = (
+                            params.info.as_mut(),
+                            updates.iter().find(|(param, _)| *param == site.param),
+                        ) {
+                            bytemuck::cast_slice_mut::<u64, u8>(info)[offset..offset + len]
+                                .copy_from_slice(bytes);
+                            rewritten = true;
+                        }
+                    }
+                }
+            }
+
+            if rewritten && let (Some(info), Some(uniform)) = (&params.info, params.buffers.last())
+            {
+                self.write_to_buffer(uniform, bytemuck::cast_slice(info));
+            }
+            if rebound {
+                if task.immediates.is_some() {
+                    task.immediates = Some(addresses(&params.buffers));
+                    task.transitions = params.buffers.clone();
+                } else if let Some(address_buffer) = &params.address_buffer {
+                    self.write_to_buffer(
+                        address_buffer,
+                        bytemuck::cast_slice(&addresses(&params.buffers)),
+                    );
+                    task.transitions = params.buffers.clone();
+                } else {
+                    task.bind_group = Some(self.replay_bind_group(&task.pipeline, &params.buffers));
+                }
+            }
+        }
+    }
+
     /// Move the in-progress recording out of the stream (leaving it empty),
     /// for `end_capture` to seal into a [`WgpuGraph`].
     pub(crate) fn take_recording(&mut self) -> GraphRecording {
@@ -7,6 +7,7 @@
 
 use cubecl_common::bytes::Bytes;
 use cubecl_core as cubecl;
+use cubecl_core::client::GraphUpdate;
 use cubecl_core::prelude::*;
 use cubecl_core::server::Handle;
 use cubecl_wgpu::WgpuRuntime;
@@ -32,6 +33,13 @@ fn mul_two(input: &[f32], output: &mut [f32]) {
     }
 }
 
+#[cube(launch)]
+fn add_scalar(input: &[f32], output: &mut [f32], value: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + value;
+    }
+}
+
 /// Capture a single kernel launch into a graph, replay it, and check the
 /// output — the end-to-end proof that graph capture works on this GPU.
 #[test]
@@ -640,3 +648,59 @@ fn wgpu_graph_capture_is_isolated_from_another_stream() {
     let out = client.read_one(output).unwrap();
     assert_eq!(f32::from_bytes(&out), &[2.0, 3.0, 4.0, 5.0]);
 }
+
+/// Parameters marked during the capture are updated on the graph between
+/// replays: the scalar lands in the task's own info uniform and the rebound
+/// buffer in a rebuilt bind group (or the addresses, in Vulkan mode).
+#[test]
+fn wgpu_graph_param_update() {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let client = WgpuRuntime::client(&Default::default());
+
+    let n = 4usize;
+    let input = client.create_from_slice(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let other = client.create_from_slice(f32::as_bytes(&[10.0, 20.0, 30.0, 40.0]));
+    let output = client.empty(n * core::mem::size_of::<f32>());
+    // Distinct from every other argument of the launch, so it matches once.
+    let placeholder = 1234.5f32;
+    let launch = |client: &ComputeClient<WgpuRuntime>, value: f32| {
+        add_scalar::launch::<WgpuRuntime>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(client, n),
+            unsafe { BufferArg::from_raw_parts(input.clone(), n) },
+            unsafe { BufferArg::from_raw_parts(output.clone(), n) },
+            value,
+        );
+    };
+
+    client.graph_prepare().expect("graph_prepare");
+    launch(&client, placeholder);
+    let _ = client.read_one(output.clone()).unwrap();
+
+    client.start_capture().expect("start_capture");
+    let value = client
+        .graph_scalar_param(placeholder)
+        .expect("scalar param");
+    let source = client.graph_binding_param(&input).expect("binding param");
+    launch(&client, placeholder);
+    let graph = client.stop_capture().expect("stop_capture");
+
+    graph
+        .update(GraphUpdate::new().scalar(value, 1.0f32))
+        .expect("scalar update");
+    unsafe { graph.replay() };
+    let out = client.read_one(output.clone()).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[2.0, 3.0, 4.0, 5.0]);
+
+    graph
+        .update(
+            GraphUpdate::new()
+                .scalar(value, 2.0f32)
+                .binding(source, &other),
+        )
+        .expect("binding update");
+    unsafe { graph.replay() };
+    let out = client.read_one(output).unwrap();
+    assert_eq!(f32::from_bytes(&out), &[12.0, 22.0, 32.0, 42.0]);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    throughput::dispatch!(R => throughput::compute_vector::<R>(&Default::default()));
+}
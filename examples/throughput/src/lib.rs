@@ -3,7 +3,8 @@ use cubecl::{
     prelude::*,
     std::throughput::{measure_memory_curve, measure_peak_throughput},
     throughput::{
-        CmmaDims, ComputeCmmaConfig, MemoryAccess, MemoryCurve, ThroughputKey, ThroughputMode,
+        CmmaDims, ComputeCmmaConfig, DotPacking, MemoryAccess, MemoryCurve, SpecialFunction,
+        ThroughputKey, ThroughputMode,
     },
 };
 
@@ -54,6 +55,22 @@ pub fn compute_cmma<R: Runtime>(device: &R::Device) {
     run::<R>(device, &[compute_cmma_key()]);
 }
 
+/// Peak packed integer dot product throughput, int8 and int4.
+pub fn compute_dot<R: Runtime>(device: &R::Device) {
+    run::<R>(device, &compute_dot_keys());
+}
+
+/// Peak half precision fma throughput two lanes at a time, the packed rate most hardware
+/// reserves for `f16x2` and `bf16x2`.
+pub fn compute_vector<R: Runtime>(device: &R::Device) {
+    run::<R>(device, &compute_vector_keys());
+}
+
+/// Peak special function throughput, `exp` and `1 / sqrt`.
+pub fn compute_special<R: Runtime>(device: &R::Device) {
+    run::<R>(device, &compute_special_keys());
+}
+
 /// Peak memory (copy) throughput — reads and writes, both counted.
 pub fn memory<R: Runtime>(device: &R::Device) {
     run::<R>(device, &[memory_key()]);
@@ -116,16 +133,13 @@ pub fn launch_overhead<R: Runtime>(device: &R::Device) {
 
 /// Runs every throughput benchmark and prints them as a table.
 pub fn all<R: Runtime>(device: &R::Device) {
-    run::<R>(
-        device,
-        &[
-            compute_direct_key(),
-            compute_cmma_key(),
-            memory_key(),
-            memory_read_key(),
-            launch_overhead_key(),
-        ],
-    );
+    let mut keys = vec![compute_direct_key(), compute_cmma_key()];
+    keys.extend(compute_dot_keys());
+    keys.extend(compute_vector_keys());
+    keys.extend(compute_special_keys());
+    keys.extend([memory_key(), memory_read_key(), launch_overhead_key()]);
+
+    run::<R>(device, &keys);
 }
 
 fn run<R: Runtime>(device: &R::Device, keys: &[ThroughputKey]) {
@@ -155,6 +169,9 @@ fn describe(key: &ThroughputKey) -> String {
             input_dtype, cfg.accumulator_type, cfg.cmma_dims.m, cfg.cmma_dims.n, cfg.cmma_dims.k,
         ),
         ThroughputMode::ComputeDirect { .. } => key.dtype().to_string(),
+        ThroughputMode::ComputeDot { packing } => format!("{packing:?}"),
+        ThroughputMode::ComputeVector { dtype, vector_size } => format!("{dtype}x{vector_size}"),
+        ThroughputMode::ComputeSpecial { dtype, function } => format!("{function:?} {dtype}"),
         ThroughputMode::MemoryWorkingSet { bytes, .. } => bytes_label(bytes),
         ThroughputMode::Memory | ThroughputMode::MemoryRead | ThroughputMode::Launch => {
             String::new()
@@ -166,6 +183,9 @@ fn mode_label(mode: &ThroughputMode) -> &'static str {
     match mode {
         ThroughputMode::ComputeDirect { .. } => "compute-direct",
         ThroughputMode::ComputeCmma { .. } => "compute-cmma",
+        ThroughputMode::ComputeDot { .. } => "compute-dot",
+        ThroughputMode::ComputeVector { .. } => "compute-vector",
+        ThroughputMode::ComputeSpecial { .. } => "compute-special",
         ThroughputMode::Memory
         | ThroughputMode::MemoryWorkingSet {
             access: MemoryAccess::Copy,
@@ -204,6 +224,30 @@ fn compute_cmma_key() -> ThroughputKey {
     }
 }
 
+fn compute_dot_keys() -> [ThroughputKey; 2] {
+    [DotPacking::Int8x4, DotPacking::Int4x8].map(|packing| ThroughputKey {
+        mode: ThroughputMode::ComputeDot { packing },
+    })
+}
+
+fn compute_vector_keys() -> [ThroughputKey; 2] {
+    [FloatKind::F16, FloatKind::BF16].map(|kind| ThroughputKey {
+        mode: ThroughputMode::ComputeVector {
+            dtype: ElemType::Float(kind),
+            vector_size: 2,
+        },
+    })
+}
+
+fn compute_special_keys() -> [ThroughputKey; 2] {
+    [SpecialFunction::Exp, SpecialFunction::InverseSqrt].map(|function| ThroughputKey {
+        mode: ThroughputMode::ComputeSpecial {
+            dtype: ElemType::Float(FloatKind::F32),
+            function,
+        },
+    })
+}
+
 fn memory_key() -> ThroughputKey {
     ThroughputKey {
         mode: ThroughputMode::Memory,